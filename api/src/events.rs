@@ -0,0 +1,349 @@
+// Copyright (c) The dgc.network
+// SPDX-License-Identifier: Apache-2.0
+
+//! WebSocket subscriptions to changes of Smart state
+//!
+//! A client connects with a query selecting the changes it wants, for
+//! example `ws://localhost:9002/?types=account,organization`:
+//!
+//! * `types` - comma separated object types: `contract`,
+//!   `namespace_registry`, `account`, `organization` and `smart_permission`
+//! * `prefixes` - comma separated address prefixes
+//! * `last_block_id` - the last block the client has seen, to resume from
+//!
+//! Changes matching any of the types or prefixes are sent, or changes of
+//! every type if neither is given. Each connection gets its own validator
+//! subscription, and a JSON message is sent for every committed block with
+//! the block's id, number and the decoded state changes.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+
+use protobuf;
+use rocket_contrib::json::JsonValue;
+use serde_json::Value;
+use ws;
+use ws::{CloseCode, Handshake};
+
+use dgc_contract_sdk::addressing::*;
+use dgc_contract_sdk::protocol::state::{
+    AccountList,
+    ContractList,
+    NamespaceRegistryList,
+    OrganizationList,
+    SmartPermissionList
+};
+use dgc_contract_sdk::protos::{FromBytes, ProtoConversionError};
+use pike_sde::subscriber::{Subscriber, SubscriberError};
+use sawtooth_sdk::messages::events::{Event, EventList};
+use sawtooth_sdk::messages::transaction_receipt::{StateChange, StateChangeList};
+use sawtooth_sdk::messages::transaction_receipt::StateChange_Type;
+
+use state::decode::*;
+
+const BLOCK_COMMIT_EVENT: &str = "sawtooth/block-commit";
+const STATE_DELTA_EVENT: &str = "sawtooth/state-delta";
+
+/// Serves state change subscriptions on `address` until the process exits
+pub fn serve(address: String, validator_url: String) {
+    thread::spawn(move || {
+        let result = ws::listen(address.as_str(), |out| Connection {
+            out,
+            validator_url: validator_url.clone(),
+            subscriber: Subscriber::new(),
+            closed: Arc::new(AtomicBool::new(false))
+        });
+
+        if let Err(err) = result {
+            eprintln!("Unable to serve state change subscriptions on {}: {}", address, err);
+        }
+    });
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ObjectType {
+    Contract,
+    NamespaceRegistry,
+    Account,
+    Organization,
+    SmartPermission
+}
+
+const OBJECT_TYPES: &[ObjectType] = &[
+    ObjectType::Contract,
+    ObjectType::NamespaceRegistry,
+    ObjectType::Account,
+    ObjectType::Organization,
+    ObjectType::SmartPermission
+];
+
+impl ObjectType {
+    fn name(&self) -> &'static str {
+        match *self {
+            ObjectType::Contract => "contract",
+            ObjectType::NamespaceRegistry => "namespace_registry",
+            ObjectType::Account => "account",
+            ObjectType::Organization => "organization",
+            ObjectType::SmartPermission => "smart_permission"
+        }
+    }
+
+    fn prefix(&self) -> &'static str {
+        match *self {
+            ObjectType::Contract => CONTRACT_PREFIX,
+            ObjectType::NamespaceRegistry => NAMESPACE_REGISTRY_PREFIX,
+            ObjectType::Account => ACCOUNT_PREFIX,
+            ObjectType::Organization => ORGANIZATION_PREFIX,
+            ObjectType::SmartPermission => SMART_PERMISSION_PREFIX
+        }
+    }
+
+    fn from_name(name: &str) -> Option<ObjectType> {
+        OBJECT_TYPES.iter().cloned().find(|object_type| object_type.name() == name)
+    }
+
+    fn of_address(address: &str) -> Option<ObjectType> {
+        OBJECT_TYPES.iter().cloned().find(|object_type| address.starts_with(object_type.prefix()))
+    }
+
+    /// Decodes a state entry holding objects of this type
+    fn decode(&self, data: &[u8]) -> Result<Vec<Value>, ProtoConversionError> {
+        let objects = match *self {
+            ObjectType::Contract => ContractList::from_bytes(data)?
+                .get_contracts()
+                .iter()
+                .map(|contract| json!(to_contract(contract)))
+                .collect::<Vec<JsonValue>>(),
+            ObjectType::NamespaceRegistry => NamespaceRegistryList::from_bytes(data)?
+                .get_registries()
+                .iter()
+                .map(|registry| json!(to_namespace_registry(registry)))
+                .collect(),
+            ObjectType::Account => AccountList::from_bytes(data)?
+                .get_accounts()
+                .iter()
+                .map(|account| json!(to_account(account)))
+                .collect(),
+            ObjectType::Organization => OrganizationList::from_bytes(data)?
+                .get_organizations()
+                .iter()
+                .map(|org| json!(to_org(org)))
+                .collect(),
+            ObjectType::SmartPermission => SmartPermissionList::from_bytes(data)?
+                .get_smart_permissions()
+                .iter()
+                .map(|permission| json!(to_smart_permission(permission)))
+                .collect()
+        };
+
+        Ok(objects.into_iter().map(Into::into).collect())
+    }
+}
+
+/// The changes a client subscribed to
+struct Filter {
+    types: Vec<ObjectType>,
+    prefixes: Vec<String>,
+    last_block_id: Option<String>
+}
+
+impl Filter {
+    /// Parses the query of the requested resource
+    fn parse(resource: &str) -> Result<Filter, String> {
+        let mut filter = Filter {
+            types: Vec::new(),
+            prefixes: Vec::new(),
+            last_block_id: None
+        };
+
+        let query = match resource.find('?') {
+            Some(index) => &resource[index + 1..],
+            None => ""
+        };
+
+        for param in query.split('&').filter(|param| !param.is_empty()) {
+            let mut parts = param.splitn(2, '=');
+            let key = parts.next().unwrap_or("");
+            let value = parts.next().unwrap_or("");
+
+            match key {
+                "types" => for name in value.split(',') {
+                    filter.types.push(ObjectType::from_name(name)
+                        .ok_or_else(|| format!("Unknown object type: {}", name))?);
+                },
+                "prefixes" => for prefix in value.split(',') {
+                    if prefix.is_empty() || !prefix.chars().all(|c| c.is_digit(16)) {
+                        return Err(format!("Invalid address prefix: {}", prefix));
+                    }
+                    filter.prefixes.push(prefix.to_lowercase());
+                },
+                "last_block_id" => if !value.is_empty() {
+                    if !value.chars().all(|c| c.is_digit(16)) {
+                        return Err(format!("Invalid block id: {}", value));
+                    }
+                    filter.last_block_id = Some(value.to_string());
+                },
+                _ => return Err(format!("Unknown parameter: {}", key))
+            }
+        }
+
+        Ok(filter)
+    }
+
+    /// Returns the address prefixes of the state deltas to subscribe to
+    fn address_prefixes(&self) -> Vec<String> {
+        let types = if self.types.is_empty() && self.prefixes.is_empty() {
+            OBJECT_TYPES
+        } else {
+            &self.types[..]
+        };
+
+        types
+            .iter()
+            .map(|object_type| object_type.prefix().to_string())
+            .chain(self.prefixes.iter().cloned())
+            .collect()
+    }
+}
+
+struct Connection {
+    out: ws::Sender,
+    validator_url: String,
+    subscriber: Subscriber,
+    closed: Arc<AtomicBool>
+}
+
+impl ws::Handler for Connection {
+    fn on_open(&mut self, shake: Handshake) -> ws::Result<()> {
+        let filter = match Filter::parse(shake.request.resource()) {
+            Ok(filter) => filter,
+            Err(message) => {
+                self.out.send(error_message(&message))?;
+                return self.out.close(CloseCode::Invalid);
+            }
+        };
+
+        let out = self.out.clone();
+        let validator_url = self.validator_url.clone();
+        let mut subscriber = self.subscriber.clone();
+        let closed = self.closed.clone();
+
+        thread::spawn(move || {
+            // the connection may have closed before the thread started, and
+            // subscribing would undo the stop requested by on_close
+            if closed.load(Ordering::SeqCst) {
+                return;
+            }
+            let last_known_block_ids = filter.last_block_id.iter().cloned().collect();
+            let result = subscriber.subscribe(
+                &validator_url,
+                &filter.address_prefixes(),
+                last_known_block_ids,
+                |events| {
+                    if closed.load(Ordering::SeqCst) {
+                        return false;
+                    }
+                    match block_message(&events) {
+                        Some(message) => out.send(message).is_ok(),
+                        None => true
+                    }
+                });
+
+            if let Err(err) = result {
+                let message = match err {
+                    SubscriberError::UnknownBlock => format!(
+                        "Unknown block: {}",
+                        filter.last_block_id.unwrap_or_default()
+                    ),
+                    err => format!("Unable to subscribe to state changes: {}", err)
+                };
+                out.send(error_message(&message))
+                    .and_then(|_| out.close(CloseCode::Error))
+                    .unwrap_or(());
+            }
+        });
+
+        Ok(())
+    }
+
+    fn on_close(&mut self, _: CloseCode, _: &str) {
+        self.closed.store(true, Ordering::SeqCst);
+        self.subscriber.stop();
+    }
+}
+
+/// Returns the message sent for a committed block, or None if the events do
+/// not include a block commit
+fn block_message(events: &EventList) -> Option<String> {
+    let block_commit = events
+        .get_events()
+        .iter()
+        .find(|event| event.get_event_type() == BLOCK_COMMIT_EVENT)?;
+
+    let state_changes: Vec<Value> = events
+        .get_events()
+        .iter()
+        .filter(|event| event.get_event_type() == STATE_DELTA_EVENT)
+        .filter_map(|event| protobuf::parse_from_bytes::<StateChangeList>(event.get_data()).ok())
+        .flat_map(|list| list.get_state_changes().to_vec())
+        .filter_map(|change| state_change_json(&change))
+        .collect();
+
+    let message: Value = json!({
+        "block_id": attribute(block_commit, "block_id"),
+        "block_num": attribute(block_commit, "block_num").parse::<u64>().unwrap_or(0),
+        "previous_block_id": attribute(block_commit, "previous_block_id"),
+        "state_changes": state_changes
+    }).into();
+
+    Some(message.to_string())
+}
+
+/// Decodes a change of a Smart object, or returns None if the address does
+/// not hold one
+fn state_change_json(change: &StateChange) -> Option<Value> {
+    let object_type = ObjectType::of_address(change.get_address())?;
+
+    let message = match change.get_field_type() {
+        StateChange_Type::SET => match object_type.decode(change.get_value()) {
+            Ok(objects) => json!({
+                "address": change.get_address(),
+                "type": object_type.name(),
+                "operation": "set",
+                "value": objects
+            }),
+            Err(err) => json!({
+                "address": change.get_address(),
+                "type": object_type.name(),
+                "operation": "set",
+                "error": format!("Unable to decode state entry: {}", err)
+            })
+        },
+        StateChange_Type::DELETE => json!({
+            "address": change.get_address(),
+            "type": object_type.name(),
+            "operation": "delete"
+        }),
+        StateChange_Type::TYPE_UNSET => return None
+    };
+
+    Some(message.into())
+}
+
+fn attribute<'a>(event: &'a Event, key: &str) -> &'a str {
+    event
+        .get_attributes()
+        .iter()
+        .find(|attribute| attribute.get_key() == key)
+        .map(|attribute| attribute.get_value())
+        .unwrap_or("")
+}
+
+fn error_message(message: &str) -> String {
+    let message: Value = json!({
+        "message": message
+    }).into();
+
+    message.to_string()
+}
@@ -1,5 +1,5 @@
 // Copyright (c) The dgc.network
 // SPDX-License-Identifier: Apache-2.0
 
-//pub mod db_conn;
+pub mod db_conn;
 pub mod validator_conn;
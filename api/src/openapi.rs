@@ -1,38 +1,45 @@
 // Copyright (c) The dgc.network
 // SPDX-License-Identifier: Apache-2.0
 
-#![feature(proc_macro_hygiene, decl_macro)]
-#![feature(rustc_private)]
-
-extern crate rocket;
-extern crate serde_yaml;
-extern crate serde_json;
-
 use std::fs::File;
 use std::io::prelude::*;
 
+use rocket::http::ContentType;
+use rocket::http::Status;
+use rocket::response::content::Content;
+use rocket::response::status::Custom;
+use serde_json;
 use serde_json::Value;
-//use serde::de::{Deserialize, Deserializer, Visitor, MapAccess};
+use serde_yaml;
 
 const SWAGGER_FILENAME: &'static str = "openapi.yaml";
 
 #[get("/openapi.json")]
-fn openapi_json() -> String {
-    let mut file = File::open(SWAGGER_FILENAME).unwrap();
-    let mut contents = String::new();
-    file.read_to_string(&mut contents).unwrap();
+pub fn openapi_json() -> Result<Content<String>, Custom<String>> {
+    let contents = read_openapi()?;
+    let deserialized_map: Value = serde_yaml::from_str(&contents)
+        .map_err(|err| Custom(Status::InternalServerError, err.to_string()))?;
+    let json = serde_json::to_string_pretty(&deserialized_map)
+        .map_err(|err| Custom(Status::InternalServerError, err.to_string()))?;
+
+    Ok(Content(ContentType::JSON, json))
+}
 
-    let deserialized_map: Value = serde_yaml::from_str(&contents).unwrap();
-    let j = serde_json::to_string_pretty(&deserialized_map).unwrap();
+#[get("/openapi.yaml")]
+pub fn openapi_yaml() -> Result<Content<String>, Custom<String>> {
+    let contents = read_openapi()?;
 
-    return j
+    Ok(Content(ContentType::new("application", "x-yaml"), contents))
 }
 
-#[get("/openapi.yaml")]
-fn openapi_yaml() -> String {
-    let mut file = File::open(SWAGGER_FILENAME).unwrap();
+fn read_openapi() -> Result<String, Custom<String>> {
     let mut contents = String::new();
-    file.read_to_string(&mut contents).unwrap();
-
-    return contents;
+    File::open(SWAGGER_FILENAME)
+        .and_then(|mut file| file.read_to_string(&mut contents))
+        .map_err(|err| Custom(
+            Status::InternalServerError,
+            format!("Unable to read {}: {}", SWAGGER_FILENAME, err)
+        ))?;
+
+    Ok(contents)
 }
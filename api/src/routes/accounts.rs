@@ -1,28 +1,26 @@
 // Copyright (c) The dgc.network
 // SPDX-License-Identifier: Apache-2.0
 
-extern crate rocket;
+use rocket::response::status::Custom;
+use rocket_contrib::json::{Json, JsonValue};
+use routes::map_read_error;
+use state::StateReader;
 
-use rocket_contrib::json::Json;
-use guard::db_conn::DbConn;
-
-use pike_db as db;
 use pike_db::models::Account;
 
 #[get("/account/<publickey>")]
-fn get_account(conn: DbConn, publickey: String) -> Option<Json<Account>> {
-    if let Ok(account) = db::get_account(&conn, &publickey) {
-        Some(Json(account))
-    } else {
-        None
-    }
+pub fn get_account(
+    state: StateReader,
+    publickey: String
+) -> Result<Json<Account>, Custom<JsonValue>> {
+    state.get_account(&publickey)
+        .map(Json)
+        .map_err(|err| map_read_error(err, &format!("Account with {}", publickey)))
 }
 
 #[get("/account")]
-fn get_accounts(conn: DbConn) -> Json<Vec<Account>> {
-    if let Ok(accounts) = db::get_accounts(&conn) {
-        Json(accounts)
-    } else {
-        Json(vec![])
-    }
+pub fn get_accounts(state: StateReader) -> Result<Json<Vec<Account>>, Custom<JsonValue>> {
+    state.get_accounts()
+        .map(Json)
+        .map_err(|err| map_read_error(err, "accounts"))
 }
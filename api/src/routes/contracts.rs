@@ -0,0 +1,37 @@
+// Copyright (c) The dgc.network
+// SPDX-License-Identifier: Apache-2.0
+
+use rocket::response::status::Custom;
+use rocket_contrib::json::{Json, JsonValue};
+use routes::map_read_error;
+use state::StateReader;
+
+use pike_db::models::Contract;
+
+#[get("/contract/<name>/<version>")]
+pub fn get_contract(
+    state: StateReader,
+    name: String,
+    version: String
+) -> Result<Json<Contract>, Custom<JsonValue>> {
+    state.get_contract(&name, &version)
+        .map(Json)
+        .map_err(|err| map_read_error(err, &format!("Contract {}:{}", name, version)))
+}
+
+#[get("/contract/<name>")]
+pub fn get_contract_versions(
+    state: StateReader,
+    name: String
+) -> Result<Json<Vec<Contract>>, Custom<JsonValue>> {
+    state.get_contract_versions(&name)
+        .map(Json)
+        .map_err(|err| map_read_error(err, "contracts"))
+}
+
+#[get("/contract")]
+pub fn get_contracts(state: StateReader) -> Result<Json<Vec<Contract>>, Custom<JsonValue>> {
+    state.get_contracts()
+        .map(Json)
+        .map_err(|err| map_read_error(err, "contracts"))
+}
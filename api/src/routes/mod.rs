@@ -1,6 +1,34 @@
 // Copyright (c) The dgc.network
 // SPDX-License-Identifier: Apache-2.0
 
-//pub mod accounts;
-//pub mod organizations;
+pub mod accounts;
+pub mod contracts;
+pub mod namespace_registries;
+pub mod organizations;
+pub mod smart_permissions;
 pub mod transactions;
+
+use rocket::http::Status;
+use rocket::response::status::Custom;
+use rocket_contrib::json::JsonValue;
+
+use state::ReadError;
+
+/// The response for a failed state read: 404 if the requested object does
+/// not exist, 500 otherwise
+pub fn map_read_error(err: ReadError, resource: &str) -> Custom<JsonValue> {
+    match err {
+        ReadError::NotFound => Custom(
+            Status::NotFound,
+            json!({
+                "message": format!("{} not found", resource)
+            })
+        ),
+        err => Custom(
+            Status::InternalServerError,
+            json!({
+                "message": format!("Unexpected error occurred while retrieving {}: {}", resource, err)
+            })
+        )
+    }
+}
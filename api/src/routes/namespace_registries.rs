@@ -0,0 +1,28 @@
+// Copyright (c) The dgc.network
+// SPDX-License-Identifier: Apache-2.0
+
+use rocket::response::status::Custom;
+use rocket_contrib::json::{Json, JsonValue};
+use routes::map_read_error;
+use state::StateReader;
+
+use pike_db::models::NamespaceRegistry;
+
+#[get("/namespace/<namespace>")]
+pub fn get_namespace_registry(
+    state: StateReader,
+    namespace: String
+) -> Result<Json<NamespaceRegistry>, Custom<JsonValue>> {
+    state.get_namespace_registry(&namespace)
+        .map(Json)
+        .map_err(|err| map_read_error(err, &format!("Namespace registry {}", namespace)))
+}
+
+#[get("/namespace")]
+pub fn get_namespace_registries(
+    state: StateReader
+) -> Result<Json<Vec<NamespaceRegistry>>, Custom<JsonValue>> {
+    state.get_namespace_registries()
+        .map(Json)
+        .map_err(|err| map_read_error(err, "namespace registries"))
+}
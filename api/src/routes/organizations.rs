@@ -1,28 +1,23 @@
 // Copyright (c) The dgc.network
 // SPDX-License-Identifier: Apache-2.0
 
-extern crate rocket;
+use rocket::response::status::Custom;
+use rocket_contrib::json::{Json, JsonValue};
+use routes::map_read_error;
+use state::StateReader;
 
-use rocket_contrib::json::Json;
-use guard::db_conn::DbConn;
-
-use pike_db as db;
 use pike_db::models::Organization;
 
 #[get("/organization/<id>")]
-fn get_org(conn: DbConn, id: String) -> Option<Json<Organization>> {
-    if let Ok(org) = db::get_org(&conn, &id) {
-        Some(Json(org))
-    } else {
-        None
-    }
+pub fn get_org(state: StateReader, id: String) -> Result<Json<Organization>, Custom<JsonValue>> {
+    state.get_org(&id)
+        .map(Json)
+        .map_err(|err| map_read_error(err, &format!("Organization {}", id)))
 }
 
 #[get("/organization")]
-fn get_orgs(conn: DbConn) -> Json<Vec<Organization>> {
-    if let Ok(orgs) = db::get_orgs(&conn) {
-        Json(orgs)
-    } else {
-        Json(vec![])
-    }
+pub fn get_orgs(state: StateReader) -> Result<Json<Vec<Organization>>, Custom<JsonValue>> {
+    state.get_orgs()
+        .map(Json)
+        .map_err(|err| map_read_error(err, "organizations"))
 }
@@ -0,0 +1,39 @@
+// Copyright (c) The dgc.network
+// SPDX-License-Identifier: Apache-2.0
+
+use rocket::response::status::Custom;
+use rocket_contrib::json::{Json, JsonValue};
+use routes::map_read_error;
+use state::StateReader;
+
+use pike_db::models::SmartPermission;
+
+#[get("/smartpermission/<org_id>/<name>")]
+pub fn get_smart_permission(
+    state: StateReader,
+    org_id: String,
+    name: String
+) -> Result<Json<SmartPermission>, Custom<JsonValue>> {
+    state.get_smart_permission(&org_id, &name)
+        .map(Json)
+        .map_err(|err| map_read_error(err, &format!("Smart permission {} of {}", name, org_id)))
+}
+
+#[get("/smartpermission/<org_id>")]
+pub fn get_org_smart_permissions(
+    state: StateReader,
+    org_id: String
+) -> Result<Json<Vec<SmartPermission>>, Custom<JsonValue>> {
+    state.get_org_smart_permissions(&org_id)
+        .map(Json)
+        .map_err(|err| map_read_error(err, "smart permissions"))
+}
+
+#[get("/smartpermission")]
+pub fn get_smart_permissions(
+    state: StateReader
+) -> Result<Json<Vec<SmartPermission>>, Custom<JsonValue>> {
+    state.get_smart_permissions()
+        .map(Json)
+        .map_err(|err| map_read_error(err, "smart permissions"))
+}
@@ -1,111 +1,139 @@
 // Copyright (c) The dgc.network
 // SPDX-License-Identifier: Apache-2.0
 
-#![feature(proc_macro_hygiene, decl_macro)]
-
-extern crate rocket;
-
-use rocket_contrib::json::Json;
-use rocket_contrib::json::JsonValue;
+use std::io::Read;
 
+use rocket::Data;
 use rocket::http::Status;
 use rocket::response::status::Custom;
-use rocket::request::FromForm;
-//use rocket::request::Form;
+use rocket_contrib::json::{Json, JsonValue};
 
 use guard::validator_conn::ValidatorConn;
 use submit::{submit_batches, check_batch_status, BatchStatus};
 use submit::TransactionError as error;
-
-#[derive(FromForm)]
-struct TxnQuery {
-    wait: u32
+use transaction::{
+    build_batch,
+    build_batch_list,
+    build_transaction,
+    BatchRequest,
+    BuildError,
+    SignedBatch,
+    TransactionRequest,
+    UnsignedBatch,
+    UnsignedTransaction
+};
+
+/// Largest serialized BatchList accepted by `submit_txns`
+const MAX_BATCH_LIST_SIZE: u64 = 10 * 1024 * 1024;
+
+#[post("/transactions?<wait>", format = "application/octet-stream", data = "<data>")]
+pub fn submit_txns(
+    conn: ValidatorConn,
+    data: Data,
+    wait: Option<u32>
+) -> Result<Custom<Json<Vec<BatchStatus>>>, Custom<JsonValue>> {
+    let mut batches = Vec::new();
+    data.open()
+        .take(MAX_BATCH_LIST_SIZE)
+        .read_to_end(&mut batches)
+        .map_err(|err| Custom(
+            Status::BadRequest,
+            json!({
+                "message": format!("Unable to read batches: {}", err)
+            })
+        ))?;
+
+    submit(&conn, &batches, wait)
 }
 
-//impl<'f> FromForm<'f> for TxnQuery {}
-
-#[derive(FromForm)]
-struct StatusQuery {
-    wait: Option<u32>,
-    ids: String
+/// Returns the unsigned header of a transaction carrying the described
+/// action, along with its payload
+#[post("/transactions/build", format = "application/json", data = "<request>")]
+pub fn build_txn(
+    request: Json<TransactionRequest>
+) -> Result<Json<UnsignedTransaction>, Custom<JsonValue>> {
+    build_transaction(request.into_inner())
+        .map(Json)
+        .map_err(map_build_error)
 }
 
-//impl<'f> FromForm<'f> for StatusQuery {}
+/// Returns the unsigned header of a batch of signed transactions
+#[post("/batches/build", format = "application/json", data = "<request>")]
+pub fn build_batch_header(
+    request: Json<BatchRequest>
+) -> Result<Json<UnsignedBatch>, Custom<JsonValue>> {
+    build_batch(request.into_inner())
+        .map(Json)
+        .map_err(map_build_error)
+}
 
-#[post("/batches?<query>", format = "application/octet-stream", data = "<data>")]
-pub fn submit_txns_wait(
+/// Submits a batch signed by the client
+#[post("/batches?<wait>", format = "application/json", data = "<batch>")]
+pub fn submit_signed_batch(
     conn: ValidatorConn,
-    data: Vec<u8>,
-    //query: TxnQuery) -> Result<Custom<Json<Vec<BatchStatus>>>, Custom<Json>> {
-    query: TxnQuery ///the trait `rocket::request::FromFormValue<'_>` is not implemented for `routes::transactions::TxnQuery`
-) -> Result<Custom<Json<Vec<BatchStatus>>>, Custom<Json<String>>> {
+    batch: Json<SignedBatch>,
+    wait: Option<u32>
+) -> Result<Custom<Json<Vec<BatchStatus>>>, Custom<JsonValue>> {
+    let batches = build_batch_list(batch.into_inner()).map_err(map_build_error)?;
 
-    let batch_status_list = submit_batches(&mut conn.clone(), &data, query.wait)
-        //.map_err(map_error)?;
-        .map_err(map_error);
+    submit(&conn, &batches, wait)
+}
+
+fn submit(
+    conn: &ValidatorConn,
+    batches: &[u8],
+    wait: Option<u32>
+) -> Result<Custom<Json<Vec<BatchStatus>>>, Custom<JsonValue>> {
+    let batch_status_list = submit_batches(&mut conn.0.clone(), batches, wait.unwrap_or(0))
+        .map_err(map_error)?;
 
     if batch_status_list
         .iter()
         .all(|x| x.status == "COMMITTED") {
-            ///unknown field
-        Ok(Custom(Status::Created, Json(batch_status_list))) ///expected struct `std::vec::Vec`, found enum `std::result::Result`
+
+        Ok(Custom(Status::Created, Json(batch_status_list)))
     } else {
-        Ok(Custom(Status::Accepted, Json(batch_status_list))) ///expected struct `std::vec::Vec`, found enum `std::result::Result`
+        Ok(Custom(Status::Accepted, Json(batch_status_list)))
     }
 }
 
-#[post("/batches", format = "application/octet-stream", data = "<data>")]
-pub fn submit_txns(
-    conn: ValidatorConn, 
-    //data: Vec<u8>) -> Result<Json<Vec<BatchStatus>>, Custom<Json>> {
-    data: Vec<u8>
-) -> Result<Json<Vec<BatchStatus>>, Custom<Json<String>>> {
-///expected `std::result::Result<rocket_contrib::json::Json<std::vec::Vec<submit::BatchStatus>>, rocket::response::status::Custom<rocket_contrib::json::Json<std::string::String>>>` because of return type
-    submit_batches(&mut conn.clone(), &data, 0)
-        .map_err(map_error)
-        .and_then(|b| Ok(Json(b)))
-}                       ///expected struct `rocket_contrib::json::Json`, found struct `rocket_contrib::json::JsonValue`
-/*
-#[get("/users/<id>")]
-fn user(
-    id: usize
-) -> Json<User> {
-    let user_from_id = User::from(id);
-    Json(user_from_id)
-}
-*/
-#[get("/batch_status?<query>")]
+#[get("/batch_status?<ids>&<wait>")]
 pub fn get_batch_status(
     conn: ValidatorConn,
-    //query: StatusQuery) -> Result<Json<Vec<BatchStatus>>, Custom<Json>> {
-    query: StatusQuery ///the trait `rocket::request::FromFormValue<'_>` is not implemented for `routes::transactions::StatusQuery`
-) -> Result<Json<Vec<BatchStatus>>, Custom<Json<String>>> {
-///expected `std::result::Result<rocket_contrib::json::Json<std::vec::Vec<submit::BatchStatus>>, rocket::response::status::Custom<rocket_contrib::json::Json<std::string::String>>>` because of return type
-    let wait = query.wait.unwrap_or(0);
-    let ids: Vec<String> = query.ids
-        .split(",")
+    ids: String,
+    wait: Option<u32>
+) -> Result<Json<Vec<BatchStatus>>, Custom<JsonValue>> {
+    let ids: Vec<String> = ids
+        .split(',')
         .map(String::from)
         .collect();
 
-    check_batch_status(&mut conn.clone(), ids, wait)
+    check_batch_status(&mut conn.clone(), ids, wait.unwrap_or(0))
+        .map(Json)
         .map_err(map_error)
-        .and_then(|b| Ok(Json(b)))
-}                           ///expected struct `rocket_contrib::json::Json`, found struct `rocket_contrib::json::JsonValue`
+}
 
-//fn map_error(err: error) -> Custom<Json> {
-//fn map_error(err: error) -> Custom<Json<String>> {
 fn map_error(err: error) -> Custom<JsonValue> {
-    let message = Json(
-        json!({
-            "message": format!("{:?}", err)
-        })
-    );
+    let message = json!({
+        "message": format!("{:?}", err)
+    });
 
     match err {
         error::BatchParseError(_) |
         error::InvalidBatch(_) |
         error::NoResource(_) |
-        error::InvalidId(_) => Custom(Status::BadRequest, message), ///expected struct `rocket_contrib::json::JsonValue`, found struct `rocket_contrib::json::Json`
-        _ => Custom(Status::InternalServerError, message) ///expected struct `rocket_contrib::json::JsonValue`, found struct `rocket_contrib::json::Json`
+        error::InvalidId(_) => Custom(Status::BadRequest, message),
+        _ => Custom(Status::InternalServerError, message)
+    }
+}
+
+fn map_build_error(err: BuildError) -> Custom<JsonValue> {
+    let message = json!({
+        "message": format!("{}", err)
+    });
+
+    match err {
+        BuildError::InvalidRequest(_) => Custom(Status::BadRequest, message),
+        _ => Custom(Status::InternalServerError, message)
     }
 }
@@ -0,0 +1,77 @@
+// Copyright (c) The dgc.network
+// SPDX-License-Identifier: Apache-2.0
+
+//! Conversions from Smart state objects to the models served by the API
+
+use crypto::digest::Digest;
+use crypto::sha2::Sha512;
+
+use dgc_contract_sdk::addressing::compute_smart_permission_address;
+use dgc_contract_sdk::protocol::state as smart;
+use pike_db::models::{Account, Contract, NamespaceRegistry, Organization, SmartPermission};
+
+pub fn to_account(account: &smart::Account) -> Account {
+    Account {
+        public_key: account.get_public_key().to_string(),
+        org_id: account.get_org_id().to_string(),
+        active: *account.get_active(),
+        roles: account.get_roles().to_vec(),
+        metadata: account
+            .get_metadata()
+            .iter()
+            .map(|entry| json!({
+                "key": entry.get_key(),
+                "value": entry.get_value()
+            }).into())
+            .collect()
+    }
+}
+
+pub fn to_org(org: &smart::Organization) -> Organization {
+    Organization {
+        id: org.get_org_id().to_string(),
+        name: org.get_name().to_string(),
+        address: org.get_address().to_string()
+    }
+}
+
+pub fn to_contract(contract: &smart::Contract) -> Contract {
+    let mut sha = Sha512::new();
+    sha.input(contract.get_contract());
+
+    Contract {
+        name: contract.get_name().to_string(),
+        version: contract.get_version().to_string(),
+        inputs: contract.get_inputs().to_vec(),
+        outputs: contract.get_outputs().to_vec(),
+        creator: contract.get_creator().to_string(),
+        contract_sha512: sha.result_str()
+    }
+}
+
+pub fn to_namespace_registry(registry: &smart::NamespaceRegistry) -> NamespaceRegistry {
+    NamespaceRegistry {
+        namespace: registry.get_namespace().to_string(),
+        owners: registry.get_owners().to_vec(),
+        owner_threshold: registry.get_owner_threshold() as i32,
+        permissions: registry
+            .get_permissions()
+            .iter()
+            .map(|permission| json!({
+                "contract_name": permission.get_contract_name(),
+                "read": permission.get_read(),
+                "write": permission.get_write(),
+                "prefix": permission.get_prefix(),
+                "deny": permission.get_deny()
+            }).into())
+            .collect()
+    }
+}
+
+pub fn to_smart_permission(permission: &smart::SmartPermission) -> SmartPermission {
+    SmartPermission {
+        org_id: permission.get_org_id().to_string(),
+        name: permission.get_name().to_string(),
+        address: compute_smart_permission_address(permission.get_org_id(), permission.get_name())
+    }
+}
@@ -0,0 +1,234 @@
+// Copyright (c) The dgc.network
+// SPDX-License-Identifier: Apache-2.0
+
+//! Read access to Smart state, served either from the Postgres database kept
+//! up to date by the state delta export, or directly from the validator
+
+pub mod decode;
+pub mod validator;
+
+use std::fmt;
+use std::str::FromStr;
+
+use protobuf;
+use rocket::request::{self, FromRequest};
+use rocket::{Request, State};
+
+use dgc_contract_sdk::protos::ProtoConversionError;
+use pike_db as db;
+use pike_db::{NotFound, QueryError};
+use pike_db::models::{Account, Contract, NamespaceRegistry, Organization, SmartPermission};
+
+use guard::db_conn::DbConn;
+use guard::validator_conn::ValidatorConn;
+use submit::TransactionError;
+
+/// Where the read routes get Smart state from, selected with the
+/// `STATE_SOURCE` environment variable
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StateSource {
+    /// The Postgres database at `DATABASE_URL`
+    Database,
+    /// The validator at `VALIDATOR_URL`
+    Validator
+}
+
+impl FromStr for StateSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "database" | "postgres" => Ok(StateSource::Database),
+            "validator" => Ok(StateSource::Validator),
+            _ => Err(format!(
+                "Invalid state source \"{}\", expected \"database\" or \"validator\"", s
+            ))
+        }
+    }
+}
+
+/// Request guard reading Smart state from the configured state source
+pub enum StateReader {
+    Database(DbConn),
+    Validator(ValidatorConn)
+}
+
+impl<'a, 'r> FromRequest<'a, 'r> for StateReader {
+    type Error = ();
+
+    fn from_request(request: &'a Request<'r>) -> request::Outcome<StateReader, ()> {
+        match *request.guard::<State<StateSource>>()? {
+            StateSource::Database => request.guard::<DbConn>().map(StateReader::Database),
+            StateSource::Validator => request.guard::<ValidatorConn>().map(StateReader::Validator)
+        }
+    }
+}
+
+impl StateReader {
+    pub fn get_account(&self, public_key: &str) -> Result<Account, ReadError> {
+        match self {
+            StateReader::Database(conn) => Ok(db::get_account(conn, public_key)?),
+            StateReader::Validator(conn) => validator::get_account(&mut conn.0.clone(), public_key)
+        }
+    }
+
+    pub fn get_accounts(&self) -> Result<Vec<Account>, ReadError> {
+        match self {
+            StateReader::Database(conn) => Ok(db::get_accounts(conn)?),
+            StateReader::Validator(conn) => validator::get_accounts(&mut conn.0.clone())
+        }
+    }
+
+    pub fn get_org(&self, id: &str) -> Result<Organization, ReadError> {
+        match self {
+            StateReader::Database(conn) => Ok(db::get_org(conn, id)?),
+            StateReader::Validator(conn) => validator::get_org(&mut conn.0.clone(), id)
+        }
+    }
+
+    pub fn get_orgs(&self) -> Result<Vec<Organization>, ReadError> {
+        match self {
+            StateReader::Database(conn) => Ok(db::get_orgs(conn)?),
+            StateReader::Validator(conn) => validator::get_orgs(&mut conn.0.clone())
+        }
+    }
+
+    pub fn get_contract(&self, name: &str, version: &str) -> Result<Contract, ReadError> {
+        match self {
+            StateReader::Database(conn) => Ok(db::get_contract(conn, name, version)?),
+            StateReader::Validator(conn) =>
+                validator::get_contract(&mut conn.0.clone(), name, version)
+        }
+    }
+
+    pub fn get_contract_versions(&self, name: &str) -> Result<Vec<Contract>, ReadError> {
+        match self {
+            StateReader::Database(conn) => Ok(db::get_contract_versions(conn, name)?),
+            StateReader::Validator(conn) =>
+                validator::get_contract_versions(&mut conn.0.clone(), name)
+        }
+    }
+
+    pub fn get_contracts(&self) -> Result<Vec<Contract>, ReadError> {
+        match self {
+            StateReader::Database(conn) => Ok(db::get_contracts(conn)?),
+            StateReader::Validator(conn) => validator::get_contracts(&mut conn.0.clone())
+        }
+    }
+
+    pub fn get_namespace_registry(&self, namespace: &str) -> Result<NamespaceRegistry, ReadError> {
+        match self {
+            StateReader::Database(conn) => Ok(db::get_namespace_registry(conn, namespace)?),
+            StateReader::Validator(conn) =>
+                validator::get_namespace_registry(&mut conn.0.clone(), namespace)
+        }
+    }
+
+    pub fn get_namespace_registries(&self) -> Result<Vec<NamespaceRegistry>, ReadError> {
+        match self {
+            StateReader::Database(conn) => Ok(db::get_namespace_registries(conn)?),
+            StateReader::Validator(conn) =>
+                validator::get_namespace_registries(&mut conn.0.clone())
+        }
+    }
+
+    pub fn get_smart_permission(
+        &self,
+        org_id: &str,
+        name: &str
+    ) -> Result<SmartPermission, ReadError> {
+        match self {
+            StateReader::Database(conn) => Ok(db::get_smart_permission(conn, org_id, name)?),
+            StateReader::Validator(conn) =>
+                validator::get_smart_permission(&mut conn.0.clone(), org_id, name)
+        }
+    }
+
+    pub fn get_org_smart_permissions(
+        &self,
+        org_id: &str
+    ) -> Result<Vec<SmartPermission>, ReadError> {
+        match self {
+            StateReader::Database(conn) => Ok(db::get_org_smart_permissions(conn, org_id)?),
+            StateReader::Validator(conn) =>
+                validator::get_org_smart_permissions(&mut conn.0.clone(), org_id)
+        }
+    }
+
+    pub fn get_smart_permissions(&self) -> Result<Vec<SmartPermission>, ReadError> {
+        match self {
+            StateReader::Database(conn) => Ok(db::get_smart_permissions(conn)?),
+            StateReader::Validator(conn) => validator::get_smart_permissions(&mut conn.0.clone())
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ReadError {
+    NotFound,
+    QueryError(QueryError),
+    StateError(StateError)
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ReadError::NotFound => write!(f, "Not found"),
+            ReadError::QueryError(ref err) => write!(f, "{}", err),
+            ReadError::StateError(ref err) => write!(f, "{}", err)
+        }
+    }
+}
+
+impl From<QueryError> for ReadError {
+    fn from(err: QueryError) -> Self {
+        match err {
+            NotFound => ReadError::NotFound,
+            err => ReadError::QueryError(err)
+        }
+    }
+}
+
+impl From<StateError> for ReadError {
+    fn from(err: StateError) -> Self {
+        ReadError::StateError(err)
+    }
+}
+
+#[derive(Debug)]
+pub enum StateError {
+    RequestError(TransactionError),
+    ProtobufError(protobuf::ProtobufError),
+    DecodeError(ProtoConversionError),
+    ValidatorError(String)
+}
+
+impl fmt::Display for StateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            StateError::RequestError(ref err) => write!(f, "Request failed: {:?}", err),
+            StateError::ProtobufError(ref err) => write!(f, "Invalid response: {}", err),
+            StateError::DecodeError(ref err) => write!(f, "Invalid state entry: {}", err),
+            StateError::ValidatorError(ref status) =>
+                write!(f, "Validator responded with {}", status)
+        }
+    }
+}
+
+impl From<TransactionError> for StateError {
+    fn from(err: TransactionError) -> Self {
+        StateError::RequestError(err)
+    }
+}
+
+impl From<protobuf::ProtobufError> for StateError {
+    fn from(err: protobuf::ProtobufError) -> Self {
+        StateError::ProtobufError(err)
+    }
+}
+
+impl From<ProtoConversionError> for StateError {
+    fn from(err: ProtoConversionError) -> Self {
+        StateError::DecodeError(err)
+    }
+}
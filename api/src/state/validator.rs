@@ -0,0 +1,239 @@
+// Copyright (c) The dgc.network
+// SPDX-License-Identifier: Apache-2.0
+
+//! Reads Smart state directly from the validator and decodes it into the
+//! same models the Postgres database serves
+
+use protobuf;
+
+use sawtooth_sdk::messages::client_list_control::ClientPagingControls;
+use sawtooth_sdk::messages::client_state::ClientStateGetResponse_Status as get_status;
+use sawtooth_sdk::messages::client_state::ClientStateListResponse_Status as list_status;
+use sawtooth_sdk::messages::client_state::{
+    ClientStateGetRequest,
+    ClientStateGetResponse,
+    ClientStateListRequest,
+    ClientStateListResponse
+};
+use sawtooth_sdk::messages::validator::Message_MessageType;
+use sawtooth_sdk::messaging::zmq_stream::ZmqMessageSender;
+
+use dgc_contract_sdk::addressing::*;
+use dgc_contract_sdk::protocol::state as smart;
+use dgc_contract_sdk::protos::FromBytes;
+use pike_db::models::{Account, Contract, NamespaceRegistry, Organization, SmartPermission};
+
+use state::decode::*;
+use state::{ReadError, StateError};
+use submit::send;
+
+/// Number of entries requested per page when listing state
+const PAGE_SIZE: i32 = 100;
+
+pub fn get_account(
+    sender: &mut ZmqMessageSender,
+    public_key: &str
+) -> Result<Account, ReadError> {
+    get_entry::<smart::AccountList>(sender, &compute_account_address(public_key))?
+        .and_then(|list| list
+            .get_accounts()
+            .iter()
+            .find(|account| account.get_public_key() == public_key)
+            .map(to_account))
+        .ok_or(ReadError::NotFound)
+}
+
+pub fn get_accounts(sender: &mut ZmqMessageSender) -> Result<Vec<Account>, ReadError> {
+    Ok(list_entries::<smart::AccountList>(sender, ACCOUNT_PREFIX)?
+        .iter()
+        .flat_map(|list| list.get_accounts().iter().map(to_account))
+        .collect())
+}
+
+pub fn get_org(sender: &mut ZmqMessageSender, id: &str) -> Result<Organization, ReadError> {
+    get_entry::<smart::OrganizationList>(sender, &compute_org_address(id))?
+        .and_then(|list| list
+            .get_organizations()
+            .iter()
+            .find(|org| org.get_org_id() == id)
+            .map(to_org))
+        .ok_or(ReadError::NotFound)
+}
+
+pub fn get_orgs(sender: &mut ZmqMessageSender) -> Result<Vec<Organization>, ReadError> {
+    Ok(list_entries::<smart::OrganizationList>(sender, ORGANIZATION_PREFIX)?
+        .iter()
+        .flat_map(|list| list.get_organizations().iter().map(to_org))
+        .collect())
+}
+
+pub fn get_contract(
+    sender: &mut ZmqMessageSender,
+    name: &str,
+    version: &str
+) -> Result<Contract, ReadError> {
+    get_entry::<smart::ContractList>(sender, &compute_contract_address(name, version))?
+        .and_then(|list| list
+            .get_contracts()
+            .iter()
+            .find(|contract| contract.get_name() == name && contract.get_version() == version)
+            .map(to_contract))
+        .ok_or(ReadError::NotFound)
+}
+
+/// Returns every version of a contract, in the order they are listed in the
+/// contract's registry
+pub fn get_contract_versions(
+    sender: &mut ZmqMessageSender,
+    name: &str
+) -> Result<Vec<Contract>, ReadError> {
+    let address = compute_contract_registry_address(name);
+    let versions: Vec<String> = match get_entry::<smart::ContractRegistryList>(sender, &address)? {
+        Some(list) => list
+            .get_registries()
+            .iter()
+            .filter(|registry| registry.get_name() == name)
+            .flat_map(|registry| registry.get_versions().iter())
+            .map(|version| version.get_version().to_string())
+            .collect(),
+        None => return Ok(Vec::new())
+    };
+
+    let mut contracts = Vec::new();
+    for version in versions {
+        match get_contract(sender, name, &version) {
+            Ok(contract) => contracts.push(contract),
+            Err(ReadError::NotFound) => (),
+            Err(err) => return Err(err)
+        }
+    }
+
+    Ok(contracts)
+}
+
+pub fn get_contracts(sender: &mut ZmqMessageSender) -> Result<Vec<Contract>, ReadError> {
+    Ok(list_entries::<smart::ContractList>(sender, CONTRACT_PREFIX)?
+        .iter()
+        .flat_map(|list| list.get_contracts().iter().map(to_contract))
+        .collect())
+}
+
+pub fn get_namespace_registry(
+    sender: &mut ZmqMessageSender,
+    namespace: &str
+) -> Result<NamespaceRegistry, ReadError> {
+    let address = compute_namespace_registry_address(namespace).map_err(|_| ReadError::NotFound)?;
+    get_entry::<smart::NamespaceRegistryList>(sender, &address)?
+        .and_then(|list| list
+            .get_registries()
+            .iter()
+            .find(|registry| registry.get_namespace() == namespace)
+            .map(to_namespace_registry))
+        .ok_or(ReadError::NotFound)
+}
+
+pub fn get_namespace_registries(
+    sender: &mut ZmqMessageSender
+) -> Result<Vec<NamespaceRegistry>, ReadError> {
+    Ok(list_entries::<smart::NamespaceRegistryList>(sender, NAMESPACE_REGISTRY_PREFIX)?
+        .iter()
+        .flat_map(|list| list.get_registries().iter().map(to_namespace_registry))
+        .collect())
+}
+
+pub fn get_smart_permission(
+    sender: &mut ZmqMessageSender,
+    org_id: &str,
+    name: &str
+) -> Result<SmartPermission, ReadError> {
+    let address = compute_smart_permission_address(org_id, name);
+    get_entry::<smart::SmartPermissionList>(sender, &address)?
+        .and_then(|list| list
+            .get_smart_permissions()
+            .iter()
+            .find(|permission| permission.get_org_id() == org_id && permission.get_name() == name)
+            .map(to_smart_permission))
+        .ok_or(ReadError::NotFound)
+}
+
+pub fn get_org_smart_permissions(
+    sender: &mut ZmqMessageSender,
+    org_id: &str
+) -> Result<Vec<SmartPermission>, ReadError> {
+    let prefix = compute_org_smart_permissions_prefix(org_id);
+    Ok(list_entries::<smart::SmartPermissionList>(sender, &prefix)?
+        .iter()
+        .flat_map(|list| list.get_smart_permissions().iter())
+        .filter(|permission| permission.get_org_id() == org_id)
+        .map(to_smart_permission)
+        .collect())
+}
+
+pub fn get_smart_permissions(
+    sender: &mut ZmqMessageSender
+) -> Result<Vec<SmartPermission>, ReadError> {
+    Ok(list_entries::<smart::SmartPermissionList>(sender, SMART_PERMISSION_PREFIX)?
+        .iter()
+        .flat_map(|list| list.get_smart_permissions().iter().map(to_smart_permission))
+        .collect())
+}
+
+/// Fetches and decodes the state entry at `address`, or returns None if the
+/// address is not set
+fn get_entry<L: FromBytes<L>>(
+    sender: &mut ZmqMessageSender,
+    address: &str
+) -> Result<Option<L>, StateError> {
+    let mut request = ClientStateGetRequest::new();
+    request.set_address(address.to_string());
+    let request_bytes = protobuf::Message::write_to_bytes(&request)?;
+
+    let response = send(sender, Message_MessageType::CLIENT_STATE_GET_REQUEST, &request_bytes)?;
+    let response: ClientStateGetResponse = protobuf::parse_from_bytes(&response.content)?;
+
+    match response.status {
+        get_status::OK => Ok(Some(L::from_bytes(&response.value)?)),
+        get_status::NO_RESOURCE => Ok(None),
+        status => Err(StateError::ValidatorError(format!("{:?}", status)))
+    }
+}
+
+/// Fetches and decodes every state entry under `prefix`, following the
+/// validator's paging until all entries are read
+fn list_entries<L: FromBytes<L>>(
+    sender: &mut ZmqMessageSender,
+    prefix: &str
+) -> Result<Vec<L>, StateError> {
+    let mut entries = Vec::new();
+    let mut start = String::new();
+
+    loop {
+        let mut paging = ClientPagingControls::new();
+        paging.set_start(start);
+        paging.set_limit(PAGE_SIZE);
+
+        let mut request = ClientStateListRequest::new();
+        request.set_address(prefix.to_string());
+        request.set_paging(paging);
+        let request_bytes = protobuf::Message::write_to_bytes(&request)?;
+
+        let response =
+            send(sender, Message_MessageType::CLIENT_STATE_LIST_REQUEST, &request_bytes)?;
+        let response: ClientStateListResponse = protobuf::parse_from_bytes(&response.content)?;
+
+        match response.status {
+            list_status::OK => (),
+            list_status::NO_RESOURCE => return Ok(entries),
+            status => return Err(StateError::ValidatorError(format!("{:?}", status)))
+        }
+
+        for entry in response.get_entries() {
+            entries.push(L::from_bytes(entry.get_data())?);
+        }
+
+        start = response.get_paging().get_next().to_string();
+        if start.is_empty() {
+            return Ok(entries);
+        }
+    }
+}
@@ -1,8 +1,6 @@
 // Copyright (c) The dgc.network
 // SPDX-License-Identifier: Apache-2.0
 
-#![feature(rustc_private)]
-
 use protobuf;
 use uuid;
 
@@ -85,7 +83,7 @@ pub fn check_batch_status(
 
 }
 
-fn send(
+pub fn send(
     sender: &mut ZmqMessageSender,
     message_type: Message_MessageType,
     content: &[u8]
@@ -0,0 +1,505 @@
+// Copyright (c) The dgc.network
+// SPDX-License-Identifier: Apache-2.0
+
+//! JSON descriptions of the Smart payload actions accepted by the
+//! transaction building routes
+//!
+//! Fields are named as in the Smart protobuf messages. Binary fields
+//! (contracts, contract payloads and smart permission functions) are given as
+//! hex strings.
+
+use dgc_contract_sdk::protocol::payload::*;
+use dgc_contract_sdk::protocol::state::{
+    ContractSource,
+    ContractSourceBuilder,
+    KeyValueEntry,
+    KeyValueEntryBuilder
+};
+
+use transaction::{from_hex, invalid, BuildError};
+
+#[derive(Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ActionRequest {
+    CreateContract {
+        name: String,
+        version: String,
+        #[serde(default)]
+        inputs: Vec<String>,
+        #[serde(default)]
+        outputs: Vec<String>,
+        contract: String,
+        source: Option<SourceRequest>
+    },
+    DeleteContract {
+        name: String,
+        version: String
+    },
+    ExecuteContract {
+        name: String,
+        version: String,
+        #[serde(default)]
+        inputs: Vec<String>,
+        #[serde(default)]
+        outputs: Vec<String>,
+        payload: String
+    },
+    UpgradeContract {
+        name: String,
+        version: String,
+        previous_version: String,
+        #[serde(default)]
+        inputs: Vec<String>,
+        #[serde(default)]
+        outputs: Vec<String>,
+        contract: String,
+        #[serde(default)]
+        migrate_payload: String,
+        source: Option<SourceRequest>
+    },
+    CreateContractRegistry {
+        name: String,
+        owners: Vec<String>,
+        #[serde(default)]
+        owner_threshold: u32
+    },
+    DeleteContractRegistry {
+        name: String
+    },
+    UpdateContractRegistryOwners {
+        name: String,
+        owners: Vec<String>
+    },
+    CreateNamespaceRegistry {
+        namespace: String,
+        owners: Vec<String>,
+        #[serde(default)]
+        owner_threshold: u32
+    },
+    DeleteNamespaceRegistry {
+        namespace: String
+    },
+    UpdateNamespaceRegistryOwners {
+        namespace: String,
+        owners: Vec<String>
+    },
+    CreateNamespaceRegistryPermission {
+        namespace: String,
+        contract_name: String,
+        read: bool,
+        write: bool,
+        #[serde(default)]
+        prefix: String,
+        #[serde(default)]
+        deny: bool
+    },
+    DeleteNamespaceRegistryPermission {
+        namespace: String,
+        contract_name: String,
+        #[serde(default)]
+        prefix: String
+    },
+    CreateSmartPermission {
+        org_id: String,
+        name: String,
+        function: String
+    },
+    UpdateSmartPermission {
+        org_id: String,
+        name: String,
+        function: String
+    },
+    DeleteSmartPermission {
+        org_id: String,
+        name: String
+    },
+    CreateAccount {
+        org_id: String,
+        public_key: String,
+        #[serde(default)]
+        roles: Vec<String>,
+        #[serde(default)]
+        metadata: Vec<MetadataRequest>
+    },
+    UpdateAccount {
+        org_id: String,
+        public_key: String,
+        #[serde(default)]
+        roles: Vec<String>,
+        #[serde(default)]
+        metadata: Vec<MetadataRequest>,
+        metadata_mode: Option<MetadataModeRequest>
+    },
+    DeactivateAccount {
+        org_id: String,
+        public_key: String
+    },
+    ReactivateAccount {
+        org_id: String,
+        public_key: String
+    },
+    CreateOrganization {
+        id: String,
+        name: String,
+        address: String,
+        #[serde(default)]
+        metadata: Vec<MetadataRequest>
+    },
+    UpdateOrganization {
+        id: String,
+        name: String,
+        address: String,
+        #[serde(default)]
+        metadata: Vec<MetadataRequest>,
+        metadata_mode: Option<MetadataModeRequest>
+    },
+    GrantRole {
+        org_id: String,
+        public_key: String,
+        role: String
+    },
+    RevokeRole {
+        org_id: String,
+        public_key: String,
+        role: String
+    },
+    CreateRole {
+        org_id: String,
+        name: String,
+        #[serde(default)]
+        permissions: Vec<String>
+    },
+    UpdateRole {
+        org_id: String,
+        name: String,
+        #[serde(default)]
+        permissions: Vec<String>
+    },
+    DeleteRole {
+        org_id: String,
+        name: String
+    },
+    ProposeOwnerChange {
+        registry_type: RegistryTypeRequest,
+        name: String,
+        owners: Vec<String>,
+        #[serde(default)]
+        owner_threshold: u32,
+        expiry_blocks: u64
+    },
+    VoteOwnerChange {
+        registry_type: RegistryTypeRequest,
+        name: String,
+        approve: bool
+    },
+    SetActionPolicy {
+        org_id: String,
+        action: String,
+        #[serde(default)]
+        smart_permission: String
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct SourceRequest {
+    repository: String,
+    commit: String,
+    #[serde(default)]
+    toolchain: String,
+    #[serde(default)]
+    wasm_sha512: String
+}
+
+#[derive(Deserialize, Debug)]
+pub struct MetadataRequest {
+    key: String,
+    value: String
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum MetadataModeRequest {
+    Merge,
+    Replace,
+    Remove
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum RegistryTypeRequest {
+    ContractRegistry,
+    NamespaceRegistry
+}
+
+impl ActionRequest {
+    /// Builds the described action, checking it the same way the Smart
+    /// builders do
+    pub fn into_action(self) -> Result<Action, BuildError> {
+        let action = match self {
+            ActionRequest::CreateContract {
+                name, version, inputs, outputs, contract, source
+            } => Action::CreateContract(CreateContractActionBuilder::new()
+                .set_name(name)
+                .set_version(version)
+                .set_inputs(inputs)
+                .set_outputs(outputs)
+                .set_contract(from_hex(&contract, "contract")?)
+                .set_source(into_source(source)?)
+                .build()
+                .map_err(invalid)?),
+            ActionRequest::DeleteContract { name, version } =>
+                Action::DeleteContract(DeleteContractActionBuilder::new()
+                    .set_name(name)
+                    .set_version(version)
+                    .build()
+                    .map_err(invalid)?),
+            ActionRequest::ExecuteContract { name, version, inputs, outputs, payload } =>
+                Action::ExecuteContract(ExecuteContractActionBuilder::new()
+                    .set_name(name)
+                    .set_version(version)
+                    .set_inputs(inputs)
+                    .set_outputs(outputs)
+                    .set_payload(from_hex(&payload, "payload")?)
+                    .build()
+                    .map_err(invalid)?),
+            ActionRequest::UpgradeContract {
+                name, version, previous_version, inputs, outputs, contract, migrate_payload, source
+            } => Action::UpgradeContract(UpgradeContractActionBuilder::new()
+                .set_name(name)
+                .set_version(version)
+                .set_previous_version(previous_version)
+                .set_inputs(inputs)
+                .set_outputs(outputs)
+                .set_contract(from_hex(&contract, "contract")?)
+                .set_migrate_payload(from_hex(&migrate_payload, "migrate_payload")?)
+                .set_source(into_source(source)?)
+                .build()
+                .map_err(invalid)?),
+            ActionRequest::CreateContractRegistry { name, owners, owner_threshold } =>
+                Action::CreateContractRegistry(CreateContractRegistryActionBuilder::new()
+                    .set_name(name)
+                    .set_owners(owners)
+                    .set_owner_threshold(owner_threshold)
+                    .build()
+                    .map_err(invalid)?),
+            ActionRequest::DeleteContractRegistry { name } =>
+                Action::DeleteContractRegistry(DeleteContractRegistryActionBuilder::new()
+                    .set_name(name)
+                    .build()
+                    .map_err(invalid)?),
+            ActionRequest::UpdateContractRegistryOwners { name, owners } =>
+                Action::UpdateContractRegistryOwners(
+                    UpdateContractRegistryOwnersActionBuilder::new()
+                        .set_name(name)
+                        .set_owners(owners)
+                        .build()
+                        .map_err(invalid)?),
+            ActionRequest::CreateNamespaceRegistry { namespace, owners, owner_threshold } =>
+                Action::CreateNamespaceRegistry(CreateNamespaceRegistryActionBuilder::new()
+                    .set_namespace(namespace)
+                    .set_owners(owners)
+                    .set_owner_threshold(owner_threshold)
+                    .build()
+                    .map_err(invalid)?),
+            ActionRequest::DeleteNamespaceRegistry { namespace } =>
+                Action::DeleteNamespaceRegistry(DeleteNamespaceRegistryActionBuilder::new()
+                    .set_namespace(namespace)
+                    .build()
+                    .map_err(invalid)?),
+            ActionRequest::UpdateNamespaceRegistryOwners { namespace, owners } =>
+                Action::UpdateNamespaceRegistryOwners(
+                    UpdateNamespaceRegistryOwnersActionBuilder::new()
+                        .set_namespace(namespace)
+                        .set_owners(owners)
+                        .build()
+                        .map_err(invalid)?),
+            ActionRequest::CreateNamespaceRegistryPermission {
+                namespace, contract_name, read, write, prefix, deny
+            } => Action::CreateNamespaceRegistryPermission(
+                CreateNamespaceRegistryPermissionActionBuilder::new()
+                    .set_namespace(namespace)
+                    .set_contract_name(contract_name)
+                    .set_read(read)
+                    .set_write(write)
+                    .set_prefix(prefix)
+                    .set_deny(deny)
+                    .build()
+                    .map_err(invalid)?),
+            ActionRequest::DeleteNamespaceRegistryPermission { namespace, contract_name, prefix } =>
+                Action::DeleteNamespaceRegistryPermission(
+                    DeleteNamespaceRegistryPermissionActionBuilder::new()
+                        .set_namespace(namespace)
+                        .set_contract_name(contract_name)
+                        .set_prefix(prefix)
+                        .build()
+                        .map_err(invalid)?),
+            ActionRequest::CreateSmartPermission { org_id, name, function } =>
+                Action::CreateSmartPermission(CreateSmartPermissionActionBuilder::new()
+                    .set_org_id(org_id)
+                    .set_name(name)
+                    .set_function(from_hex(&function, "function")?)
+                    .build()
+                    .map_err(invalid)?),
+            ActionRequest::UpdateSmartPermission { org_id, name, function } =>
+                Action::UpdateSmartPermission(UpdateSmartPermissionActionBuilder::new()
+                    .set_org_id(org_id)
+                    .set_name(name)
+                    .set_function(from_hex(&function, "function")?)
+                    .build()
+                    .map_err(invalid)?),
+            ActionRequest::DeleteSmartPermission { org_id, name } =>
+                Action::DeleteSmartPermission(DeleteSmartPermissionActionBuilder::new()
+                    .set_org_id(org_id)
+                    .set_name(name)
+                    .build()
+                    .map_err(invalid)?),
+            ActionRequest::CreateAccount { org_id, public_key, roles, metadata } =>
+                Action::CreateAccount(CreateAccountActionBuilder::new()
+                    .set_org_id(org_id)
+                    .set_public_key(public_key)
+                    .set_roles(roles)
+                    .set_metadata(into_metadata(metadata)?)
+                    .build()
+                    .map_err(invalid)?),
+            ActionRequest::UpdateAccount {
+                org_id, public_key, roles, metadata, metadata_mode
+            } => Action::UpdateAccount(UpdateAccountActionBuilder::new()
+                .set_org_id(org_id)
+                .set_public_key(public_key)
+                .set_roles(roles)
+                .set_metadata(into_metadata(metadata)?)
+                .set_metadata_mode(into_metadata_mode(metadata_mode))
+                .build()
+                .map_err(invalid)?),
+            ActionRequest::DeactivateAccount { org_id, public_key } =>
+                Action::DeactivateAccount(DeactivateAccountActionBuilder::new()
+                    .set_org_id(org_id)
+                    .set_public_key(public_key)
+                    .build()
+                    .map_err(invalid)?),
+            ActionRequest::ReactivateAccount { org_id, public_key } =>
+                Action::ReactivateAccount(ReactivateAccountActionBuilder::new()
+                    .set_org_id(org_id)
+                    .set_public_key(public_key)
+                    .build()
+                    .map_err(invalid)?),
+            ActionRequest::CreateOrganization { id, name, address, metadata } =>
+                Action::CreateOrganization(CreateOrganizationActionBuilder::new()
+                    .set_id(id)
+                    .set_name(name)
+                    .set_address(address)
+                    .set_metadata(into_metadata(metadata)?)
+                    .build()
+                    .map_err(invalid)?),
+            ActionRequest::UpdateOrganization { id, name, address, metadata, metadata_mode } =>
+                Action::UpdateOrganization(UpdateOrganizationActionBuilder::new()
+                    .set_id(id)
+                    .set_name(name)
+                    .set_address(address)
+                    .set_metadata(into_metadata(metadata)?)
+                    .set_metadata_mode(into_metadata_mode(metadata_mode))
+                    .build()
+                    .map_err(invalid)?),
+            ActionRequest::GrantRole { org_id, public_key, role } =>
+                Action::GrantRole(GrantRoleActionBuilder::new()
+                    .set_org_id(org_id)
+                    .set_public_key(public_key)
+                    .set_role(role)
+                    .build()
+                    .map_err(invalid)?),
+            ActionRequest::RevokeRole { org_id, public_key, role } =>
+                Action::RevokeRole(RevokeRoleActionBuilder::new()
+                    .set_org_id(org_id)
+                    .set_public_key(public_key)
+                    .set_role(role)
+                    .build()
+                    .map_err(invalid)?),
+            ActionRequest::CreateRole { org_id, name, permissions } =>
+                Action::CreateRole(CreateRoleActionBuilder::new()
+                    .set_org_id(org_id)
+                    .set_name(name)
+                    .set_permissions(permissions)
+                    .build()
+                    .map_err(invalid)?),
+            ActionRequest::UpdateRole { org_id, name, permissions } =>
+                Action::UpdateRole(UpdateRoleActionBuilder::new()
+                    .set_org_id(org_id)
+                    .set_name(name)
+                    .set_permissions(permissions)
+                    .build()
+                    .map_err(invalid)?),
+            ActionRequest::DeleteRole { org_id, name } =>
+                Action::DeleteRole(DeleteRoleActionBuilder::new()
+                    .set_org_id(org_id)
+                    .set_name(name)
+                    .build()
+                    .map_err(invalid)?),
+            ActionRequest::ProposeOwnerChange {
+                registry_type, name, owners, owner_threshold, expiry_blocks
+            } => Action::ProposeOwnerChange(ProposeOwnerChangeActionBuilder::new()
+                .set_registry_type(into_registry_type(registry_type))
+                .set_name(name)
+                .set_owners(owners)
+                .set_owner_threshold(owner_threshold)
+                .set_expiry_blocks(expiry_blocks)
+                .build()
+                .map_err(invalid)?),
+            ActionRequest::VoteOwnerChange { registry_type, name, approve } =>
+                Action::VoteOwnerChange(VoteOwnerChangeActionBuilder::new()
+                    .set_registry_type(into_registry_type(registry_type))
+                    .set_name(name)
+                    .set_approve(approve)
+                    .build()
+                    .map_err(invalid)?),
+            ActionRequest::SetActionPolicy { org_id, action, smart_permission } =>
+                Action::SetActionPolicy(SetActionPolicyActionBuilder::new()
+                    .set_org_id(org_id)
+                    .set_action(action)
+                    .set_smart_permission(smart_permission)
+                    .build()
+                    .map_err(invalid)?)
+        };
+
+        Ok(action)
+    }
+}
+
+fn into_source(source: Option<SourceRequest>) -> Result<Option<ContractSource>, BuildError> {
+    match source {
+        Some(source) => ContractSourceBuilder::new()
+            .set_repository(source.repository)
+            .set_commit(source.commit)
+            .set_toolchain(source.toolchain)
+            .set_wasm_sha512(source.wasm_sha512)
+            .build()
+            .map(Some)
+            .map_err(invalid),
+        None => Ok(None)
+    }
+}
+
+fn into_metadata(metadata: Vec<MetadataRequest>) -> Result<Vec<KeyValueEntry>, BuildError> {
+    metadata
+        .into_iter()
+        .map(|entry| KeyValueEntryBuilder::new()
+            .set_key(entry.key)
+            .set_value(entry.value)
+            .build()
+            .map_err(invalid))
+        .collect()
+}
+
+fn into_metadata_mode(mode: Option<MetadataModeRequest>) -> MetadataUpdateMode {
+    match mode {
+        Some(MetadataModeRequest::Merge) | None => MetadataUpdateMode::Merge,
+        Some(MetadataModeRequest::Replace) => MetadataUpdateMode::Replace,
+        Some(MetadataModeRequest::Remove) => MetadataUpdateMode::Remove
+    }
+}
+
+fn into_registry_type(registry_type: RegistryTypeRequest) -> RegistryType {
+    match registry_type {
+        RegistryTypeRequest::ContractRegistry => RegistryType::ContractRegistry,
+        RegistryTypeRequest::NamespaceRegistry => RegistryType::NamespaceRegistry
+    }
+}
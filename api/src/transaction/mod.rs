@@ -0,0 +1,233 @@
+// Copyright (c) The dgc.network
+// SPDX-License-Identifier: Apache-2.0
+
+//! Builds unsigned Smart transactions and batches for clients that sign them
+//! with their own keys
+//!
+//! Building a batch takes three requests. The client sends an action and
+//! gets back a transaction header to sign. It then sends the signed
+//! transactions and gets back a batch header to sign. Finally it sends the
+//! signed batch, which is submitted to the validator. Headers, payloads and
+//! signatures are exchanged as hex strings, and private keys never leave the
+//! client.
+
+pub mod action;
+
+use std::fmt;
+
+use crypto::digest::Digest;
+use crypto::sha2::Sha512;
+use protobuf;
+use protobuf::Message;
+use uuid::Uuid;
+
+use dgc_contract_sdk::addressing::{compute_addresses, AddressingError};
+use dgc_contract_sdk::protocol::payload::SmartPayloadBuilder;
+use dgc_contract_sdk::protos::{IntoBytes, ProtoConversionError};
+use sawtooth_sdk::messages::batch::{Batch, BatchHeader, BatchList};
+use sawtooth_sdk::messages::transaction::{Transaction, TransactionHeader};
+
+use self::action::ActionRequest;
+
+/// The Sawtooth Smart transaction family name (smart)
+const SMART_FAMILY_NAME: &str = "smart";
+
+/// The Sawtooth Smart transaction family version (0.4)
+const SMART_FAMILY_VERSION: &str = "0.4";
+
+#[derive(Deserialize, Debug)]
+pub struct TransactionRequest {
+    /// Public key of the key that will sign the transaction
+    signer_public_key: String,
+    /// Public key of the key that will sign the batch, if it is not the
+    /// transaction signer
+    batcher_public_key: Option<String>,
+    action: ActionRequest
+}
+
+#[derive(Serialize, Debug)]
+pub struct UnsignedTransaction {
+    /// The serialized TransactionHeader, to be signed by the client
+    header: String,
+    payload: String,
+    inputs: Vec<String>,
+    outputs: Vec<String>
+}
+
+#[derive(Deserialize, Debug)]
+pub struct SignedTransaction {
+    header: String,
+    header_signature: String,
+    payload: String
+}
+
+#[derive(Deserialize, Debug)]
+pub struct BatchRequest {
+    /// Public key of the key that will sign the batch
+    signer_public_key: String,
+    transactions: Vec<SignedTransaction>
+}
+
+#[derive(Serialize, Debug)]
+pub struct UnsignedBatch {
+    /// The serialized BatchHeader, to be signed by the client
+    header: String
+}
+
+#[derive(Deserialize, Debug)]
+pub struct SignedBatch {
+    header: String,
+    header_signature: String,
+    transactions: Vec<SignedTransaction>
+}
+
+/// Builds the payload and unsigned header of a transaction carrying the
+/// requested action
+pub fn build_transaction(request: TransactionRequest) -> Result<UnsignedTransaction, BuildError> {
+    let action = request.action.into_action()?;
+    let (inputs, outputs) = compute_addresses(&action, &request.signer_public_key)?;
+
+    let payload = SmartPayloadBuilder::new()
+        .set_action(action)
+        .build()
+        .map_err(invalid)?
+        .into_bytes()?;
+
+    let mut sha = Sha512::new();
+    sha.input(&payload);
+
+    let mut header = TransactionHeader::new();
+    header.set_family_name(String::from(SMART_FAMILY_NAME));
+    header.set_family_version(String::from(SMART_FAMILY_VERSION));
+    header.set_nonce(Uuid::new_v4().to_string());
+    header.set_batcher_public_key(
+        request.batcher_public_key.unwrap_or_else(|| request.signer_public_key.clone()));
+    header.set_signer_public_key(request.signer_public_key);
+    header.set_inputs(protobuf::RepeatedField::from_vec(inputs.clone()));
+    header.set_outputs(protobuf::RepeatedField::from_vec(outputs.clone()));
+    header.set_payload_sha512(sha.result_str());
+
+    Ok(UnsignedTransaction {
+        header: to_hex(&header.write_to_bytes()?),
+        payload: to_hex(&payload),
+        inputs,
+        outputs
+    })
+}
+
+/// Builds the unsigned header of a batch of signed transactions
+///
+/// Every transaction must name the batch signer as its batcher.
+pub fn build_batch(request: BatchRequest) -> Result<UnsignedBatch, BuildError> {
+    let mut transaction_ids = Vec::new();
+    for transaction in request.transactions {
+        let header: TransactionHeader =
+            protobuf::parse_from_bytes(&from_hex(&transaction.header, "header")?)
+                .map_err(|_| BuildError::InvalidRequest(
+                    "'header' is not a transaction header".into()))?;
+        if header.get_batcher_public_key() != request.signer_public_key {
+            return Err(BuildError::InvalidRequest(format!(
+                "Transaction {} is not batched by {}",
+                transaction.header_signature, request.signer_public_key
+            )));
+        }
+        transaction_ids.push(transaction.header_signature);
+    }
+
+    if transaction_ids.is_empty() {
+        return Err(BuildError::InvalidRequest("Batch has no transactions".into()));
+    }
+
+    let mut header = BatchHeader::new();
+    header.set_signer_public_key(request.signer_public_key);
+    header.set_transaction_ids(protobuf::RepeatedField::from_vec(transaction_ids));
+
+    Ok(UnsignedBatch {
+        header: to_hex(&header.write_to_bytes()?)
+    })
+}
+
+/// Assembles a signed batch into a serialized BatchList, ready for
+/// `submit::submit_batches`
+pub fn build_batch_list(request: SignedBatch) -> Result<Vec<u8>, BuildError> {
+    let mut transactions = Vec::new();
+    for signed in request.transactions {
+        let mut transaction = Transaction::new();
+        transaction.set_header(from_hex(&signed.header, "header")?);
+        transaction.set_header_signature(signed.header_signature);
+        transaction.set_payload(from_hex(&signed.payload, "payload")?);
+        transactions.push(transaction);
+    }
+
+    let mut batch = Batch::new();
+    batch.set_header(from_hex(&request.header, "header")?);
+    batch.set_header_signature(request.header_signature);
+    batch.set_transactions(protobuf::RepeatedField::from_vec(transactions));
+
+    let mut batch_list = BatchList::new();
+    batch_list.set_batches(protobuf::RepeatedField::from_vec(vec![batch]));
+
+    Ok(batch_list.write_to_bytes()?)
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect::<Vec<_>>()
+        .join("")
+}
+
+fn from_hex(hex: &str, field: &str) -> Result<Vec<u8>, BuildError> {
+    if hex.len() % 2 != 0 || !hex.is_ascii() {
+        return Err(BuildError::InvalidRequest(format!("'{}' is not valid hex", field)));
+    }
+
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16))
+        .collect::<Result<Vec<u8>, _>>()
+        .map_err(|_| BuildError::InvalidRequest(format!("'{}' is not valid hex", field)))
+}
+
+fn invalid<E: fmt::Display>(err: E) -> BuildError {
+    BuildError::InvalidRequest(err.to_string())
+}
+
+#[derive(Debug)]
+pub enum BuildError {
+    /// The request does not describe a valid transaction or batch
+    InvalidRequest(String),
+    ProtobufError(protobuf::ProtobufError),
+    ProtoConversionError(ProtoConversionError)
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            BuildError::InvalidRequest(ref msg) => write!(f, "{}", msg),
+            BuildError::ProtobufError(ref err) => write!(f, "{}", err),
+            BuildError::ProtoConversionError(ref err) => write!(f, "{}", err)
+        }
+    }
+}
+
+impl From<protobuf::ProtobufError> for BuildError {
+    fn from(err: protobuf::ProtobufError) -> Self {
+        BuildError::ProtobufError(err)
+    }
+}
+
+impl From<ProtoConversionError> for BuildError {
+    fn from(err: ProtoConversionError) -> Self {
+        BuildError::ProtoConversionError(err)
+    }
+}
+
+impl From<AddressingError> for BuildError {
+    fn from(err: AddressingError) -> Self {
+        match err {
+            AddressingError::InvalidAddress(msg) => BuildError::InvalidRequest(msg)
+        }
+    }
+}
@@ -0,0 +1,207 @@
+// Copyright (c) The dgc.network
+// SPDX-License-Identifier: Apache-2.0
+
+use dgc_contract_sdk::addressing::compute_account_address;
+use dgc_contract_sdk::protocol::payload::{
+    Action, CreateAccountActionBuilder, DeactivateAccountActionBuilder, GrantRoleActionBuilder,
+    MetadataUpdateMode, ReactivateAccountActionBuilder, RevokeRoleActionBuilder,
+    SmartPayloadBuilder, UpdateAccountActionBuilder,
+};
+use dgc_contract_sdk::protocol::state::{Account, AccountList, KeyValueEntry};
+use dgc_contract_sdk::protos::FromBytes;
+use sawtooth_sdk::signing;
+
+use crate::error::CliError;
+use crate::key;
+use crate::submit::{get_state, submit_batch_list};
+use crate::transaction::{create_batch, create_batch_list_from_one, create_transaction};
+
+pub fn do_create(
+    url: &str,
+    key: Option<&str>,
+    org_id: &str,
+    account_public_key: &str,
+    roles: Vec<String>,
+    metadata: Vec<KeyValueEntry>,
+) -> Result<String, CliError> {
+    let private_key = key::load_signing_key(key)?;
+    let context = signing::create_context("secp256k1")?;
+    let public_key = context.get_public_key(&private_key)?.as_hex();
+    let factory = signing::CryptoFactory::new(&*context);
+    let signer = factory.new_signer(&private_key);
+
+    let action = CreateAccountActionBuilder::new()
+        .set_org_id(org_id.into())
+        .set_public_key(account_public_key.into())
+        .set_roles(roles)
+        .set_metadata(metadata)
+        .build()?;
+
+    let payload = SmartPayloadBuilder::new()
+        .set_action(Action::CreateAccount(action))
+        .build()?;
+
+    let txn = create_transaction(payload, &signer, &public_key)?;
+    let batch = create_batch(txn, &signer, &public_key)?;
+    let batch_list = create_batch_list_from_one(batch);
+
+    submit_batch_list(url, &batch_list)
+}
+
+/// Updates an account, keeping the current organization and roles of the
+/// account for any that are not provided
+pub fn do_update(
+    url: &str,
+    key: Option<&str>,
+    account_public_key: &str,
+    org_id: Option<&str>,
+    roles: Option<Vec<String>>,
+    metadata: Vec<KeyValueEntry>,
+    metadata_mode: MetadataUpdateMode,
+) -> Result<String, CliError> {
+    let private_key = key::load_signing_key(key)?;
+    let context = signing::create_context("secp256k1")?;
+    let public_key = context.get_public_key(&private_key)?.as_hex();
+    let factory = signing::CryptoFactory::new(&*context);
+    let signer = factory.new_signer(&private_key);
+
+    let account = fetch_account(url, account_public_key)?.ok_or_else(|| {
+        CliError::UserError(format!("Account does not exist: {}", account_public_key))
+    })?;
+
+    let action = UpdateAccountActionBuilder::new()
+        .set_org_id(org_id.unwrap_or_else(|| account.get_org_id()).into())
+        .set_public_key(account_public_key.into())
+        .set_roles(roles.unwrap_or_else(|| account.get_roles().to_vec()))
+        .set_metadata(metadata)
+        .set_metadata_mode(metadata_mode)
+        .build()?;
+
+    let payload = SmartPayloadBuilder::new()
+        .set_action(Action::UpdateAccount(action))
+        .build()?;
+
+    let txn = create_transaction(payload, &signer, &public_key)?;
+    let batch = create_batch(txn, &signer, &public_key)?;
+    let batch_list = create_batch_list_from_one(batch);
+
+    submit_batch_list(url, &batch_list)
+}
+
+/// Marks an account active or inactive through `account update --active`,
+/// using the account's current organization unless one is provided
+pub fn do_update_active(
+    url: &str,
+    key: Option<&str>,
+    account_public_key: &str,
+    org_id: Option<&str>,
+    active: bool,
+) -> Result<String, CliError> {
+    let org_id = match org_id {
+        Some(org_id) => org_id.to_string(),
+        None => fetch_account(url, account_public_key)?
+            .ok_or_else(|| {
+                CliError::UserError(format!("Account does not exist: {}", account_public_key))
+            })?
+            .get_org_id()
+            .to_string(),
+    };
+
+    do_set_active(url, key, &org_id, account_public_key, active)
+}
+
+/// Marks an account of the organization active or inactive
+pub fn do_set_active(
+    url: &str,
+    key: Option<&str>,
+    org_id: &str,
+    account_public_key: &str,
+    active: bool,
+) -> Result<String, CliError> {
+    let private_key = key::load_signing_key(key)?;
+    let context = signing::create_context("secp256k1")?;
+    let public_key = context.get_public_key(&private_key)?.as_hex();
+    let factory = signing::CryptoFactory::new(&*context);
+    let signer = factory.new_signer(&private_key);
+
+    let action = if active {
+        Action::ReactivateAccount(
+            ReactivateAccountActionBuilder::new()
+                .set_org_id(org_id.into())
+                .set_public_key(account_public_key.into())
+                .build()?,
+        )
+    } else {
+        Action::DeactivateAccount(
+            DeactivateAccountActionBuilder::new()
+                .set_org_id(org_id.into())
+                .set_public_key(account_public_key.into())
+                .build()?,
+        )
+    };
+
+    let payload = SmartPayloadBuilder::new().set_action(action).build()?;
+
+    let txn = create_transaction(payload, &signer, &public_key)?;
+    let batch = create_batch(txn, &signer, &public_key)?;
+    let batch_list = create_batch_list_from_one(batch);
+
+    submit_batch_list(url, &batch_list)
+}
+
+/// Grants a role to or revokes a role from an account of the organization
+pub fn do_set_role(
+    url: &str,
+    key: Option<&str>,
+    org_id: &str,
+    account_public_key: &str,
+    role: &str,
+    grant: bool,
+) -> Result<String, CliError> {
+    let private_key = key::load_signing_key(key)?;
+    let context = signing::create_context("secp256k1")?;
+    let public_key = context.get_public_key(&private_key)?.as_hex();
+    let factory = signing::CryptoFactory::new(&*context);
+    let signer = factory.new_signer(&private_key);
+
+    let action = if grant {
+        Action::GrantRole(
+            GrantRoleActionBuilder::new()
+                .set_org_id(org_id.into())
+                .set_public_key(account_public_key.into())
+                .set_role(role.into())
+                .build()?,
+        )
+    } else {
+        Action::RevokeRole(
+            RevokeRoleActionBuilder::new()
+                .set_org_id(org_id.into())
+                .set_public_key(account_public_key.into())
+                .set_role(role.into())
+                .build()?,
+        )
+    };
+
+    let payload = SmartPayloadBuilder::new().set_action(action).build()?;
+
+    let txn = create_transaction(payload, &signer, &public_key)?;
+    let batch = create_batch(txn, &signer, &public_key)?;
+    let batch_list = create_batch_list_from_one(batch);
+
+    submit_batch_list(url, &batch_list)
+}
+
+/// Loads an account from state through the REST API
+pub fn fetch_account(url: &str, account_public_key: &str) -> Result<Option<Account>, CliError> {
+    let bytes = match get_state(url, &compute_account_address(account_public_key))? {
+        Some(bytes) => bytes,
+        None => return Ok(None),
+    };
+
+    let accounts = AccountList::from_bytes(&bytes)?;
+    Ok(accounts
+        .get_accounts()
+        .iter()
+        .find(|account| account.get_public_key() == account_public_key)
+        .cloned())
+}
@@ -17,6 +17,7 @@ pub fn do_cr_create(
     url: &str,
     name: &str,
     owners: Vec<String>,
+    owner_threshold: u32,
 ) -> Result<String, CliError> {
     let private_key = key::load_signing_key(key_name)?;
     let context = signing::create_context("secp256k1")?;
@@ -27,6 +28,7 @@ pub fn do_cr_create(
     let action = CreateContractRegistryActionBuilder::new()
         .set_name(name.into())
         .set_owners(owners)
+        .set_owner_threshold(owner_threshold)
         .build()?;
 
     let payload = SmartPayloadBuilder::new()
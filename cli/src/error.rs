@@ -7,15 +7,24 @@ use std::error::Error as StdError;
 
 use hyper;
 use protobuf;
+use dgc_contract_sdk::addressing::AddressingError;
 use dgc_contract_sdk::protocol::payload::{
-    CreateContractActionBuildError, CreateContractRegistryActionBuildError,
-    CreateNamespaceRegistryActionBuildError, CreateNamespaceRegistryPermissionActionBuildError,
-    CreateSmartPermissionActionBuildError, DeleteContractRegistryActionBuildError,
+    CreateAccountActionBuildError, CreateContractActionBuildError,
+    CreateContractRegistryActionBuildError, CreateNamespaceRegistryActionBuildError,
+    CreateNamespaceRegistryPermissionActionBuildError, CreateOrganizationActionBuildError,
+    CreateRoleActionBuildError, CreateSmartPermissionActionBuildError,
+    DeactivateAccountActionBuildError, DeleteContractRegistryActionBuildError,
     DeleteNamespaceRegistryActionBuildError, DeleteNamespaceRegistryPermissionActionBuildError,
-    DeleteSmartPermissionActionBuildError, ExecuteContractActionBuildError, SmartPayloadBuildError,
-    UpdateContractRegistryOwnersActionBuildError, UpdateNamespaceRegistryOwnersActionBuildError,
-    UpdateSmartPermissionActionBuildError,
+    DeleteRoleActionBuildError, DeleteSmartPermissionActionBuildError,
+    ExecuteContractActionBuildError, GrantRoleActionBuildError,
+    ProposeOwnerChangeActionBuildError, ReactivateAccountActionBuildError,
+    RevokeRoleActionBuildError, SetActionPolicyActionBuildError, SmartPayloadBuildError,
+    UpdateAccountActionBuildError, UpdateContractRegistryOwnersActionBuildError,
+    UpdateNamespaceRegistryOwnersActionBuildError, UpdateOrganizationActionBuildError,
+    UpdateRoleActionBuildError, UpdateSmartPermissionActionBuildError,
+    UpgradeContractActionBuildError, VoteOwnerChangeActionBuildError,
 };
+use dgc_contract_sdk::protocol::state::{ContractSourceBuildError, KeyValueEntryBuildError};
 use dgc_contract_sdk::protos::ProtoConversionError;
 use sawtooth_sdk::signing;
 
@@ -60,6 +69,14 @@ impl std::fmt::Display for CliError {
     }
 }
 
+impl From<AddressingError> for CliError {
+    fn from(e: AddressingError) -> Self {
+        match e {
+            AddressingError::InvalidAddress(msg) => CliError::UserError(msg),
+        }
+    }
+}
+
 impl From<std::io::Error> for CliError {
     fn from(e: std::io::Error) -> Self {
         CliError::IoError(e)
@@ -117,5 +134,22 @@ impl_builder_errors!(
     CreateSmartPermissionActionBuildError,
     UpdateSmartPermissionActionBuildError,
     DeleteSmartPermissionActionBuildError,
-    CreateContractActionBuildError
+    CreateContractActionBuildError,
+    UpgradeContractActionBuildError,
+    CreateAccountActionBuildError,
+    UpdateAccountActionBuildError,
+    CreateOrganizationActionBuildError,
+    UpdateOrganizationActionBuildError,
+    DeactivateAccountActionBuildError,
+    ReactivateAccountActionBuildError,
+    GrantRoleActionBuildError,
+    RevokeRoleActionBuildError,
+    CreateRoleActionBuildError,
+    UpdateRoleActionBuildError,
+    DeleteRoleActionBuildError,
+    ProposeOwnerChangeActionBuildError,
+    VoteOwnerChangeActionBuildError,
+    SetActionPolicyActionBuildError,
+    KeyValueEntryBuildError,
+    ContractSourceBuildError
 );
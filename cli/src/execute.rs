@@ -41,7 +41,7 @@ pub fn do_exec(
     submit_batch_list(url, &batch_list)
 }
 
-fn create_exec_txn_payload(
+pub fn create_exec_txn_payload(
     name: &str,
     version: &str,
     inputs: Vec<String>,
@@ -63,7 +63,7 @@ fn create_exec_txn_payload(
     Ok(payload)
 }
 
-fn load_contract_payload_file(payload_file: &str) -> Result<Vec<u8>, CliError> {
+pub fn load_contract_payload_file(payload_file: &str) -> Result<Vec<u8>, CliError> {
     let file = File::open(payload_file).map_err(|e| {
         CliError::UserError(format!(
             "Could not load payload \"{}\": {}",
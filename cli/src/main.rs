@@ -5,22 +5,37 @@
 extern crate clap;
 #[macro_use]
 extern crate serde_derive;
+#[macro_use]
+extern crate serde_json;
 
+mod account;
 mod contract_registry;
 mod error;
 mod execute;
 mod key;
 mod namespace;
+mod organization;
+mod owner_change;
+mod role;
+mod show;
+mod simulate;
 mod smart_permission;
 mod submit;
 mod transaction;
 mod upload;
+mod verify;
 
 use std::time::Instant;
 
+use dgc_contract_sdk::protocol::payload::{MetadataUpdateMode, RegistryType};
+use dgc_contract_sdk::protocol::state::{KeyValueEntry, KeyValueEntryBuilder};
+
 const APP_NAME: &str = env!("CARGO_PKG_NAME");
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// Number of blocks an owner change proposal stays open when --expiry is not given
+const DEFAULT_PROPOSAL_EXPIRY_BLOCKS: u64 = 100;
+
 fn run() -> Result<(), error::CliError> {
     // Below, unwrap() is used on required arguments, since they will always
     // contain a value (and lack of value is should cause a panic). unwrap()
@@ -40,9 +55,19 @@ fn run() -> Result<(), error::CliError> {
             (@arg wait: --wait +takes_value "A time in seconds to wait for batches to be committed")
             (@arg wasm: -w --wasm +takes_value "Path to compiled smart contract (*.wasm)")
         )
+        (@subcommand upgrade =>
+            (about: "upgrade a Smart contract to a new version, deprecating the previous version")
+            (@arg filename: -f --filename +required +takes_value "Path to Smart contract definition (*.yaml)")
+            (@arg previous: -P --previous +required +takes_value "Version of the contract being upgraded")
+            (@arg migrate_payload: -m --("migrate-payload") +takes_value "Path to payload passed to the contract's migrate function")
+            (@arg key: -k --key +takes_value "Signing key name")
+            (@arg url: --url +takes_value "URL to the Sawtooth REST API")
+            (@arg wait: --wait +takes_value "A time in seconds to wait for batches to be committed")
+            (@arg wasm: -w --wasm +takes_value "Path to compiled smart contract (*.wasm)")
+        )
         (@subcommand exec =>
             (about: "execute a Smart contract")
-            (@arg contract: -C --contract +required +takes_value "Name:Version of a Smart contract")
+            (@arg contract: -C --contract +required +takes_value "Name:Version of a Smart contract; Version may be 'latest' or a semver range")
             (@arg payload: -p --payload +required +takes_value "Path to Smart contract payload")
             (@arg key: -k --key +takes_value "Signing key name")
             (@arg url: --url +takes_value "URL to the Sawtooth REST API")
@@ -56,22 +81,29 @@ fn run() -> Result<(), error::CliError> {
                 (@arg create: -c --create "Create the namespace")
                 (@arg update: -u --update "Update the namespace")
                 (@arg delete: -d --delete "Delete the namespace")
+                (@arg propose: --propose "Propose new owners, applied once enough owners approve")
+                (@arg approve: --approve "Approve the pending owner change")
+                (@arg reject: --reject "Reject the pending owner change")
             )
             (@arg namespace: +required "A global state address prefix (namespace)")
             (@arg key: -k --key +takes_value "Signing key name")
             (@arg url: -U --url +takes_value "URL to the Sawtooth REST API")
             (@arg owner: -O --owner +takes_value +multiple "Owner of this namespace")
+            (@arg threshold: -t --threshold +takes_value "Number of owners that must approve an owner change")
+            (@arg expiry: --expiry +takes_value "Number of blocks before an owner change proposal expires")
             (@arg wait: --wait +takes_value "A time in seconds to wait for batches to be committed")
         )
         (@subcommand perm =>
             (about: "set or delete a Smart namespace permission")
             (@arg namespace: +required "A global state address prefix (namespace)")
-            (@arg contract: +required "Name of the contract")
+            (@arg contract: +required "Name of the contract, '*' matches any sequence of characters")
             (@arg key: -k --key +takes_value "Signing key name")
             (@arg url: -U --url +takes_value "URL to the Sawtooth REST API")
+            (@arg prefix: -p --prefix +takes_value "Address prefix within the namespace the permission applies to")
             (@arg delete: -d --delete "Remove all permissions")
             (@arg read: -r --read conflicts_with[delete] "Set read permission")
             (@arg write: -w --write conflicts_with[delete] "Set write permission")
+            (@arg deny: --deny conflicts_with[delete] "Deny the given permissions instead of granting them")
             (@arg wait: --wait +takes_value "A time in seconds to wait for batches to be committed")
         )
         (@subcommand cr =>
@@ -80,11 +112,16 @@ fn run() -> Result<(), error::CliError> {
                 (@arg create: -c --create "Create the contract registry")
                 (@arg update: -u --update "Update the contract registry")
                 (@arg delete: -d --delete "Delete the contract registry")
+                (@arg propose: --propose "Propose new owners, applied once enough owners approve")
+                (@arg approve: --approve "Approve the pending owner change")
+                (@arg reject: --reject "Reject the pending owner change")
             )
             (@arg name: +required "Name of the contracts in the registry")
             (@arg key: -k --key +takes_value "Signing key name")
             (@arg url: -U --url +takes_value "URL to the Sawtooth REST API")
             (@arg owner: -O --owner +takes_value +multiple "Owner of this contract registry")
+            (@arg threshold: -t --threshold +takes_value "Number of owners that must approve an owner change")
+            (@arg expiry: --expiry +takes_value "Number of blocks before an owner change proposal expires")
             (@arg wait: --wait +takes_value "A time in seconds to wait for batches to be committed")
         )
         (@subcommand sp =>
@@ -109,11 +146,186 @@ fn run() -> Result<(), error::CliError> {
                 (@arg key: -k --key +takes_value "Signing key name")
             )
         )
+        (@subcommand account =>
+            (about: "create, update, deactivate or show an account, or change its roles")
+            (@setting SubcommandRequiredElseHelp)
+            (@arg url: -U --url +takes_value "URL to the Sawtooth REST API")
+            (@arg wait: --wait +takes_value "A time in seconds to wait for batches to be committed")
+            (@subcommand create =>
+                (about: "create an account in an organization")
+                (@arg public_key: +required "Public key of the account")
+                (@arg org_id: -o --org +required +takes_value "Organization ID of the account")
+                (@arg roles: --roles +required +takes_value +multiple "Roles of the account")
+                (@arg metadata: --metadata +takes_value +multiple number_of_values(1) "Metadata entry of the account as key=value")
+                (@arg key: -k --key +takes_value "Signing key name")
+            )
+            (@subcommand update =>
+                (about: "update an account, keeping any values that are not provided")
+                (@arg public_key: +required "Public key of the account")
+                (@arg org_id: -o --org +takes_value "Organization ID of the account")
+                (@arg roles: --roles +takes_value +multiple "Roles of the account")
+                (@arg metadata: --metadata +takes_value +multiple number_of_values(1) "Metadata entry of the account as key=value")
+                (@arg metadata_mode: --("metadata-mode") +takes_value possible_value[merge replace remove] "How metadata entries are applied (default merge)")
+                (@arg active: --active +takes_value possible_value[true false] conflicts_with[roles metadata metadata_mode] "Reactivate or deactivate the account instead")
+                (@arg key: -k --key +takes_value "Signing key name")
+            )
+            (@subcommand deactivate =>
+                (about: "mark an account of an organization inactive")
+                (@arg public_key: +required "Public key of the account")
+                (@arg org_id: -o --org +required +takes_value "Organization ID of the account")
+                (@arg key: -k --key +takes_value "Signing key name")
+            )
+            (@subcommand reactivate =>
+                (about: "mark an inactive account of an organization active again")
+                (@arg public_key: +required "Public key of the account")
+                (@arg org_id: -o --org +required +takes_value "Organization ID of the account")
+                (@arg key: -k --key +takes_value "Signing key name")
+            )
+            (@subcommand grant =>
+                (about: "grant a role to an account of an organization")
+                (@arg public_key: +required "Public key of the account")
+                (@arg role: +required "Role to grant")
+                (@arg org_id: -o --org +required +takes_value "Organization ID of the account")
+                (@arg key: -k --key +takes_value "Signing key name")
+            )
+            (@subcommand revoke =>
+                (about: "revoke a role from an account of an organization")
+                (@arg public_key: +required "Public key of the account")
+                (@arg role: +required "Role to revoke")
+                (@arg org_id: -o --org +required +takes_value "Organization ID of the account")
+                (@arg key: -k --key +takes_value "Signing key name")
+            )
+            (@subcommand show =>
+                (about: "show an account")
+                (@arg public_key: +required "Public key of the account")
+                (@arg format: -f --format +takes_value possible_value[table json] "Output format (default table)")
+            )
+        )
+        (@subcommand org =>
+            (about: "create, update or show an organization")
+            (@setting SubcommandRequiredElseHelp)
+            (@arg url: -U --url +takes_value "URL to the Sawtooth REST API")
+            (@arg wait: --wait +takes_value "A time in seconds to wait for batches to be committed")
+            (@subcommand create =>
+                (about: "create an organization")
+                (@arg org_id: +required "Organization ID")
+                (@arg name: -n --name +required +takes_value "Name of the organization")
+                (@arg address: -a --address +required +takes_value "Address of the organization")
+                (@arg metadata: --metadata +takes_value +multiple number_of_values(1) "Metadata entry of the organization as key=value")
+                (@arg key: -k --key +takes_value "Signing key name")
+            )
+            (@subcommand update =>
+                (about: "update an organization, keeping any values that are not provided")
+                (@arg org_id: +required "Organization ID")
+                (@arg name: -n --name +takes_value "Name of the organization")
+                (@arg address: -a --address +takes_value "Address of the organization")
+                (@arg metadata: --metadata +takes_value +multiple number_of_values(1) "Metadata entry of the organization as key=value")
+                (@arg metadata_mode: --("metadata-mode") +takes_value possible_value[merge replace remove] "How metadata entries are applied (default merge)")
+                (@arg key: -k --key +takes_value "Signing key name")
+            )
+            (@subcommand policy =>
+                (about: "attach a smart permission to an action, or detach it if none is given")
+                (@arg org_id: +required "Organization ID")
+                (@arg action: +required "Action guarded by the smart permission, e.g. create_account")
+                (@arg smart_permission: -s --("smart-permission") +takes_value "Name of a smart permission of the organization")
+                (@arg key: -k --key +takes_value "Signing key name")
+            )
+            (@subcommand show =>
+                (about: "show an organization")
+                (@arg org_id: +required "Organization ID")
+                (@arg format: -f --format +takes_value possible_value[table json] "Output format (default table)")
+            )
+        )
+        (@subcommand role =>
+            (about: "create, update, delete or show a role of an organization")
+            (@setting SubcommandRequiredElseHelp)
+            (@arg url: -U --url +takes_value "URL to the Sawtooth REST API")
+            (@arg wait: --wait +takes_value "A time in seconds to wait for batches to be committed")
+            (@subcommand create =>
+                (about: "create a role granting a set of permissions")
+                (@arg org_id: +required "Organization ID")
+                (@arg name: +required "Name of the role")
+                (@arg permissions: --permissions +required +takes_value +multiple "Permissions granted by the role")
+                (@arg key: -k --key +takes_value "Signing key name")
+            )
+            (@subcommand update =>
+                (about: "replace the permissions granted by a role")
+                (@arg org_id: +required "Organization ID")
+                (@arg name: +required "Name of the role")
+                (@arg permissions: --permissions +required +takes_value +multiple "Permissions granted by the role")
+                (@arg key: -k --key +takes_value "Signing key name")
+            )
+            (@subcommand delete =>
+                (about: "delete a role")
+                (@arg org_id: +required "Organization ID")
+                (@arg name: +required "Name of the role")
+                (@arg key: -k --key +takes_value "Signing key name")
+            )
+            (@subcommand show =>
+                (about: "show a role")
+                (@arg org_id: +required "Organization ID")
+                (@arg name: +required "Name of the role")
+                (@arg format: -f --format +takes_value possible_value[table json] "Output format (default table)")
+            )
+        )
+        (@subcommand show =>
+            (about: "show a Smart object stored in state")
+            (@setting SubcommandRequiredElseHelp)
+            (@arg url: -U --url +takes_value "URL to the Sawtooth REST API")
+            (@arg format: -f --format +takes_value possible_value[table json] "Output format (default table)")
+            (@subcommand contract =>
+                (about: "show a contract")
+                (@arg name: +required "Name of the contract")
+                (@arg version: +required "Version of the contract")
+            )
+            (@subcommand registry =>
+                (about: "show a contract registry")
+                (@arg name: +required "Name of the contract registry")
+            )
+            (@subcommand namespace =>
+                (about: "show a namespace registry")
+                (@arg namespace: +required "Namespace of the registry")
+            )
+            (@subcommand sp =>
+                (about: "show a smart permission")
+                (@arg org_id: +required "Organization ID")
+                (@arg name: +required "Name of the Smart Permission")
+            )
+            (@subcommand account =>
+                (about: "show an account")
+                (@arg public_key: +required "Public key of the account")
+            )
+            (@subcommand org =>
+                (about: "show an organization")
+                (@arg org_id: +required "Organization ID")
+            )
+            (@subcommand role =>
+                (about: "show a role")
+                (@arg org_id: +required "Organization ID")
+                (@arg name: +required "Name of the role")
+            )
+        )
+        (@subcommand simulate =>
+            (about: "apply the transactions of a scenario file to in-memory state, without a validator")
+            (@arg scenario: +required "Path to the scenario file (*.yaml or *.json)")
+            (@arg format: -f --format +takes_value possible_value[table json] "Output format (default table)")
+            (@arg trace_dir: --("trace-dir") +takes_value "Directory to write the execution trace of each transaction to")
+        )
+        (@subcommand verify =>
+            (about: "rebuild a contract from a local checkout of its source and compare it with the deployed contract")
+            (@arg name: +required "Name of the contract")
+            (@arg version: +required "Version of the contract")
+            (@arg path: -p --path +takes_value "Path to a checkout of the contract's source (default current directory)")
+            (@arg wasm: -w --wasm +takes_value "Path to the built contract, relative to the checkout")
+            (@arg url: --url +takes_value "URL to the Sawtooth REST API")
+        )
     ).get_matches();
 
     let (batch_link, mut wait) = if let Some(upload_matches) = matches.subcommand_matches("upload")
     {
         upload(upload_matches)?
+    } else if let Some(upgrade_matches) = matches.subcommand_matches("upgrade") {
+        upgrade(upgrade_matches)?
     } else if let Some(exec_matches) = matches.subcommand_matches("exec") {
         execute(exec_matches)?
     } else if let Some(ns_matches) = matches.subcommand_matches("ns") {
@@ -124,6 +336,39 @@ fn run() -> Result<(), error::CliError> {
         contract_registry(cr_matches)?
     } else if let Some(sp_matches) = matches.subcommand_matches("sp") {
         smart_permission(sp_matches)?
+    } else if let Some(account_matches) = matches.subcommand_matches("account") {
+        match account(account_matches)? {
+            Some(submitted) => submitted,
+            None => return Ok(()),
+        }
+    } else if let Some(org_matches) = matches.subcommand_matches("org") {
+        match organization(org_matches)? {
+            Some(submitted) => submitted,
+            None => return Ok(()),
+        }
+    } else if let Some(role_matches) = matches.subcommand_matches("role") {
+        match role(role_matches)? {
+            Some(submitted) => submitted,
+            None => return Ok(()),
+        }
+    } else if let Some(show_matches) = matches.subcommand_matches("show") {
+        return show(show_matches);
+    } else if let Some(simulate_matches) = matches.subcommand_matches("simulate") {
+        return simulate::do_simulate(
+            simulate_matches.value_of("scenario").unwrap(),
+            parse_format(simulate_matches)?,
+            simulate_matches.value_of("trace_dir"),
+        );
+    } else if let Some(verify_matches) = matches.subcommand_matches("verify") {
+        return verify::do_verify(
+            verify_matches
+                .value_of("url")
+                .unwrap_or("http://localhost:8008/"),
+            verify_matches.value_of("name").unwrap(),
+            verify_matches.value_of("version").unwrap(),
+            verify_matches.value_of("path").unwrap_or("."),
+            verify_matches.value_of("wasm"),
+        );
     } else {
         return Err(error::CliError::UserError("Subcommand required".into()));
     };
@@ -166,6 +411,35 @@ fn upload(upload_matches: &clap::ArgMatches) -> Result<(String, u64), error::Cli
     Ok((batch_link, wait))
 }
 
+fn upgrade(upgrade_matches: &clap::ArgMatches) -> Result<(String, u64), error::CliError> {
+    let filename = upgrade_matches.value_of("filename").unwrap();
+    let previous_version = upgrade_matches.value_of("previous").unwrap();
+    let migrate_payload = upgrade_matches.value_of("migrate_payload");
+    let key_name = upgrade_matches.value_of("key");
+    let url = upgrade_matches
+        .value_of("url")
+        .unwrap_or("http://localhost:8008/");
+    let wasm_name = upgrade_matches.value_of("wasm");
+
+    let wait = match value_t!(upgrade_matches, "wait", u64) {
+        Ok(wait) => wait,
+        Err(err) => match err.kind {
+            clap::ErrorKind::ArgumentNotFound => 0,
+            _ => return Err(error::CliError::UserError("Wait must be an integer".into())),
+        },
+    };
+
+    let batch_link = upload::do_upgrade(
+        &filename,
+        &previous_version,
+        migrate_payload,
+        key_name,
+        &url,
+        wasm_name,
+    )?;
+    Ok((batch_link, wait))
+}
+
 fn execute(exec_matches: &clap::ArgMatches) -> Result<(String, u64), error::CliError> {
     let contract = exec_matches.value_of("contract").unwrap();
     let payload = exec_matches.value_of("payload").unwrap();
@@ -241,6 +515,29 @@ fn namespace_registry(ns_matches: &clap::ArgMatches) -> Result<(String, u64), er
             )
         })?;
         namespace::do_ns_update(key_name, &url, &namespace, o)?
+    } else if ns_matches.is_present("propose") {
+        let o = owners.ok_or_else(|| {
+            error::CliError::UserError(
+                "propose action requires one or more --owner arguments".into(),
+            )
+        })?;
+        owner_change::do_propose(
+            key_name,
+            &url,
+            RegistryType::NamespaceRegistry,
+            &namespace,
+            o,
+            parse_owner_threshold(ns_matches)?,
+            parse_expiry(ns_matches)?,
+        )?
+    } else if ns_matches.is_present("approve") || ns_matches.is_present("reject") {
+        owner_change::do_vote(
+            key_name,
+            &url,
+            RegistryType::NamespaceRegistry,
+            &namespace,
+            ns_matches.is_present("approve"),
+        )?
     } else if ns_matches.is_present("delete") {
         if ns_matches.is_present("owner") {
             return Err(error::CliError::UserError(
@@ -254,7 +551,13 @@ fn namespace_registry(ns_matches: &clap::ArgMatches) -> Result<(String, u64), er
                 "create action requires one or more --owner arguments".into(),
             )
         })?;
-        namespace::do_ns_create(key_name, &url, &namespace, o)?
+        namespace::do_ns_create(
+            key_name,
+            &url,
+            &namespace,
+            o,
+            parse_owner_threshold(ns_matches)?,
+        )?
     };
 
     Ok((batch_link, wait))
@@ -276,17 +579,22 @@ fn namespace_permission(perm_matches: &clap::ArgMatches) -> Result<(String, u64)
         },
     };
 
+    let prefix = perm_matches.value_of("prefix").unwrap_or("");
+
     let batch_link = if perm_matches.is_present("delete") {
-        namespace::do_perm_delete(key_name, &url, &namespace)?
+        namespace::do_perm_delete(key_name, &url, &namespace, &contract, &prefix)?
     } else {
         let read = perm_matches.is_present("read");
         let write = perm_matches.is_present("write");
+        let deny = perm_matches.is_present("deny");
 
         if !(read || write) {
             return Err(error::CliError::UserError("no permissions provided".into()));
         }
 
-        namespace::do_perm_create(key_name, &url, &namespace, &contract, read, write)?
+        namespace::do_perm_create(
+            key_name, &url, &namespace, &contract, &prefix, read, write, deny,
+        )?
     };
 
     Ok((batch_link, wait))
@@ -314,6 +622,29 @@ fn contract_registry(cr_matches: &clap::ArgMatches) -> Result<(String, u64), err
             )
         })?;
         contract_registry::do_cr_update(key_name, &url, &name, o)?
+    } else if cr_matches.is_present("propose") {
+        let o = owners.ok_or_else(|| {
+            error::CliError::UserError(
+                "propose action requires one or more --owner arguments".into(),
+            )
+        })?;
+        owner_change::do_propose(
+            key_name,
+            &url,
+            RegistryType::ContractRegistry,
+            &name,
+            o,
+            parse_owner_threshold(cr_matches)?,
+            parse_expiry(cr_matches)?,
+        )?
+    } else if cr_matches.is_present("approve") || cr_matches.is_present("reject") {
+        owner_change::do_vote(
+            key_name,
+            &url,
+            RegistryType::ContractRegistry,
+            &name,
+            cr_matches.is_present("approve"),
+        )?
     } else if cr_matches.is_present("delete") {
         if cr_matches.is_present("owner") {
             return Err(error::CliError::UserError(
@@ -327,7 +658,13 @@ fn contract_registry(cr_matches: &clap::ArgMatches) -> Result<(String, u64), err
                 "create action requires one or more --owner arguments".into(),
             )
         })?;
-        contract_registry::do_cr_create(key_name, &url, &name, o)?
+        contract_registry::do_cr_create(
+            key_name,
+            &url,
+            &name,
+            o,
+            parse_owner_threshold(cr_matches)?,
+        )?
     };
     Ok((batch_link, wait))
 }
@@ -376,6 +713,319 @@ fn smart_permission(sp_matches: &clap::ArgMatches) -> Result<(String, u64), erro
     Ok((batch_link, wait))
 }
 
+// Returns None for subcommands that only read state and submit no batch
+fn account(account_matches: &clap::ArgMatches) -> Result<Option<(String, u64)>, error::CliError> {
+    let url = account_matches
+        .value_of("url")
+        .unwrap_or("http://localhost:8008/");
+
+    let wait = match value_t!(account_matches, "wait", u64) {
+        Ok(wait) => wait,
+        Err(err) => match err.kind {
+            clap::ErrorKind::ArgumentNotFound => 0,
+            _ => return Err(error::CliError::UserError("Wait must be an integer".into())),
+        },
+    };
+
+    let batch_link = match account_matches.subcommand() {
+        ("create", Some(m)) => account::do_create(
+            url,
+            m.value_of("key"),
+            m.value_of("org_id").unwrap(),
+            m.value_of("public_key").unwrap(),
+            m.values_of("roles").unwrap().map(String::from).collect(),
+            parse_metadata(m)?,
+        )?,
+        ("update", Some(m)) => match parse_active(m)? {
+            Some(active) => account::do_update_active(
+                url,
+                m.value_of("key"),
+                m.value_of("public_key").unwrap(),
+                m.value_of("org_id"),
+                active,
+            )?,
+            None => account::do_update(
+                url,
+                m.value_of("key"),
+                m.value_of("public_key").unwrap(),
+                m.value_of("org_id"),
+                m.values_of("roles")
+                    .map(|values| values.map(String::from).collect()),
+                parse_metadata(m)?,
+                parse_metadata_mode(m)?,
+            )?,
+        },
+        ("deactivate", Some(m)) => account::do_set_active(
+            url,
+            m.value_of("key"),
+            m.value_of("org_id").unwrap(),
+            m.value_of("public_key").unwrap(),
+            false,
+        )?,
+        ("reactivate", Some(m)) => account::do_set_active(
+            url,
+            m.value_of("key"),
+            m.value_of("org_id").unwrap(),
+            m.value_of("public_key").unwrap(),
+            true,
+        )?,
+        ("grant", Some(m)) => account::do_set_role(
+            url,
+            m.value_of("key"),
+            m.value_of("org_id").unwrap(),
+            m.value_of("public_key").unwrap(),
+            m.value_of("role").unwrap(),
+            true,
+        )?,
+        ("revoke", Some(m)) => account::do_set_role(
+            url,
+            m.value_of("key"),
+            m.value_of("org_id").unwrap(),
+            m.value_of("public_key").unwrap(),
+            m.value_of("role").unwrap(),
+            false,
+        )?,
+        ("show", Some(m)) => {
+            show::do_show_account(url, m.value_of("public_key").unwrap(), parse_format(m)?)?;
+            return Ok(None);
+        }
+        _ => {
+            return Err(error::CliError::UserError(
+                "Unrecognized account subcommand".into(),
+            ));
+        }
+    };
+
+    Ok(Some((batch_link, wait)))
+}
+
+// Returns None for subcommands that only read state and submit no batch
+fn organization(org_matches: &clap::ArgMatches) -> Result<Option<(String, u64)>, error::CliError> {
+    let url = org_matches
+        .value_of("url")
+        .unwrap_or("http://localhost:8008/");
+
+    let wait = match value_t!(org_matches, "wait", u64) {
+        Ok(wait) => wait,
+        Err(err) => match err.kind {
+            clap::ErrorKind::ArgumentNotFound => 0,
+            _ => return Err(error::CliError::UserError("Wait must be an integer".into())),
+        },
+    };
+
+    let batch_link = match org_matches.subcommand() {
+        ("create", Some(m)) => organization::do_create(
+            url,
+            m.value_of("key"),
+            m.value_of("org_id").unwrap(),
+            m.value_of("name").unwrap(),
+            m.value_of("address").unwrap(),
+            parse_metadata(m)?,
+        )?,
+        ("update", Some(m)) => organization::do_update(
+            url,
+            m.value_of("key"),
+            m.value_of("org_id").unwrap(),
+            m.value_of("name"),
+            m.value_of("address"),
+            parse_metadata(m)?,
+            parse_metadata_mode(m)?,
+        )?,
+        ("policy", Some(m)) => organization::do_set_policy(
+            url,
+            m.value_of("key"),
+            m.value_of("org_id").unwrap(),
+            m.value_of("action").unwrap(),
+            m.value_of("smart_permission"),
+        )?,
+        ("show", Some(m)) => {
+            show::do_show_organization(url, m.value_of("org_id").unwrap(), parse_format(m)?)?;
+            return Ok(None);
+        }
+        _ => {
+            return Err(error::CliError::UserError(
+                "Unrecognized org subcommand".into(),
+            ));
+        }
+    };
+
+    Ok(Some((batch_link, wait)))
+}
+
+// Returns None for subcommands that only read state and submit no batch
+fn role(role_matches: &clap::ArgMatches) -> Result<Option<(String, u64)>, error::CliError> {
+    let url = role_matches
+        .value_of("url")
+        .unwrap_or("http://localhost:8008/");
+
+    let wait = match value_t!(role_matches, "wait", u64) {
+        Ok(wait) => wait,
+        Err(err) => match err.kind {
+            clap::ErrorKind::ArgumentNotFound => 0,
+            _ => return Err(error::CliError::UserError("Wait must be an integer".into())),
+        },
+    };
+
+    let batch_link = match role_matches.subcommand() {
+        ("create", Some(m)) => role::do_create(
+            url,
+            m.value_of("key"),
+            m.value_of("org_id").unwrap(),
+            m.value_of("name").unwrap(),
+            m.values_of("permissions").unwrap().map(String::from).collect(),
+        )?,
+        ("update", Some(m)) => role::do_update(
+            url,
+            m.value_of("key"),
+            m.value_of("org_id").unwrap(),
+            m.value_of("name").unwrap(),
+            m.values_of("permissions").unwrap().map(String::from).collect(),
+        )?,
+        ("delete", Some(m)) => role::do_delete(
+            url,
+            m.value_of("key"),
+            m.value_of("org_id").unwrap(),
+            m.value_of("name").unwrap(),
+        )?,
+        ("show", Some(m)) => {
+            show::do_show_role(
+                url,
+                m.value_of("org_id").unwrap(),
+                m.value_of("name").unwrap(),
+                parse_format(m)?,
+            )?;
+            return Ok(None);
+        }
+        _ => {
+            return Err(error::CliError::UserError(
+                "Unrecognized role subcommand".into(),
+            ));
+        }
+    };
+
+    Ok(Some((batch_link, wait)))
+}
+
+fn show(show_matches: &clap::ArgMatches) -> Result<(), error::CliError> {
+    let url = show_matches
+        .value_of("url")
+        .unwrap_or("http://localhost:8008/");
+    let format = parse_format(show_matches)?;
+
+    match show_matches.subcommand() {
+        ("contract", Some(m)) => show::do_show_contract(
+            url,
+            m.value_of("name").unwrap(),
+            m.value_of("version").unwrap(),
+            format,
+        ),
+        ("registry", Some(m)) => {
+            show::do_show_contract_registry(url, m.value_of("name").unwrap(), format)
+        }
+        ("namespace", Some(m)) => {
+            show::do_show_namespace_registry(url, m.value_of("namespace").unwrap(), format)
+        }
+        ("sp", Some(m)) => show::do_show_smart_permission(
+            url,
+            m.value_of("org_id").unwrap(),
+            m.value_of("name").unwrap(),
+            format,
+        ),
+        ("account", Some(m)) => {
+            show::do_show_account(url, m.value_of("public_key").unwrap(), format)
+        }
+        ("org", Some(m)) => show::do_show_organization(url, m.value_of("org_id").unwrap(), format),
+        ("role", Some(m)) => show::do_show_role(
+            url,
+            m.value_of("org_id").unwrap(),
+            m.value_of("name").unwrap(),
+            format,
+        ),
+        _ => Err(error::CliError::UserError(
+            "Unrecognized show subcommand".into(),
+        )),
+    }
+}
+
+fn parse_owner_threshold(matches: &clap::ArgMatches) -> Result<u32, error::CliError> {
+    match value_t!(matches, "threshold", u32) {
+        Ok(threshold) => Ok(threshold),
+        Err(err) => match err.kind {
+            clap::ErrorKind::ArgumentNotFound => Ok(0),
+            _ => Err(error::CliError::UserError(
+                "Threshold must be an integer".into(),
+            )),
+        },
+    }
+}
+
+fn parse_expiry(matches: &clap::ArgMatches) -> Result<u64, error::CliError> {
+    match value_t!(matches, "expiry", u64) {
+        Ok(expiry) => Ok(expiry),
+        Err(err) => match err.kind {
+            clap::ErrorKind::ArgumentNotFound => Ok(DEFAULT_PROPOSAL_EXPIRY_BLOCKS),
+            _ => Err(error::CliError::UserError("Expiry must be an integer".into())),
+        },
+    }
+}
+
+fn parse_format(matches: &clap::ArgMatches) -> Result<show::OutputFormat, error::CliError> {
+    show::OutputFormat::parse(matches.value_of("format").unwrap_or("table"))
+}
+
+// parses the repeated --metadata key=value arguments
+fn parse_metadata(matches: &clap::ArgMatches) -> Result<Vec<KeyValueEntry>, error::CliError> {
+    let values = match matches.values_of("metadata") {
+        Some(values) => values,
+        None => return Ok(Vec::new()),
+    };
+
+    let mut metadata = Vec::new();
+    for value in values {
+        let mut parts = value.splitn(2, '=');
+        match (parts.next(), parts.next()) {
+            (Some(key), Some(value)) if !key.is_empty() => metadata.push(
+                KeyValueEntryBuilder::new()
+                    .set_key(key.into())
+                    .set_value(value.into())
+                    .build()?,
+            ),
+            _ => {
+                return Err(error::CliError::UserError(format!(
+                    "Metadata must be formatted as key=value: {}",
+                    value
+                )));
+            }
+        }
+    }
+
+    Ok(metadata)
+}
+
+fn parse_active(matches: &clap::ArgMatches) -> Result<Option<bool>, error::CliError> {
+    match matches.value_of("active") {
+        Some("true") => Ok(Some(true)),
+        Some("false") => Ok(Some(false)),
+        Some(value) => Err(error::CliError::UserError(format!(
+            "Active must be true or false: {}",
+            value
+        ))),
+        None => Ok(None),
+    }
+}
+
+fn parse_metadata_mode(matches: &clap::ArgMatches) -> Result<MetadataUpdateMode, error::CliError> {
+    match matches.value_of("metadata_mode") {
+        None | Some("merge") => Ok(MetadataUpdateMode::Merge),
+        Some("replace") => Ok(MetadataUpdateMode::Replace),
+        Some("remove") => Ok(MetadataUpdateMode::Remove),
+        Some(mode) => Err(error::CliError::UserError(format!(
+            "Metadata mode must be merge, replace or remove: {}",
+            mode
+        ))),
+    }
+}
+
 fn main() {
     if let Err(e) = run() {
         println!("{}", e);
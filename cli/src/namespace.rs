@@ -18,6 +18,7 @@ pub fn do_ns_create(
     url: &str,
     namespace: &str,
     owners: Vec<String>,
+    owner_threshold: u32,
 ) -> Result<String, CliError> {
     let private_key = key::load_signing_key(key_name)?;
     let context = signing::create_context("secp256k1")?;
@@ -28,6 +29,7 @@ pub fn do_ns_create(
     let action = CreateNamespaceRegistryActionBuilder::new()
         .set_namespace(namespace.into())
         .set_owners(owners)
+        .set_owner_threshold(owner_threshold)
         .build()?;
 
     let payload = SmartPayloadBuilder::new()
@@ -95,13 +97,16 @@ pub fn do_ns_delete(
     submit_batch_list(url, &batch_list)
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn do_perm_create(
     key_name: Option<&str>,
     url: &str,
     namespace: &str,
     contract: &str,
+    prefix: &str,
     read: bool,
     write: bool,
+    deny: bool,
 ) -> Result<String, CliError> {
     let private_key = key::load_signing_key(key_name)?;
     let context = signing::create_context("secp256k1")?;
@@ -114,6 +119,8 @@ pub fn do_perm_create(
         .set_contract_name(contract.into())
         .set_read(read)
         .set_write(write)
+        .set_prefix(prefix.into())
+        .set_deny(deny)
         .build()?;
 
     let payload = SmartPayloadBuilder::new()
@@ -130,6 +137,8 @@ pub fn do_perm_delete(
     key_name: Option<&str>,
     url: &str,
     namespace: &str,
+    contract: &str,
+    prefix: &str,
 ) -> Result<String, CliError> {
     let private_key = key::load_signing_key(key_name)?;
     let context = signing::create_context("secp256k1")?;
@@ -139,6 +148,8 @@ pub fn do_perm_delete(
 
     let action = DeleteNamespaceRegistryPermissionActionBuilder::new()
         .set_namespace(namespace.into())
+        .set_contract_name(contract.into())
+        .set_prefix(prefix.into())
         .build()?;
 
     let payload = SmartPayloadBuilder::new()
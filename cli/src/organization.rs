@@ -0,0 +1,136 @@
+// Copyright (c) The dgc.network
+// SPDX-License-Identifier: Apache-2.0
+
+use dgc_contract_sdk::addressing::compute_org_address;
+use dgc_contract_sdk::protocol::payload::{
+    Action, CreateOrganizationActionBuilder, MetadataUpdateMode, SetActionPolicyActionBuilder,
+    SmartPayloadBuilder, UpdateOrganizationActionBuilder,
+};
+use dgc_contract_sdk::protocol::state::{KeyValueEntry, Organization, OrganizationList};
+use dgc_contract_sdk::protos::FromBytes;
+use sawtooth_sdk::signing;
+
+use crate::error::CliError;
+use crate::key;
+use crate::submit::{get_state, submit_batch_list};
+use crate::transaction::{create_batch, create_batch_list_from_one, create_transaction};
+
+pub fn do_create(
+    url: &str,
+    key: Option<&str>,
+    org_id: &str,
+    name: &str,
+    address: &str,
+    metadata: Vec<KeyValueEntry>,
+) -> Result<String, CliError> {
+    let private_key = key::load_signing_key(key)?;
+    let context = signing::create_context("secp256k1")?;
+    let public_key = context.get_public_key(&private_key)?.as_hex();
+    let factory = signing::CryptoFactory::new(&*context);
+    let signer = factory.new_signer(&private_key);
+
+    let action = CreateOrganizationActionBuilder::new()
+        .set_id(org_id.into())
+        .set_name(name.into())
+        .set_address(address.into())
+        .set_metadata(metadata)
+        .build()?;
+
+    let payload = SmartPayloadBuilder::new()
+        .set_action(Action::CreateOrganization(action))
+        .build()?;
+
+    let txn = create_transaction(payload, &signer, &public_key)?;
+    let batch = create_batch(txn, &signer, &public_key)?;
+    let batch_list = create_batch_list_from_one(batch);
+
+    submit_batch_list(url, &batch_list)
+}
+
+/// Updates an organization, keeping the current name and address of the
+/// organization for any that are not provided
+pub fn do_update(
+    url: &str,
+    key: Option<&str>,
+    org_id: &str,
+    name: Option<&str>,
+    address: Option<&str>,
+    metadata: Vec<KeyValueEntry>,
+    metadata_mode: MetadataUpdateMode,
+) -> Result<String, CliError> {
+    let private_key = key::load_signing_key(key)?;
+    let context = signing::create_context("secp256k1")?;
+    let public_key = context.get_public_key(&private_key)?.as_hex();
+    let factory = signing::CryptoFactory::new(&*context);
+    let signer = factory.new_signer(&private_key);
+
+    let organization = fetch_organization(url, org_id)?
+        .ok_or_else(|| CliError::UserError(format!("Organization does not exist: {}", org_id)))?;
+
+    let action = UpdateOrganizationActionBuilder::new()
+        .set_id(org_id.into())
+        .set_name(name.unwrap_or_else(|| organization.get_name()).into())
+        .set_address(address.unwrap_or_else(|| organization.get_address()).into())
+        .set_metadata(metadata)
+        .set_metadata_mode(metadata_mode)
+        .build()?;
+
+    let payload = SmartPayloadBuilder::new()
+        .set_action(Action::UpdateOrganization(action))
+        .build()?;
+
+    let txn = create_transaction(payload, &signer, &public_key)?;
+    let batch = create_batch(txn, &signer, &public_key)?;
+    let batch_list = create_batch_list_from_one(batch);
+
+    submit_batch_list(url, &batch_list)
+}
+
+/// Attaches a smart permission of the organization to an action, or detaches
+/// the action's smart permission if none is given
+pub fn do_set_policy(
+    url: &str,
+    key: Option<&str>,
+    org_id: &str,
+    action: &str,
+    smart_permission: Option<&str>,
+) -> Result<String, CliError> {
+    let private_key = key::load_signing_key(key)?;
+    let context = signing::create_context("secp256k1")?;
+    let public_key = context.get_public_key(&private_key)?.as_hex();
+    let factory = signing::CryptoFactory::new(&*context);
+    let signer = factory.new_signer(&private_key);
+
+    let mut builder = SetActionPolicyActionBuilder::new()
+        .set_org_id(org_id.into())
+        .set_action(action.into());
+    if let Some(smart_permission) = smart_permission {
+        builder = builder.set_smart_permission(smart_permission.into());
+    }
+    let action = builder.build()?;
+
+    let payload = SmartPayloadBuilder::new()
+        .set_action(Action::SetActionPolicy(action))
+        .build()?;
+
+    let txn = create_transaction(payload, &signer, &public_key)?;
+    let batch = create_batch(txn, &signer, &public_key)?;
+    let batch_list = create_batch_list_from_one(batch);
+
+    submit_batch_list(url, &batch_list)
+}
+
+/// Loads an organization from state through the REST API
+pub fn fetch_organization(url: &str, org_id: &str) -> Result<Option<Organization>, CliError> {
+    let bytes = match get_state(url, &compute_org_address(org_id))? {
+        Some(bytes) => bytes,
+        None => return Ok(None),
+    };
+
+    let organizations = OrganizationList::from_bytes(&bytes)?;
+    Ok(organizations
+        .get_organizations()
+        .iter()
+        .find(|organization| organization.get_org_id() == org_id)
+        .cloned())
+}
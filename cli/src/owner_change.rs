@@ -0,0 +1,82 @@
+// Copyright (c) The dgc.network
+// SPDX-License-Identifier: Apache-2.0
+
+use dgc_contract_sdk::protocol::payload::{
+    Action, ProposeOwnerChangeActionBuilder, RegistryType, SmartPayloadBuilder,
+    VoteOwnerChangeActionBuilder,
+};
+use sawtooth_sdk::signing;
+
+use crate::error::CliError;
+use crate::key;
+use crate::submit::submit_batch_list;
+use crate::transaction::{create_batch, create_batch_list_from_one, create_transaction};
+
+/// Proposes new owners for a contract or namespace registry
+///
+/// The change is applied once the registry's owner threshold is reached,
+/// counting the proposer's own approval.
+pub fn do_propose(
+    key_name: Option<&str>,
+    url: &str,
+    registry_type: RegistryType,
+    name: &str,
+    owners: Vec<String>,
+    owner_threshold: u32,
+    expiry_blocks: u64,
+) -> Result<String, CliError> {
+    let private_key = key::load_signing_key(key_name)?;
+    let context = signing::create_context("secp256k1")?;
+    let public_key = context.get_public_key(&private_key)?.as_hex();
+    let factory = signing::CryptoFactory::new(&*context);
+    let signer = factory.new_signer(&private_key);
+
+    let action = ProposeOwnerChangeActionBuilder::new()
+        .set_registry_type(registry_type)
+        .set_name(name.into())
+        .set_owners(owners)
+        .set_owner_threshold(owner_threshold)
+        .set_expiry_blocks(expiry_blocks)
+        .build()?;
+
+    let payload = SmartPayloadBuilder::new()
+        .set_action(Action::ProposeOwnerChange(action))
+        .build()?;
+
+    let txn = create_transaction(payload, &signer, &public_key)?;
+    let batch = create_batch(txn, &signer, &public_key)?;
+    let batch_list = create_batch_list_from_one(batch);
+
+    submit_batch_list(url, &batch_list)
+}
+
+/// Approves or rejects the pending owner change of a registry
+pub fn do_vote(
+    key_name: Option<&str>,
+    url: &str,
+    registry_type: RegistryType,
+    name: &str,
+    approve: bool,
+) -> Result<String, CliError> {
+    let private_key = key::load_signing_key(key_name)?;
+    let context = signing::create_context("secp256k1")?;
+    let public_key = context.get_public_key(&private_key)?.as_hex();
+    let factory = signing::CryptoFactory::new(&*context);
+    let signer = factory.new_signer(&private_key);
+
+    let action = VoteOwnerChangeActionBuilder::new()
+        .set_registry_type(registry_type)
+        .set_name(name.into())
+        .set_approve(approve)
+        .build()?;
+
+    let payload = SmartPayloadBuilder::new()
+        .set_action(Action::VoteOwnerChange(action))
+        .build()?;
+
+    let txn = create_transaction(payload, &signer, &public_key)?;
+    let batch = create_batch(txn, &signer, &public_key)?;
+    let batch_list = create_batch_list_from_one(batch);
+
+    submit_batch_list(url, &batch_list)
+}
@@ -0,0 +1,120 @@
+// Copyright (c) The dgc.network
+// SPDX-License-Identifier: Apache-2.0
+
+use dgc_contract_sdk::addressing::compute_role_address;
+use dgc_contract_sdk::protocol::payload::{
+    Action, CreateRoleActionBuilder, DeleteRoleActionBuilder, SmartPayloadBuilder,
+    UpdateRoleActionBuilder,
+};
+use dgc_contract_sdk::protocol::state::{Role, RoleList};
+use dgc_contract_sdk::protos::FromBytes;
+use sawtooth_sdk::signing;
+
+use crate::error::CliError;
+use crate::key;
+use crate::submit::{get_state, submit_batch_list};
+use crate::transaction::{create_batch, create_batch_list_from_one, create_transaction};
+
+pub fn do_create(
+    url: &str,
+    key: Option<&str>,
+    org_id: &str,
+    name: &str,
+    permissions: Vec<String>,
+) -> Result<String, CliError> {
+    let private_key = key::load_signing_key(key)?;
+    let context = signing::create_context("secp256k1")?;
+    let public_key = context.get_public_key(&private_key)?.as_hex();
+    let factory = signing::CryptoFactory::new(&*context);
+    let signer = factory.new_signer(&private_key);
+
+    let action = CreateRoleActionBuilder::new()
+        .set_org_id(org_id.into())
+        .set_name(name.into())
+        .set_permissions(permissions)
+        .build()?;
+
+    let payload = SmartPayloadBuilder::new()
+        .set_action(Action::CreateRole(action))
+        .build()?;
+
+    let txn = create_transaction(payload, &signer, &public_key)?;
+    let batch = create_batch(txn, &signer, &public_key)?;
+    let batch_list = create_batch_list_from_one(batch);
+
+    submit_batch_list(url, &batch_list)
+}
+
+/// Replaces the permissions granted by a role
+pub fn do_update(
+    url: &str,
+    key: Option<&str>,
+    org_id: &str,
+    name: &str,
+    permissions: Vec<String>,
+) -> Result<String, CliError> {
+    let private_key = key::load_signing_key(key)?;
+    let context = signing::create_context("secp256k1")?;
+    let public_key = context.get_public_key(&private_key)?.as_hex();
+    let factory = signing::CryptoFactory::new(&*context);
+    let signer = factory.new_signer(&private_key);
+
+    let action = UpdateRoleActionBuilder::new()
+        .set_org_id(org_id.into())
+        .set_name(name.into())
+        .set_permissions(permissions)
+        .build()?;
+
+    let payload = SmartPayloadBuilder::new()
+        .set_action(Action::UpdateRole(action))
+        .build()?;
+
+    let txn = create_transaction(payload, &signer, &public_key)?;
+    let batch = create_batch(txn, &signer, &public_key)?;
+    let batch_list = create_batch_list_from_one(batch);
+
+    submit_batch_list(url, &batch_list)
+}
+
+pub fn do_delete(
+    url: &str,
+    key: Option<&str>,
+    org_id: &str,
+    name: &str,
+) -> Result<String, CliError> {
+    let private_key = key::load_signing_key(key)?;
+    let context = signing::create_context("secp256k1")?;
+    let public_key = context.get_public_key(&private_key)?.as_hex();
+    let factory = signing::CryptoFactory::new(&*context);
+    let signer = factory.new_signer(&private_key);
+
+    let action = DeleteRoleActionBuilder::new()
+        .set_org_id(org_id.into())
+        .set_name(name.into())
+        .build()?;
+
+    let payload = SmartPayloadBuilder::new()
+        .set_action(Action::DeleteRole(action))
+        .build()?;
+
+    let txn = create_transaction(payload, &signer, &public_key)?;
+    let batch = create_batch(txn, &signer, &public_key)?;
+    let batch_list = create_batch_list_from_one(batch);
+
+    submit_batch_list(url, &batch_list)
+}
+
+/// Loads a role from state through the REST API
+pub fn fetch_role(url: &str, org_id: &str, name: &str) -> Result<Option<Role>, CliError> {
+    let bytes = match get_state(url, &compute_role_address(org_id, name))? {
+        Some(bytes) => bytes,
+        None => return Ok(None),
+    };
+
+    let roles = RoleList::from_bytes(&bytes)?;
+    Ok(roles
+        .get_roles()
+        .iter()
+        .find(|role| role.get_org_id() == org_id && role.get_name() == name)
+        .cloned())
+}
@@ -0,0 +1,503 @@
+// Copyright (c) The dgc.network
+// SPDX-License-Identifier: Apache-2.0
+
+use crypto::digest::Digest;
+use crypto::sha2::Sha512;
+use dgc_contract_sdk::addressing::{
+    compute_contract_address, compute_contract_registry_address,
+    compute_namespace_registry_address, compute_smart_permission_address,
+};
+use dgc_contract_sdk::protocol::state::{
+    Account, Contract, ContractList, ContractRegistry, ContractRegistryList, KeyValueEntry,
+    NamespaceRegistry, NamespaceRegistryList, Organization, OwnerChangeProposal, Role,
+    SmartPermission, SmartPermissionList,
+};
+use dgc_contract_sdk::protos::FromBytes;
+use serde_json::{self, Value};
+
+use crate::account::fetch_account;
+use crate::error::CliError;
+use crate::organization::fetch_organization;
+use crate::role::fetch_role;
+use crate::submit::get_state;
+
+/// The format state objects are printed in
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OutputFormat {
+    Table,
+    Json,
+}
+
+impl OutputFormat {
+    pub fn parse(format: &str) -> Result<OutputFormat, CliError> {
+        match format {
+            "table" => Ok(OutputFormat::Table),
+            "json" => Ok(OutputFormat::Json),
+            _ => Err(CliError::UserError(format!(
+                "Format must be table or json: {}",
+                format
+            ))),
+        }
+    }
+}
+
+pub fn do_show_contract(
+    url: &str,
+    name: &str,
+    version: &str,
+    format: OutputFormat,
+) -> Result<(), CliError> {
+    let contract = fetch_contract(url, name, version)?.ok_or_else(|| {
+        CliError::UserError(format!("Contract does not exist: {}:{}", name, version))
+    })?;
+
+    match format {
+        OutputFormat::Json => print_json(&contract_to_json(&contract)),
+        OutputFormat::Table => {
+            println!("Name: {}", contract.get_name());
+            println!("Version: {}", contract.get_version());
+            println!("Creator: {}", contract.get_creator());
+            println!("Inputs: {}", contract.get_inputs().join(", "));
+            println!("Outputs: {}", contract.get_outputs().join(", "));
+            println!("Size: {} bytes", contract.get_contract().len());
+            println!("SHA512: {}", sha512_hex(contract.get_contract()));
+            if let Some(source) = contract.get_source() {
+                println!("Repository: {}", source.get_repository());
+                println!("Commit: {}", source.get_commit());
+                if !source.get_toolchain().is_empty() {
+                    println!("Toolchain: {}", source.get_toolchain());
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+pub fn do_show_contract_registry(
+    url: &str,
+    name: &str,
+    format: OutputFormat,
+) -> Result<(), CliError> {
+    let registry = fetch_contract_registry(url, name)?.ok_or_else(|| {
+        CliError::UserError(format!("Contract registry does not exist: {}", name))
+    })?;
+
+    match format {
+        OutputFormat::Json => print_json(&contract_registry_to_json(&registry)),
+        OutputFormat::Table => {
+            println!("Name: {}", registry.get_name());
+            println!("Owners: {}", registry.get_owners().join(", "));
+            print_owner_change(
+                registry.get_owner_threshold(),
+                registry.get_owner_change_proposal(),
+            );
+            println!("Versions:");
+            print_table(
+                &["VERSION", "DEPRECATED", "CREATOR", "SHA512"],
+                registry
+                    .get_versions()
+                    .iter()
+                    .map(|version| {
+                        vec![
+                            version.get_version().to_string(),
+                            version.get_deprecated().to_string(),
+                            version.get_creator().to_string(),
+                            version.get_contract_sha512().to_string(),
+                        ]
+                    })
+                    .collect(),
+            );
+            Ok(())
+        }
+    }
+}
+
+pub fn do_show_namespace_registry(
+    url: &str,
+    namespace: &str,
+    format: OutputFormat,
+) -> Result<(), CliError> {
+    let registry = fetch_namespace_registry(url, namespace)?.ok_or_else(|| {
+        CliError::UserError(format!("Namespace registry does not exist: {}", namespace))
+    })?;
+
+    match format {
+        OutputFormat::Json => print_json(&namespace_registry_to_json(&registry)),
+        OutputFormat::Table => {
+            println!("Namespace: {}", registry.get_namespace());
+            println!("Owners: {}", registry.get_owners().join(", "));
+            print_owner_change(
+                registry.get_owner_threshold(),
+                registry.get_owner_change_proposal(),
+            );
+            println!("Permissions:");
+            print_table(
+                &["CONTRACT", "PREFIX", "READ", "WRITE", "DENY"],
+                registry
+                    .get_permissions()
+                    .iter()
+                    .map(|permission| {
+                        vec![
+                            permission.get_contract_name().to_string(),
+                            permission.get_prefix().to_string(),
+                            permission.get_read().to_string(),
+                            permission.get_write().to_string(),
+                            permission.get_deny().to_string(),
+                        ]
+                    })
+                    .collect(),
+            );
+            Ok(())
+        }
+    }
+}
+
+pub fn do_show_smart_permission(
+    url: &str,
+    org_id: &str,
+    name: &str,
+    format: OutputFormat,
+) -> Result<(), CliError> {
+    let smart_permission = fetch_smart_permission(url, org_id, name)?.ok_or_else(|| {
+        CliError::UserError(format!(
+            "Smart permission does not exist: {} {}",
+            org_id, name
+        ))
+    })?;
+
+    match format {
+        OutputFormat::Json => print_json(&smart_permission_to_json(&smart_permission)),
+        OutputFormat::Table => {
+            println!("Name: {}", smart_permission.get_name());
+            println!("Organization: {}", smart_permission.get_org_id());
+            println!("Size: {} bytes", smart_permission.get_function().len());
+            println!("SHA512: {}", sha512_hex(smart_permission.get_function()));
+            Ok(())
+        }
+    }
+}
+
+pub fn do_show_account(
+    url: &str,
+    account_public_key: &str,
+    format: OutputFormat,
+) -> Result<(), CliError> {
+    let account = fetch_account(url, account_public_key)?.ok_or_else(|| {
+        CliError::UserError(format!("Account does not exist: {}", account_public_key))
+    })?;
+
+    match format {
+        OutputFormat::Json => print_json(&account_to_json(&account)),
+        OutputFormat::Table => {
+            println!("Public Key: {}", account.get_public_key());
+            println!("Organization: {}", account.get_org_id());
+            println!("Active: {}", account.get_active());
+            println!("Roles: {}", account.get_roles().join(", "));
+            print_metadata(account.get_metadata());
+            Ok(())
+        }
+    }
+}
+
+pub fn do_show_organization(url: &str, org_id: &str, format: OutputFormat) -> Result<(), CliError> {
+    let organization = fetch_organization(url, org_id)?
+        .ok_or_else(|| CliError::UserError(format!("Organization does not exist: {}", org_id)))?;
+
+    match format {
+        OutputFormat::Json => print_json(&organization_to_json(&organization)),
+        OutputFormat::Table => {
+            println!("ID: {}", organization.get_org_id());
+            println!("Name: {}", organization.get_name());
+            println!("Address: {}", organization.get_address());
+            print_metadata(organization.get_metadata());
+            println!("Action policies:");
+            for policy in organization.get_action_policies() {
+                println!("  {}: {}", policy.get_action(), policy.get_smart_permission());
+            }
+            Ok(())
+        }
+    }
+}
+
+pub fn do_show_role(
+    url: &str,
+    org_id: &str,
+    name: &str,
+    format: OutputFormat,
+) -> Result<(), CliError> {
+    let role = fetch_role(url, org_id, name)?
+        .ok_or_else(|| CliError::UserError(format!("Role does not exist: {} {}", org_id, name)))?;
+
+    match format {
+        OutputFormat::Json => print_json(&role_to_json(&role)),
+        OutputFormat::Table => {
+            println!("Name: {}", role.get_name());
+            println!("Organization: {}", role.get_org_id());
+            println!("Permissions: {}", role.get_permissions().join(", "));
+            Ok(())
+        }
+    }
+}
+
+/// Loads a contract from state through the REST API
+pub fn fetch_contract(url: &str, name: &str, version: &str) -> Result<Option<Contract>, CliError> {
+    let bytes = match get_state(url, &compute_contract_address(name, version))? {
+        Some(bytes) => bytes,
+        None => return Ok(None),
+    };
+
+    let contracts = ContractList::from_bytes(&bytes)?;
+    Ok(contracts
+        .get_contracts()
+        .iter()
+        .find(|contract| contract.get_name() == name && contract.get_version() == version)
+        .cloned())
+}
+
+/// Loads a contract registry from state through the REST API
+pub fn fetch_contract_registry(
+    url: &str,
+    name: &str,
+) -> Result<Option<ContractRegistry>, CliError> {
+    let bytes = match get_state(url, &compute_contract_registry_address(name))? {
+        Some(bytes) => bytes,
+        None => return Ok(None),
+    };
+
+    let registries = ContractRegistryList::from_bytes(&bytes)?;
+    Ok(registries
+        .get_registries()
+        .iter()
+        .find(|registry| registry.get_name() == name)
+        .cloned())
+}
+
+/// Loads a namespace registry from state through the REST API
+pub fn fetch_namespace_registry(
+    url: &str,
+    namespace: &str,
+) -> Result<Option<NamespaceRegistry>, CliError> {
+    let bytes = match get_state(url, &compute_namespace_registry_address(namespace)?)? {
+        Some(bytes) => bytes,
+        None => return Ok(None),
+    };
+
+    let registries = NamespaceRegistryList::from_bytes(&bytes)?;
+    Ok(registries
+        .get_registries()
+        .iter()
+        .find(|registry| registry.get_namespace() == namespace)
+        .cloned())
+}
+
+/// Loads a smart permission from state through the REST API
+pub fn fetch_smart_permission(
+    url: &str,
+    org_id: &str,
+    name: &str,
+) -> Result<Option<SmartPermission>, CliError> {
+    let bytes = match get_state(url, &compute_smart_permission_address(org_id, name))? {
+        Some(bytes) => bytes,
+        None => return Ok(None),
+    };
+
+    let smart_permissions = SmartPermissionList::from_bytes(&bytes)?;
+    Ok(smart_permissions
+        .get_smart_permissions()
+        .iter()
+        .find(|smart_permission| {
+            smart_permission.get_org_id() == org_id && smart_permission.get_name() == name
+        })
+        .cloned())
+}
+
+fn contract_to_json(contract: &Contract) -> Value {
+    json!({
+        "name": contract.get_name(),
+        "version": contract.get_version(),
+        "creator": contract.get_creator(),
+        "inputs": contract.get_inputs(),
+        "outputs": contract.get_outputs(),
+        "size": contract.get_contract().len(),
+        "sha512": sha512_hex(contract.get_contract()),
+        "source": contract.get_source().map(|source| json!({
+            "repository": source.get_repository(),
+            "commit": source.get_commit(),
+            "toolchain": source.get_toolchain(),
+            "wasm_sha512": source.get_wasm_sha512(),
+        })),
+    })
+}
+
+fn contract_registry_to_json(registry: &ContractRegistry) -> Value {
+    let versions: Vec<Value> = registry
+        .get_versions()
+        .iter()
+        .map(|version| {
+            json!({
+                "version": version.get_version(),
+                "contract_sha512": version.get_contract_sha512(),
+                "creator": version.get_creator(),
+                "deprecated": version.get_deprecated(),
+            })
+        })
+        .collect();
+
+    json!({
+        "name": registry.get_name(),
+        "owners": registry.get_owners(),
+        "owner_threshold": registry.get_owner_threshold(),
+        "owner_change_proposal": registry.get_owner_change_proposal().map(owner_change_to_json),
+        "versions": versions,
+    })
+}
+
+fn namespace_registry_to_json(registry: &NamespaceRegistry) -> Value {
+    let permissions: Vec<Value> = registry
+        .get_permissions()
+        .iter()
+        .map(|permission| {
+            json!({
+                "contract_name": permission.get_contract_name(),
+                "prefix": permission.get_prefix(),
+                "read": permission.get_read(),
+                "write": permission.get_write(),
+                "deny": permission.get_deny(),
+            })
+        })
+        .collect();
+
+    json!({
+        "namespace": registry.get_namespace(),
+        "owners": registry.get_owners(),
+        "owner_threshold": registry.get_owner_threshold(),
+        "owner_change_proposal": registry.get_owner_change_proposal().map(owner_change_to_json),
+        "permissions": permissions,
+    })
+}
+
+fn owner_change_to_json(proposal: &OwnerChangeProposal) -> Value {
+    json!({
+        "owners": proposal.get_owners(),
+        "owner_threshold": proposal.get_owner_threshold(),
+        "proposer": proposal.get_proposer(),
+        "approvals": proposal.get_approvals(),
+        "rejections": proposal.get_rejections(),
+        "expires_at_block": proposal.get_expires_at_block(),
+    })
+}
+
+// Prints the owner threshold of a registry and its pending owner change, if any
+fn print_owner_change(owner_threshold: u32, proposal: Option<&OwnerChangeProposal>) {
+    println!("Owner threshold: {}", owner_threshold.max(1));
+    if let Some(proposal) = proposal {
+        println!("Pending owner change:");
+        println!("  Owners: {}", proposal.get_owners().join(", "));
+        println!("  Owner threshold: {}", proposal.get_owner_threshold().max(1));
+        println!("  Proposer: {}", proposal.get_proposer());
+        println!("  Approvals: {}", proposal.get_approvals().join(", "));
+        println!("  Rejections: {}", proposal.get_rejections().join(", "));
+        println!("  Expires at block: {}", proposal.get_expires_at_block());
+    }
+}
+
+fn smart_permission_to_json(smart_permission: &SmartPermission) -> Value {
+    json!({
+        "name": smart_permission.get_name(),
+        "org_id": smart_permission.get_org_id(),
+        "size": smart_permission.get_function().len(),
+        "sha512": sha512_hex(smart_permission.get_function()),
+    })
+}
+
+fn account_to_json(account: &Account) -> Value {
+    json!({
+        "public_key": account.get_public_key(),
+        "org_id": account.get_org_id(),
+        "active": account.get_active(),
+        "roles": account.get_roles(),
+        "metadata": metadata_to_json(account.get_metadata()),
+    })
+}
+
+fn organization_to_json(organization: &Organization) -> Value {
+    json!({
+        "org_id": organization.get_org_id(),
+        "name": organization.get_name(),
+        "address": organization.get_address(),
+        "metadata": metadata_to_json(organization.get_metadata()),
+        "action_policies": organization
+            .get_action_policies()
+            .iter()
+            .map(|policy| {
+                json!({
+                    "action": policy.get_action(),
+                    "smart_permission": policy.get_smart_permission(),
+                })
+            })
+            .collect::<Vec<Value>>(),
+    })
+}
+
+fn role_to_json(role: &Role) -> Value {
+    json!({
+        "org_id": role.get_org_id(),
+        "name": role.get_name(),
+        "permissions": role.get_permissions(),
+    })
+}
+
+fn metadata_to_json(metadata: &[KeyValueEntry]) -> Value {
+    Value::Array(
+        metadata
+            .iter()
+            .map(|entry| json!({ "key": entry.get_key(), "value": entry.get_value() }))
+            .collect(),
+    )
+}
+
+fn print_json(value: &Value) -> Result<(), CliError> {
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|err| CliError::UserError(format!("Unable to format json: {}", err)))?;
+    println!("{}", json);
+    Ok(())
+}
+
+fn print_metadata(metadata: &[KeyValueEntry]) {
+    println!("Metadata:");
+    for entry in metadata {
+        println!("  {}: {}", entry.get_key(), entry.get_value());
+    }
+}
+
+// helper function to print rows as columns padded to the widest value
+fn print_table(headers: &[&str], rows: Vec<Vec<String>>) {
+    let mut widths: Vec<usize> = headers.iter().map(|header| header.len()).collect();
+    for row in &rows {
+        for (width, value) in widths.iter_mut().zip(row) {
+            *width = (*width).max(value.len());
+        }
+    }
+
+    let format_row = |values: Vec<&str>| {
+        values
+            .iter()
+            .zip(&widths)
+            .map(|(value, width)| format!("{:width$}", value, width = width))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    println!("  {}", format_row(headers.to_vec()));
+    for row in &rows {
+        println!("  {}", format_row(row.iter().map(String::as_str).collect()));
+    }
+}
+
+pub fn sha512_hex(bytes: &[u8]) -> String {
+    let mut sha = Sha512::new();
+    sha.input(bytes);
+    sha.result_str()
+}
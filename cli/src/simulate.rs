@@ -0,0 +1,310 @@
+// Copyright (c) The dgc.network
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+use dgc_contract_sdk::protocol::payload::SmartPayload;
+use dgc_contract_sdk::protos::FromBytes;
+use dgc_contract_tp::simulator::{SimulatedTransaction, SimulationResult, Simulator};
+use protobuf;
+use sawtooth_sdk::messages::transaction::TransactionHeader;
+use sawtooth_sdk::signing;
+use serde_json::{self, Value};
+use yaml_rust::{Yaml, YamlLoader};
+
+use crate::error::CliError;
+use crate::execute::{create_exec_txn_payload, load_contract_payload_file};
+use crate::key;
+use crate::show::OutputFormat;
+use crate::transaction::create_transaction;
+use crate::upload::load_upload_payload;
+
+/// Applies the transactions of a scenario file to in-memory state and prints
+/// the outcome of each one
+///
+/// If a trace directory is given, the execution trace of each transaction is
+/// written to it. Returns an error if any transaction was rejected.
+pub fn do_simulate(
+    filename: &str,
+    format: OutputFormat,
+    trace_dir: Option<&str>,
+) -> Result<(), CliError> {
+    let scenario = Scenario::load(filename)?;
+    let mut simulator = Simulator::new(scenario.state);
+    if let Some(trace_dir) = trace_dir {
+        simulator = simulator.with_trace_dir(PathBuf::from(trace_dir));
+    }
+
+    let mut rejected = 0;
+    let mut outcomes = Vec::new();
+    for (index, step) in scenario.transactions.iter().enumerate() {
+        let txn = create_simulated_transaction(step)?;
+        let result = simulator.apply(&txn);
+        if result.error.is_some() {
+            rejected += 1;
+        }
+
+        match format {
+            OutputFormat::Json => outcomes.push(result_to_json(step, &result)),
+            OutputFormat::Table => print_result(index, step, &result),
+        }
+    }
+
+    if format == OutputFormat::Json {
+        let json = serde_json::to_string_pretty(&Value::Array(outcomes))
+            .map_err(|err| CliError::UserError(format!("Unable to format json: {}", err)))?;
+        println!("{}", json);
+    }
+
+    if rejected > 0 {
+        return Err(CliError::UserError(format!(
+            "{} of {} transactions were rejected",
+            rejected,
+            scenario.transactions.len()
+        )));
+    }
+
+    Ok(())
+}
+
+// Signs the step's payload with its key, the same way the payload would be
+// submitted to a validator
+fn create_simulated_transaction(step: &ScenarioStep) -> Result<SimulatedTransaction, CliError> {
+    let private_key = key::load_signing_key(Some(&step.key))?;
+    let context = signing::create_context("secp256k1")?;
+    let public_key = context.get_public_key(&private_key)?.as_hex();
+    let factory = signing::CryptoFactory::new(&*context);
+    let signer = factory.new_signer(&private_key);
+
+    let txn = create_transaction(step.payload.clone(), &signer, &public_key)?;
+    let header: TransactionHeader = protobuf::parse_from_bytes(txn.get_header())?;
+
+    Ok(SimulatedTransaction {
+        signer_public_key: public_key,
+        signature: txn.get_header_signature().to_string(),
+        payload: txn.get_payload().to_vec(),
+        inputs: header.get_inputs().to_vec(),
+        outputs: header.get_outputs().to_vec(),
+    })
+}
+
+fn print_result(index: usize, step: &ScenarioStep, result: &SimulationResult) {
+    println!("Transaction {}: {} (key {})", index + 1, step.payload.action(), step.key);
+    match result.error {
+        Some(ref error) => println!("  Rejected: {}", error),
+        None => println!("  Applied"),
+    }
+    for change in &result.changes {
+        match (&change.before, &change.after) {
+            (None, Some(after)) => println!("  + {} ({} bytes)", change.address, after.len()),
+            (Some(_), Some(after)) => println!("  ~ {} ({} bytes)", change.address, after.len()),
+            (_, None) => println!("  - {}", change.address),
+        }
+    }
+    for event in &result.events {
+        let attributes: Vec<String> = event
+            .attributes
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect();
+        println!(
+            "  Event: {} [{}] ({} bytes)",
+            event.event_type,
+            attributes.join(", "),
+            event.data.len()
+        );
+    }
+    for data in &result.receipt_data {
+        println!("  Receipt data: {}", base64::encode(data));
+    }
+    if let Some(ref trace_file) = result.trace_file {
+        println!("  Trace: {}", trace_file.display());
+    }
+}
+
+fn result_to_json(step: &ScenarioStep, result: &SimulationResult) -> Value {
+    json!({
+        "action": step.payload.action().to_string(),
+        "key": step.key,
+        "error": result.error,
+        "changes": result
+            .changes
+            .iter()
+            .map(|change| json!({
+                "address": change.address,
+                "before": change.before.as_ref().map(base64::encode),
+                "after": change.after.as_ref().map(base64::encode),
+            }))
+            .collect::<Vec<_>>(),
+        "events": result
+            .events
+            .iter()
+            .map(|event| json!({
+                "event_type": event.event_type,
+                "attributes": event
+                    .attributes
+                    .iter()
+                    .map(|(key, value)| json!({"key": key, "value": value}))
+                    .collect::<Vec<_>>(),
+                "data": base64::encode(&event.data),
+            }))
+            .collect::<Vec<_>>(),
+        "receipt_data": result
+            .receipt_data
+            .iter()
+            .map(base64::encode)
+            .collect::<Vec<_>>(),
+        "trace_file": result
+            .trace_file
+            .as_ref()
+            .map(|trace_file| trace_file.display().to_string()),
+    })
+}
+
+/// A transaction of a scenario: a payload and the name of the key signing it
+struct ScenarioStep {
+    key: String,
+    payload: SmartPayload,
+}
+
+/// The initial state and transactions loaded from a scenario file
+struct Scenario {
+    state: BTreeMap<String, Vec<u8>>,
+    transactions: Vec<ScenarioStep>,
+}
+
+impl Scenario {
+    fn load(filename: &str) -> Result<Scenario, CliError> {
+        let file = File::open(filename).map_err(|e| {
+            CliError::UserError(format!(
+                "Could not load scenario file \"{}\": {}",
+                filename, e
+            ))
+        })?;
+        let mut buf_reader = BufReader::new(file);
+        let mut contents = String::new();
+        buf_reader.read_to_string(&mut contents).map_err(|e| {
+            CliError::UserError(format!(
+                "IoError while reading scenario file \"{}\": {}",
+                filename, e
+            ))
+        })?;
+
+        // JSON scenarios are valid YAML as well
+        let docs = YamlLoader::load_from_str(&contents).map_err(|e| {
+            CliError::UserError(format!("Malformed scenario file \"{}\": {}", filename, e))
+        })?;
+        let doc = docs.get(0).ok_or_else(|| {
+            CliError::UserError(format!("Malformed scenario file \"{}\": no content", filename))
+        })?;
+
+        let mut state = BTreeMap::new();
+        if let Some(entries) = doc["state"].as_hash() {
+            for (address, data) in entries {
+                let (address, data) = match (address.as_str(), data.as_str()) {
+                    (Some(address), Some(data)) => (address, data),
+                    _ => {
+                        return Err(malformed(
+                            filename,
+                            "state entries must map addresses to base64 strings",
+                        ))
+                    }
+                };
+                let data = base64::decode(data).map_err(|e| {
+                    CliError::UserError(format!(
+                        "Malformed scenario file \"{}\": state of {} is not valid base64: {}",
+                        filename, address, e
+                    ))
+                })?;
+                state.insert(address.to_string(), data);
+            }
+        }
+
+        let steps = doc["transactions"]
+            .as_vec()
+            .ok_or_else(|| malformed(filename, "missing list field \"transactions\""))?;
+
+        let transactions = steps
+            .iter()
+            .map(|step| load_step(filename, step))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Scenario {
+            state,
+            transactions,
+        })
+    }
+}
+
+// Loads the payload of a transaction, with paths relative to the directory
+// containing the scenario file
+fn load_step(filename: &str, step: &Yaml) -> Result<ScenarioStep, CliError> {
+    let key = step["key"]
+        .as_str()
+        .ok_or_else(|| malformed(filename, "transaction missing string field \"key\""))?;
+
+    let payload = if let Some(path) = step["payload"].as_str() {
+        let bytes = load_contract_payload_file(&relative_path(filename, path))?;
+        SmartPayload::from_bytes(&bytes)?
+    } else if let Some(path) = step["upload"].as_str() {
+        load_upload_payload(&relative_path(filename, path))?
+    } else if !step["execute"].is_badvalue() {
+        let execute = &step["execute"];
+        let contract = execute["contract"]
+            .as_str()
+            .ok_or_else(|| malformed(filename, "execute missing string field \"contract\""))?;
+        let version = execute["version"].as_str().unwrap_or("latest");
+        let path = execute["payload"]
+            .as_str()
+            .ok_or_else(|| malformed(filename, "execute missing string field \"payload\""))?;
+        let contract_payload = load_contract_payload_file(&relative_path(filename, path))?;
+
+        create_exec_txn_payload(
+            contract,
+            version,
+            string_list(&execute["inputs"]),
+            string_list(&execute["outputs"]),
+            contract_payload,
+        )?
+    } else {
+        return Err(malformed(
+            filename,
+            "transaction requires one of \"payload\", \"upload\" or \"execute\"",
+        ));
+    };
+
+    Ok(ScenarioStep {
+        key: key.to_string(),
+        payload,
+    })
+}
+
+fn relative_path(filename: &str, path: &str) -> String {
+    let mut path_buf = PathBuf::from(filename);
+    path_buf.pop();
+    path_buf.push(Path::new(path));
+    path_buf.to_string_lossy().into_owned()
+}
+
+fn string_list(value: &Yaml) -> Vec<String> {
+    value
+        .as_vec()
+        .map(|values| {
+            values
+                .iter()
+                .filter_map(|value| value.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn malformed(filename: &str, reason: &str) -> CliError {
+    CliError::UserError(format!(
+        "Malformed scenario file \"{}\": {}",
+        filename, reason
+    ))
+}
@@ -3,6 +3,7 @@
 
 //! Contains functions which assist with batch submission to a REST API
 
+use base64;
 use futures::Stream;
 use futures::{future, Future};
 use hyper;
@@ -106,11 +107,73 @@ pub fn wait_for_batch(url: &str, wait: u64) -> Result<StatusResponse, CliError>
     Ok(body)
 }
 
+/// Fetches the data stored at a state address from the REST API, returning
+/// None if the address is not set
+pub fn get_state(url: &str, address: &str) -> Result<Option<Vec<u8>>, CliError> {
+    let get_url = String::from(url) + "/state/" + address;
+    let hyper_uri = match get_url.parse::<hyper::Uri>() {
+        Ok(uri) => uri,
+        Err(e) => return Err(CliError::UserError(format!("Invalid URL: {}: {}", e, url))),
+    };
+
+    match hyper_uri.scheme() {
+        Some(scheme) => {
+            if scheme != "http" {
+                return Err(CliError::UserError(format!(
+                    "Unsupported scheme ({}) in URL: {}",
+                    scheme, url
+                )));
+            }
+        }
+        None => {
+            return Err(CliError::UserError(format!("No scheme in URL: {}", url)));
+        }
+    }
+
+    let mut core = tokio_core::reactor::Core::new()?;
+    let handle = core.handle();
+    let client = Client::configure().build(&handle);
+
+    let work = client.get(hyper_uri).and_then(|res| {
+        let status = res.status();
+        res.body()
+            .concat2()
+            .and_then(move |chunks| future::ok((status, chunks)))
+    });
+
+    let (status, body) = core.run(work)?;
+    match status {
+        StatusCode::Ok => (),
+        StatusCode::NotFound => return Ok(None),
+        _ => {
+            return Err(CliError::UserError(format!(
+                "Unable to fetch state at {}: {}",
+                address,
+                String::from_utf8_lossy(&body)
+            )));
+        }
+    }
+
+    let entry = serde_json::from_slice::<StateEntry>(&body).map_err(|err| {
+        CliError::UserError(format!("Invalid state response for {}: {}", address, err))
+    })?;
+    let data = base64::decode(&entry.data).map_err(|err| {
+        CliError::UserError(format!("Invalid state data at {}: {}", address, err))
+    })?;
+
+    Ok(Some(data))
+}
+
 #[derive(Deserialize, Debug)]
 struct Link {
     link: String,
 }
 
+#[derive(Deserialize, Debug)]
+struct StateEntry {
+    data: String,
+}
+
 #[derive(Deserialize, Debug)]
 pub struct BatchStatus {
     id: String,
@@ -9,12 +9,15 @@ use std::path::PathBuf;
 
 use dgc_contract_sdk::protocol::payload::{
     Action, CreateContractActionBuilder, SmartPayload, SmartPayloadBuilder,
+    UpgradeContractActionBuilder,
 };
+use dgc_contract_sdk::protocol::state::{ContractSource, ContractSourceBuilder};
 use sawtooth_sdk::signing;
 use yaml_rust::YamlLoader;
 
 use crate::error::CliError;
 use crate::key;
+use crate::show::sha512_hex;
 use crate::submit::submit_batch_list;
 use crate::transaction::{create_batch, create_batch_list_from_one, create_transaction};
 
@@ -31,13 +34,98 @@ pub fn do_upload(
     let signer = factory.new_signer(&private_key);
 
     let definition = ContractDefinition::load(filename)?;
+    let contract = load_definition_contract(filename, &definition, wasm_name)?;
+    let source = create_contract_source(&definition, &contract)?;
 
-    // Load the contract file relative to the directory containing the
-    // definition YAML
+    let payload = create_upload_payload(
+        &definition.name,
+        &definition.version,
+        definition.inputs,
+        definition.outputs,
+        contract,
+        source,
+    )?;
+
+    let txn = create_transaction(payload, &signer, &public_key)?;
+    let batch = create_batch(txn, &signer, &public_key)?;
+    let batch_list = create_batch_list_from_one(batch);
+
+    submit_batch_list(url, &batch_list)
+}
+
+pub fn do_upgrade(
+    filename: &str,
+    previous_version: &str,
+    migrate_payload_file: Option<&str>,
+    key_name: Option<&str>,
+    url: &str,
+    wasm_name: Option<&str>,
+) -> Result<String, CliError> {
+    let private_key = key::load_signing_key(key_name)?;
+    let context = signing::create_context("secp256k1")?;
+    let public_key = context.get_public_key(&private_key)?.as_hex();
+    let factory = signing::CryptoFactory::new(&*context);
+    let signer = factory.new_signer(&private_key);
+
+    let definition = ContractDefinition::load(filename)?;
+    let contract = load_definition_contract(filename, &definition, wasm_name)?;
+    let source = create_contract_source(&definition, &contract)?;
+
+    let migrate_payload = match migrate_payload_file {
+        Some(path) => load_contract_file(Path::new(path))?,
+        None => Vec::new(),
+    };
+
+    let upgrade_contract = UpgradeContractActionBuilder::new()
+        .set_name(definition.name)
+        .set_version(definition.version)
+        .set_previous_version(String::from(previous_version))
+        .set_inputs(definition.inputs)
+        .set_outputs(definition.outputs)
+        .set_contract(contract)
+        .set_migrate_payload(migrate_payload)
+        .set_source(source)
+        .build()?;
+
+    let payload = SmartPayloadBuilder::new()
+        .set_action(Action::UpgradeContract(upgrade_contract))
+        .build()?;
+
+    let txn = create_transaction(payload, &signer, &public_key)?;
+    let batch = create_batch(txn, &signer, &public_key)?;
+    let batch_list = create_batch_list_from_one(batch);
+
+    submit_batch_list(url, &batch_list)
+}
+
+/// Creates the payload uploading the contract described by a contract
+/// definition file, as `do_upload` submits it
+pub fn load_upload_payload(filename: &str) -> Result<SmartPayload, CliError> {
+    let definition = ContractDefinition::load(filename)?;
+    let contract = load_definition_contract(filename, &definition, None)?;
+    let source = create_contract_source(&definition, &contract)?;
+
+    create_upload_payload(
+        &definition.name,
+        &definition.version,
+        definition.inputs,
+        definition.outputs,
+        contract,
+        source,
+    )
+}
+
+// Load the contract file relative to the directory containing the definition
+// YAML, unless a path was given with --wasm
+fn load_definition_contract(
+    filename: &str,
+    definition: &ContractDefinition,
+    wasm_name: Option<&str>,
+) -> Result<Vec<u8>, CliError> {
     let mut contract_path_buf = PathBuf::new();
     if let Some(path) = wasm_name {
         contract_path_buf.push(path);
-    } else if let Some(wasm) = definition.wasm {
+    } else if let Some(ref wasm) = definition.wasm {
         contract_path_buf.push(filename);
         contract_path_buf.pop();
         contract_path_buf.push(wasm);
@@ -48,21 +136,28 @@ pub fn do_upload(
         )));
     }
 
-    let contract = load_contract_file(contract_path_buf.as_path())?;
+    load_contract_file(contract_path_buf.as_path())
+}
 
-    let payload = create_upload_payload(
-        &definition.name,
-        &definition.version,
-        definition.inputs,
-        definition.outputs,
-        contract,
-    )?;
+// Records where the contract was built from, if the definition has a source section, along with
+// the hash of the contract being uploaded
+fn create_contract_source(
+    definition: &ContractDefinition,
+    contract: &[u8],
+) -> Result<Option<ContractSource>, CliError> {
+    let source = match definition.source {
+        Some(ref source) => source,
+        None => return Ok(None),
+    };
 
-    let txn = create_transaction(payload, &signer, &public_key)?;
-    let batch = create_batch(txn, &signer, &public_key)?;
-    let batch_list = create_batch_list_from_one(batch);
+    let contract_source = ContractSourceBuilder::new()
+        .set_repository(source.repository.clone())
+        .set_commit(source.commit.clone())
+        .set_toolchain(source.toolchain.clone().unwrap_or_default())
+        .set_wasm_sha512(sha512_hex(contract))
+        .build()?;
 
-    submit_batch_list(url, &batch_list)
+    Ok(Some(contract_source))
 }
 
 fn create_upload_payload(
@@ -71,6 +166,7 @@ fn create_upload_payload(
     inputs: Vec<String>,
     outputs: Vec<String>,
     contract: Vec<u8>,
+    source: Option<ContractSource>,
 ) -> Result<SmartPayload, CliError> {
     let create_contract = CreateContractActionBuilder::new()
         .set_name(String::from(name))
@@ -78,6 +174,7 @@ fn create_upload_payload(
         .set_inputs(inputs)
         .set_outputs(outputs)
         .set_contract(contract)
+        .set_source(source)
         .build()?;
 
     let payload = SmartPayloadBuilder::new()
@@ -114,6 +211,14 @@ struct ContractDefinition {
     inputs: Vec<String>,
     outputs: Vec<String>,
     wasm: Option<String>,
+    source: Option<DefinitionSource>,
+}
+
+/// The optional `source` section of a contract definition
+struct DefinitionSource {
+    repository: String,
+    commit: String,
+    toolchain: Option<String>,
 }
 
 impl ContractDefinition {
@@ -158,6 +263,30 @@ impl ContractDefinition {
 
         let wasm = doc["wasm"].as_str().map(ToString::to_string);
 
+        let source = if doc["source"].is_badvalue() {
+            None
+        } else {
+            let source = &doc["source"];
+            let repository = source["repository"].as_str().ok_or_else(|| {
+                CliError::UserError(format!(
+                    "Malformed contract definition file \"{}\": source missing string field \"repository\"",
+                    filename
+                ))
+            })?;
+            let commit = source["commit"].as_str().ok_or_else(|| {
+                CliError::UserError(format!(
+                    "Malformed contract definition file \"{}\": source missing string field \"commit\"",
+                    filename
+                ))
+            })?;
+
+            Some(DefinitionSource {
+                repository: String::from(repository),
+                commit: String::from(commit),
+                toolchain: source["toolchain"].as_str().map(String::from),
+            })
+        };
+
         let inputs = doc["inputs"]
             .as_vec()
             .ok_or_else(|| CliError::UserError(format!(
@@ -196,6 +325,7 @@ impl ContractDefinition {
             inputs,
             outputs,
             wasm,
+            source,
         })
     }
 }
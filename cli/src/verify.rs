@@ -0,0 +1,180 @@
+// Copyright (c) The dgc.network
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::error::CliError;
+use crate::show::{fetch_contract, sha512_hex};
+
+const WASM_TARGET: &str = "wasm32-unknown-unknown";
+
+/// Rebuilds a contract from a local checkout of its source and checks that
+/// the result is byte-for-byte the contract stored in state
+///
+/// The checkout must be clean and at the commit recorded with the contract.
+/// The contract is built with `cargo build --release`, using the recorded
+/// toolchain if there is one. If the build produces several wasm files, the
+/// one to compare is given with `wasm`, relative to the checkout.
+pub fn do_verify(
+    url: &str,
+    name: &str,
+    version: &str,
+    checkout: &str,
+    wasm: Option<&str>,
+) -> Result<(), CliError> {
+    let contract = fetch_contract(url, name, version)?.ok_or_else(|| {
+        CliError::UserError(format!("Contract does not exist: {}:{}", name, version))
+    })?;
+    let source = contract.get_source().ok_or_else(|| {
+        CliError::UserError(format!(
+            "Contract has no recorded source: {}:{}",
+            name, version
+        ))
+    })?;
+
+    let deployed_sha512 = sha512_hex(contract.get_contract());
+    if !source.get_wasm_sha512().is_empty()
+        && !source.get_wasm_sha512().eq_ignore_ascii_case(&deployed_sha512)
+    {
+        return Err(CliError::UserError(format!(
+            "Recorded source hash does not match the deployed contract: {}:{}",
+            name, version
+        )));
+    }
+
+    println!("Repository: {}", source.get_repository());
+    println!("Commit: {}", source.get_commit());
+
+    let checkout = Path::new(checkout);
+    check_checkout(checkout, source.get_commit())?;
+    build_contract(checkout, source.get_toolchain())?;
+
+    let wasm_path = find_built_wasm(checkout, wasm)?;
+    let built = fs::read(&wasm_path).map_err(|e| {
+        CliError::UserError(format!(
+            "Could not load built contract \"{}\": {}",
+            wasm_path.display(),
+            e
+        ))
+    })?;
+    let built_sha512 = sha512_hex(&built);
+
+    println!("Built: {}", wasm_path.display());
+    println!("Built SHA512: {}", built_sha512);
+    println!("Deployed SHA512: {}", deployed_sha512);
+
+    if built_sha512 != deployed_sha512 {
+        return Err(CliError::UserError(format!(
+            "Built contract does not match the deployed contract: {}:{}",
+            name, version
+        )));
+    }
+
+    println!("Verified: {}:{}", name, version);
+    Ok(())
+}
+
+// Checks that the checkout has no local changes and that HEAD is the
+// recorded commit, which must be a full commit hash
+fn check_checkout(checkout: &Path, commit: &str) -> Result<(), CliError> {
+    if commit.len() != 40 || !commit.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(CliError::UserError(format!(
+            "Recorded commit is not a full 40 character commit hash: {}",
+            commit
+        )));
+    }
+
+    let head = run_git(checkout, &["rev-parse", "HEAD"])?;
+    if !head.eq_ignore_ascii_case(commit) {
+        return Err(CliError::UserError(format!(
+            "Checkout is at commit {}, contract was built from {}",
+            head, commit
+        )));
+    }
+
+    let status = run_git(checkout, &["status", "--porcelain", "--untracked-files=no"])?;
+    if !status.is_empty() {
+        return Err(CliError::UserError(format!(
+            "Checkout \"{}\" has uncommitted changes",
+            checkout.display()
+        )));
+    }
+
+    Ok(())
+}
+
+fn run_git(checkout: &Path, args: &[&str]) -> Result<String, CliError> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(checkout)
+        .args(args)
+        .output()
+        .map_err(|e| CliError::UserError(format!("Unable to run git: {}", e)))?;
+    if !output.status.success() {
+        return Err(CliError::UserError(format!(
+            "git {} failed in \"{}\": {}",
+            args.join(" "),
+            checkout.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn build_contract(checkout: &Path, toolchain: &str) -> Result<(), CliError> {
+    let mut command = Command::new("cargo");
+    if !toolchain.is_empty() {
+        command.arg(format!("+{}", toolchain));
+    }
+    command
+        .args(&["build", "--release", "--target", WASM_TARGET])
+        .current_dir(checkout);
+
+    let status = command
+        .status()
+        .map_err(|e| CliError::UserError(format!("Unable to run cargo: {}", e)))?;
+    if !status.success() {
+        return Err(CliError::UserError(format!(
+            "Unable to build contract in \"{}\"",
+            checkout.display()
+        )));
+    }
+
+    Ok(())
+}
+
+// Returns the wasm file given with --wasm, or else the only wasm file the
+// release build produced
+fn find_built_wasm(checkout: &Path, wasm: Option<&str>) -> Result<PathBuf, CliError> {
+    if let Some(wasm) = wasm {
+        return Ok(checkout.join(wasm));
+    }
+
+    let release_dir = checkout.join("target").join(WASM_TARGET).join("release");
+    let mut wasm_files = fs::read_dir(&release_dir)
+        .map_err(|e| {
+            CliError::UserError(format!(
+                "Could not read build directory \"{}\": {}",
+                release_dir.display(),
+                e
+            ))
+        })?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().map_or(false, |ext| ext == "wasm"))
+        .collect::<Vec<_>>();
+
+    match wasm_files.len() {
+        1 => Ok(wasm_files.remove(0)),
+        0 => Err(CliError::UserError(format!(
+            "Build produced no wasm file in \"{}\"",
+            release_dir.display()
+        ))),
+        _ => Err(CliError::UserError(format!(
+            "Build produced several wasm files in \"{}\", select one with --wasm",
+            release_dir.display()
+        ))),
+    }
+}
@@ -20,8 +20,84 @@ CREATE TABLE agents (
     metadata JSON NOT NULL
 );
 
+CREATE TABLE blocks (
+    block_num BIGINT PRIMARY KEY NOT NULL,
+    block_id VARCHAR(128) NOT NULL,
+    state_root_hash VARCHAR(64) NOT NULL
+);
+
+-- Rows of Smart state are valid from start_block_num up to, but not
+-- including, end_block_num. Current rows end at the maximum BIGINT.
+
 CREATE TABLE organizations (
-    id VARCHAR(256) PRIMARY KEY NOT NULL,
+    row_id BIGSERIAL PRIMARY KEY,
+    id VARCHAR(256) NOT NULL,
+    name VARCHAR(256) NOT NULL,
+    address VARCHAR(256) NOT NULL,
+    state_address VARCHAR(70) NOT NULL,
+    start_block_num BIGINT NOT NULL,
+    end_block_num BIGINT NOT NULL DEFAULT 9223372036854775807
+);
+
+CREATE TABLE accounts (
+    row_id BIGSERIAL PRIMARY KEY,
+    public_key VARCHAR(70) NOT NULL,
+    org_id VARCHAR(256) NOT NULL,
+    active BOOLEAN NOT NULL,
+    roles VARCHAR(256)[] NOT NULL,
+    metadata JSON[] NOT NULL,
+    state_address VARCHAR(70) NOT NULL,
+    start_block_num BIGINT NOT NULL,
+    end_block_num BIGINT NOT NULL DEFAULT 9223372036854775807
+);
+
+CREATE TABLE contracts (
+    row_id BIGSERIAL PRIMARY KEY,
     name VARCHAR(256) NOT NULL,
-    address VARCHAR(256) NOT NULL
+    version VARCHAR(256) NOT NULL,
+    inputs VARCHAR(256)[] NOT NULL,
+    outputs VARCHAR(256)[] NOT NULL,
+    creator VARCHAR(70) NOT NULL,
+    contract_sha512 VARCHAR(128) NOT NULL,
+    state_address VARCHAR(70) NOT NULL,
+    start_block_num BIGINT NOT NULL,
+    end_block_num BIGINT NOT NULL DEFAULT 9223372036854775807
 );
+
+CREATE TABLE contract_registries (
+    row_id BIGSERIAL PRIMARY KEY,
+    name VARCHAR(256) NOT NULL,
+    versions JSON[] NOT NULL,
+    owners VARCHAR(70)[] NOT NULL,
+    owner_threshold INTEGER NOT NULL,
+    state_address VARCHAR(70) NOT NULL,
+    start_block_num BIGINT NOT NULL,
+    end_block_num BIGINT NOT NULL DEFAULT 9223372036854775807
+);
+
+CREATE TABLE namespace_registries (
+    row_id BIGSERIAL PRIMARY KEY,
+    namespace VARCHAR(70) NOT NULL,
+    owners VARCHAR(70)[] NOT NULL,
+    owner_threshold INTEGER NOT NULL,
+    permissions JSON[] NOT NULL,
+    state_address VARCHAR(70) NOT NULL,
+    start_block_num BIGINT NOT NULL,
+    end_block_num BIGINT NOT NULL DEFAULT 9223372036854775807
+);
+
+CREATE TABLE smart_permissions (
+    row_id BIGSERIAL PRIMARY KEY,
+    org_id VARCHAR(256) NOT NULL,
+    name VARCHAR(256) NOT NULL,
+    address VARCHAR(70) NOT NULL,
+    start_block_num BIGINT NOT NULL,
+    end_block_num BIGINT NOT NULL DEFAULT 9223372036854775807
+);
+
+CREATE INDEX organizations_state_idx ON organizations (state_address, end_block_num);
+CREATE INDEX accounts_state_idx ON accounts (state_address, end_block_num);
+CREATE INDEX contracts_state_idx ON contracts (state_address, end_block_num);
+CREATE INDEX contract_registries_state_idx ON contract_registries (state_address, end_block_num);
+CREATE INDEX namespace_registries_state_idx ON namespace_registries (state_address, end_block_num);
+CREATE INDEX smart_permissions_state_idx ON smart_permissions (address, end_block_num);
@@ -0,0 +1,69 @@
+// Copyright (c) The dgc.network
+// SPDX-License-Identifier: Apache-2.0
+
+use schema::accounts;
+use schema::accounts::dsl;
+use models::{Account, NewAccount};
+use MAX_BLOCK_NUM;
+
+use diesel;
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use diesel::QueryResult;
+
+const ACCOUNT_COLUMNS: (
+    dsl::public_key,
+    dsl::org_id,
+    dsl::active,
+    dsl::roles,
+    dsl::metadata
+) = (dsl::public_key, dsl::org_id, dsl::active, dsl::roles, dsl::metadata);
+
+pub fn insert_accounts(conn: &PgConnection, accounts: &[NewAccount]) -> QueryResult<()> {
+    diesel::insert_into(accounts::table)
+        .values(accounts)
+        .execute(conn)
+        .map(|_| ())
+}
+
+/// Ends the history of the accounts stored at `address` at `block_num`
+pub fn end_accounts_at_address(
+    conn: &PgConnection,
+    address: &str,
+    block_num: i64
+) -> QueryResult<()> {
+    diesel::update(accounts::table)
+        .filter(dsl::state_address.eq(address))
+        .filter(dsl::end_block_num.eq(MAX_BLOCK_NUM))
+        .set(dsl::end_block_num.eq(block_num))
+        .execute(conn)
+        .map(|_| ())
+}
+
+/// Removes the changes made by `block_num` and later blocks
+pub fn rollback_accounts(conn: &PgConnection, block_num: i64) -> QueryResult<()> {
+    diesel::delete(accounts::table)
+        .filter(dsl::start_block_num.ge(block_num))
+        .execute(conn)?;
+
+    diesel::update(accounts::table)
+        .filter(dsl::end_block_num.ge(block_num))
+        .set(dsl::end_block_num.eq(MAX_BLOCK_NUM))
+        .execute(conn)
+        .map(|_| ())
+}
+
+pub fn get_account(conn: &PgConnection, public_key: &str) -> QueryResult<Account> {
+    accounts::table
+        .select(ACCOUNT_COLUMNS)
+        .filter(dsl::public_key.eq(public_key))
+        .filter(dsl::end_block_num.eq(MAX_BLOCK_NUM))
+        .first(conn)
+}
+
+pub fn get_accounts(conn: &PgConnection) -> QueryResult<Vec<Account>> {
+    accounts::table
+        .select(ACCOUNT_COLUMNS)
+        .filter(dsl::end_block_num.eq(MAX_BLOCK_NUM))
+        .load(conn)
+}
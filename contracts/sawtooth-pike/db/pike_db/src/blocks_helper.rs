@@ -0,0 +1,56 @@
+// Copyright (c) The dgc.network
+// SPDX-License-Identifier: Apache-2.0
+
+use schema::blocks;
+use schema::blocks::dsl;
+use models::{Block, NewBlock};
+
+use accounts_helper::rollback_accounts;
+use contract_registries_helper::rollback_contract_registries;
+use contracts_helper::rollback_contracts;
+use namespace_registries_helper::rollback_namespace_registries;
+use orgs_helper::rollback_organizations;
+use sp_helper::rollback_smart_permissions;
+
+use diesel;
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use diesel::QueryResult;
+
+pub fn insert_block(conn: &PgConnection, block: NewBlock) -> QueryResult<Block> {
+    diesel::insert_into(blocks::table)
+        .values(&block)
+        .get_result::<Block>(conn)
+}
+
+pub fn get_block(conn: &PgConnection, block_num: i64) -> QueryResult<Block> {
+    blocks::table
+        .select(blocks::all_columns)
+        .find(block_num)
+        .first(conn)
+}
+
+/// Returns up to `limit` of the most recent blocks, newest first
+pub fn get_recent_blocks(conn: &PgConnection, limit: i64) -> QueryResult<Vec<Block>> {
+    blocks::table
+        .select(blocks::all_columns)
+        .order(dsl::block_num.desc())
+        .limit(limit)
+        .load(conn)
+}
+
+/// Removes `block_num` and every later block, along with the state changes
+/// they made, leaving the state as of the block before `block_num`
+pub fn rollback_from(conn: &PgConnection, block_num: i64) -> QueryResult<()> {
+    rollback_accounts(conn, block_num)?;
+    rollback_contract_registries(conn, block_num)?;
+    rollback_contracts(conn, block_num)?;
+    rollback_namespace_registries(conn, block_num)?;
+    rollback_organizations(conn, block_num)?;
+    rollback_smart_permissions(conn, block_num)?;
+
+    diesel::delete(blocks::table)
+        .filter(dsl::block_num.ge(block_num))
+        .execute(conn)
+        .map(|_| ())
+}
@@ -0,0 +1,68 @@
+// Copyright (c) The dgc.network
+// SPDX-License-Identifier: Apache-2.0
+
+use schema::contract_registries;
+use schema::contract_registries::dsl;
+use models::{ContractRegistry, NewContractRegistry};
+use MAX_BLOCK_NUM;
+
+use diesel;
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use diesel::QueryResult;
+
+const CONTRACT_REGISTRY_COLUMNS: (dsl::name, dsl::versions, dsl::owners, dsl::owner_threshold) =
+    (dsl::name, dsl::versions, dsl::owners, dsl::owner_threshold);
+
+pub fn insert_contract_registries(
+    conn: &PgConnection,
+    registries: &[NewContractRegistry]
+) -> QueryResult<()> {
+    diesel::insert_into(contract_registries::table)
+        .values(registries)
+        .execute(conn)
+        .map(|_| ())
+}
+
+/// Ends the history of the contract registries stored at `address` at
+/// `block_num`
+pub fn end_contract_registries_at_address(
+    conn: &PgConnection,
+    address: &str,
+    block_num: i64
+) -> QueryResult<()> {
+    diesel::update(contract_registries::table)
+        .filter(dsl::state_address.eq(address))
+        .filter(dsl::end_block_num.eq(MAX_BLOCK_NUM))
+        .set(dsl::end_block_num.eq(block_num))
+        .execute(conn)
+        .map(|_| ())
+}
+
+/// Removes the changes made by `block_num` and later blocks
+pub fn rollback_contract_registries(conn: &PgConnection, block_num: i64) -> QueryResult<()> {
+    diesel::delete(contract_registries::table)
+        .filter(dsl::start_block_num.ge(block_num))
+        .execute(conn)?;
+
+    diesel::update(contract_registries::table)
+        .filter(dsl::end_block_num.ge(block_num))
+        .set(dsl::end_block_num.eq(MAX_BLOCK_NUM))
+        .execute(conn)
+        .map(|_| ())
+}
+
+pub fn get_contract_registry(conn: &PgConnection, name: &str) -> QueryResult<ContractRegistry> {
+    contract_registries::table
+        .select(CONTRACT_REGISTRY_COLUMNS)
+        .filter(dsl::name.eq(name))
+        .filter(dsl::end_block_num.eq(MAX_BLOCK_NUM))
+        .first(conn)
+}
+
+pub fn get_contract_registries(conn: &PgConnection) -> QueryResult<Vec<ContractRegistry>> {
+    contract_registries::table
+        .select(CONTRACT_REGISTRY_COLUMNS)
+        .filter(dsl::end_block_num.eq(MAX_BLOCK_NUM))
+        .load(conn)
+}
@@ -0,0 +1,79 @@
+// Copyright (c) The dgc.network
+// SPDX-License-Identifier: Apache-2.0
+
+use schema::contracts;
+use schema::contracts::dsl;
+use models::{Contract, NewContract};
+use MAX_BLOCK_NUM;
+
+use diesel;
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use diesel::QueryResult;
+
+const CONTRACT_COLUMNS: (
+    dsl::name,
+    dsl::version,
+    dsl::inputs,
+    dsl::outputs,
+    dsl::creator,
+    dsl::contract_sha512
+) = (dsl::name, dsl::version, dsl::inputs, dsl::outputs, dsl::creator, dsl::contract_sha512);
+
+pub fn insert_contracts(conn: &PgConnection, contracts: &[NewContract]) -> QueryResult<()> {
+    diesel::insert_into(contracts::table)
+        .values(contracts)
+        .execute(conn)
+        .map(|_| ())
+}
+
+/// Ends the history of the contracts stored at `address` at `block_num`
+pub fn end_contracts_at_address(
+    conn: &PgConnection,
+    address: &str,
+    block_num: i64
+) -> QueryResult<()> {
+    diesel::update(contracts::table)
+        .filter(dsl::state_address.eq(address))
+        .filter(dsl::end_block_num.eq(MAX_BLOCK_NUM))
+        .set(dsl::end_block_num.eq(block_num))
+        .execute(conn)
+        .map(|_| ())
+}
+
+/// Removes the changes made by `block_num` and later blocks
+pub fn rollback_contracts(conn: &PgConnection, block_num: i64) -> QueryResult<()> {
+    diesel::delete(contracts::table)
+        .filter(dsl::start_block_num.ge(block_num))
+        .execute(conn)?;
+
+    diesel::update(contracts::table)
+        .filter(dsl::end_block_num.ge(block_num))
+        .set(dsl::end_block_num.eq(MAX_BLOCK_NUM))
+        .execute(conn)
+        .map(|_| ())
+}
+
+pub fn get_contract(conn: &PgConnection, name: &str, version: &str) -> QueryResult<Contract> {
+    contracts::table
+        .select(CONTRACT_COLUMNS)
+        .filter(dsl::name.eq(name))
+        .filter(dsl::version.eq(version))
+        .filter(dsl::end_block_num.eq(MAX_BLOCK_NUM))
+        .first(conn)
+}
+
+pub fn get_contract_versions(conn: &PgConnection, name: &str) -> QueryResult<Vec<Contract>> {
+    contracts::table
+        .select(CONTRACT_COLUMNS)
+        .filter(dsl::name.eq(name))
+        .filter(dsl::end_block_num.eq(MAX_BLOCK_NUM))
+        .load(conn)
+}
+
+pub fn get_contracts(conn: &PgConnection) -> QueryResult<Vec<Contract>> {
+    contracts::table
+        .select(CONTRACT_COLUMNS)
+        .filter(dsl::end_block_num.eq(MAX_BLOCK_NUM))
+        .load(conn)
+}
@@ -15,16 +15,35 @@ use diesel::result::Error;
 mod schema;
 mod agents_helper;
 mod orgs_helper;
+mod accounts_helper;
+mod contracts_helper;
+mod contract_registries_helper;
+mod namespace_registries_helper;
+mod sp_helper;
+mod blocks_helper;
 
 pub mod pools;
 pub mod models;
 
 pub use orgs_helper::*;
 pub use agents_helper::*;
+pub use accounts_helper::*;
+pub use contracts_helper::*;
+pub use contract_registries_helper::*;
+pub use namespace_registries_helper::*;
+pub use sp_helper::*;
+pub use blocks_helper::*;
 
 pub use Error::NotFound;
+pub use diesel::Connection;
 pub use diesel::pg::PgConnection;
 pub use r2d2_diesel::ConnectionManager;
 pub use r2d2::PooledConnection;
 
 pub type QueryError = Error;
+
+/// The end block number of the rows holding current state
+///
+/// Each row of Smart state is valid from its `start_block_num` up to, but
+/// not including, its `end_block_num`.
+pub const MAX_BLOCK_NUM: i64 = i64::max_value();
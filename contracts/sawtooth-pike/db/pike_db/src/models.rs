@@ -3,6 +3,12 @@
 
 use schema::agents;
 use schema::organizations;
+use schema::accounts;
+use schema::contracts;
+use schema::contract_registries;
+use schema::namespace_registries;
+use schema::smart_permissions;
+use schema::blocks;
 use serde_json;
 
 #[derive(Queryable)]
@@ -43,7 +49,9 @@ pub struct Organization {
 pub struct NewOrganization<'a> {
     pub id: &'a str,
     pub name: &'a str,
-    pub address: &'a str
+    pub address: &'a str,
+    pub state_address: &'a str,
+    pub start_block_num: i64
 }
 
 #[derive(Queryable)]
@@ -54,3 +62,125 @@ pub struct SmartPermission {
     pub name: String,
     pub address: String
 }
+
+#[derive(Insertable)]
+#[table_name = "smart_permissions"]
+#[derive(Debug)]
+pub struct NewSmartPermission<'a> {
+    pub org_id: &'a str,
+    pub name: &'a str,
+    pub address: &'a str,
+    pub start_block_num: i64
+}
+
+#[derive(Queryable)]
+#[derive(Deserialize, Serialize)]
+#[derive(Debug)]
+pub struct Account {
+    pub public_key: String,
+    pub org_id: String,
+    pub active: bool,
+    pub roles: Vec<String>,
+    pub metadata: Vec<serde_json::Value>
+}
+
+#[derive(Insertable)]
+#[table_name = "accounts"]
+#[derive(Debug)]
+pub struct NewAccount<'a> {
+    pub public_key: &'a str,
+    pub org_id: &'a str,
+    pub active: bool,
+    pub roles: Vec<String>,
+    pub metadata: Vec<serde_json::Value>,
+    pub state_address: &'a str,
+    pub start_block_num: i64
+}
+
+#[derive(Queryable)]
+#[derive(Deserialize, Serialize)]
+#[derive(Debug)]
+pub struct Contract {
+    pub name: String,
+    pub version: String,
+    pub inputs: Vec<String>,
+    pub outputs: Vec<String>,
+    pub creator: String,
+    pub contract_sha512: String
+}
+
+#[derive(Insertable)]
+#[table_name = "contracts"]
+#[derive(Debug)]
+pub struct NewContract<'a> {
+    pub name: &'a str,
+    pub version: &'a str,
+    pub inputs: Vec<String>,
+    pub outputs: Vec<String>,
+    pub creator: &'a str,
+    pub contract_sha512: &'a str,
+    pub state_address: &'a str,
+    pub start_block_num: i64
+}
+
+#[derive(Queryable)]
+#[derive(Deserialize, Serialize)]
+#[derive(Debug)]
+pub struct ContractRegistry {
+    pub name: String,
+    pub versions: Vec<serde_json::Value>,
+    pub owners: Vec<String>,
+    pub owner_threshold: i32
+}
+
+#[derive(Insertable)]
+#[table_name = "contract_registries"]
+#[derive(Debug)]
+pub struct NewContractRegistry<'a> {
+    pub name: &'a str,
+    pub versions: Vec<serde_json::Value>,
+    pub owners: Vec<String>,
+    pub owner_threshold: i32,
+    pub state_address: &'a str,
+    pub start_block_num: i64
+}
+
+#[derive(Queryable)]
+#[derive(Deserialize, Serialize)]
+#[derive(Debug)]
+pub struct NamespaceRegistry {
+    pub namespace: String,
+    pub owners: Vec<String>,
+    pub owner_threshold: i32,
+    pub permissions: Vec<serde_json::Value>
+}
+
+#[derive(Insertable)]
+#[table_name = "namespace_registries"]
+#[derive(Debug)]
+pub struct NewNamespaceRegistry<'a> {
+    pub namespace: &'a str,
+    pub owners: Vec<String>,
+    pub owner_threshold: i32,
+    pub permissions: Vec<serde_json::Value>,
+    pub state_address: &'a str,
+    pub start_block_num: i64
+}
+
+#[derive(Queryable)]
+#[derive(Deserialize, Serialize)]
+#[derive(Debug)]
+pub struct Block {
+    pub block_num: i64,
+    pub block_id: String,
+    pub state_root_hash: String
+}
+
+#[derive(Insertable)]
+#[table_name = "blocks"]
+#[derive(Debug)]
+pub struct NewBlock<'a> {
+    pub block_num: i64,
+    pub block_id: &'a str,
+    pub state_root_hash: &'a str
+}
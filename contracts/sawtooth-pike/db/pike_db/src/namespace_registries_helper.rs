@@ -0,0 +1,72 @@
+// Copyright (c) The dgc.network
+// SPDX-License-Identifier: Apache-2.0
+
+use schema::namespace_registries;
+use schema::namespace_registries::dsl;
+use models::{NamespaceRegistry, NewNamespaceRegistry};
+use MAX_BLOCK_NUM;
+
+use diesel;
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use diesel::QueryResult;
+
+const NAMESPACE_REGISTRY_COLUMNS: (
+    dsl::namespace,
+    dsl::owners,
+    dsl::owner_threshold,
+    dsl::permissions
+) = (dsl::namespace, dsl::owners, dsl::owner_threshold, dsl::permissions);
+
+pub fn insert_namespace_registries(
+    conn: &PgConnection,
+    registries: &[NewNamespaceRegistry]
+) -> QueryResult<()> {
+    diesel::insert_into(namespace_registries::table)
+        .values(registries)
+        .execute(conn)
+        .map(|_| ())
+}
+
+/// Ends the history of the namespace registries stored at `address` at
+/// `block_num`
+pub fn end_namespace_registries_at_address(
+    conn: &PgConnection,
+    address: &str,
+    block_num: i64
+) -> QueryResult<()> {
+    diesel::update(namespace_registries::table)
+        .filter(dsl::state_address.eq(address))
+        .filter(dsl::end_block_num.eq(MAX_BLOCK_NUM))
+        .set(dsl::end_block_num.eq(block_num))
+        .execute(conn)
+        .map(|_| ())
+}
+
+/// Removes the changes made by `block_num` and later blocks
+pub fn rollback_namespace_registries(conn: &PgConnection, block_num: i64) -> QueryResult<()> {
+    diesel::delete(namespace_registries::table)
+        .filter(dsl::start_block_num.ge(block_num))
+        .execute(conn)?;
+
+    diesel::update(namespace_registries::table)
+        .filter(dsl::end_block_num.ge(block_num))
+        .set(dsl::end_block_num.eq(MAX_BLOCK_NUM))
+        .execute(conn)
+        .map(|_| ())
+}
+
+pub fn get_namespace_registry(conn: &PgConnection, namespace: &str) -> QueryResult<NamespaceRegistry> {
+    namespace_registries::table
+        .select(NAMESPACE_REGISTRY_COLUMNS)
+        .filter(dsl::namespace.eq(namespace))
+        .filter(dsl::end_block_num.eq(MAX_BLOCK_NUM))
+        .first(conn)
+}
+
+pub fn get_namespace_registries(conn: &PgConnection) -> QueryResult<Vec<NamespaceRegistry>> {
+    namespace_registries::table
+        .select(NAMESPACE_REGISTRY_COLUMNS)
+        .filter(dsl::end_block_num.eq(MAX_BLOCK_NUM))
+        .load(conn)
+}
@@ -4,37 +4,61 @@
 use schema::organizations;
 use schema::organizations::dsl;
 use models::{NewOrganization, Organization};
+use MAX_BLOCK_NUM;
 
 use diesel;
 use diesel::pg::PgConnection;
 use diesel::prelude::*;
 use diesel::QueryResult;
 
-pub fn create_organization(conn: &PgConnection, org: NewOrganization) -> QueryResult<Organization> {
+const ORGANIZATION_COLUMNS: (dsl::id, dsl::name, dsl::address) =
+    (dsl::id, dsl::name, dsl::address);
+
+pub fn insert_organizations(conn: &PgConnection, orgs: &[NewOrganization]) -> QueryResult<()> {
     diesel::insert_into(organizations::table)
-        .values(&org)
-        .get_result::<Organization>(conn)
+        .values(orgs)
+        .execute(conn)
+        .map(|_| ())
 }
 
-pub fn update_organization(conn: &PgConnection, id: &str, org: NewOrganization) -> QueryResult<Organization> {
+/// Ends the history of the organizations stored at `address` at `block_num`
+pub fn end_organizations_at_address(
+    conn: &PgConnection,
+    address: &str,
+    block_num: i64
+) -> QueryResult<()> {
     diesel::update(organizations::table)
-        .filter(dsl::id.eq(id))
-        .set((
-            dsl::name.eq(org.name),
-            dsl::address.eq(org.address),
-        ))
-        .get_result::<Organization>(conn)
+        .filter(dsl::state_address.eq(address))
+        .filter(dsl::end_block_num.eq(MAX_BLOCK_NUM))
+        .set(dsl::end_block_num.eq(block_num))
+        .execute(conn)
+        .map(|_| ())
+}
+
+/// Removes the changes made by `block_num` and later blocks
+pub fn rollback_organizations(conn: &PgConnection, block_num: i64) -> QueryResult<()> {
+    diesel::delete(organizations::table)
+        .filter(dsl::start_block_num.ge(block_num))
+        .execute(conn)?;
+
+    diesel::update(organizations::table)
+        .filter(dsl::end_block_num.ge(block_num))
+        .set(dsl::end_block_num.eq(MAX_BLOCK_NUM))
+        .execute(conn)
+        .map(|_| ())
 }
 
 pub fn get_org(conn: &PgConnection, id: &str) -> QueryResult<Organization> {
     organizations::table
-        .select(organizations::all_columns)
-        .find(id)
+        .select(ORGANIZATION_COLUMNS)
+        .filter(dsl::id.eq(id))
+        .filter(dsl::end_block_num.eq(MAX_BLOCK_NUM))
         .first(conn)
 }
 
 pub fn get_orgs(conn: &PgConnection) -> QueryResult<Vec<Organization>> {
     organizations::table
-        .select(organizations::all_columns)
+        .select(ORGANIZATION_COLUMNS)
+        .filter(dsl::end_block_num.eq(MAX_BLOCK_NUM))
         .load(conn)
 }
@@ -12,9 +12,87 @@ table! {
 }
 
 table! {
-    organizations (id) {
+    organizations (row_id) {
+        row_id -> Int8,
         id -> Varchar,
         name -> Varchar,
         address -> Varchar,
+        state_address -> Varchar,
+        start_block_num -> Int8,
+        end_block_num -> Int8,
+    }
+}
+
+table! {
+    accounts (row_id) {
+        row_id -> Int8,
+        public_key -> Varchar,
+        org_id -> Varchar,
+        active -> Bool,
+        roles -> Array<Varchar>,
+        metadata -> Array<Json>,
+        state_address -> Varchar,
+        start_block_num -> Int8,
+        end_block_num -> Int8,
+    }
+}
+
+table! {
+    contracts (row_id) {
+        row_id -> Int8,
+        name -> Varchar,
+        version -> Varchar,
+        inputs -> Array<Varchar>,
+        outputs -> Array<Varchar>,
+        creator -> Varchar,
+        contract_sha512 -> Varchar,
+        state_address -> Varchar,
+        start_block_num -> Int8,
+        end_block_num -> Int8,
+    }
+}
+
+table! {
+    contract_registries (row_id) {
+        row_id -> Int8,
+        name -> Varchar,
+        versions -> Array<Json>,
+        owners -> Array<Varchar>,
+        owner_threshold -> Int4,
+        state_address -> Varchar,
+        start_block_num -> Int8,
+        end_block_num -> Int8,
+    }
+}
+
+table! {
+    namespace_registries (row_id) {
+        row_id -> Int8,
+        namespace -> Varchar,
+        owners -> Array<Varchar>,
+        owner_threshold -> Int4,
+        permissions -> Array<Json>,
+        state_address -> Varchar,
+        start_block_num -> Int8,
+        end_block_num -> Int8,
+    }
+}
+
+table! {
+    smart_permissions (row_id) {
+        row_id -> Int8,
+        org_id -> Varchar,
+        name -> Varchar,
+        address -> Varchar,
+        start_block_num -> Int8,
+        end_block_num -> Int8,
+    }
+}
+
+table! {
+    blocks (block_num) {
+        block_num -> Int8,
+        block_id -> Varchar,
+        state_root_hash -> Varchar,
     }
 }
@@ -1,36 +1,77 @@
 // Copyright (c) The dgc.network
 // SPDX-License-Identifier: Apache-2.0
 
-use schema::smartpermissions;
-use schema::smartpermissions::dsl;
+use schema::smart_permissions;
+use schema::smart_permissions::dsl;
 use models::{SmartPermission, NewSmartPermission};
+use MAX_BLOCK_NUM;
 
 use diesel;
 use diesel::pg::PgConnection;
 use diesel::prelude::*;
 use diesel::QueryResult;
 
-pub fn create_smart_permission(conn: &PgConnection, sp: NewSmartPermission) -> QueryResult<SmartPermission> {
-    diesel::insert_into(smartpermissions::table)
-        .values(&sp)
-        .get_result::<SmartPermission>(conn)
+const SMART_PERMISSION_COLUMNS: (dsl::org_id, dsl::name, dsl::address) =
+    (dsl::org_id, dsl::name, dsl::address);
+
+pub fn insert_smart_permissions(
+    conn: &PgConnection,
+    sps: &[NewSmartPermission]
+) -> QueryResult<()> {
+    diesel::insert_into(smart_permissions::table)
+        .values(sps)
+        .execute(conn)
+        .map(|_| ())
 }
 
-pub fn delete_smart_permission(conn: &PgConnection, address: &str) -> QueryResult<SmartPermission> {
-    diesel::delete(smartpermissions::table)
+/// Ends the history of the smart permissions stored at `address` at
+/// `block_num`
+pub fn end_smart_permissions_at_address(
+    conn: &PgConnection,
+    address: &str,
+    block_num: i64
+) -> QueryResult<()> {
+    diesel::update(smart_permissions::table)
         .filter(dsl::address.eq(address))
-        .get_result::<SmartPermission>(conn)
+        .filter(dsl::end_block_num.eq(MAX_BLOCK_NUM))
+        .set(dsl::end_block_num.eq(block_num))
+        .execute(conn)
+        .map(|_| ())
+}
+
+/// Removes the changes made by `block_num` and later blocks
+pub fn rollback_smart_permissions(conn: &PgConnection, block_num: i64) -> QueryResult<()> {
+    diesel::delete(smart_permissions::table)
+        .filter(dsl::start_block_num.ge(block_num))
+        .execute(conn)?;
+
+    diesel::update(smart_permissions::table)
+        .filter(dsl::end_block_num.ge(block_num))
+        .set(dsl::end_block_num.eq(MAX_BLOCK_NUM))
+        .execute(conn)
+        .map(|_| ())
 }
 
-pub fn get_smart_permission(conn: &PgConnection, name: &str) -> QueryResult<SmartPermission> {
-    smartpermissions::table
-        .select(smartpermissions::all_columns)
-        .find(name)
+pub fn get_smart_permission(conn: &PgConnection, org_id: &str, name: &str) -> QueryResult<SmartPermission> {
+    smart_permissions::table
+        .select(SMART_PERMISSION_COLUMNS)
+        .filter(dsl::org_id.eq(org_id))
+        .filter(dsl::name.eq(name))
+        .filter(dsl::end_block_num.eq(MAX_BLOCK_NUM))
         .first(conn)
 }
 
+pub fn get_org_smart_permissions(conn: &PgConnection, org_id: &str) -> QueryResult<Vec<SmartPermission>> {
+    smart_permissions::table
+        .select(SMART_PERMISSION_COLUMNS)
+        .filter(dsl::org_id.eq(org_id))
+        .filter(dsl::end_block_num.eq(MAX_BLOCK_NUM))
+        .load(conn)
+}
+
 pub fn get_smart_permissions(conn: &PgConnection) -> QueryResult<Vec<SmartPermission>> {
-    smartpermissions::table
-        .select(smartpermissions::all_columns)
+    smart_permissions::table
+        .select(SMART_PERMISSION_COLUMNS)
+        .filter(dsl::end_block_num.eq(MAX_BLOCK_NUM))
         .load(conn)
 }
@@ -12,109 +12,360 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+//! Indexes the Smart state changed by each committed block
+//!
+//! Every row records the block it was set in and the block that replaced or
+//! deleted it, so the tables hold the history of the state as well as the
+//! current state. When a fork replaces indexed blocks, the changes made by
+//! the replaced blocks are rolled back before the new block is applied.
+
+use crypto::digest::Digest;
+use crypto::sha2::Sha512;
 use protobuf;
 
-use sawtooth_sdk::messages::transaction_receipt::StateChange;
+use sawtooth_sdk::messages::events::{Event, EventList};
+use sawtooth_sdk::messages::transaction_receipt::{StateChange, StateChangeList};
 use sawtooth_sdk::messages::transaction_receipt::StateChange_Type;
 
+use dgc_contract_sdk::addressing::{
+    ACCOUNT_PREFIX,
+    CONTRACT_PREFIX,
+    CONTRACT_REGISTRY_PREFIX,
+    NAMESPACE_REGISTRY_PREFIX,
+    ORGANIZATION_PREFIX,
+    SMART_PERMISSION_PREFIX
+};
+use dgc_contract_sdk::protocol::state::{
+    AccountList,
+    ContractList,
+    ContractRegistryList,
+    NamespaceRegistryList,
+    OrganizationList,
+    SmartPermissionList
+};
+use dgc_contract_sdk::protos::{FromBytes, ProtoConversionError};
+
 use pike_db as db;
-use pike_db::{PgConnection, NotFound, QueryError};
-use pike_db::models::{NewAgent, NewOrganization};
+use pike_db::{Connection, PgConnection, NotFound, QueryError};
+use pike_db::models::{
+    NewAccount,
+    NewBlock,
+    NewContract,
+    NewContractRegistry,
+    NewNamespaceRegistry,
+    NewOrganization,
+    NewSmartPermission
+};
 
-use addresser::{Resource, ResourceError, byte_to_resource};
+const BLOCK_COMMIT_EVENT: &str = "sawtooth/block-commit";
+const STATE_DELTA_EVENT: &str = "sawtooth/state-delta";
 
-use protos::state::{
-    Agent,
-    Organization,
-    AgentList,
-    OrganizationList
-};
+/// The address prefixes of the Smart state that is indexed
+pub const INDEXED_PREFIXES: &[&str] = &[
+    NAMESPACE_REGISTRY_PREFIX,
+    CONTRACT_REGISTRY_PREFIX,
+    CONTRACT_PREFIX,
+    SMART_PERMISSION_PREFIX,
+    ACCOUNT_PREFIX,
+    ORGANIZATION_PREFIX
+];
 
-pub fn apply_state_change(conn: &PgConnection, state_change: &StateChange) -> Result<(), StateChangeError> {
-    match state_change.field_type{
-        StateChange_Type::SET => set(conn, &state_change.address, &state_change.value),
-        _ => Err(
-            StateChangeError::UnsupportedTypeError(
-                format!("unsuppoted type {:?}", state_change.field_type)))
+#[derive(Clone, Copy, Debug)]
+enum Resource {
+    NamespaceRegistry,
+    ContractRegistry,
+    Contract,
+    SmartPermission,
+    Account,
+    Organization
+}
+
+impl Resource {
+    fn of_address(address: &str) -> Option<Resource> {
+        if address.starts_with(NAMESPACE_REGISTRY_PREFIX) {
+            Some(Resource::NamespaceRegistry)
+        } else if address.starts_with(CONTRACT_REGISTRY_PREFIX) {
+            Some(Resource::ContractRegistry)
+        } else if address.starts_with(CONTRACT_PREFIX) {
+            Some(Resource::Contract)
+        } else if address.starts_with(SMART_PERMISSION_PREFIX) {
+            Some(Resource::SmartPermission)
+        } else if address.starts_with(ACCOUNT_PREFIX) {
+            Some(Resource::Account)
+        } else if address.starts_with(ORGANIZATION_PREFIX) {
+            Some(Resource::Organization)
+        } else {
+            None
+        }
     }
 }
 
-fn set(conn: &PgConnection, address: &str, value: &[u8]) -> Result<(), StateChangeError> {
-    let resource_byte = &address[6..8];
-
-    let results: Vec<StateChangeError> = match byte_to_resource(resource_byte)? {
-        Resource::AGENT => protobuf::parse_from_bytes::<AgentList>(value)?
-            .get_agents()
-            .into_iter()
-            .filter_map(|agent| set_agent(conn, agent).err())
-            .collect(),
-        Resource::ORG => protobuf::parse_from_bytes::<OrganizationList>(value)?
-            .get_organizations()
-            .into_iter()
-            .filter_map(|org| set_org(conn, org).err())
-            .collect()
-    };
+/// Indexes the state changes of a committed block in a single transaction
+///
+/// A block already indexed under the same id is skipped. A block already
+/// indexed under another id was replaced by a fork, so it and every later
+/// block are rolled back first.
+pub fn apply_events(conn: &PgConnection, events: &EventList) -> Result<(), StateChangeError> {
+    let block_commit = events
+        .get_events()
+        .iter()
+        .find(|event| event.get_event_type() == BLOCK_COMMIT_EVENT)
+        .ok_or_else(|| StateChangeError::BlockCommitError("missing block commit".into()))?;
+
+    let block_id = attribute(block_commit, "block_id")?;
+    let state_root_hash = attribute(block_commit, "state_root_hash")?;
+    let block_num = attribute(block_commit, "block_num")?
+        .parse::<i64>()
+        .map_err(|err| StateChangeError::BlockCommitError(
+            format!("invalid block_num: {}", err)))?;
+
+    let state_changes = events
+        .get_events()
+        .iter()
+        .filter(|event| event.get_event_type() == STATE_DELTA_EVENT)
+        .map(|event| protobuf::parse_from_bytes::<StateChangeList>(event.get_data()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    conn.transaction(|| {
+        match db::get_block(conn, block_num) {
+            Ok(ref block) if block.block_id == block_id => {
+                info!("Block {} is already indexed", block_id);
+                return Ok(());
+            }
+            Ok(block) => {
+                info!("Block {} replaces block {}, rolling back", block_id, block.block_id);
+                db::rollback_from(conn, block_num)?;
+            }
+            Err(NotFound) => (),
+            Err(err) => return Err(StateChangeError::from(err))
+        }
 
-    if results.is_empty() {
+        db::insert_block(conn, NewBlock {
+            block_num,
+            block_id,
+            state_root_hash
+        })?;
+
+        for state_change in state_changes.iter().flat_map(|list| list.get_state_changes()) {
+            debug!("Applying state change {:?}", state_change);
+            apply_state_change(conn, state_change, block_num)?;
+        }
+
+        info!("Indexed block {} ({})", block_num, block_id);
         Ok(())
-    } else {
-        Err(StateChangeError::SetErrors(results))
-    }
+    })
 }
 
-fn set_agent(conn: &PgConnection, agent: &Agent) -> Result<(), StateChangeError> {
-    let metadata = agent
-        .metadata
-        .iter()
-        .map(|x| json!({
-            "key": x.get_key(),
-            "value": x.get_value()
-        }))
-    .collect();
-    let new_agent = NewAgent {
-        org_id: &agent.org_id,
-        public_key: &agent.public_key,
-        active: agent.active,
-        roles: agent.roles.to_vec(),
-        metadata,
+fn apply_state_change(
+    conn: &PgConnection,
+    state_change: &StateChange,
+    block_num: i64
+) -> Result<(), StateChangeError> {
+    let address = state_change.get_address();
+
+    let resource = match Resource::of_address(address) {
+        Some(resource) => resource,
+        None => {
+            debug!("Ignoring change of unindexed address {}", address);
+            return Ok(());
+        }
     };
-    match db::get_agent(conn, &new_agent.public_key) {
-        Ok(_) => db::update_agent(conn, &new_agent.public_key, new_agent)
-            .and_then(|_| Ok(()))
-            .map_err(StateChangeError::from),
-        Err(NotFound) => db::create_agent(conn, new_agent)
-            .and_then(|_| Ok(()))
-            .map_err(StateChangeError::from),
-        Err(e) => Err(StateChangeError::from(e))
+
+    match state_change.get_field_type() {
+        StateChange_Type::SET => {
+            end_at_address(conn, resource, address, block_num)?;
+            set(conn, resource, address, state_change.get_value(), block_num)
+        }
+        StateChange_Type::DELETE => end_at_address(conn, resource, address, block_num),
+        field_type => Err(
+            StateChangeError::UnsupportedTypeError(
+                format!("unsupported type {:?}", field_type)))
     }
 }
 
-fn set_org(conn: &PgConnection, org: &Organization) -> Result<(), StateChangeError> {
-    let new_org = NewOrganization {
-        id: &org.org_id,
-        name: &org.name,
-        address: &org.address
+/// Ends the history of the objects stored at `address`
+fn end_at_address(
+    conn: &PgConnection,
+    resource: Resource,
+    address: &str,
+    block_num: i64
+) -> Result<(), StateChangeError> {
+    let result = match resource {
+        Resource::NamespaceRegistry =>
+            db::end_namespace_registries_at_address(conn, address, block_num),
+        Resource::ContractRegistry =>
+            db::end_contract_registries_at_address(conn, address, block_num),
+        Resource::Contract => db::end_contracts_at_address(conn, address, block_num),
+        Resource::SmartPermission => db::end_smart_permissions_at_address(conn, address, block_num),
+        Resource::Account => db::end_accounts_at_address(conn, address, block_num),
+        Resource::Organization => db::end_organizations_at_address(conn, address, block_num)
     };
 
-    match db::get_org(conn, &new_org.id) {
-        Ok(_) => db::update_organization(conn, &new_org.id, new_org)
-            .and_then(|_| Ok(()))
-            .map_err(StateChangeError::from),
-        Err(NotFound) => db::create_organization(conn, new_org)
-            .and_then(|_| Ok(()))
-            .map_err(StateChangeError::from),
-        Err(e) => Err(StateChangeError::from(e))
+    result.map_err(StateChangeError::from)
+}
+
+/// Inserts the objects now stored at `address`
+fn set(
+    conn: &PgConnection,
+    resource: Resource,
+    address: &str,
+    value: &[u8],
+    block_num: i64
+) -> Result<(), StateChangeError> {
+    match resource {
+        Resource::NamespaceRegistry => {
+            let list = NamespaceRegistryList::from_bytes(value)?;
+            let registries: Vec<NewNamespaceRegistry> = list
+                .get_registries()
+                .iter()
+                .map(|registry| NewNamespaceRegistry {
+                    namespace: registry.get_namespace(),
+                    owners: registry.get_owners().to_vec(),
+                    owner_threshold: registry.get_owner_threshold() as i32,
+                    permissions: registry
+                        .get_permissions()
+                        .iter()
+                        .map(|permission| json!({
+                            "contract_name": permission.get_contract_name(),
+                            "read": permission.get_read(),
+                            "write": permission.get_write(),
+                            "prefix": permission.get_prefix(),
+                            "deny": permission.get_deny()
+                        }))
+                        .collect(),
+                    state_address: address,
+                    start_block_num: block_num
+                })
+                .collect();
+            db::insert_namespace_registries(conn, &registries)?;
+        }
+        Resource::ContractRegistry => {
+            let list = ContractRegistryList::from_bytes(value)?;
+            let registries: Vec<NewContractRegistry> = list
+                .get_registries()
+                .iter()
+                .map(|registry| NewContractRegistry {
+                    name: registry.get_name(),
+                    versions: registry
+                        .get_versions()
+                        .iter()
+                        .map(|version| json!({
+                            "version": version.get_version(),
+                            "contract_sha512": version.get_contract_sha512(),
+                            "creator": version.get_creator(),
+                            "deprecated": version.get_deprecated()
+                        }))
+                        .collect(),
+                    owners: registry.get_owners().to_vec(),
+                    owner_threshold: registry.get_owner_threshold() as i32,
+                    state_address: address,
+                    start_block_num: block_num
+                })
+                .collect();
+            db::insert_contract_registries(conn, &registries)?;
+        }
+        Resource::Contract => {
+            let list = ContractList::from_bytes(value)?;
+            let hashes: Vec<String> = list
+                .get_contracts()
+                .iter()
+                .map(|contract| {
+                    let mut sha = Sha512::new();
+                    sha.input(contract.get_contract());
+                    sha.result_str()
+                })
+                .collect();
+            let contracts: Vec<NewContract> = list
+                .get_contracts()
+                .iter()
+                .zip(hashes.iter())
+                .map(|(contract, hash)| NewContract {
+                    name: contract.get_name(),
+                    version: contract.get_version(),
+                    inputs: contract.get_inputs().to_vec(),
+                    outputs: contract.get_outputs().to_vec(),
+                    creator: contract.get_creator(),
+                    contract_sha512: hash,
+                    state_address: address,
+                    start_block_num: block_num
+                })
+                .collect();
+            db::insert_contracts(conn, &contracts)?;
+        }
+        Resource::SmartPermission => {
+            let list = SmartPermissionList::from_bytes(value)?;
+            let permissions: Vec<NewSmartPermission> = list
+                .get_smart_permissions()
+                .iter()
+                .map(|permission| NewSmartPermission {
+                    org_id: permission.get_org_id(),
+                    name: permission.get_name(),
+                    address,
+                    start_block_num: block_num
+                })
+                .collect();
+            db::insert_smart_permissions(conn, &permissions)?;
+        }
+        Resource::Account => {
+            let list = AccountList::from_bytes(value)?;
+            let accounts: Vec<NewAccount> = list
+                .get_accounts()
+                .iter()
+                .map(|account| NewAccount {
+                    public_key: account.get_public_key(),
+                    org_id: account.get_org_id(),
+                    active: *account.get_active(),
+                    roles: account.get_roles().to_vec(),
+                    metadata: account
+                        .get_metadata()
+                        .iter()
+                        .map(|entry| json!({
+                            "key": entry.get_key(),
+                            "value": entry.get_value()
+                        }))
+                        .collect(),
+                    state_address: address,
+                    start_block_num: block_num
+                })
+                .collect();
+            db::insert_accounts(conn, &accounts)?;
+        }
+        Resource::Organization => {
+            let list = OrganizationList::from_bytes(value)?;
+            let orgs: Vec<NewOrganization> = list
+                .get_organizations()
+                .iter()
+                .map(|org| NewOrganization {
+                    id: org.get_org_id(),
+                    name: org.get_name(),
+                    address: org.get_address(),
+                    state_address: address,
+                    start_block_num: block_num
+                })
+                .collect();
+            db::insert_organizations(conn, &orgs)?;
+        }
     }
+
+    Ok(())
+}
+
+fn attribute<'a>(event: &'a Event, key: &str) -> Result<&'a str, StateChangeError> {
+    event
+        .get_attributes()
+        .iter()
+        .find(|attribute| attribute.get_key() == key)
+        .map(|attribute| attribute.get_value())
+        .ok_or_else(|| StateChangeError::BlockCommitError(format!("missing {}", key)))
 }
 
 #[derive(Debug)]
 pub enum StateChangeError {
     UnsupportedTypeError(String),
-    ResourceError(ResourceError),
-    UnsupportedResourceError(String),
+    BlockCommitError(String),
     SqlQueryError(QueryError),
     ParseError(protobuf::ProtobufError),
-    SetErrors(Vec<StateChangeError>)
+    DecodeError(ProtoConversionError)
 }
 
 impl From<protobuf::ProtobufError> for StateChangeError {
@@ -129,9 +380,8 @@ impl From<QueryError> for StateChangeError {
     }
 }
 
-
-impl From<ResourceError> for StateChangeError {
-    fn from(e: ResourceError) -> Self {
-        StateChangeError::ResourceError(e)
+impl From<ProtoConversionError> for StateChangeError {
+    fn from(e: ProtoConversionError) -> Self {
+        StateChangeError::DecodeError(e)
     }
 }
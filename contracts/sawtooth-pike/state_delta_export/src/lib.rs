@@ -12,23 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-extern crate protoc_rust;
-use protoc_rust::Customize;
 
-use std::fs;
-use std::fs::File;
-use std::io::prelude::*;
+//! Subscription to the state deltas committed by a validator, shared by the
+//! state delta export service and the Smart API
 
-fn main() {
-    fs::create_dir_all("src/protos").unwrap();
-    protoc_rust::run(protoc_rust::Args {
-        out_dir: "src/protos",
-        input: &["../protos/payload.proto", "../protos/state.proto"],
-        includes: &["../protos"],
-        customize: Customize::default()
-    }).expect("protoc");
+#[macro_use] extern crate log;
+extern crate sawtooth_sdk;
+extern crate protobuf;
+extern crate uuid;
 
-    let mut file = File::create("src/protos/mod.rs").unwrap();
-    file.write_all(b"pub mod payload;\n").unwrap();
-    file.write_all(b"pub mod state;\n").unwrap();
-}
+pub mod subscriber;
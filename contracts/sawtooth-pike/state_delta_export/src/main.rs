@@ -17,31 +17,35 @@
 #[macro_use] extern crate serde_json;
 extern crate sawtooth_sdk;
 extern crate pike_db;
-extern crate addresser;
+extern crate dgc_contract_sdk;
+extern crate crypto;
 extern crate simple_logger;
 extern crate protobuf;
 extern crate uuid;
 extern crate chan_signal;
 extern crate regex;
+extern crate pike_sde;
 
-mod subscriber;
 mod database;
-mod protos;
 
-use std::{thread, env};
+use std::{thread, env, process};
 use chan_signal::Signal;
-use subscriber::Subscriber;
+use pike_sde::subscriber::{Subscriber, SubscriberError, NULL_BLOCK_ID};
 use log::LogLevel;
 use regex::Regex;
-use database::apply_state_change;
+use database::{apply_events, INDEXED_PREFIXES};
+use pike_db as db;
 use pike_db::pools::init_pg_pool;
 
-use sawtooth_sdk::messages::transaction_receipt::StateChangeList;
-
 const APP_NAME: &'static str = env!("CARGO_PKG_NAME");
 const VERSION: &'static str = env!("CARGO_PKG_VERSION");
 const AUTHOR: &'static str = "Cargill";
 
+/// How many of the most recently indexed blocks are offered to the validator
+/// when resuming, so indexing can resume from before a fork that replaced
+/// the latest of them
+const KNOWN_BLOCK_COUNT: i64 = 100;
+
 fn url_is_valid(url: &str) -> bool {
     Regex::new(r"^[a-zA-Z]+://(.+:.+@)?.+:[0-9]{2,5}/?.*$")
         .unwrap()
@@ -53,7 +57,7 @@ fn main() {
         (name: APP_NAME)
         (version: VERSION)
         (author: AUTHOR)
-        (about: "State delta export service for pike")
+        (about: "Indexes Smart state into a database")
         (@arg verbose: -v +multiple "Log verbosely")
         (@arg connect: -c  --connect +takes_value "Validator to connect to")
         (@arg db: -d --database +takes_value "Full url to database")
@@ -98,24 +102,46 @@ fn main() {
         .get()
         .expect("Failed to create postgress database connection");
 
-    thread::spawn(move || subscriber.start(connect, |e| {
-        e.events
-            .iter()
-            .filter(|x| "sawtooth/state-delta" == x.event_type)
-            .filter_map(|x| -> Option<StateChangeList> {
-                protobuf::parse_from_bytes(&x.data).ok()
-            })
-            .flat_map(|x| x.state_changes.into_iter())
-            .for_each(|x| {
-                info!("Applying state change {:?}", x);
-                apply_state_change(&db_conn, &x)
-                    .and_then(|_| {
-                        info!("State change applied successfully");
-                        Ok(())
-                    })
-                    .unwrap_or_else(|err| error!("{:?}", err))
-            })
-    }));
+    let last_known_block_ids = match db::get_recent_blocks(&db_conn, KNOWN_BLOCK_COUNT) {
+        Ok(ref blocks) if blocks.is_empty() => vec![String::from(NULL_BLOCK_ID)],
+        Ok(blocks) => {
+            info!("Resuming after block {}", blocks[0].block_num);
+            blocks.into_iter().map(|block| block.block_id).collect()
+        }
+        Err(err) => {
+            error!("Failed to read indexed blocks: {:?}", err);
+            process::exit(1);
+        }
+    };
+
+    let prefixes: Vec<String> = INDEXED_PREFIXES.iter().map(|prefix| prefix.to_string()).collect();
+
+    let mut thread_subscriber = subscriber.clone();
+    thread::spawn(move || {
+        let mut indexed = true;
+        let result = thread_subscriber.subscribe(
+            &connect,
+            &prefixes,
+            last_known_block_ids,
+            |events| {
+                if let Err(err) = apply_events(&db_conn, &events) {
+                    error!("Failed to index block: {:?}", err);
+                    indexed = false;
+                }
+                indexed
+            });
+
+        match result {
+            Ok(()) if indexed => return,
+            Ok(()) => (),
+            Err(SubscriberError::UnknownBlock) =>
+                error!("None of the indexed blocks are known to the validator"),
+            Err(err) => error!("{}", err)
+        }
+
+        // Exit so the indexer is restarted and resumes from the last block it indexed
+        process::exit(1);
+    });
 
     signal
         .recv()
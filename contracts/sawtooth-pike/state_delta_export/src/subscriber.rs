@@ -12,42 +12,92 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-extern crate chan_signal;
+use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::RecvTimeoutError;
+use std::time::Duration;
 
 use protobuf;
 use uuid;
 
 use sawtooth_sdk::messaging::stream::MessageSender;
 use sawtooth_sdk::messaging::stream::MessageConnection;
-use sawtooth_sdk::messaging::zmq_stream::{ZmqMessageConnection};
+use sawtooth_sdk::messaging::zmq_stream::{ZmqMessageConnection, ZmqMessageSender};
 use sawtooth_sdk::messages::events::{EventSubscription, EventFilter, EventFilter_FilterType, EventList};
-use sawtooth_sdk::messages::client_event::{ClientEventsSubscribeRequest, ClientEventsSubscribeResponse};
+use sawtooth_sdk::messages::client_event::{
+    ClientEventsSubscribeRequest,
+    ClientEventsSubscribeResponse,
+    ClientEventsUnsubscribeRequest
+};
 use sawtooth_sdk::messages::client_event::ClientEventsSubscribeResponse_Status;
-use sawtooth_sdk::messages::validator::Message_MessageType::CLIENT_EVENTS_SUBSCRIBE_REQUEST;
+use sawtooth_sdk::messages::validator::Message_MessageType;
+use sawtooth_sdk::messages::validator::Message_MessageType::{
+    CLIENT_EVENTS_SUBSCRIBE_REQUEST,
+    CLIENT_EVENTS_UNSUBSCRIBE_REQUEST
+};
 
 const PIKE_NAMESPACE: &'static str = "cad11d";
-const NULL_BLOCK_ID: &'static str = "0000000000000000";
+/// The id to give as the last known block to receive events from genesis
+pub const NULL_BLOCK_ID: &'static str = "0000000000000000";
 
-#[derive(Clone, Copy)]
+/// How often a subscriber waiting for events checks whether it was stopped
+const STOP_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Subscribes to the state deltas and block commits of a validator
+///
+/// Clones share their state, so a subscriber running on another thread can
+/// be stopped through a clone.
+#[derive(Clone)]
 pub struct Subscriber {
-    is_active: bool
+    is_active: Arc<AtomicBool>
 }
 
 impl Subscriber {
     pub fn new() -> Subscriber {
         Subscriber {
-            is_active: false,
+            is_active: Arc::new(AtomicBool::new(false)),
         }
     }
 
+    /// Feeds `handler` the pike state deltas of every block since genesis,
+    /// then of each new block, until the subscriber is stopped
     pub fn start<F>(&mut self, validator: String, handler: F) where F : Fn(EventList) {
+        let prefixes = vec![String::from(PIKE_NAMESPACE)];
+        let last_known_block_ids = vec![String::from(NULL_BLOCK_ID)];
+
+        self.subscribe(&validator, &prefixes, last_known_block_ids, |events| {
+            handler(events);
+            true
+        }).unwrap_or_else(|err| error!("{}", err));
+    }
+
+    /// Feeds `handler` the block commits and the state deltas of addresses
+    /// starting with one of `prefixes`, until the subscriber is stopped or
+    /// `handler` returns false
+    ///
+    /// Events are replayed from the block following the last of
+    /// `last_known_block_ids` that is on the current chain. If none are
+    /// given, events start with the next block committed.
+    pub fn subscribe<F>(
+        &mut self,
+        validator: &str,
+        prefixes: &[String],
+        last_known_block_ids: Vec<String>,
+        mut handler: F
+    ) -> Result<(), SubscriberError> where F : FnMut(EventList) -> bool {
+
+        // Mark the subscriber active before subscribing, so that a stop
+        // requested while the subscription is set up is not overwritten
+
+        self.is_active.store(true, Ordering::SeqCst);
 
         // Connect to Validator
 
         info!("Establishing connection with validator {}", validator);
 
-        let connection = ZmqMessageConnection::new(&validator);
-        let (sender, receiver) = connection.create();
+        let connection = ZmqMessageConnection::new(validator);
+        let (mut sender, receiver) = connection.create();
 
         // Build event subscription payload
 
@@ -59,7 +109,7 @@ impl Subscriber {
 
         let mut event_filter = EventFilter::new();
         event_filter.set_key(String::from("address"));
-        event_filter.set_match_string(format!("^{}.*", PIKE_NAMESPACE));
+        event_filter.set_match_string(format!("^({}).*", prefixes.join("|")));
         event_filter.set_filter_type(EventFilter_FilterType::REGEX_ANY);
 
         state_delta_sub.set_filters(
@@ -70,72 +120,46 @@ impl Subscriber {
         let mut subscription_request = ClientEventsSubscribeRequest::new();
 
         subscription_request.set_last_known_block_ids(
-            protobuf::RepeatedField::from_vec(vec![String::from(NULL_BLOCK_ID)]));
+            protobuf::RepeatedField::from_vec(last_known_block_ids));
         subscription_request.set_subscriptions(
             protobuf::RepeatedField::from_vec(vec![state_delta_sub, block_commit_sub]));
 
         // Attempt to send state delta subscription request
 
-        let msg_bytes = match protobuf::Message::write_to_bytes(&subscription_request) {
-            Ok(b) => b,
-            Err(error) => {
-                error!("Error serializing request: {:?}", error);
-                return;
-            },
-        };
-
-        let correlation_id = match uuid::Uuid::new(uuid::UuidVersion::Random) {
-            Some(cid) => cid.to_string(),
-            None => {
-                error!("Error generating UUID");
-                return;
-            },
-        };
-
-        let mut future = match sender.send(CLIENT_EVENTS_SUBSCRIBE_REQUEST, &correlation_id, &msg_bytes) {
-            Ok(f) => f,
-            Err(error) => {
-                error!("Error unwrapping future: {:?}", error);
-                return;
-            },
-        };
-
-        let response_msg = match future.get() {
-            Ok(m) => m,
-            Err(error) => {
-                error!("Error getting future: {:?}", error);
-                return;
-            },
-        };
-
-        let response: ClientEventsSubscribeResponse = match protobuf::parse_from_bytes(&response_msg.content) {
-            Ok(r) => r,
-            Err(error) => {
-                error!("Error parsing response: {:?}", error);
-                return;
-            },
-        };
+        let response_msg =
+            send(&mut sender, CLIENT_EVENTS_SUBSCRIBE_REQUEST, &subscription_request)?;
+
+        let response: ClientEventsSubscribeResponse = protobuf::parse_from_bytes(&response_msg)
+            .map_err(|err| SubscriberError::RequestError(
+                format!("Error parsing response: {:?}", err)))?;
 
         // Validate response
 
-        if !(response.status == ClientEventsSubscribeResponse_Status::OK) {
-            error!("subscription status: {:?}", response.status);
-            return;
+        match response.status {
+            ClientEventsSubscribeResponse_Status::OK => (),
+            ClientEventsSubscribeResponse_Status::UNKNOWN_BLOCK =>
+                return Err(SubscriberError::UnknownBlock),
+            status => return Err(SubscriberError::RequestError(
+                format!("subscription status: {:?}", status)))
         }
 
         info!("Successfully subscribed to validator state delta events");
 
         // Listen for state delta events
 
-        self.is_active = true;
-
-        while self.is_active {
-            let content = match receiver.recv().unwrap() {
-                Ok(res) => res.content,
-                Err(err) => {
+        while self.is_active() {
+            let content = match receiver.recv_timeout(STOP_POLL_INTERVAL) {
+                Ok(Ok(res)) => res.content,
+                Ok(Err(err)) => {
                     error!("An error occurred while attempting to receive message {:?}", err);
                     continue;
                 }
+                Err(RecvTimeoutError::Timeout) => continue,
+                Err(RecvTimeoutError::Disconnected) => {
+                    self.stop();
+                    return Err(SubscriberError::RequestError(
+                        String::from("Connection to validator was closed")));
+                }
             };
 
             let event_list = match protobuf::parse_from_bytes(&content) {
@@ -146,13 +170,59 @@ impl Subscriber {
                 }
             };
 
-            handler(event_list);
+            if !handler(event_list) {
+                self.stop();
+            }
         }
+
+        send(&mut sender, CLIENT_EVENTS_UNSUBSCRIBE_REQUEST, &ClientEventsUnsubscribeRequest::new())
+            .map(|_| ())
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.is_active.load(Ordering::SeqCst)
     }
 
     pub fn stop(&mut self) {
-        if self.is_active {
-            self.is_active = false;
+        self.is_active.store(false, Ordering::SeqCst);
+    }
+}
+
+fn send<M: protobuf::Message>(
+    sender: &mut ZmqMessageSender,
+    message_type: Message_MessageType,
+    request: &M
+) -> Result<Vec<u8>, SubscriberError> {
+    let msg_bytes = protobuf::Message::write_to_bytes(request)
+        .map_err(|err| SubscriberError::RequestError(
+            format!("Error serializing request: {:?}", err)))?;
+
+    let correlation_id = uuid::Uuid::new(uuid::UuidVersion::Random)
+        .ok_or_else(|| SubscriberError::RequestError(String::from("Error generating UUID")))?
+        .to_string();
+
+    let mut future = sender.send(message_type, &correlation_id, &msg_bytes)
+        .map_err(|err| SubscriberError::RequestError(
+            format!("Error unwrapping future: {:?}", err)))?;
+
+    future.get()
+        .map(|m| m.content)
+        .map_err(|err| SubscriberError::RequestError(
+            format!("Error getting future: {:?}", err)))
+}
+
+#[derive(Debug)]
+pub enum SubscriberError {
+    /// None of the last known blocks are known to the validator
+    UnknownBlock,
+    RequestError(String)
+}
+
+impl fmt::Display for SubscriberError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SubscriberError::UnknownBlock => write!(f, "Unknown block"),
+            SubscriberError::RequestError(ref msg) => write!(f, "{}", msg)
         }
     }
 }
@@ -0,0 +1,656 @@
+// Copyright (c) The dgc.network
+// SPDX-License-Identifier: Apache-2.0
+
+//! Computes the state addresses of Smart objects and the inputs and outputs
+//! of Smart transactions, as the transaction processor expects them
+
+use std::error::Error as StdError;
+
+use crypto::digest::Digest;
+use crypto::sha2::Sha512;
+
+use crate::protocol::payload::{Action, RegistryType};
+use crate::protocol::{
+    ADMINISTRATORS_SETTING_ADDRESS, BLOCK_INFO_CONFIG_ADDRESS, FUEL_LIMIT_SETTING_ADDRESS,
+    MAX_ADDRESSES_TOUCHED_SETTING_ADDRESS, MAX_BYTES_WRITTEN_SETTING_ADDRESS,
+    MAX_CONTRACT_SIZE_SETTING_ADDRESS, MAX_MEMORY_PAGES_SETTING_ADDRESS,
+    MAX_STATE_ENTRY_SIZE_SETTING_ADDRESS,
+};
+
+/// The namespace registry prefix for global state (00ec00)
+pub const NAMESPACE_REGISTRY_PREFIX: &str = "00ec00";
+
+/// The contract registry prefix for global state (00ec01)
+pub const CONTRACT_REGISTRY_PREFIX: &str = "00ec01";
+
+/// The contract prefix for global state (00ec02)
+pub const CONTRACT_PREFIX: &str = "00ec02";
+
+/// The smart permission prefix for global state (00ec03)
+pub const SMART_PERMISSION_PREFIX: &str = "00ec03";
+
+/// The account prefix for global state (cad11d00)
+pub const ACCOUNT_PREFIX: &str = "cad11d00";
+
+/// The organization prefix for global state (cad11d01)
+pub const ORGANIZATION_PREFIX: &str = "cad11d01";
+
+/// The role prefix for global state (cad11d02)
+pub const ROLE_PREFIX: &str = "cad11d02";
+
+#[derive(Debug)]
+pub enum AddressingError {
+    InvalidAddress(String),
+}
+
+impl StdError for AddressingError {
+    fn description(&self) -> &str {
+        match *self {
+            AddressingError::InvalidAddress(ref msg) => msg,
+        }
+    }
+}
+
+impl std::fmt::Display for AddressingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            AddressingError::InvalidAddress(ref s) => write!(f, "InvalidAddress: {}", s),
+        }
+    }
+}
+
+fn sha512_hex(data: &str) -> String {
+    let mut sha = Sha512::new();
+    sha.input(data.as_bytes());
+    sha.result_str()
+}
+
+/// Returns the state address of a namespace registry
+///
+/// # Errors
+///
+/// Returns an `AddressingError` if the namespace is shorter than 6
+/// characters.
+pub fn compute_namespace_registry_address(namespace: &str) -> Result<String, AddressingError> {
+    let prefix = namespace.get(..6).ok_or_else(|| {
+        AddressingError::InvalidAddress(format!(
+            "Namespace must be at least 6 characters long: {}",
+            namespace
+        ))
+    })?;
+
+    Ok(String::from(NAMESPACE_REGISTRY_PREFIX) + &sha512_hex(prefix)[..64])
+}
+
+/// Returns the state address of a contract registry
+pub fn compute_contract_registry_address(name: &str) -> String {
+    String::from(CONTRACT_REGISTRY_PREFIX) + &sha512_hex(name)[..64]
+}
+
+/// Returns the state address of a contract
+pub fn compute_contract_address(name: &str, version: &str) -> String {
+    String::from(CONTRACT_PREFIX) + &sha512_hex(&format!("{},{}", name, version))[..64]
+}
+
+/// Returns the address prefix shared by the smart permissions of an
+/// organization
+pub fn compute_org_smart_permissions_prefix(org_id: &str) -> String {
+    String::from(SMART_PERMISSION_PREFIX) + &sha512_hex(org_id)[..6]
+}
+
+/// Returns the state address of a smart permission
+pub fn compute_smart_permission_address(org_id: &str, name: &str) -> String {
+    compute_org_smart_permissions_prefix(org_id) + &sha512_hex(name)[..58]
+}
+
+/// Returns the state address of an account
+pub fn compute_account_address(public_key: &str) -> String {
+    String::from(ACCOUNT_PREFIX) + &sha512_hex(public_key)[..62]
+}
+
+/// Returns the state address of an organization
+pub fn compute_org_address(id: &str) -> String {
+    String::from(ORGANIZATION_PREFIX) + &sha512_hex(id)[..62]
+}
+
+/// Returns the address prefix shared by the roles of an organization, which
+/// is included in the inputs of organization actions since the transaction
+/// processor reads the signer's roles to authorize them
+pub fn compute_org_roles_prefix(org_id: &str) -> String {
+    String::from(ROLE_PREFIX) + &sha512_hex(org_id)[..6]
+}
+
+/// Returns the state address of a role
+pub fn compute_role_address(org_id: &str, name: &str) -> String {
+    compute_org_roles_prefix(org_id) + &sha512_hex(name)[..56]
+}
+
+/// Returns the input and output addresses of a transaction carrying `action`
+/// signed by `public_key`
+///
+/// # Errors
+///
+/// Returns an `AddressingError` if a namespace, or an input or output of a
+/// contract, is shorter than 6 characters.
+pub fn compute_addresses(
+    action: &Action,
+    public_key: &str,
+) -> Result<(Vec<String>, Vec<String>), AddressingError> {
+    let (mut input_addresses, output_addresses) = match action {
+        Action::CreateContract(create_contract) => {
+            let name = create_contract.get_name();
+            let version = create_contract.get_version();
+
+            let addresses = vec![
+                compute_contract_registry_address(name),
+                compute_contract_address(name, version),
+            ];
+
+            // the contract is validated against the size and memory limits
+            let mut input_addresses = addresses.clone();
+            input_addresses.push(MAX_CONTRACT_SIZE_SETTING_ADDRESS.into());
+            input_addresses.push(MAX_MEMORY_PAGES_SETTING_ADDRESS.into());
+
+            // the signer needs the contract.upload permission of its account's
+            // organization, which is only known once the account is read, unless
+            // it is an administrator
+            input_addresses.push(compute_account_address(public_key));
+            input_addresses.push(ROLE_PREFIX.into());
+            input_addresses.push(ADMINISTRATORS_SETTING_ADDRESS.into());
+
+            (input_addresses, addresses)
+        }
+        Action::DeleteContract(delete_contract) => {
+            let name = delete_contract.get_name();
+            let version = delete_contract.get_version();
+
+            let addresses = vec![
+                compute_contract_registry_address(name),
+                compute_contract_address(name, version),
+            ];
+
+            (addresses.clone(), addresses)
+        }
+        Action::ExecuteContract(execute_contract) => {
+            let name = execute_contract.get_name();
+            let version = execute_contract.get_version();
+
+            let mut input_addresses = vec![
+                compute_contract_registry_address(name),
+                compute_requested_contract_address(name, version),
+                FUEL_LIMIT_SETTING_ADDRESS.into(),
+                MAX_MEMORY_PAGES_SETTING_ADDRESS.into(),
+                MAX_STATE_ENTRY_SIZE_SETTING_ADDRESS.into(),
+                MAX_ADDRESSES_TOUCHED_SETTING_ADDRESS.into(),
+                MAX_BYTES_WRITTEN_SETTING_ADDRESS.into(),
+            ];
+            input_addresses.append(&mut compute_contract_state_addresses(
+                execute_contract.get_inputs(),
+                "Input",
+            )?);
+
+            // the contract prefix only needs to be readable to resolve the
+            // version, so outputs only name an exact contract address
+            let mut output_addresses = vec![compute_contract_registry_address(name)];
+            if semver::Version::parse(version).is_ok() {
+                output_addresses.push(compute_contract_address(name, version));
+            }
+            output_addresses.append(&mut compute_contract_state_addresses(
+                execute_contract.get_outputs(),
+                "Output",
+            )?);
+
+            (input_addresses, output_addresses)
+        }
+        Action::UpgradeContract(upgrade_contract) => {
+            let name = upgrade_contract.get_name();
+            let version = upgrade_contract.get_version();
+
+            let mut input_addresses = vec![
+                compute_contract_registry_address(name),
+                compute_contract_address(name, version),
+                FUEL_LIMIT_SETTING_ADDRESS.into(),
+                MAX_MEMORY_PAGES_SETTING_ADDRESS.into(),
+                MAX_STATE_ENTRY_SIZE_SETTING_ADDRESS.into(),
+                MAX_ADDRESSES_TOUCHED_SETTING_ADDRESS.into(),
+                MAX_BYTES_WRITTEN_SETTING_ADDRESS.into(),
+                MAX_CONTRACT_SIZE_SETTING_ADDRESS.into(),
+                compute_account_address(public_key),
+                ROLE_PREFIX.into(),
+                ADMINISTRATORS_SETTING_ADDRESS.into(),
+            ];
+            input_addresses.append(&mut compute_contract_state_addresses(
+                upgrade_contract.get_inputs(),
+                "Input",
+            )?);
+
+            let mut output_addresses = vec![
+                compute_contract_registry_address(name),
+                compute_contract_address(name, version),
+            ];
+            output_addresses.append(&mut compute_contract_state_addresses(
+                upgrade_contract.get_outputs(),
+                "Output",
+            )?);
+
+            (input_addresses, output_addresses)
+        }
+        Action::CreateContractRegistry(create_contract_registry) => {
+            let name = create_contract_registry.get_name();
+            let addresses = vec![
+                compute_contract_registry_address(name),
+                ADMINISTRATORS_SETTING_ADDRESS.into(),
+            ];
+            (addresses.clone(), addresses)
+        }
+        Action::DeleteContractRegistry(delete_contract_registry) => {
+            let name = delete_contract_registry.get_name();
+            let addresses = vec![
+                compute_contract_registry_address(name),
+                ADMINISTRATORS_SETTING_ADDRESS.into(),
+            ];
+            (addresses.clone(), addresses)
+        }
+        Action::UpdateContractRegistryOwners(update_contract_registry_owners) => {
+            let name = update_contract_registry_owners.get_name();
+            let addresses = vec![
+                compute_contract_registry_address(name),
+                ADMINISTRATORS_SETTING_ADDRESS.into(),
+            ];
+            (addresses.clone(), addresses)
+        }
+        Action::CreateNamespaceRegistry(create_namespace_registry) => {
+            let namespace = create_namespace_registry.get_namespace();
+            let addresses = vec![
+                compute_namespace_registry_address(namespace)?,
+                ADMINISTRATORS_SETTING_ADDRESS.into(),
+            ];
+            (addresses.clone(), addresses)
+        }
+        Action::DeleteNamespaceRegistry(delete_namespace_registry) => {
+            let namespace = delete_namespace_registry.get_namespace();
+            let addresses = vec![
+                compute_namespace_registry_address(namespace)?,
+                ADMINISTRATORS_SETTING_ADDRESS.into(),
+            ];
+            (addresses.clone(), addresses)
+        }
+        Action::UpdateNamespaceRegistryOwners(update_namespace_registry_owners) => {
+            let namespace = update_namespace_registry_owners.get_namespace();
+            let addresses = vec![
+                compute_namespace_registry_address(namespace)?,
+                ADMINISTRATORS_SETTING_ADDRESS.into(),
+            ];
+            (addresses.clone(), addresses)
+        }
+        Action::CreateNamespaceRegistryPermission(create_namespace_registry_permission) => {
+            let namespace = create_namespace_registry_permission.get_namespace();
+            let addresses = vec![
+                compute_namespace_registry_address(namespace)?,
+                ADMINISTRATORS_SETTING_ADDRESS.into(),
+            ];
+            (addresses.clone(), addresses)
+        }
+        Action::DeleteNamespaceRegistryPermission(delete_namespace_registry_permission) => {
+            let namespace = delete_namespace_registry_permission.get_namespace();
+            let addresses = vec![
+                compute_namespace_registry_address(namespace)?,
+                ADMINISTRATORS_SETTING_ADDRESS.into(),
+            ];
+            (addresses.clone(), addresses)
+        }
+        Action::CreateSmartPermission(create_smart_permission) => {
+            let org_id = create_smart_permission.get_org_id();
+            let name = create_smart_permission.get_name();
+            let addresses = vec![
+                compute_smart_permission_address(org_id, name),
+                compute_org_address(org_id),
+                compute_account_address(public_key),
+                compute_org_roles_prefix(org_id),
+            ];
+
+            (addresses.clone(), addresses)
+        }
+        Action::UpdateSmartPermission(update_smart_permission) => {
+            let org_id = update_smart_permission.get_org_id();
+            let name = update_smart_permission.get_name();
+            let addresses = vec![
+                compute_smart_permission_address(org_id, name),
+                compute_org_address(org_id),
+                compute_account_address(public_key),
+                compute_org_roles_prefix(org_id),
+            ];
+
+            (addresses.clone(), addresses)
+        }
+        Action::DeleteSmartPermission(delete_smart_permission) => {
+            let org_id = delete_smart_permission.get_org_id();
+            let name = delete_smart_permission.get_name();
+            let addresses = vec![
+                compute_smart_permission_address(org_id, name),
+                compute_org_address(org_id),
+                compute_account_address(public_key),
+                compute_org_roles_prefix(org_id),
+            ];
+
+            (addresses.clone(), addresses)
+        }
+        Action::CreateAccount(create_account) => {
+            let org_id = create_account.get_org_id();
+            let account_public_key = create_account.get_public_key();
+            let addresses = vec![
+                compute_org_address(org_id),
+                compute_account_address(account_public_key),
+                compute_account_address(public_key),
+                compute_org_roles_prefix(org_id),
+            ];
+
+            (addresses.clone(), addresses)
+        }
+        Action::UpdateAccount(update_account) => {
+            let org_id = update_account.get_org_id();
+            let account_public_key = update_account.get_public_key();
+            let addresses = vec![
+                compute_org_address(org_id),
+                compute_account_address(account_public_key),
+                compute_account_address(public_key),
+                compute_org_roles_prefix(org_id),
+            ];
+
+            (addresses.clone(), addresses)
+        }
+        Action::CreateOrganization(create_organization) => {
+            let org_id = create_organization.get_id();
+            let addresses = vec![
+                compute_org_address(org_id),
+                compute_account_address(public_key),
+                compute_org_roles_prefix(org_id),
+            ];
+
+            (addresses.clone(), addresses)
+        }
+        Action::UpdateOrganization(update_organization) => {
+            let org_id = update_organization.get_id();
+            let addresses = vec![
+                compute_org_address(org_id),
+                compute_account_address(public_key),
+                compute_org_roles_prefix(org_id),
+            ];
+
+            (addresses.clone(), addresses)
+        }
+        Action::DeactivateAccount(deactivate_account) => {
+            let account_public_key = deactivate_account.get_public_key();
+            let addresses = vec![
+                compute_account_address(account_public_key),
+                compute_account_address(public_key),
+                compute_org_roles_prefix(deactivate_account.get_org_id()),
+            ];
+
+            (addresses.clone(), addresses)
+        }
+        Action::ReactivateAccount(reactivate_account) => {
+            let account_public_key = reactivate_account.get_public_key();
+            let addresses = vec![
+                compute_account_address(account_public_key),
+                compute_account_address(public_key),
+                compute_org_roles_prefix(reactivate_account.get_org_id()),
+            ];
+
+            (addresses.clone(), addresses)
+        }
+        Action::GrantRole(grant_role) => {
+            let account_public_key = grant_role.get_public_key();
+            let addresses = vec![
+                compute_account_address(account_public_key),
+                compute_account_address(public_key),
+                compute_org_roles_prefix(grant_role.get_org_id()),
+            ];
+
+            (addresses.clone(), addresses)
+        }
+        Action::RevokeRole(revoke_role) => {
+            let account_public_key = revoke_role.get_public_key();
+            let addresses = vec![
+                compute_account_address(account_public_key),
+                compute_account_address(public_key),
+                compute_org_roles_prefix(revoke_role.get_org_id()),
+            ];
+
+            (addresses.clone(), addresses)
+        }
+        Action::CreateRole(create_role) => {
+            let org_id = create_role.get_org_id();
+            let addresses = vec![
+                compute_role_address(org_id, create_role.get_name()),
+                compute_org_roles_prefix(org_id),
+                compute_account_address(public_key),
+            ];
+
+            (addresses.clone(), addresses)
+        }
+        Action::UpdateRole(update_role) => {
+            let org_id = update_role.get_org_id();
+            let addresses = vec![
+                compute_role_address(org_id, update_role.get_name()),
+                compute_org_roles_prefix(org_id),
+                compute_account_address(public_key),
+            ];
+
+            (addresses.clone(), addresses)
+        }
+        Action::DeleteRole(delete_role) => {
+            let org_id = delete_role.get_org_id();
+            let addresses = vec![
+                compute_role_address(org_id, delete_role.get_name()),
+                compute_org_roles_prefix(org_id),
+                compute_account_address(public_key),
+            ];
+
+            (addresses.clone(), addresses)
+        }
+        Action::ProposeOwnerChange(propose_owner_change) => {
+            let address = compute_registry_address(
+                propose_owner_change.get_registry_type(),
+                propose_owner_change.get_name(),
+            )?;
+
+            (
+                vec![address.clone(), BLOCK_INFO_CONFIG_ADDRESS.into()],
+                vec![address],
+            )
+        }
+        Action::VoteOwnerChange(vote_owner_change) => {
+            let address = compute_registry_address(
+                vote_owner_change.get_registry_type(),
+                vote_owner_change.get_name(),
+            )?;
+
+            (
+                vec![address.clone(), BLOCK_INFO_CONFIG_ADDRESS.into()],
+                vec![address],
+            )
+        }
+        Action::SetActionPolicy(set_action_policy) => {
+            let org_id = set_action_policy.get_org_id();
+            let mut input_addresses = vec![
+                compute_org_address(org_id),
+                compute_account_address(public_key),
+                compute_org_roles_prefix(org_id),
+            ];
+            if !set_action_policy.get_smart_permission().is_empty() {
+                input_addresses.push(compute_smart_permission_address(
+                    org_id,
+                    set_action_policy.get_smart_permission(),
+                ));
+            }
+
+            (input_addresses, vec![compute_org_address(org_id)])
+        }
+    };
+
+    // the signer's organization may have attached a smart permission to the action, which is
+    // found through the signer's account and run with the execution limit settings
+    if action.policy_name().is_some() {
+        input_addresses.extend(vec![
+            compute_account_address(public_key),
+            ORGANIZATION_PREFIX.into(),
+            SMART_PERMISSION_PREFIX.into(),
+            FUEL_LIMIT_SETTING_ADDRESS.into(),
+            MAX_MEMORY_PAGES_SETTING_ADDRESS.into(),
+            MAX_STATE_ENTRY_SIZE_SETTING_ADDRESS.into(),
+            MAX_ADDRESSES_TOUCHED_SETTING_ADDRESS.into(),
+            MAX_BYTES_WRITTEN_SETTING_ADDRESS.into(),
+        ]);
+        input_addresses.sort();
+        input_addresses.dedup();
+    }
+
+    Ok((input_addresses, output_addresses))
+}
+
+/// Returns the state address of a contract, or the contract prefix if the
+/// version is not an exact semver version, such as "latest", "1.0" or a range,
+/// since the version is then resolved by the transaction processor
+fn compute_requested_contract_address(name: &str, version: &str) -> String {
+    if semver::Version::parse(version).is_ok() {
+        compute_contract_address(name, version)
+    } else {
+        String::from(CONTRACT_PREFIX)
+    }
+}
+
+/// Returns the namespace registry addresses covering the given contract
+/// inputs or outputs, followed by the addresses themselves
+fn compute_contract_state_addresses(
+    addresses: &[String],
+    kind: &str,
+) -> Result<Vec<String>, AddressingError> {
+    let mut state_addresses = Vec::new();
+    for address in addresses {
+        let namespace = address.get(..6).ok_or_else(|| {
+            AddressingError::InvalidAddress(format!(
+                "{} must be at least 6 characters long: {}",
+                kind, address
+            ))
+        })?;
+
+        state_addresses.push(compute_namespace_registry_address(namespace)?);
+    }
+    state_addresses.extend_from_slice(addresses);
+
+    Ok(state_addresses)
+}
+
+/// Returns the state address of a contract or namespace registry
+fn compute_registry_address(
+    registry_type: RegistryType,
+    name: &str,
+) -> Result<String, AddressingError> {
+    match registry_type {
+        RegistryType::ContractRegistry => Ok(compute_contract_registry_address(name)),
+        RegistryType::NamespaceRegistry => compute_namespace_registry_address(name),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::payload::ExecuteContractActionBuilder;
+
+    #[test]
+    // check that namespaces shorter than 6 characters are rejected
+    fn check_namespace_registry_address() {
+        let address = compute_namespace_registry_address("abcdef01").unwrap();
+        assert!(address.starts_with(NAMESPACE_REGISTRY_PREFIX));
+        assert_eq!(address.len(), 70);
+        assert_eq!(
+            address,
+            compute_namespace_registry_address("abcdef").unwrap()
+        );
+
+        assert!(compute_namespace_registry_address("abcde").is_err());
+    }
+
+    #[test]
+    // check that the roles of an organization share its roles prefix
+    fn check_role_address() {
+        let address = compute_role_address("org_01", "admin");
+        assert!(address.starts_with(&compute_org_roles_prefix("org_01")));
+        assert_eq!(address.len(), 70);
+        assert_eq!(compute_account_address("public_key").len(), 70);
+        assert_eq!(compute_smart_permission_address("org_01", "perm").len(), 70);
+    }
+
+    #[test]
+    // check that only exact versions are addressed directly
+    fn check_requested_contract_address() {
+        assert_eq!(
+            compute_requested_contract_address("intkey", "1.0.0"),
+            compute_contract_address("intkey", "1.0.0")
+        );
+        assert_eq!(
+            compute_requested_contract_address("intkey", "latest"),
+            CONTRACT_PREFIX
+        );
+        assert_eq!(
+            compute_requested_contract_address("intkey", "1.0"),
+            CONTRACT_PREFIX
+        );
+        assert_eq!(
+            compute_requested_contract_address("intkey", "^1.0.0"),
+            CONTRACT_PREFIX
+        );
+    }
+
+    #[test]
+    // check that the inputs of an executed contract include the namespace
+    // registries covering them
+    fn check_execute_contract_addresses() {
+        let action = Action::ExecuteContract(
+            ExecuteContractActionBuilder::new()
+                .set_name("intkey".to_string())
+                .set_version("1.0.0".to_string())
+                .set_inputs(vec!["1cf126".to_string()])
+                .set_outputs(vec!["1cf126".to_string()])
+                .set_payload(b"payload".to_vec())
+                .build()
+                .unwrap(),
+        );
+
+        let (inputs, outputs) = compute_addresses(&action, "public_key").unwrap();
+        let namespace_registry = compute_namespace_registry_address("1cf126").unwrap();
+        assert!(inputs.contains(&compute_contract_address("intkey", "1.0.0")));
+        assert!(inputs.contains(&namespace_registry));
+        assert!(inputs.contains(&"1cf126".to_string()));
+        assert!(outputs.contains(&namespace_registry));
+        assert!(outputs.contains(&compute_contract_address("intkey", "1.0.0")));
+
+        let action = Action::ExecuteContract(
+            ExecuteContractActionBuilder::new()
+                .set_name("intkey".to_string())
+                .set_version("latest".to_string())
+                .set_inputs(vec!["1cf126".to_string()])
+                .set_outputs(vec!["1cf126".to_string()])
+                .set_payload(b"payload".to_vec())
+                .build()
+                .unwrap(),
+        );
+
+        let (inputs, outputs) = compute_addresses(&action, "public_key").unwrap();
+        assert!(inputs.contains(&CONTRACT_PREFIX.to_string()));
+        assert!(!outputs.contains(&CONTRACT_PREFIX.to_string()));
+        assert!(outputs.contains(&compute_contract_registry_address("intkey")));
+
+        let action = Action::ExecuteContract(
+            ExecuteContractActionBuilder::new()
+                .set_name("intkey".to_string())
+                .set_version("1.0.0".to_string())
+                .set_inputs(vec!["1cf1".to_string()])
+                .set_payload(b"payload".to_vec())
+                .build()
+                .unwrap(),
+        );
+        assert!(compute_addresses(&action, "public_key").is_err());
+    }
+}
@@ -25,6 +25,12 @@ extern "C" {
         public_key: WasmPtr,
         payload: WasmPtr,
     ) -> i32;
+    pub fn invoke_contract(name: WasmPtr, version: WasmPtr, payload: WasmPtr) -> i32;
+    pub fn set_return_data(data: WasmPtr) -> i32;
+    pub fn get_return_data() -> WasmPtr;
+    pub fn set_error_data(data: WasmPtr) -> i32;
+    pub fn add_event(event_type: WasmPtr, attributes: WasmPtrList, data: WasmPtr) -> i32;
+    pub fn add_receipt_data(data: WasmPtr) -> i32;
     pub fn log_buffer(log_level: WasmPtr, log_string: WasmPtr);
     pub fn log_level() -> WasmPtr;
 }
@@ -3,6 +3,7 @@
 
 #![allow(renamed_and_removed_lints)]
 
+pub mod addressing;
 mod externs;
 pub mod log;
 pub mod protocol;
@@ -12,7 +13,10 @@ use std::collections::HashMap;
 use std::error::Error;
 use std::string::FromUtf8Error;
 
+use protobuf::Message;
+
 pub use crate::externs::{WasmPtr, WasmPtrList};
+use crate::protos::contract_error::{ContractError, ContractError_ErrorType};
 
 pub struct Header {
     signer: String,
@@ -170,6 +174,42 @@ pub trait TransactionContext {
     ///
     /// * `addresses` - the addresses to delete
     fn delete_state_entries(&self, addresses: &[String]) -> Result<Vec<String>, WasmSdkError>;
+
+    /// add_receipt_data adds a blob to the execution result for this transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - the data to add
+    ///
+    /// Returns an error unless the context supports receipt data.
+    fn add_receipt_data(&self, _data: &[u8]) -> Result<(), WasmSdkError> {
+        Err(WasmSdkError::InternalError(
+            "add_receipt_data is not supported by this context".into(),
+        ))
+    }
+
+    /// add_event adds a new event to the execution result for this transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `event_type` -  This is used to subscribe to events. It should be globally unique and
+    ///         describe what, in general, has occured.
+    /// * `attributes` - Additional information about the event that is transparent to the
+    ///          validator. Attributes can be used by subscribers to filter the type of events
+    ///          they receive.
+    /// * `data` - Additional information about the event that is opaque to the validator.
+    ///
+    /// Returns an error unless the context supports events.
+    fn add_event(
+        &self,
+        _event_type: String,
+        _attributes: Vec<(String, String)>,
+        _data: &[u8],
+    ) -> Result<(), WasmSdkError> {
+        Err(WasmSdkError::InternalError(
+            "add_event is not supported by this context".into(),
+        ))
+    }
 }
 
 #[derive(Default)]
@@ -284,6 +324,69 @@ impl TransactionContext for SmartTransactionContext {
             Ok(result_vec)
         }
     }
+
+    fn add_receipt_data(&self, data: &[u8]) -> Result<(), WasmSdkError> {
+        unsafe {
+            let data_buffer = WasmBuffer::new(data)?;
+            if externs::add_receipt_data(data_buffer.to_raw()) != 1 {
+                return Err(WasmSdkError::InternalError(
+                    "Unable to add receipt data".into(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn add_event(
+        &self,
+        event_type: String,
+        attributes: Vec<(String, String)>,
+        data: &[u8],
+    ) -> Result<(), WasmSdkError> {
+        unsafe {
+            let event_type_buffer = WasmBuffer::new(event_type.as_bytes())?;
+            let data_buffer = WasmBuffer::new(data)?;
+
+            // attributes are passed as a collection of alternating keys and
+            // values, or -1 if there are none
+            let mut attributes_iter = attributes.iter();
+            let attributes_head = match attributes_iter.next() {
+                Some((key, value)) => {
+                    let header_key_buffer = WasmBuffer::new(key.as_bytes())?;
+                    externs::create_collection(header_key_buffer.to_raw());
+
+                    let value_buffer = WasmBuffer::new(value.as_bytes())?;
+                    externs::add_to_collection(header_key_buffer.to_raw(), value_buffer.to_raw());
+
+                    for (key, value) in attributes_iter {
+                        let key_buffer = WasmBuffer::new(key.as_bytes())?;
+                        externs::add_to_collection(header_key_buffer.to_raw(), key_buffer.to_raw());
+
+                        let value_buffer = WasmBuffer::new(value.as_bytes())?;
+                        externs::add_to_collection(
+                            header_key_buffer.to_raw(),
+                            value_buffer.to_raw(),
+                        );
+                    }
+                    header_key_buffer.to_raw()
+                }
+                None => -1,
+            };
+
+            if externs::add_event(
+                event_type_buffer.to_raw(),
+                attributes_head,
+                data_buffer.to_raw(),
+            ) != 1
+            {
+                return Err(WasmSdkError::InternalError(format!(
+                    "Unable to add event: {}",
+                    event_type
+                )));
+            }
+        }
+        Ok(())
+    }
 }
 
 // Mimics the sawtooth sdk TransactionHandler
@@ -335,11 +438,95 @@ pub fn invoke_smart_permission(
     }
 }
 
+/// The outcome of invoking another contract with `invoke_contract`
+pub struct InvokeResult {
+    /// Return code of the invoked contract's entrypoint, 1 on success
+    pub code: i32,
+    /// Output buffer the invoked contract set with `set_return_data`
+    pub output: Vec<u8>,
+}
+
+/// Invokes the entrypoint of another contract with the given payload
+///
+/// The invoked contract executes on behalf of the same transaction signer,
+/// but may only access state its own namespace registry permissions allow.
+pub fn invoke_contract(
+    name: &str,
+    version: &str,
+    payload: &[u8],
+) -> Result<InvokeResult, WasmSdkError> {
+    unsafe {
+        let name_buffer = WasmBuffer::new(name.as_bytes())?;
+        let version_buffer = WasmBuffer::new(version.as_bytes())?;
+        let payload_buffer = WasmBuffer::new(payload)?;
+
+        let code = externs::invoke_contract(
+            name_buffer.to_raw(),
+            version_buffer.to_raw(),
+            payload_buffer.to_raw(),
+        );
+
+        let output_ptr = externs::get_return_data();
+        let output = if output_ptr < 0 {
+            Vec::new()
+        } else {
+            ptr_to_vec(output_ptr)?.unwrap_or_default()
+        };
+
+        Ok(InvokeResult { code, output })
+    }
+}
+
+/// Sets the output buffer returned to the contract that invoked this one
+pub fn set_return_data(data: &[u8]) -> Result<(), WasmSdkError> {
+    unsafe {
+        let data_buffer = WasmBuffer::new(data)?;
+        if externs::set_return_data(data_buffer.to_raw()) < 0 {
+            return Err(WasmSdkError::InternalError(
+                "Unable to set return data".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Hands the error returned by apply to the transaction processor, which
+/// rejects the transaction with the error's message
+///
+/// `execute_entrypoint` calls this for the error returned by apply, so it is
+/// only needed by contracts that implement their own entrypoint.
+pub fn set_error(error: &ApplyError) -> Result<(), WasmSdkError> {
+    let mut proto = ContractError::new();
+    match error {
+        ApplyError::InvalidTransaction(message) => {
+            proto.set_error_type(ContractError_ErrorType::INVALID_TRANSACTION);
+            proto.set_message(message.clone());
+        }
+        ApplyError::InternalError(message) => {
+            proto.set_error_type(ContractError_ErrorType::INTERNAL_ERROR);
+            proto.set_message(message.clone());
+        }
+    }
+    let bytes = proto.write_to_bytes()?;
+
+    unsafe {
+        let data_buffer = WasmBuffer::new(&bytes)?;
+        if externs::set_error_data(data_buffer.to_raw()) < 0 {
+            return Err(WasmSdkError::InternalError(
+                "Unable to set error data".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
 /// -1: Failed to deserialize payload
 /// -2: Failed to deserialize signer
 /// -3: apply returned InvalidTransaction
 /// -4: apply returned InternalError
 ///
+/// The message of an error returned by apply is passed along with `set_error`.
+///
 /// # Safety
 ///
 /// This function is unsafe due to the call to WasmBuffer::from_raw which converts a WasmPtr
@@ -389,8 +576,14 @@ where
                 0
             }
         }
-        Err(ApplyError::InvalidTransaction(_)) => -3,
-        Err(ApplyError::InternalError(_)) => -4,
+        Err(err) => {
+            // the return code still reports the error if its message cannot be set
+            let _ = set_error(&err);
+            match err {
+                ApplyError::InvalidTransaction(_) => -3,
+                ApplyError::InternalError(_) => -4,
+            }
+        }
     }
 }
 
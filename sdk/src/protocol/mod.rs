@@ -8,3 +8,91 @@ pub mod state;
 pub const ADMINISTRATORS_SETTING_ADDRESS: &str =
     "000000a87cb5eafdcca6a814e4add97c4b517d3c530c2f44b31d18e3b0c44298fc1c14";
 pub const ADMINISTRATORS_SETTING_KEY: &str = "sawtooth.swa.administrators";
+
+pub const FUEL_LIMIT_SETTING_ADDRESS: &str =
+    "000000a87cb5eafdcca6a814e4add97c4b517d104892a6a65077ade3b0c44298fc1c14";
+pub const FUEL_LIMIT_SETTING_KEY: &str = "sawtooth.swa.fuel_limit";
+
+pub const MAX_MEMORY_PAGES_SETTING_ADDRESS: &str =
+    "000000a87cb5eafdcca6a814e4add97c4b517dcfab91b78c5d8d2de3b0c44298fc1c14";
+pub const MAX_MEMORY_PAGES_SETTING_KEY: &str = "sawtooth.swa.max_memory_pages";
+
+pub const MAX_STATE_ENTRY_SIZE_SETTING_ADDRESS: &str =
+    "000000a87cb5eafdcca6a814e4add97c4b517d903cef8de46ff4c0e3b0c44298fc1c14";
+pub const MAX_STATE_ENTRY_SIZE_SETTING_KEY: &str = "sawtooth.swa.max_state_entry_size";
+
+pub const MAX_ADDRESSES_TOUCHED_SETTING_ADDRESS: &str =
+    "000000a87cb5eafdcca6a814e4add97c4b517d0c8b911be51e5520e3b0c44298fc1c14";
+pub const MAX_ADDRESSES_TOUCHED_SETTING_KEY: &str = "sawtooth.swa.max_addresses_touched";
+
+pub const MAX_BYTES_WRITTEN_SETTING_ADDRESS: &str =
+    "000000a87cb5eafdcca6a814e4add97c4b517dcdfd2ea58ac44511e3b0c44298fc1c14";
+pub const MAX_BYTES_WRITTEN_SETTING_KEY: &str = "sawtooth.swa.max_bytes_written";
+
+pub const MAX_CONTRACT_SIZE_SETTING_ADDRESS: &str =
+    "000000a87cb5eafdcca6a814e4add97c4b517dacf67ee4f6606477e3b0c44298fc1c14";
+pub const MAX_CONTRACT_SIZE_SETTING_KEY: &str = "sawtooth.swa.max_contract_size";
+
+/// The address of the BlockInfoConfig kept by the Sawtooth BlockInfo family,
+/// which holds the number of the latest block
+pub const BLOCK_INFO_CONFIG_ADDRESS: &str =
+    "00b10c0100000000000000000000000000000000000000000000000000000000000000";
+
+/// The role that implicitly grants every permission of its organization
+pub const ADMIN_ROLE: &str = "admin";
+
+pub const SMART_PERMISSION_CREATE_PERMISSION: &str = "smart_permission.create";
+pub const SMART_PERMISSION_UPDATE_PERMISSION: &str = "smart_permission.update";
+pub const SMART_PERMISSION_DELETE_PERMISSION: &str = "smart_permission.delete";
+pub const ACCOUNT_CREATE_PERMISSION: &str = "account.create";
+pub const ACCOUNT_UPDATE_PERMISSION: &str = "account.update";
+pub const ACCOUNT_DEACTIVATE_PERMISSION: &str = "account.deactivate";
+pub const ACCOUNT_GRANT_ROLE_PERMISSION: &str = "account.grant_role";
+pub const CONTRACT_UPLOAD_PERMISSION: &str = "contract.upload";
+pub const ORGANIZATION_CREATE_PERMISSION: &str = "organization.create";
+pub const ORGANIZATION_UPDATE_PERMISSION: &str = "organization.update";
+pub const ROLE_CREATE_PERMISSION: &str = "role.create";
+pub const ROLE_UPDATE_PERMISSION: &str = "role.update";
+pub const ROLE_DELETE_PERMISSION: &str = "role.delete";
+
+/// Every permission that can be granted by a Role
+pub const ROLE_PERMISSIONS: &[&str] = &[
+    SMART_PERMISSION_CREATE_PERMISSION,
+    SMART_PERMISSION_UPDATE_PERMISSION,
+    SMART_PERMISSION_DELETE_PERMISSION,
+    ACCOUNT_CREATE_PERMISSION,
+    ACCOUNT_UPDATE_PERMISSION,
+    ACCOUNT_DEACTIVATE_PERMISSION,
+    ACCOUNT_GRANT_ROLE_PERMISSION,
+    CONTRACT_UPLOAD_PERMISSION,
+    ORGANIZATION_CREATE_PERMISSION,
+    ORGANIZATION_UPDATE_PERMISSION,
+    ROLE_CREATE_PERMISSION,
+    ROLE_UPDATE_PERMISSION,
+    ROLE_DELETE_PERMISSION,
+];
+
+/// Every action an organization can attach a smart permission to, by the name
+/// used in an ActionPolicy
+pub const POLICY_ACTIONS: &[&str] = &[
+    "create_contract_registry",
+    "delete_contract_registry",
+    "update_contract_registry_owners",
+    "create_namespace_registry",
+    "delete_namespace_registry",
+    "update_namespace_registry_owners",
+    "create_namespace_registry_permission",
+    "delete_namespace_registry_permission",
+    "propose_owner_change",
+    "vote_owner_change",
+    "create_account",
+    "update_account",
+    "deactivate_account",
+    "reactivate_account",
+    "grant_role",
+    "revoke_role",
+    "update_organization",
+    "create_role",
+    "update_role",
+    "delete_role",
+];
@@ -10,9 +10,7 @@ use crate::protos;
 use crate::protos::{
     FromBytes, FromNative, FromProto, IntoBytes, IntoNative, IntoProto, ProtoConversionError,
 };
-//use protos::state::KeyValueEntry;
-//use crate::protocol::state::KeyValueEntry;
-use crate::protos::account::KeyValueEntry;
+use crate::protocol::state::{ContractSource, KeyValueEntry};
 
 /// Native implementation for SmartPayload_Action
 #[derive(Debug, Clone, PartialEq)]
@@ -35,6 +33,64 @@ pub enum Action {
     UpdateAccount(UpdateAccountAction),
     CreateOrganization(CreateOrganizationAction),
     UpdateOrganization(UpdateOrganizationAction),
+    UpgradeContract(UpgradeContractAction),
+    DeactivateAccount(DeactivateAccountAction),
+    ReactivateAccount(ReactivateAccountAction),
+    GrantRole(GrantRoleAction),
+    RevokeRole(RevokeRoleAction),
+    CreateRole(CreateRoleAction),
+    UpdateRole(UpdateRoleAction),
+    DeleteRole(DeleteRoleAction),
+    ProposeOwnerChange(ProposeOwnerChangeAction),
+    VoteOwnerChange(VoteOwnerChangeAction),
+    SetActionPolicy(SetActionPolicyAction),
+}
+
+impl Action {
+    /// Returns the name an organization uses to attach a smart permission to
+    /// the action, or None if the action cannot be guarded by one
+    ///
+    /// Contract actions are governed by namespace permissions, and the smart
+    /// permission and action policy actions are left unguarded so a faulty
+    /// smart permission can always be replaced.
+    pub fn policy_name(&self) -> Option<&'static str> {
+        let name = match *self {
+            Action::CreateContractRegistry(_) => "create_contract_registry",
+            Action::DeleteContractRegistry(_) => "delete_contract_registry",
+            Action::UpdateContractRegistryOwners(_) => "update_contract_registry_owners",
+            Action::CreateNamespaceRegistry(_) => "create_namespace_registry",
+            Action::DeleteNamespaceRegistry(_) => "delete_namespace_registry",
+            Action::UpdateNamespaceRegistryOwners(_) => "update_namespace_registry_owners",
+            Action::CreateNamespaceRegistryPermission(_) => {
+                "create_namespace_registry_permission"
+            }
+            Action::DeleteNamespaceRegistryPermission(_) => {
+                "delete_namespace_registry_permission"
+            }
+            Action::ProposeOwnerChange(_) => "propose_owner_change",
+            Action::VoteOwnerChange(_) => "vote_owner_change",
+            Action::CreateAccount(_) => "create_account",
+            Action::UpdateAccount(_) => "update_account",
+            Action::DeactivateAccount(_) => "deactivate_account",
+            Action::ReactivateAccount(_) => "reactivate_account",
+            Action::GrantRole(_) => "grant_role",
+            Action::RevokeRole(_) => "revoke_role",
+            Action::UpdateOrganization(_) => "update_organization",
+            Action::CreateRole(_) => "create_role",
+            Action::UpdateRole(_) => "update_role",
+            Action::DeleteRole(_) => "delete_role",
+            Action::CreateContract(_)
+            | Action::DeleteContract(_)
+            | Action::ExecuteContract(_)
+            | Action::UpgradeContract(_)
+            | Action::CreateSmartPermission(_)
+            | Action::UpdateSmartPermission(_)
+            | Action::DeleteSmartPermission(_)
+            | Action::CreateOrganization(_)
+            | Action::SetActionPolicy(_) => return None,
+        };
+        Some(name)
+    }
 }
 
 impl std::fmt::Display for Action {
@@ -66,6 +122,17 @@ impl std::fmt::Display for Action {
             Action::UpdateAccount(_) => write!(f, "Action: Update Account"),
             Action::CreateOrganization(_) => write!(f, "Action: Create Organization"),
             Action::UpdateOrganization(_) => write!(f, "Action: Update Organization"),
+            Action::UpgradeContract(_) => write!(f, "Action: Upgrade Contract"),
+            Action::DeactivateAccount(_) => write!(f, "Action: Deactivate Account"),
+            Action::ReactivateAccount(_) => write!(f, "Action: Reactivate Account"),
+            Action::GrantRole(_) => write!(f, "Action: Grant Role"),
+            Action::RevokeRole(_) => write!(f, "Action: Revoke Role"),
+            Action::CreateRole(_) => write!(f, "Action: Create Role"),
+            Action::UpdateRole(_) => write!(f, "Action: Update Role"),
+            Action::DeleteRole(_) => write!(f, "Action: Delete Role"),
+            Action::ProposeOwnerChange(_) => write!(f, "Action: Propose Owner Change"),
+            Action::VoteOwnerChange(_) => write!(f, "Action: Vote Owner Change"),
+            Action::SetActionPolicy(_) => write!(f, "Action: Set Action Policy"),
         }
     }
 }
@@ -78,6 +145,7 @@ pub struct CreateContractAction {
     inputs: Vec<String>,
     outputs: Vec<String>,
     contract: Vec<u8>,
+    source: Option<ContractSource>,
 }
 
 impl CreateContractAction {
@@ -100,6 +168,10 @@ impl CreateContractAction {
     pub fn get_contract(&self) -> &[u8] {
         &self.contract
     }
+
+    pub fn get_source(&self) -> Option<&ContractSource> {
+        self.source.as_ref()
+    }
 }
 
 impl FromProto<protos::payload::CreateContractAction> for CreateContractAction {
@@ -112,6 +184,11 @@ impl FromProto<protos::payload::CreateContractAction> for CreateContractAction {
             inputs: proto.get_inputs().to_vec(),
             outputs: proto.get_outputs().to_vec(),
             contract: proto.get_contract().to_vec(),
+            source: if proto.has_source() {
+                Some(ContractSource::from_proto(proto.get_source().clone())?)
+            } else {
+                None
+            },
         })
     }
 }
@@ -130,6 +207,9 @@ impl FromNative<CreateContractAction> for protos::payload::CreateContractAction
             create_contract_action.get_outputs().to_vec(),
         ));
         proto.set_contract(create_contract_action.get_contract().to_vec());
+        if let Some(source) = create_contract_action.source {
+            proto.set_source(source.into_proto()?);
+        }
         Ok(proto)
     }
 }
@@ -190,6 +270,7 @@ pub struct CreateContractActionBuilder {
     inputs: Vec<String>,
     outputs: Vec<String>,
     contract: Vec<u8>,
+    source: Option<ContractSource>,
 }
 
 impl CreateContractActionBuilder {
@@ -222,6 +303,11 @@ impl CreateContractActionBuilder {
         self
     }
 
+    pub fn set_source(mut self, source: Option<ContractSource>) -> CreateContractActionBuilder {
+        self.source = source;
+        self
+    }
+
     pub fn build(self) -> Result<CreateContractAction, CreateContractActionBuildError> {
         let name = self.name.ok_or_else(|| {
             CreateContractActionBuildError::MissingField("'name' field is required".to_string())
@@ -250,6 +336,7 @@ impl CreateContractActionBuilder {
             inputs,
             outputs,
             contract,
+            source: self.source,
         })
     }
 }
@@ -562,11 +649,255 @@ impl ExecuteContractActionBuilder {
     }
 }
 
+/// Native implementation for UpgradeContractAction
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct UpgradeContractAction {
+    name: String,
+    version: String,
+    previous_version: String,
+    inputs: Vec<String>,
+    outputs: Vec<String>,
+    contract: Vec<u8>,
+    migrate_payload: Vec<u8>,
+    source: Option<ContractSource>,
+}
+
+impl UpgradeContractAction {
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_version(&self) -> &str {
+        &self.version
+    }
+
+    pub fn get_previous_version(&self) -> &str {
+        &self.previous_version
+    }
+
+    pub fn get_inputs(&self) -> &[String] {
+        &self.inputs
+    }
+
+    pub fn get_outputs(&self) -> &[String] {
+        &self.outputs
+    }
+
+    pub fn get_contract(&self) -> &[u8] {
+        &self.contract
+    }
+
+    pub fn get_migrate_payload(&self) -> &[u8] {
+        &self.migrate_payload
+    }
+
+    pub fn get_source(&self) -> Option<&ContractSource> {
+        self.source.as_ref()
+    }
+}
+
+impl FromProto<protos::payload::UpgradeContractAction> for UpgradeContractAction {
+    fn from_proto(
+        proto: protos::payload::UpgradeContractAction,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(UpgradeContractAction {
+            name: proto.get_name().to_string(),
+            version: proto.get_version().to_string(),
+            previous_version: proto.get_previous_version().to_string(),
+            inputs: proto.get_inputs().to_vec(),
+            outputs: proto.get_outputs().to_vec(),
+            contract: proto.get_contract().to_vec(),
+            migrate_payload: proto.get_migrate_payload().to_vec(),
+            source: if proto.has_source() {
+                Some(ContractSource::from_proto(proto.get_source().clone())?)
+            } else {
+                None
+            },
+        })
+    }
+}
+
+impl FromNative<UpgradeContractAction> for protos::payload::UpgradeContractAction {
+    fn from_native(
+        upgrade_contract_action: UpgradeContractAction,
+    ) -> Result<Self, ProtoConversionError> {
+        let mut proto = protos::payload::UpgradeContractAction::new();
+        proto.set_name(upgrade_contract_action.get_name().to_string());
+        proto.set_version(upgrade_contract_action.get_version().to_string());
+        proto.set_previous_version(upgrade_contract_action.get_previous_version().to_string());
+        proto.set_inputs(RepeatedField::from_vec(
+            upgrade_contract_action.get_inputs().to_vec(),
+        ));
+        proto.set_outputs(RepeatedField::from_vec(
+            upgrade_contract_action.get_outputs().to_vec(),
+        ));
+        proto.set_contract(upgrade_contract_action.get_contract().to_vec());
+        proto.set_migrate_payload(upgrade_contract_action.get_migrate_payload().to_vec());
+        if let Some(source) = upgrade_contract_action.source {
+            proto.set_source(source.into_proto()?);
+        }
+        Ok(proto)
+    }
+}
+
+impl FromBytes<UpgradeContractAction> for UpgradeContractAction {
+    fn from_bytes(bytes: &[u8]) -> Result<UpgradeContractAction, ProtoConversionError> {
+        let proto: protos::payload::UpgradeContractAction = protobuf::parse_from_bytes(bytes)
+            .map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get UpgradeContractAction from bytes".to_string(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+
+impl IntoBytes for UpgradeContractAction {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get bytes from UpgradeContractAction".to_string(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+
+impl IntoProto<protos::payload::UpgradeContractAction> for UpgradeContractAction {}
+impl IntoNative<UpgradeContractAction> for protos::payload::UpgradeContractAction {}
+
+#[derive(Debug)]
+pub enum UpgradeContractActionBuildError {
+    MissingField(String),
+}
+
+impl StdError for UpgradeContractActionBuildError {
+    fn description(&self) -> &str {
+        match *self {
+            UpgradeContractActionBuildError::MissingField(ref msg) => msg,
+        }
+    }
+}
+
+impl std::fmt::Display for UpgradeContractActionBuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            UpgradeContractActionBuildError::MissingField(ref s) => {
+                write!(f, "MissingField: {}", s)
+            }
+        }
+    }
+}
+
+/// Builder used to create a UpgradeContractAction
+#[derive(Default, Clone)]
+pub struct UpgradeContractActionBuilder {
+    name: Option<String>,
+    version: Option<String>,
+    previous_version: Option<String>,
+    inputs: Vec<String>,
+    outputs: Vec<String>,
+    contract: Vec<u8>,
+    migrate_payload: Vec<u8>,
+    source: Option<ContractSource>,
+}
+
+impl UpgradeContractActionBuilder {
+    pub fn new() -> Self {
+        UpgradeContractActionBuilder::default()
+    }
+
+    pub fn set_name(mut self, name: String) -> UpgradeContractActionBuilder {
+        self.name = Some(name);
+        self
+    }
+
+    pub fn set_version(mut self, version: String) -> UpgradeContractActionBuilder {
+        self.version = Some(version);
+        self
+    }
+
+    pub fn set_previous_version(
+        mut self,
+        previous_version: String,
+    ) -> UpgradeContractActionBuilder {
+        self.previous_version = Some(previous_version);
+        self
+    }
+
+    pub fn set_inputs(mut self, inputs: Vec<String>) -> UpgradeContractActionBuilder {
+        self.inputs = inputs;
+        self
+    }
+
+    pub fn set_outputs(mut self, outputs: Vec<String>) -> UpgradeContractActionBuilder {
+        self.outputs = outputs;
+        self
+    }
+
+    pub fn set_contract(mut self, contract: Vec<u8>) -> UpgradeContractActionBuilder {
+        self.contract = contract;
+        self
+    }
+
+    pub fn set_migrate_payload(mut self, migrate_payload: Vec<u8>) -> UpgradeContractActionBuilder {
+        self.migrate_payload = migrate_payload;
+        self
+    }
+
+    pub fn set_source(mut self, source: Option<ContractSource>) -> UpgradeContractActionBuilder {
+        self.source = source;
+        self
+    }
+
+    pub fn build(self) -> Result<UpgradeContractAction, UpgradeContractActionBuildError> {
+        let name = self.name.ok_or_else(|| {
+            UpgradeContractActionBuildError::MissingField("'name' field is required".to_string())
+        })?;
+
+        let version = self.version.ok_or_else(|| {
+            UpgradeContractActionBuildError::MissingField("'version' field is required".to_string())
+        })?;
+
+        let previous_version = self.previous_version.ok_or_else(|| {
+            UpgradeContractActionBuildError::MissingField(
+                "'previous_version' field is required".to_string(),
+            )
+        })?;
+
+        let inputs = self.inputs;
+        let outputs = self.outputs;
+
+        let contract = {
+            if self.contract.is_empty() {
+                return Err(UpgradeContractActionBuildError::MissingField(
+                    "'contract' field is required".to_string(),
+                ));
+            } else {
+                self.contract
+            }
+        };
+
+        Ok(UpgradeContractAction {
+            name,
+            version,
+            previous_version,
+            inputs,
+            outputs,
+            contract,
+            migrate_payload: self.migrate_payload,
+            source: self.source,
+        })
+    }
+}
+
 /// Native implementation for CreateContractRegistryAction
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct CreateContractRegistryAction {
     name: String,
     owners: Vec<String>,
+    owner_threshold: u32,
 }
 
 impl CreateContractRegistryAction {
@@ -577,6 +908,10 @@ impl CreateContractRegistryAction {
     pub fn get_owners(&self) -> &[String] {
         &self.owners
     }
+
+    pub fn get_owner_threshold(&self) -> u32 {
+        self.owner_threshold
+    }
 }
 
 impl FromProto<protos::payload::CreateContractRegistryAction> for CreateContractRegistryAction {
@@ -586,6 +921,7 @@ impl FromProto<protos::payload::CreateContractRegistryAction> for CreateContract
         Ok(CreateContractRegistryAction {
             name: proto.get_name().to_string(),
             owners: proto.get_owners().to_vec(),
+            owner_threshold: proto.get_owner_threshold(),
         })
     }
 }
@@ -599,6 +935,7 @@ impl FromNative<CreateContractRegistryAction> for protos::payload::CreateContrac
         proto.set_owners(RepeatedField::from_vec(
             create_contract_registry_action.get_owners().to_vec(),
         ));
+        proto.set_owner_threshold(create_contract_registry_action.get_owner_threshold());
         Ok(proto)
     }
 }
@@ -658,6 +995,7 @@ impl std::fmt::Display for CreateContractRegistryActionBuildError {
 pub struct CreateContractRegistryActionBuilder {
     name: Option<String>,
     owners: Vec<String>,
+    owner_threshold: u32,
 }
 
 impl CreateContractRegistryActionBuilder {
@@ -675,6 +1013,14 @@ impl CreateContractRegistryActionBuilder {
         self
     }
 
+    pub fn set_owner_threshold(
+        mut self,
+        owner_threshold: u32,
+    ) -> CreateContractRegistryActionBuilder {
+        self.owner_threshold = owner_threshold;
+        self
+    }
+
     pub fn build(
         self,
     ) -> Result<CreateContractRegistryAction, CreateContractRegistryActionBuildError> {
@@ -694,7 +1040,11 @@ impl CreateContractRegistryActionBuilder {
             }
         };
 
-        Ok(CreateContractRegistryAction { name, owners })
+        Ok(CreateContractRegistryAction {
+            name,
+            owners,
+            owner_threshold: self.owner_threshold,
+        })
     }
 }
 
@@ -963,6 +1313,7 @@ impl UpdateContractRegistryOwnersActionBuilder {
 pub struct CreateNamespaceRegistryAction {
     namespace: String,
     owners: Vec<String>,
+    owner_threshold: u32,
 }
 
 impl CreateNamespaceRegistryAction {
@@ -973,6 +1324,10 @@ impl CreateNamespaceRegistryAction {
     pub fn get_owners(&self) -> &[String] {
         &self.owners
     }
+
+    pub fn get_owner_threshold(&self) -> u32 {
+        self.owner_threshold
+    }
 }
 
 impl FromProto<protos::payload::CreateNamespaceRegistryAction> for CreateNamespaceRegistryAction {
@@ -982,6 +1337,7 @@ impl FromProto<protos::payload::CreateNamespaceRegistryAction> for CreateNamespa
         Ok(CreateNamespaceRegistryAction {
             namespace: proto.get_namespace().to_string(),
             owners: proto.get_owners().to_vec(),
+            owner_threshold: proto.get_owner_threshold(),
         })
     }
 }
@@ -995,6 +1351,7 @@ impl FromNative<CreateNamespaceRegistryAction> for protos::payload::CreateNamesp
         proto.set_owners(RepeatedField::from_vec(
             create_namespace_registry_action.get_owners().to_vec(),
         ));
+        proto.set_owner_threshold(create_namespace_registry_action.get_owner_threshold());
         Ok(proto)
     }
 }
@@ -1054,6 +1411,7 @@ impl std::fmt::Display for CreateNamespaceRegistryActionBuildError {
 pub struct CreateNamespaceRegistryActionBuilder {
     namespace: Option<String>,
     owners: Vec<String>,
+    owner_threshold: u32,
 }
 
 impl CreateNamespaceRegistryActionBuilder {
@@ -1071,6 +1429,14 @@ impl CreateNamespaceRegistryActionBuilder {
         self
     }
 
+    pub fn set_owner_threshold(
+        mut self,
+        owner_threshold: u32,
+    ) -> CreateNamespaceRegistryActionBuilder {
+        self.owner_threshold = owner_threshold;
+        self
+    }
+
     pub fn build(
         self,
     ) -> Result<CreateNamespaceRegistryAction, CreateNamespaceRegistryActionBuildError> {
@@ -1090,7 +1456,11 @@ impl CreateNamespaceRegistryActionBuilder {
             }
         };
 
-        Ok(CreateNamespaceRegistryAction { namespace, owners })
+        Ok(CreateNamespaceRegistryAction {
+            namespace,
+            owners,
+            owner_threshold: self.owner_threshold,
+        })
     }
 }
 
@@ -1366,6 +1736,8 @@ pub struct CreateNamespaceRegistryPermissionAction {
     contract_name: String,
     read: bool,
     write: bool,
+    prefix: String,
+    deny: bool,
 }
 
 impl CreateNamespaceRegistryPermissionAction {
@@ -1384,6 +1756,14 @@ impl CreateNamespaceRegistryPermissionAction {
     pub fn get_write(&self) -> bool {
         self.write
     }
+
+    pub fn get_prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    pub fn get_deny(&self) -> bool {
+        self.deny
+    }
 }
 
 impl FromProto<protos::payload::CreateNamespaceRegistryPermissionAction>
@@ -1397,6 +1777,8 @@ impl FromProto<protos::payload::CreateNamespaceRegistryPermissionAction>
             contract_name: proto.get_contract_name().to_string(),
             read: proto.get_read(),
             write: proto.get_write(),
+            prefix: proto.get_prefix().to_string(),
+            deny: proto.get_deny(),
         })
     }
 }
@@ -1416,6 +1798,8 @@ impl FromNative<CreateNamespaceRegistryPermissionAction>
         );
         proto.set_read(create_namespace_permission_action.get_read());
         proto.set_write(create_namespace_permission_action.get_write());
+        proto.set_prefix(create_namespace_permission_action.get_prefix().to_string());
+        proto.set_deny(create_namespace_permission_action.get_deny());
         Ok(proto)
     }
 }
@@ -1487,6 +1871,8 @@ pub struct CreateNamespaceRegistryPermissionActionBuilder {
     contract_name: Option<String>,
     read: Option<bool>,
     write: Option<bool>,
+    prefix: String,
+    deny: bool,
 }
 
 impl CreateNamespaceRegistryPermissionActionBuilder {
@@ -1520,6 +1906,16 @@ impl CreateNamespaceRegistryPermissionActionBuilder {
         self
     }
 
+    pub fn set_prefix(mut self, prefix: String) -> CreateNamespaceRegistryPermissionActionBuilder {
+        self.prefix = prefix;
+        self
+    }
+
+    pub fn set_deny(mut self, deny: bool) -> CreateNamespaceRegistryPermissionActionBuilder {
+        self.deny = deny;
+        self
+    }
+
     pub fn build(
         self,
     ) -> Result<
@@ -1546,6 +1942,8 @@ impl CreateNamespaceRegistryPermissionActionBuilder {
             contract_name,
             read,
             write,
+            prefix: self.prefix,
+            deny: self.deny,
         })
     }
 }
@@ -1555,6 +1953,7 @@ impl CreateNamespaceRegistryPermissionActionBuilder {
 pub struct DeleteNamespaceRegistryPermissionAction {
     namespace: String,
     contract_name: String,
+    prefix: String,
 }
 
 impl DeleteNamespaceRegistryPermissionAction {
@@ -1565,6 +1964,10 @@ impl DeleteNamespaceRegistryPermissionAction {
     pub fn get_contract_name(&self) -> &str {
         &self.contract_name
     }
+
+    pub fn get_prefix(&self) -> &str {
+        &self.prefix
+    }
 }
 
 impl FromProto<protos::payload::DeleteNamespaceRegistryPermissionAction>
@@ -1576,6 +1979,7 @@ impl FromProto<protos::payload::DeleteNamespaceRegistryPermissionAction>
         Ok(DeleteNamespaceRegistryPermissionAction {
             namespace: proto.get_namespace().to_string(),
             contract_name: proto.get_contract_name().to_string(),
+            prefix: proto.get_prefix().to_string(),
         })
     }
 }
@@ -1593,6 +1997,7 @@ impl FromNative<DeleteNamespaceRegistryPermissionAction>
                 .get_contract_name()
                 .to_string(),
         );
+        proto.set_prefix(delete_namespace_permission_action.get_prefix().to_string());
         Ok(proto)
     }
 }
@@ -1662,6 +2067,7 @@ impl std::fmt::Display for DeleteNamespaceRegistryPermissionActionBuildError {
 pub struct DeleteNamespaceRegistryPermissionActionBuilder {
     namespace: Option<String>,
     contract_name: Option<String>,
+    prefix: String,
 }
 
 impl DeleteNamespaceRegistryPermissionActionBuilder {
@@ -1685,6 +2091,11 @@ impl DeleteNamespaceRegistryPermissionActionBuilder {
         self
     }
 
+    pub fn set_prefix(mut self, prefix: String) -> DeleteNamespaceRegistryPermissionActionBuilder {
+        self.prefix = prefix;
+        self
+    }
+
     pub fn build(
         self,
     ) -> Result<
@@ -1706,6 +2117,7 @@ impl DeleteNamespaceRegistryPermissionActionBuilder {
         Ok(DeleteNamespaceRegistryPermissionAction {
             namespace,
             contract_name,
+            prefix: self.prefix,
         })
     }
 }
@@ -2154,6 +2566,49 @@ impl DeleteSmartPermissionActionBuilder {
     }
 }
 
+/// Native implementation for MetadataUpdateMode
+///
+/// Determines how the metadata of an update action is applied to the existing
+/// metadata of an account or organization.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MetadataUpdateMode {
+    /// Entries are added, replacing existing entries with the same key
+    Merge,
+    /// The entries replace all of the existing metadata
+    Replace,
+    /// Existing entries with the same keys as the entries are removed
+    Remove,
+}
+
+impl Default for MetadataUpdateMode {
+    fn default() -> Self {
+        MetadataUpdateMode::Merge
+    }
+}
+
+impl FromProto<protos::payload::MetadataUpdateMode> for MetadataUpdateMode {
+    fn from_proto(mode: protos::payload::MetadataUpdateMode) -> Result<Self, ProtoConversionError> {
+        match mode {
+            protos::payload::MetadataUpdateMode::MERGE => Ok(MetadataUpdateMode::Merge),
+            protos::payload::MetadataUpdateMode::REPLACE => Ok(MetadataUpdateMode::Replace),
+            protos::payload::MetadataUpdateMode::REMOVE => Ok(MetadataUpdateMode::Remove),
+        }
+    }
+}
+
+impl FromNative<MetadataUpdateMode> for protos::payload::MetadataUpdateMode {
+    fn from_native(mode: MetadataUpdateMode) -> Result<Self, ProtoConversionError> {
+        match mode {
+            MetadataUpdateMode::Merge => Ok(protos::payload::MetadataUpdateMode::MERGE),
+            MetadataUpdateMode::Replace => Ok(protos::payload::MetadataUpdateMode::REPLACE),
+            MetadataUpdateMode::Remove => Ok(protos::payload::MetadataUpdateMode::REMOVE),
+        }
+    }
+}
+
+impl IntoProto<protos::payload::MetadataUpdateMode> for MetadataUpdateMode {}
+impl IntoNative<MetadataUpdateMode> for protos::payload::MetadataUpdateMode {}
+
 /// Native implementation for CreateAccountAction
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct CreateAccountAction {
@@ -2189,7 +2644,12 @@ impl FromProto<protos::payload::CreateAccountAction> for CreateAccountAction {
             org_id: proto.get_org_id().to_string(),
             public_key: proto.get_public_key().to_string(),
             roles: proto.get_roles().to_vec(),
-            metadata: proto.get_metadata().to_vec(),
+            metadata: proto
+                .get_metadata()
+                .to_vec()
+                .into_iter()
+                .map(KeyValueEntry::from_proto)
+                .collect::<Result<Vec<KeyValueEntry>, ProtoConversionError>>()?,
         })
     }
 }
@@ -2206,6 +2666,9 @@ impl FromNative<CreateAccountAction> for protos::payload::CreateAccountAction {
             create_account_action
                 .get_metadata()
                 .to_vec()
+                .into_iter()
+                .map(KeyValueEntry::into_proto)
+                .collect::<Result<Vec<protos::account::KeyValueEntry>, ProtoConversionError>>()?,
         ));
         Ok(proto)
     }
@@ -2320,15 +2783,7 @@ impl CreateAccountActionBuilder {
             }
         };
 
-        let metadata = {
-            if self.metadata.is_empty() {
-                return Err(CreateAccountActionBuildError::MissingField(
-                    "'metadata' field is required".to_string(),
-                ));
-            } else {
-                self.metadata
-            }
-        };
+        let metadata = self.metadata;
 
         Ok(CreateAccountAction {
             org_id,
@@ -2346,6 +2801,7 @@ pub struct UpdateAccountAction {
     public_key: String,
     roles: Vec<String>,
     metadata: Vec<KeyValueEntry>,
+    metadata_mode: MetadataUpdateMode,
 }
 
 impl UpdateAccountAction {
@@ -2364,6 +2820,10 @@ impl UpdateAccountAction {
     pub fn get_metadata(&self) -> &[KeyValueEntry] {
         &self.metadata
     }
+
+    pub fn get_metadata_mode(&self) -> MetadataUpdateMode {
+        self.metadata_mode
+    }
 }
 
 impl FromProto<protos::payload::UpdateAccountAction> for UpdateAccountAction {
@@ -2374,7 +2834,13 @@ impl FromProto<protos::payload::UpdateAccountAction> for UpdateAccountAction {
             org_id: proto.get_org_id().to_string(),
             public_key: proto.get_public_key().to_string(),
             roles: proto.get_roles().to_vec(),
-            metadata: proto.get_metadata().to_vec(),
+            metadata: proto
+                .get_metadata()
+                .to_vec()
+                .into_iter()
+                .map(KeyValueEntry::from_proto)
+                .collect::<Result<Vec<KeyValueEntry>, ProtoConversionError>>()?,
+            metadata_mode: MetadataUpdateMode::from_proto(proto.get_metadata_mode())?,
         })
     }
 }
@@ -2391,7 +2857,11 @@ impl FromNative<UpdateAccountAction> for protos::payload::UpdateAccountAction {
             update_account_action
                 .get_metadata()
                 .to_vec()
+                .into_iter()
+                .map(KeyValueEntry::into_proto)
+                .collect::<Result<Vec<protos::account::KeyValueEntry>, ProtoConversionError>>()?,
         ));
+        proto.set_metadata_mode(update_account_action.get_metadata_mode().into_proto()?);
         Ok(proto)
     }
 }
@@ -2453,6 +2923,7 @@ pub struct UpdateAccountActionBuilder {
     public_key: Option<String>,
     roles: Vec<String>,
     metadata: Vec<KeyValueEntry>,
+    metadata_mode: MetadataUpdateMode,
 }
 
 impl UpdateAccountActionBuilder {
@@ -2480,6 +2951,14 @@ impl UpdateAccountActionBuilder {
         self
     }
 
+    pub fn set_metadata_mode(
+        mut self,
+        metadata_mode: MetadataUpdateMode,
+    ) -> UpdateAccountActionBuilder {
+        self.metadata_mode = metadata_mode;
+        self
+    }
+
     pub fn build(
         self,
     ) -> Result<UpdateAccountAction, UpdateAccountActionBuildError> {
@@ -2505,21 +2984,15 @@ impl UpdateAccountActionBuilder {
             }
         };
 
-        let metadata = {
-            if self.metadata.is_empty() {
-                return Err(UpdateAccountActionBuildError::MissingField(
-                    "'metadata' field is required".to_string(),
-                ));
-            } else {
-                self.metadata
-            }
-        };
+        let metadata = self.metadata;
+        let metadata_mode = self.metadata_mode;
 
         Ok(UpdateAccountAction {
             org_id,
             public_key,
             roles,
             metadata,
+            metadata_mode,
         })
     }
 }
@@ -2530,6 +3003,7 @@ pub struct CreateOrganizationAction {
     id: String,
     name: String,
     address: String,
+    metadata: Vec<KeyValueEntry>,
 }
 
 impl CreateOrganizationAction {
@@ -2544,6 +3018,10 @@ impl CreateOrganizationAction {
     pub fn get_address(&self) -> &str {
         &self.address
     }
+
+    pub fn get_metadata(&self) -> &[KeyValueEntry] {
+        &self.metadata
+    }
 }
 
 impl FromProto<protos::payload::CreateOrganizationAction> for CreateOrganizationAction {
@@ -2554,6 +3032,12 @@ impl FromProto<protos::payload::CreateOrganizationAction> for CreateOrganization
             id: proto.get_id().to_string(),
             name: proto.get_name().to_string(),
             address: proto.get_address().to_string(),
+            metadata: proto
+                .get_metadata()
+                .to_vec()
+                .into_iter()
+                .map(KeyValueEntry::from_proto)
+                .collect::<Result<Vec<KeyValueEntry>, ProtoConversionError>>()?,
         })
     }
 }
@@ -2566,9 +3050,17 @@ impl FromNative<CreateOrganizationAction> for protos::payload::CreateOrganizatio
         proto.set_id(create_organization_action.get_id().to_string());
         proto.set_name(create_organization_action.get_name().to_string());
         proto.set_address(create_organization_action.get_address().to_string());
-        Ok(proto)
-    }
-}
+        proto.set_metadata(RepeatedField::from_vec(
+            create_organization_action
+                .get_metadata()
+                .to_vec()
+                .into_iter()
+                .map(KeyValueEntry::into_proto)
+                .collect::<Result<Vec<protos::account::KeyValueEntry>, ProtoConversionError>>()?,
+        ));
+        Ok(proto)
+    }
+}
 
 impl FromBytes<CreateOrganizationAction> for CreateOrganizationAction {
     fn from_bytes(bytes: &[u8]) -> Result<CreateOrganizationAction, ProtoConversionError> {
@@ -2626,6 +3118,7 @@ pub struct CreateOrganizationActionBuilder {
     id: Option<String>,
     name: Option<String>,
     address: Option<String>,
+    metadata: Vec<KeyValueEntry>,
 }
 
 impl CreateOrganizationActionBuilder {
@@ -2648,6 +3141,11 @@ impl CreateOrganizationActionBuilder {
         self
     }
 
+    pub fn set_metadata(mut self, metadata: Vec<KeyValueEntry>) -> CreateOrganizationActionBuilder {
+        self.metadata = metadata;
+        self
+    }
+
     pub fn build(
         self,
     ) -> Result<CreateOrganizationAction, CreateOrganizationActionBuildError> {
@@ -2669,10 +3167,13 @@ impl CreateOrganizationActionBuilder {
             )
         })?;
 
+        let metadata = self.metadata;
+
         Ok(CreateOrganizationAction {
             id,
             name,
             address,
+            metadata,
         })
     }
 }
@@ -2683,6 +3184,8 @@ pub struct UpdateOrganizationAction {
     id: String,
     name: String,
     address: String,
+    metadata: Vec<KeyValueEntry>,
+    metadata_mode: MetadataUpdateMode,
 }
 
 impl UpdateOrganizationAction {
@@ -2694,139 +3197,1659 @@ impl UpdateOrganizationAction {
         &self.name
     }
 
-    pub fn get_address(&self) -> &str {
-        &self.address
+    pub fn get_address(&self) -> &str {
+        &self.address
+    }
+
+    pub fn get_metadata(&self) -> &[KeyValueEntry] {
+        &self.metadata
+    }
+
+    pub fn get_metadata_mode(&self) -> MetadataUpdateMode {
+        self.metadata_mode
+    }
+}
+
+impl FromProto<protos::payload::UpdateOrganizationAction> for UpdateOrganizationAction {
+    fn from_proto(
+        proto: protos::payload::UpdateOrganizationAction,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(UpdateOrganizationAction {
+            id: proto.get_id().to_string(),
+            name: proto.get_name().to_string(),
+            address: proto.get_address().to_string(),
+            metadata: proto
+                .get_metadata()
+                .to_vec()
+                .into_iter()
+                .map(KeyValueEntry::from_proto)
+                .collect::<Result<Vec<KeyValueEntry>, ProtoConversionError>>()?,
+            metadata_mode: MetadataUpdateMode::from_proto(proto.get_metadata_mode())?,
+        })
+    }
+}
+
+impl FromNative<UpdateOrganizationAction> for protos::payload::UpdateOrganizationAction {
+    fn from_native(
+        update_organization_action: UpdateOrganizationAction,
+    ) -> Result<Self, ProtoConversionError> {
+        let mut proto = protos::payload::UpdateOrganizationAction::new();
+        proto.set_id(update_organization_action.get_id().to_string());
+        proto.set_name(update_organization_action.get_name().to_string());
+        proto.set_address(update_organization_action.get_address().to_string());
+        proto.set_metadata(RepeatedField::from_vec(
+            update_organization_action
+                .get_metadata()
+                .to_vec()
+                .into_iter()
+                .map(KeyValueEntry::into_proto)
+                .collect::<Result<Vec<protos::account::KeyValueEntry>, ProtoConversionError>>()?,
+        ));
+        proto.set_metadata_mode(
+            update_organization_action
+                .get_metadata_mode()
+                .into_proto()?,
+        );
+        Ok(proto)
+    }
+}
+
+impl FromBytes<UpdateOrganizationAction> for UpdateOrganizationAction {
+    fn from_bytes(bytes: &[u8]) -> Result<UpdateOrganizationAction, ProtoConversionError> {
+        let proto: protos::payload::UpdateOrganizationAction = protobuf::parse_from_bytes(bytes)
+            .map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get UpdateOrganizationAction from bytes".to_string(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+
+impl IntoBytes for UpdateOrganizationAction {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get bytes from UpdateOrganizationAction".to_string(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+
+impl IntoProto<protos::payload::UpdateOrganizationAction> for UpdateOrganizationAction {}
+impl IntoNative<UpdateOrganizationAction> for protos::payload::UpdateOrganizationAction {}
+
+#[derive(Debug)]
+pub enum UpdateOrganizationActionBuildError {
+    MissingField(String),
+}
+
+impl StdError for UpdateOrganizationActionBuildError {
+    fn description(&self) -> &str {
+        match *self {
+            UpdateOrganizationActionBuildError::MissingField(ref msg) => msg,
+        }
+    }
+}
+
+impl std::fmt::Display for UpdateOrganizationActionBuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            UpdateOrganizationActionBuildError::MissingField(ref s) => {
+                write!(f, "MissingField: {}", s)
+            }
+        }
+    }
+}
+
+/// Builder used to create UpdateOrganizationAction
+#[derive(Default, Clone)]
+pub struct UpdateOrganizationActionBuilder {
+    id: Option<String>,
+    name: Option<String>,
+    address: Option<String>,
+    metadata: Vec<KeyValueEntry>,
+    metadata_mode: MetadataUpdateMode,
+}
+
+impl UpdateOrganizationActionBuilder {
+    pub fn new() -> Self {
+        UpdateOrganizationActionBuilder::default()
+    }
+
+    pub fn set_id(mut self, id: String) -> UpdateOrganizationActionBuilder {
+        self.id = Some(id);
+        self
+    }
+
+    pub fn set_name(mut self, name: String) -> UpdateOrganizationActionBuilder {
+        self.name = Some(name);
+        self
+    }
+
+    pub fn set_address(mut self, address: String) -> UpdateOrganizationActionBuilder {
+        self.address = Some(address);
+        self
+    }
+
+    pub fn set_metadata(mut self, metadata: Vec<KeyValueEntry>) -> UpdateOrganizationActionBuilder {
+        self.metadata = metadata;
+        self
+    }
+
+    pub fn set_metadata_mode(
+        mut self,
+        metadata_mode: MetadataUpdateMode,
+    ) -> UpdateOrganizationActionBuilder {
+        self.metadata_mode = metadata_mode;
+        self
+    }
+
+    pub fn build(
+        self,
+    ) -> Result<UpdateOrganizationAction, UpdateOrganizationActionBuildError> {
+        let id = self.id.ok_or_else(|| {
+            UpdateOrganizationActionBuildError::MissingField(
+                "'id' field is required".to_string(),
+            )
+        })?;
+
+        let name = self.name.ok_or_else(|| {
+            UpdateOrganizationActionBuildError::MissingField(
+                "'name' field is required".to_string(),
+            )
+        })?;
+
+        let address = self.address.ok_or_else(|| {
+            UpdateOrganizationActionBuildError::MissingField(
+                "'address' field is required".to_string(),
+            )
+        })?;
+
+        let metadata = self.metadata;
+        let metadata_mode = self.metadata_mode;
+
+        Ok(UpdateOrganizationAction {
+            id,
+            name,
+            address,
+            metadata,
+            metadata_mode,
+        })
+    }
+}
+
+/// Native implementation for DeactivateAccountAction
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct DeactivateAccountAction {
+    org_id: String,
+    public_key: String,
+}
+
+impl DeactivateAccountAction {
+    pub fn get_org_id(&self) -> &str {
+        &self.org_id
+    }
+
+    pub fn get_public_key(&self) -> &str {
+        &self.public_key
+    }
+}
+
+impl FromProto<protos::payload::DeactivateAccountAction> for DeactivateAccountAction {
+    fn from_proto(
+        proto: protos::payload::DeactivateAccountAction,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(DeactivateAccountAction {
+            org_id: proto.get_org_id().to_string(),
+            public_key: proto.get_public_key().to_string(),
+        })
+    }
+}
+
+impl FromNative<DeactivateAccountAction> for protos::payload::DeactivateAccountAction {
+    fn from_native(
+        deactivate_account_action: DeactivateAccountAction,
+    ) -> Result<Self, ProtoConversionError> {
+        let mut proto = protos::payload::DeactivateAccountAction::new();
+        proto.set_org_id(deactivate_account_action.get_org_id().to_string());
+        proto.set_public_key(deactivate_account_action.get_public_key().to_string());
+        Ok(proto)
+    }
+}
+
+impl FromBytes<DeactivateAccountAction> for DeactivateAccountAction {
+    fn from_bytes(bytes: &[u8]) -> Result<DeactivateAccountAction, ProtoConversionError> {
+        let proto: protos::payload::DeactivateAccountAction = protobuf::parse_from_bytes(bytes)
+            .map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get DeactivateAccountAction from bytes".to_string(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+
+impl IntoBytes for DeactivateAccountAction {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get bytes from DeactivateAccountAction".to_string(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+
+impl IntoProto<protos::payload::DeactivateAccountAction> for DeactivateAccountAction {}
+impl IntoNative<DeactivateAccountAction> for protos::payload::DeactivateAccountAction {}
+
+#[derive(Debug)]
+pub enum DeactivateAccountActionBuildError {
+    MissingField(String),
+}
+
+impl StdError for DeactivateAccountActionBuildError {
+    fn description(&self) -> &str {
+        match *self {
+            DeactivateAccountActionBuildError::MissingField(ref msg) => msg,
+        }
+    }
+}
+
+impl std::fmt::Display for DeactivateAccountActionBuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            DeactivateAccountActionBuildError::MissingField(ref s) => {
+                write!(f, "MissingField: {}", s)
+            }
+        }
+    }
+}
+
+/// Builder used to create DeactivateAccountAction
+#[derive(Default, Clone)]
+pub struct DeactivateAccountActionBuilder {
+    org_id: Option<String>,
+    public_key: Option<String>,
+}
+
+impl DeactivateAccountActionBuilder {
+    pub fn new() -> Self {
+        DeactivateAccountActionBuilder::default()
+    }
+
+    pub fn set_org_id(mut self, org_id: String) -> DeactivateAccountActionBuilder {
+        self.org_id = Some(org_id);
+        self
+    }
+
+    pub fn set_public_key(mut self, public_key: String) -> DeactivateAccountActionBuilder {
+        self.public_key = Some(public_key);
+        self
+    }
+
+    pub fn build(self) -> Result<DeactivateAccountAction, DeactivateAccountActionBuildError> {
+        let org_id = self.org_id.ok_or_else(|| {
+            DeactivateAccountActionBuildError::MissingField(
+                "'org_id' field is required".to_string(),
+            )
+        })?;
+
+        let public_key = self.public_key.ok_or_else(|| {
+            DeactivateAccountActionBuildError::MissingField(
+                "'public_key' field is required".to_string(),
+            )
+        })?;
+
+        Ok(DeactivateAccountAction { org_id, public_key })
+    }
+}
+
+/// Native implementation for ReactivateAccountAction
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct ReactivateAccountAction {
+    org_id: String,
+    public_key: String,
+}
+
+impl ReactivateAccountAction {
+    pub fn get_org_id(&self) -> &str {
+        &self.org_id
+    }
+
+    pub fn get_public_key(&self) -> &str {
+        &self.public_key
+    }
+}
+
+impl FromProto<protos::payload::ReactivateAccountAction> for ReactivateAccountAction {
+    fn from_proto(
+        proto: protos::payload::ReactivateAccountAction,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(ReactivateAccountAction {
+            org_id: proto.get_org_id().to_string(),
+            public_key: proto.get_public_key().to_string(),
+        })
+    }
+}
+
+impl FromNative<ReactivateAccountAction> for protos::payload::ReactivateAccountAction {
+    fn from_native(
+        reactivate_account_action: ReactivateAccountAction,
+    ) -> Result<Self, ProtoConversionError> {
+        let mut proto = protos::payload::ReactivateAccountAction::new();
+        proto.set_org_id(reactivate_account_action.get_org_id().to_string());
+        proto.set_public_key(reactivate_account_action.get_public_key().to_string());
+        Ok(proto)
+    }
+}
+
+impl FromBytes<ReactivateAccountAction> for ReactivateAccountAction {
+    fn from_bytes(bytes: &[u8]) -> Result<ReactivateAccountAction, ProtoConversionError> {
+        let proto: protos::payload::ReactivateAccountAction = protobuf::parse_from_bytes(bytes)
+            .map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get ReactivateAccountAction from bytes".to_string(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+
+impl IntoBytes for ReactivateAccountAction {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get bytes from ReactivateAccountAction".to_string(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+
+impl IntoProto<protos::payload::ReactivateAccountAction> for ReactivateAccountAction {}
+impl IntoNative<ReactivateAccountAction> for protos::payload::ReactivateAccountAction {}
+
+#[derive(Debug)]
+pub enum ReactivateAccountActionBuildError {
+    MissingField(String),
+}
+
+impl StdError for ReactivateAccountActionBuildError {
+    fn description(&self) -> &str {
+        match *self {
+            ReactivateAccountActionBuildError::MissingField(ref msg) => msg,
+        }
+    }
+}
+
+impl std::fmt::Display for ReactivateAccountActionBuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            ReactivateAccountActionBuildError::MissingField(ref s) => {
+                write!(f, "MissingField: {}", s)
+            }
+        }
+    }
+}
+
+/// Builder used to create ReactivateAccountAction
+#[derive(Default, Clone)]
+pub struct ReactivateAccountActionBuilder {
+    org_id: Option<String>,
+    public_key: Option<String>,
+}
+
+impl ReactivateAccountActionBuilder {
+    pub fn new() -> Self {
+        ReactivateAccountActionBuilder::default()
+    }
+
+    pub fn set_org_id(mut self, org_id: String) -> ReactivateAccountActionBuilder {
+        self.org_id = Some(org_id);
+        self
+    }
+
+    pub fn set_public_key(mut self, public_key: String) -> ReactivateAccountActionBuilder {
+        self.public_key = Some(public_key);
+        self
+    }
+
+    pub fn build(self) -> Result<ReactivateAccountAction, ReactivateAccountActionBuildError> {
+        let org_id = self.org_id.ok_or_else(|| {
+            ReactivateAccountActionBuildError::MissingField(
+                "'org_id' field is required".to_string(),
+            )
+        })?;
+
+        let public_key = self.public_key.ok_or_else(|| {
+            ReactivateAccountActionBuildError::MissingField(
+                "'public_key' field is required".to_string(),
+            )
+        })?;
+
+        Ok(ReactivateAccountAction { org_id, public_key })
+    }
+}
+
+/// Native implementation for GrantRoleAction
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct GrantRoleAction {
+    org_id: String,
+    public_key: String,
+    role: String,
+}
+
+impl GrantRoleAction {
+    pub fn get_org_id(&self) -> &str {
+        &self.org_id
+    }
+
+    pub fn get_public_key(&self) -> &str {
+        &self.public_key
+    }
+
+    pub fn get_role(&self) -> &str {
+        &self.role
+    }
+}
+
+impl FromProto<protos::payload::GrantRoleAction> for GrantRoleAction {
+    fn from_proto(proto: protos::payload::GrantRoleAction) -> Result<Self, ProtoConversionError> {
+        Ok(GrantRoleAction {
+            org_id: proto.get_org_id().to_string(),
+            public_key: proto.get_public_key().to_string(),
+            role: proto.get_role().to_string(),
+        })
+    }
+}
+
+impl FromNative<GrantRoleAction> for protos::payload::GrantRoleAction {
+    fn from_native(grant_role_action: GrantRoleAction) -> Result<Self, ProtoConversionError> {
+        let mut proto = protos::payload::GrantRoleAction::new();
+        proto.set_org_id(grant_role_action.get_org_id().to_string());
+        proto.set_public_key(grant_role_action.get_public_key().to_string());
+        proto.set_role(grant_role_action.get_role().to_string());
+        Ok(proto)
+    }
+}
+
+impl FromBytes<GrantRoleAction> for GrantRoleAction {
+    fn from_bytes(bytes: &[u8]) -> Result<GrantRoleAction, ProtoConversionError> {
+        let proto: protos::payload::GrantRoleAction =
+            protobuf::parse_from_bytes(bytes).map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get GrantRoleAction from bytes".to_string(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+
+impl IntoBytes for GrantRoleAction {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get bytes from GrantRoleAction".to_string(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+
+impl IntoProto<protos::payload::GrantRoleAction> for GrantRoleAction {}
+impl IntoNative<GrantRoleAction> for protos::payload::GrantRoleAction {}
+
+#[derive(Debug)]
+pub enum GrantRoleActionBuildError {
+    MissingField(String),
+}
+
+impl StdError for GrantRoleActionBuildError {
+    fn description(&self) -> &str {
+        match *self {
+            GrantRoleActionBuildError::MissingField(ref msg) => msg,
+        }
+    }
+}
+
+impl std::fmt::Display for GrantRoleActionBuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            GrantRoleActionBuildError::MissingField(ref s) => write!(f, "MissingField: {}", s),
+        }
+    }
+}
+
+/// Builder used to create GrantRoleAction
+#[derive(Default, Clone)]
+pub struct GrantRoleActionBuilder {
+    org_id: Option<String>,
+    public_key: Option<String>,
+    role: Option<String>,
+}
+
+impl GrantRoleActionBuilder {
+    pub fn new() -> Self {
+        GrantRoleActionBuilder::default()
+    }
+
+    pub fn set_org_id(mut self, org_id: String) -> GrantRoleActionBuilder {
+        self.org_id = Some(org_id);
+        self
+    }
+
+    pub fn set_public_key(mut self, public_key: String) -> GrantRoleActionBuilder {
+        self.public_key = Some(public_key);
+        self
+    }
+
+    pub fn set_role(mut self, role: String) -> GrantRoleActionBuilder {
+        self.role = Some(role);
+        self
+    }
+
+    pub fn build(self) -> Result<GrantRoleAction, GrantRoleActionBuildError> {
+        let org_id = self.org_id.ok_or_else(|| {
+            GrantRoleActionBuildError::MissingField("'org_id' field is required".to_string())
+        })?;
+
+        let public_key = self.public_key.ok_or_else(|| {
+            GrantRoleActionBuildError::MissingField("'public_key' field is required".to_string())
+        })?;
+
+        let role = self.role.ok_or_else(|| {
+            GrantRoleActionBuildError::MissingField("'role' field is required".to_string())
+        })?;
+
+        Ok(GrantRoleAction {
+            org_id,
+            public_key,
+            role,
+        })
+    }
+}
+
+/// Native implementation for RevokeRoleAction
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct RevokeRoleAction {
+    org_id: String,
+    public_key: String,
+    role: String,
+}
+
+impl RevokeRoleAction {
+    pub fn get_org_id(&self) -> &str {
+        &self.org_id
+    }
+
+    pub fn get_public_key(&self) -> &str {
+        &self.public_key
+    }
+
+    pub fn get_role(&self) -> &str {
+        &self.role
+    }
+}
+
+impl FromProto<protos::payload::RevokeRoleAction> for RevokeRoleAction {
+    fn from_proto(proto: protos::payload::RevokeRoleAction) -> Result<Self, ProtoConversionError> {
+        Ok(RevokeRoleAction {
+            org_id: proto.get_org_id().to_string(),
+            public_key: proto.get_public_key().to_string(),
+            role: proto.get_role().to_string(),
+        })
+    }
+}
+
+impl FromNative<RevokeRoleAction> for protos::payload::RevokeRoleAction {
+    fn from_native(revoke_role_action: RevokeRoleAction) -> Result<Self, ProtoConversionError> {
+        let mut proto = protos::payload::RevokeRoleAction::new();
+        proto.set_org_id(revoke_role_action.get_org_id().to_string());
+        proto.set_public_key(revoke_role_action.get_public_key().to_string());
+        proto.set_role(revoke_role_action.get_role().to_string());
+        Ok(proto)
+    }
+}
+
+impl FromBytes<RevokeRoleAction> for RevokeRoleAction {
+    fn from_bytes(bytes: &[u8]) -> Result<RevokeRoleAction, ProtoConversionError> {
+        let proto: protos::payload::RevokeRoleAction =
+            protobuf::parse_from_bytes(bytes).map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get RevokeRoleAction from bytes".to_string(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+
+impl IntoBytes for RevokeRoleAction {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get bytes from RevokeRoleAction".to_string(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+
+impl IntoProto<protos::payload::RevokeRoleAction> for RevokeRoleAction {}
+impl IntoNative<RevokeRoleAction> for protos::payload::RevokeRoleAction {}
+
+#[derive(Debug)]
+pub enum RevokeRoleActionBuildError {
+    MissingField(String),
+}
+
+impl StdError for RevokeRoleActionBuildError {
+    fn description(&self) -> &str {
+        match *self {
+            RevokeRoleActionBuildError::MissingField(ref msg) => msg,
+        }
+    }
+}
+
+impl std::fmt::Display for RevokeRoleActionBuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            RevokeRoleActionBuildError::MissingField(ref s) => write!(f, "MissingField: {}", s),
+        }
+    }
+}
+
+/// Builder used to create RevokeRoleAction
+#[derive(Default, Clone)]
+pub struct RevokeRoleActionBuilder {
+    org_id: Option<String>,
+    public_key: Option<String>,
+    role: Option<String>,
+}
+
+impl RevokeRoleActionBuilder {
+    pub fn new() -> Self {
+        RevokeRoleActionBuilder::default()
+    }
+
+    pub fn set_org_id(mut self, org_id: String) -> RevokeRoleActionBuilder {
+        self.org_id = Some(org_id);
+        self
+    }
+
+    pub fn set_public_key(mut self, public_key: String) -> RevokeRoleActionBuilder {
+        self.public_key = Some(public_key);
+        self
+    }
+
+    pub fn set_role(mut self, role: String) -> RevokeRoleActionBuilder {
+        self.role = Some(role);
+        self
+    }
+
+    pub fn build(self) -> Result<RevokeRoleAction, RevokeRoleActionBuildError> {
+        let org_id = self.org_id.ok_or_else(|| {
+            RevokeRoleActionBuildError::MissingField("'org_id' field is required".to_string())
+        })?;
+
+        let public_key = self.public_key.ok_or_else(|| {
+            RevokeRoleActionBuildError::MissingField("'public_key' field is required".to_string())
+        })?;
+
+        let role = self.role.ok_or_else(|| {
+            RevokeRoleActionBuildError::MissingField("'role' field is required".to_string())
+        })?;
+
+        Ok(RevokeRoleAction {
+            org_id,
+            public_key,
+            role,
+        })
+    }
+}
+
+/// Native implementation for CreateRoleAction
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct CreateRoleAction {
+    org_id: String,
+    name: String,
+    permissions: Vec<String>,
+}
+
+impl CreateRoleAction {
+    pub fn get_org_id(&self) -> &str {
+        &self.org_id
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_permissions(&self) -> &[String] {
+        &self.permissions
+    }
+}
+
+impl FromProto<protos::payload::CreateRoleAction> for CreateRoleAction {
+    fn from_proto(proto: protos::payload::CreateRoleAction) -> Result<Self, ProtoConversionError> {
+        Ok(CreateRoleAction {
+            org_id: proto.get_org_id().to_string(),
+            name: proto.get_name().to_string(),
+            permissions: proto.get_permissions().to_vec(),
+        })
+    }
+}
+
+impl FromNative<CreateRoleAction> for protos::payload::CreateRoleAction {
+    fn from_native(create_role_action: CreateRoleAction) -> Result<Self, ProtoConversionError> {
+        let mut proto = protos::payload::CreateRoleAction::new();
+        proto.set_org_id(create_role_action.get_org_id().to_string());
+        proto.set_name(create_role_action.get_name().to_string());
+        proto.set_permissions(RepeatedField::from_vec(
+            create_role_action.get_permissions().to_vec(),
+        ));
+        Ok(proto)
+    }
+}
+
+impl FromBytes<CreateRoleAction> for CreateRoleAction {
+    fn from_bytes(bytes: &[u8]) -> Result<CreateRoleAction, ProtoConversionError> {
+        let proto: protos::payload::CreateRoleAction =
+            protobuf::parse_from_bytes(bytes).map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get CreateRoleAction from bytes".to_string(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+
+impl IntoBytes for CreateRoleAction {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get bytes from CreateRoleAction".to_string(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+
+impl IntoProto<protos::payload::CreateRoleAction> for CreateRoleAction {}
+impl IntoNative<CreateRoleAction> for protos::payload::CreateRoleAction {}
+
+#[derive(Debug)]
+pub enum CreateRoleActionBuildError {
+    MissingField(String),
+}
+
+impl StdError for CreateRoleActionBuildError {
+    fn description(&self) -> &str {
+        match *self {
+            CreateRoleActionBuildError::MissingField(ref msg) => msg,
+        }
+    }
+}
+
+impl std::fmt::Display for CreateRoleActionBuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            CreateRoleActionBuildError::MissingField(ref s) => write!(f, "MissingField: {}", s),
+        }
+    }
+}
+
+/// Builder used to create CreateRoleAction
+#[derive(Default, Clone)]
+pub struct CreateRoleActionBuilder {
+    org_id: Option<String>,
+    name: Option<String>,
+    permissions: Vec<String>,
+}
+
+impl CreateRoleActionBuilder {
+    pub fn new() -> Self {
+        CreateRoleActionBuilder::default()
+    }
+
+    pub fn set_org_id(mut self, org_id: String) -> CreateRoleActionBuilder {
+        self.org_id = Some(org_id);
+        self
+    }
+
+    pub fn set_name(mut self, name: String) -> CreateRoleActionBuilder {
+        self.name = Some(name);
+        self
+    }
+
+    pub fn set_permissions(mut self, permissions: Vec<String>) -> CreateRoleActionBuilder {
+        self.permissions = permissions;
+        self
+    }
+
+    pub fn build(self) -> Result<CreateRoleAction, CreateRoleActionBuildError> {
+        let org_id = self.org_id.ok_or_else(|| {
+            CreateRoleActionBuildError::MissingField("'org_id' field is required".to_string())
+        })?;
+
+        let name = self.name.ok_or_else(|| {
+            CreateRoleActionBuildError::MissingField("'name' field is required".to_string())
+        })?;
+
+        let permissions = self.permissions;
+
+        Ok(CreateRoleAction {
+            org_id,
+            name,
+            permissions,
+        })
+    }
+}
+
+/// Native implementation for UpdateRoleAction
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct UpdateRoleAction {
+    org_id: String,
+    name: String,
+    permissions: Vec<String>,
+}
+
+impl UpdateRoleAction {
+    pub fn get_org_id(&self) -> &str {
+        &self.org_id
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_permissions(&self) -> &[String] {
+        &self.permissions
+    }
+}
+
+impl FromProto<protos::payload::UpdateRoleAction> for UpdateRoleAction {
+    fn from_proto(proto: protos::payload::UpdateRoleAction) -> Result<Self, ProtoConversionError> {
+        Ok(UpdateRoleAction {
+            org_id: proto.get_org_id().to_string(),
+            name: proto.get_name().to_string(),
+            permissions: proto.get_permissions().to_vec(),
+        })
+    }
+}
+
+impl FromNative<UpdateRoleAction> for protos::payload::UpdateRoleAction {
+    fn from_native(update_role_action: UpdateRoleAction) -> Result<Self, ProtoConversionError> {
+        let mut proto = protos::payload::UpdateRoleAction::new();
+        proto.set_org_id(update_role_action.get_org_id().to_string());
+        proto.set_name(update_role_action.get_name().to_string());
+        proto.set_permissions(RepeatedField::from_vec(
+            update_role_action.get_permissions().to_vec(),
+        ));
+        Ok(proto)
+    }
+}
+
+impl FromBytes<UpdateRoleAction> for UpdateRoleAction {
+    fn from_bytes(bytes: &[u8]) -> Result<UpdateRoleAction, ProtoConversionError> {
+        let proto: protos::payload::UpdateRoleAction =
+            protobuf::parse_from_bytes(bytes).map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get UpdateRoleAction from bytes".to_string(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+
+impl IntoBytes for UpdateRoleAction {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get bytes from UpdateRoleAction".to_string(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+
+impl IntoProto<protos::payload::UpdateRoleAction> for UpdateRoleAction {}
+impl IntoNative<UpdateRoleAction> for protos::payload::UpdateRoleAction {}
+
+#[derive(Debug)]
+pub enum UpdateRoleActionBuildError {
+    MissingField(String),
+}
+
+impl StdError for UpdateRoleActionBuildError {
+    fn description(&self) -> &str {
+        match *self {
+            UpdateRoleActionBuildError::MissingField(ref msg) => msg,
+        }
+    }
+}
+
+impl std::fmt::Display for UpdateRoleActionBuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            UpdateRoleActionBuildError::MissingField(ref s) => write!(f, "MissingField: {}", s),
+        }
+    }
+}
+
+/// Builder used to create UpdateRoleAction
+#[derive(Default, Clone)]
+pub struct UpdateRoleActionBuilder {
+    org_id: Option<String>,
+    name: Option<String>,
+    permissions: Vec<String>,
+}
+
+impl UpdateRoleActionBuilder {
+    pub fn new() -> Self {
+        UpdateRoleActionBuilder::default()
+    }
+
+    pub fn set_org_id(mut self, org_id: String) -> UpdateRoleActionBuilder {
+        self.org_id = Some(org_id);
+        self
+    }
+
+    pub fn set_name(mut self, name: String) -> UpdateRoleActionBuilder {
+        self.name = Some(name);
+        self
+    }
+
+    pub fn set_permissions(mut self, permissions: Vec<String>) -> UpdateRoleActionBuilder {
+        self.permissions = permissions;
+        self
+    }
+
+    pub fn build(self) -> Result<UpdateRoleAction, UpdateRoleActionBuildError> {
+        let org_id = self.org_id.ok_or_else(|| {
+            UpdateRoleActionBuildError::MissingField("'org_id' field is required".to_string())
+        })?;
+
+        let name = self.name.ok_or_else(|| {
+            UpdateRoleActionBuildError::MissingField("'name' field is required".to_string())
+        })?;
+
+        let permissions = self.permissions;
+
+        Ok(UpdateRoleAction {
+            org_id,
+            name,
+            permissions,
+        })
+    }
+}
+
+/// Native implementation for DeleteRoleAction
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct DeleteRoleAction {
+    org_id: String,
+    name: String,
+}
+
+impl DeleteRoleAction {
+    pub fn get_org_id(&self) -> &str {
+        &self.org_id
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl FromProto<protos::payload::DeleteRoleAction> for DeleteRoleAction {
+    fn from_proto(proto: protos::payload::DeleteRoleAction) -> Result<Self, ProtoConversionError> {
+        Ok(DeleteRoleAction {
+            org_id: proto.get_org_id().to_string(),
+            name: proto.get_name().to_string(),
+        })
+    }
+}
+
+impl FromNative<DeleteRoleAction> for protos::payload::DeleteRoleAction {
+    fn from_native(delete_role_action: DeleteRoleAction) -> Result<Self, ProtoConversionError> {
+        let mut proto = protos::payload::DeleteRoleAction::new();
+        proto.set_org_id(delete_role_action.get_org_id().to_string());
+        proto.set_name(delete_role_action.get_name().to_string());
+        Ok(proto)
+    }
+}
+
+impl FromBytes<DeleteRoleAction> for DeleteRoleAction {
+    fn from_bytes(bytes: &[u8]) -> Result<DeleteRoleAction, ProtoConversionError> {
+        let proto: protos::payload::DeleteRoleAction =
+            protobuf::parse_from_bytes(bytes).map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get DeleteRoleAction from bytes".to_string(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+
+impl IntoBytes for DeleteRoleAction {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get bytes from DeleteRoleAction".to_string(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+
+impl IntoProto<protos::payload::DeleteRoleAction> for DeleteRoleAction {}
+impl IntoNative<DeleteRoleAction> for protos::payload::DeleteRoleAction {}
+
+#[derive(Debug)]
+pub enum DeleteRoleActionBuildError {
+    MissingField(String),
+}
+
+impl StdError for DeleteRoleActionBuildError {
+    fn description(&self) -> &str {
+        match *self {
+            DeleteRoleActionBuildError::MissingField(ref msg) => msg,
+        }
+    }
+}
+
+impl std::fmt::Display for DeleteRoleActionBuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            DeleteRoleActionBuildError::MissingField(ref s) => write!(f, "MissingField: {}", s),
+        }
+    }
+}
+
+/// Builder used to create DeleteRoleAction
+#[derive(Default, Clone)]
+pub struct DeleteRoleActionBuilder {
+    org_id: Option<String>,
+    name: Option<String>,
+}
+
+impl DeleteRoleActionBuilder {
+    pub fn new() -> Self {
+        DeleteRoleActionBuilder::default()
+    }
+
+    pub fn set_org_id(mut self, org_id: String) -> DeleteRoleActionBuilder {
+        self.org_id = Some(org_id);
+        self
+    }
+
+    pub fn set_name(mut self, name: String) -> DeleteRoleActionBuilder {
+        self.name = Some(name);
+        self
+    }
+
+    pub fn build(self) -> Result<DeleteRoleAction, DeleteRoleActionBuildError> {
+        let org_id = self.org_id.ok_or_else(|| {
+            DeleteRoleActionBuildError::MissingField("'org_id' field is required".to_string())
+        })?;
+
+        let name = self.name.ok_or_else(|| {
+            DeleteRoleActionBuildError::MissingField("'name' field is required".to_string())
+        })?;
+
+        Ok(DeleteRoleAction { org_id, name })
+    }
+}
+
+/// Native implementation for RegistryType
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RegistryType {
+    ContractRegistry,
+    NamespaceRegistry,
+}
+
+impl std::fmt::Display for RegistryType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            RegistryType::ContractRegistry => write!(f, "contract registry"),
+            RegistryType::NamespaceRegistry => write!(f, "namespace registry"),
+        }
+    }
+}
+
+impl FromProto<protos::payload::RegistryType> for RegistryType {
+    fn from_proto(
+        registry_type: protos::payload::RegistryType,
+    ) -> Result<Self, ProtoConversionError> {
+        match registry_type {
+            protos::payload::RegistryType::CONTRACT_REGISTRY => Ok(RegistryType::ContractRegistry),
+            protos::payload::RegistryType::NAMESPACE_REGISTRY => {
+                Ok(RegistryType::NamespaceRegistry)
+            }
+            protos::payload::RegistryType::REGISTRY_TYPE_UNSET => {
+                Err(ProtoConversionError::InvalidTypeError(
+                    "Cannot convert RegistryType with type unset.".to_string(),
+                ))
+            }
+        }
+    }
+}
+
+impl FromNative<RegistryType> for protos::payload::RegistryType {
+    fn from_native(registry_type: RegistryType) -> Result<Self, ProtoConversionError> {
+        match registry_type {
+            RegistryType::ContractRegistry => Ok(protos::payload::RegistryType::CONTRACT_REGISTRY),
+            RegistryType::NamespaceRegistry => {
+                Ok(protos::payload::RegistryType::NAMESPACE_REGISTRY)
+            }
+        }
+    }
+}
+
+impl IntoProto<protos::payload::RegistryType> for RegistryType {}
+impl IntoNative<RegistryType> for protos::payload::RegistryType {}
+
+/// Native implementation for ProposeOwnerChangeAction
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProposeOwnerChangeAction {
+    registry_type: RegistryType,
+    name: String,
+    owners: Vec<String>,
+    owner_threshold: u32,
+    expiry_blocks: u64,
+}
+
+impl ProposeOwnerChangeAction {
+    pub fn get_registry_type(&self) -> RegistryType {
+        self.registry_type
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_owners(&self) -> &[String] {
+        &self.owners
+    }
+
+    pub fn get_owner_threshold(&self) -> u32 {
+        self.owner_threshold
+    }
+
+    pub fn get_expiry_blocks(&self) -> u64 {
+        self.expiry_blocks
+    }
+}
+
+impl FromProto<protos::payload::ProposeOwnerChangeAction> for ProposeOwnerChangeAction {
+    fn from_proto(
+        proto: protos::payload::ProposeOwnerChangeAction,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(ProposeOwnerChangeAction {
+            registry_type: RegistryType::from_proto(proto.get_registry_type())?,
+            name: proto.get_name().to_string(),
+            owners: proto.get_owners().to_vec(),
+            owner_threshold: proto.get_owner_threshold(),
+            expiry_blocks: proto.get_expiry_blocks(),
+        })
+    }
+}
+
+impl FromNative<ProposeOwnerChangeAction> for protos::payload::ProposeOwnerChangeAction {
+    fn from_native(
+        propose_owner_change_action: ProposeOwnerChangeAction,
+    ) -> Result<Self, ProtoConversionError> {
+        let mut proto = protos::payload::ProposeOwnerChangeAction::new();
+        proto.set_registry_type(
+            propose_owner_change_action
+                .get_registry_type()
+                .into_proto()?,
+        );
+        proto.set_name(propose_owner_change_action.get_name().to_string());
+        proto.set_owners(RepeatedField::from_vec(
+            propose_owner_change_action.get_owners().to_vec(),
+        ));
+        proto.set_owner_threshold(propose_owner_change_action.get_owner_threshold());
+        proto.set_expiry_blocks(propose_owner_change_action.get_expiry_blocks());
+        Ok(proto)
+    }
+}
+
+impl FromBytes<ProposeOwnerChangeAction> for ProposeOwnerChangeAction {
+    fn from_bytes(bytes: &[u8]) -> Result<ProposeOwnerChangeAction, ProtoConversionError> {
+        let proto: protos::payload::ProposeOwnerChangeAction =
+            protobuf::parse_from_bytes(bytes).map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get ProposeOwnerChangeAction from bytes".to_string(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+
+impl IntoBytes for ProposeOwnerChangeAction {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get bytes from ProposeOwnerChangeAction".to_string(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+
+impl IntoProto<protos::payload::ProposeOwnerChangeAction> for ProposeOwnerChangeAction {}
+impl IntoNative<ProposeOwnerChangeAction> for protos::payload::ProposeOwnerChangeAction {}
+
+#[derive(Debug)]
+pub enum ProposeOwnerChangeActionBuildError {
+    MissingField(String),
+}
+
+impl StdError for ProposeOwnerChangeActionBuildError {
+    fn description(&self) -> &str {
+        match *self {
+            ProposeOwnerChangeActionBuildError::MissingField(ref msg) => msg,
+        }
+    }
+}
+
+impl std::fmt::Display for ProposeOwnerChangeActionBuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            ProposeOwnerChangeActionBuildError::MissingField(ref s) => {
+                write!(f, "MissingField: {}", s)
+            }
+        }
+    }
+}
+
+/// Builder used to create ProposeOwnerChangeAction
+#[derive(Default, Clone)]
+pub struct ProposeOwnerChangeActionBuilder {
+    registry_type: Option<RegistryType>,
+    name: Option<String>,
+    owners: Vec<String>,
+    owner_threshold: u32,
+    expiry_blocks: Option<u64>,
+}
+
+impl ProposeOwnerChangeActionBuilder {
+    pub fn new() -> Self {
+        ProposeOwnerChangeActionBuilder::default()
+    }
+
+    pub fn set_registry_type(
+        mut self,
+        registry_type: RegistryType,
+    ) -> ProposeOwnerChangeActionBuilder {
+        self.registry_type = Some(registry_type);
+        self
+    }
+
+    pub fn set_name(mut self, name: String) -> ProposeOwnerChangeActionBuilder {
+        self.name = Some(name);
+        self
+    }
+
+    pub fn set_owners(mut self, owners: Vec<String>) -> ProposeOwnerChangeActionBuilder {
+        self.owners = owners;
+        self
+    }
+
+    pub fn set_owner_threshold(mut self, owner_threshold: u32) -> ProposeOwnerChangeActionBuilder {
+        self.owner_threshold = owner_threshold;
+        self
+    }
+
+    pub fn set_expiry_blocks(mut self, expiry_blocks: u64) -> ProposeOwnerChangeActionBuilder {
+        self.expiry_blocks = Some(expiry_blocks);
+        self
+    }
+
+    pub fn build(self) -> Result<ProposeOwnerChangeAction, ProposeOwnerChangeActionBuildError> {
+        let registry_type = self.registry_type.ok_or_else(|| {
+            ProposeOwnerChangeActionBuildError::MissingField(
+                "'registry_type' field is required".to_string(),
+            )
+        })?;
+
+        let name = self.name.ok_or_else(|| {
+            ProposeOwnerChangeActionBuildError::MissingField(
+                "'name' field is required".to_string(),
+            )
+        })?;
+
+        if self.owners.is_empty() {
+            return Err(ProposeOwnerChangeActionBuildError::MissingField(
+                "'owners' field is required".to_string(),
+            ));
+        }
+
+        let expiry_blocks = self.expiry_blocks.ok_or_else(|| {
+            ProposeOwnerChangeActionBuildError::MissingField(
+                "'expiry_blocks' field is required".to_string(),
+            )
+        })?;
+
+        Ok(ProposeOwnerChangeAction {
+            registry_type,
+            name,
+            owners: self.owners,
+            owner_threshold: self.owner_threshold,
+            expiry_blocks,
+        })
+    }
+}
+
+/// Native implementation for VoteOwnerChangeAction
+#[derive(Debug, Clone, PartialEq)]
+pub struct VoteOwnerChangeAction {
+    registry_type: RegistryType,
+    name: String,
+    approve: bool,
+}
+
+impl VoteOwnerChangeAction {
+    pub fn get_registry_type(&self) -> RegistryType {
+        self.registry_type
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_approve(&self) -> bool {
+        self.approve
+    }
+}
+
+impl FromProto<protos::payload::VoteOwnerChangeAction> for VoteOwnerChangeAction {
+    fn from_proto(
+        proto: protos::payload::VoteOwnerChangeAction,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(VoteOwnerChangeAction {
+            registry_type: RegistryType::from_proto(proto.get_registry_type())?,
+            name: proto.get_name().to_string(),
+            approve: proto.get_approve(),
+        })
+    }
+}
+
+impl FromNative<VoteOwnerChangeAction> for protos::payload::VoteOwnerChangeAction {
+    fn from_native(
+        vote_owner_change_action: VoteOwnerChangeAction,
+    ) -> Result<Self, ProtoConversionError> {
+        let mut proto = protos::payload::VoteOwnerChangeAction::new();
+        proto.set_registry_type(vote_owner_change_action.get_registry_type().into_proto()?);
+        proto.set_name(vote_owner_change_action.get_name().to_string());
+        proto.set_approve(vote_owner_change_action.get_approve());
+        Ok(proto)
+    }
+}
+
+impl FromBytes<VoteOwnerChangeAction> for VoteOwnerChangeAction {
+    fn from_bytes(bytes: &[u8]) -> Result<VoteOwnerChangeAction, ProtoConversionError> {
+        let proto: protos::payload::VoteOwnerChangeAction =
+            protobuf::parse_from_bytes(bytes).map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get VoteOwnerChangeAction from bytes".to_string(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+
+impl IntoBytes for VoteOwnerChangeAction {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get bytes from VoteOwnerChangeAction".to_string(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+
+impl IntoProto<protos::payload::VoteOwnerChangeAction> for VoteOwnerChangeAction {}
+impl IntoNative<VoteOwnerChangeAction> for protos::payload::VoteOwnerChangeAction {}
+
+#[derive(Debug)]
+pub enum VoteOwnerChangeActionBuildError {
+    MissingField(String),
+}
+
+impl StdError for VoteOwnerChangeActionBuildError {
+    fn description(&self) -> &str {
+        match *self {
+            VoteOwnerChangeActionBuildError::MissingField(ref msg) => msg,
+        }
+    }
+}
+
+impl std::fmt::Display for VoteOwnerChangeActionBuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            VoteOwnerChangeActionBuildError::MissingField(ref s) => {
+                write!(f, "MissingField: {}", s)
+            }
+        }
+    }
+}
+
+/// Builder used to create VoteOwnerChangeAction
+#[derive(Default, Clone)]
+pub struct VoteOwnerChangeActionBuilder {
+    registry_type: Option<RegistryType>,
+    name: Option<String>,
+    approve: Option<bool>,
+}
+
+impl VoteOwnerChangeActionBuilder {
+    pub fn new() -> Self {
+        VoteOwnerChangeActionBuilder::default()
+    }
+
+    pub fn set_registry_type(
+        mut self,
+        registry_type: RegistryType,
+    ) -> VoteOwnerChangeActionBuilder {
+        self.registry_type = Some(registry_type);
+        self
+    }
+
+    pub fn set_name(mut self, name: String) -> VoteOwnerChangeActionBuilder {
+        self.name = Some(name);
+        self
+    }
+
+    pub fn set_approve(mut self, approve: bool) -> VoteOwnerChangeActionBuilder {
+        self.approve = Some(approve);
+        self
+    }
+
+    pub fn build(self) -> Result<VoteOwnerChangeAction, VoteOwnerChangeActionBuildError> {
+        let registry_type = self.registry_type.ok_or_else(|| {
+            VoteOwnerChangeActionBuildError::MissingField(
+                "'registry_type' field is required".to_string(),
+            )
+        })?;
+
+        let name = self.name.ok_or_else(|| {
+            VoteOwnerChangeActionBuildError::MissingField("'name' field is required".to_string())
+        })?;
+
+        let approve = self.approve.ok_or_else(|| {
+            VoteOwnerChangeActionBuildError::MissingField(
+                "'approve' field is required".to_string(),
+            )
+        })?;
+
+        Ok(VoteOwnerChangeAction {
+            registry_type,
+            name,
+            approve,
+        })
+    }
+}
+
+/// Native implementation for SetActionPolicyAction
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct SetActionPolicyAction {
+    org_id: String,
+    action: String,
+    smart_permission: String,
+}
+
+impl SetActionPolicyAction {
+    pub fn get_org_id(&self) -> &str {
+        &self.org_id
+    }
+
+    pub fn get_action(&self) -> &str {
+        &self.action
+    }
+
+    /// Returns the name of the smart permission to attach, empty to detach
+    pub fn get_smart_permission(&self) -> &str {
+        &self.smart_permission
     }
-
 }
 
-impl FromProto<protos::payload::UpdateOrganizationAction> for UpdateOrganizationAction {
+impl FromProto<protos::payload::SetActionPolicyAction> for SetActionPolicyAction {
     fn from_proto(
-        proto: protos::payload::UpdateOrganizationAction,
+        proto: protos::payload::SetActionPolicyAction,
     ) -> Result<Self, ProtoConversionError> {
-        Ok(UpdateOrganizationAction {
-            id: proto.get_id().to_string(),
-            name: proto.get_name().to_string(),
-            address: proto.get_address().to_string(),
+        Ok(SetActionPolicyAction {
+            org_id: proto.get_org_id().to_string(),
+            action: proto.get_action().to_string(),
+            smart_permission: proto.get_smart_permission().to_string(),
         })
     }
 }
 
-impl FromNative<UpdateOrganizationAction> for protos::payload::UpdateOrganizationAction {
+impl FromNative<SetActionPolicyAction> for protos::payload::SetActionPolicyAction {
     fn from_native(
-        update_organization_action: UpdateOrganizationAction,
+        set_action_policy_action: SetActionPolicyAction,
     ) -> Result<Self, ProtoConversionError> {
-        let mut proto = protos::payload::UpdateOrganizationAction::new();
-        proto.set_id(update_organization_action.get_id().to_string());
-        proto.set_name(update_organization_action.get_name().to_string());
-        proto.set_address(update_organization_action.get_address().to_string());
+        let mut proto = protos::payload::SetActionPolicyAction::new();
+        proto.set_org_id(set_action_policy_action.get_org_id().to_string());
+        proto.set_action(set_action_policy_action.get_action().to_string());
+        proto.set_smart_permission(set_action_policy_action.get_smart_permission().to_string());
         Ok(proto)
     }
 }
 
-impl FromBytes<UpdateOrganizationAction> for UpdateOrganizationAction {
-    fn from_bytes(bytes: &[u8]) -> Result<UpdateOrganizationAction, ProtoConversionError> {
-        let proto: protos::payload::UpdateOrganizationAction = protobuf::parse_from_bytes(bytes)
-            .map_err(|_| {
+impl FromBytes<SetActionPolicyAction> for SetActionPolicyAction {
+    fn from_bytes(bytes: &[u8]) -> Result<SetActionPolicyAction, ProtoConversionError> {
+        let proto: protos::payload::SetActionPolicyAction =
+            protobuf::parse_from_bytes(bytes).map_err(|_| {
                 ProtoConversionError::SerializationError(
-                    "Unable to get UpdateOrganizationAction from bytes".to_string(),
+                    "Unable to get SetActionPolicyAction from bytes".to_string(),
                 )
             })?;
         proto.into_native()
     }
 }
 
-impl IntoBytes for UpdateOrganizationAction {
+impl IntoBytes for SetActionPolicyAction {
     fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
         let proto = self.into_proto()?;
         let bytes = proto.write_to_bytes().map_err(|_| {
             ProtoConversionError::SerializationError(
-                "Unable to get bytes from UpdateOrganizationAction".to_string(),
+                "Unable to get bytes from SetActionPolicyAction".to_string(),
             )
         })?;
         Ok(bytes)
     }
 }
 
-impl IntoProto<protos::payload::UpdateOrganizationAction> for UpdateOrganizationAction {}
-impl IntoNative<UpdateOrganizationAction> for protos::payload::UpdateOrganizationAction {}
+impl IntoProto<protos::payload::SetActionPolicyAction> for SetActionPolicyAction {}
+impl IntoNative<SetActionPolicyAction> for protos::payload::SetActionPolicyAction {}
 
 #[derive(Debug)]
-pub enum UpdateOrganizationActionBuildError {
+pub enum SetActionPolicyActionBuildError {
     MissingField(String),
 }
 
-impl StdError for UpdateOrganizationActionBuildError {
+impl StdError for SetActionPolicyActionBuildError {
     fn description(&self) -> &str {
         match *self {
-            UpdateOrganizationActionBuildError::MissingField(ref msg) => msg,
+            SetActionPolicyActionBuildError::MissingField(ref msg) => msg,
         }
     }
 }
 
-impl std::fmt::Display for UpdateOrganizationActionBuildError {
+impl std::fmt::Display for SetActionPolicyActionBuildError {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match *self {
-            UpdateOrganizationActionBuildError::MissingField(ref s) => {
+            SetActionPolicyActionBuildError::MissingField(ref s) => {
                 write!(f, "MissingField: {}", s)
             }
         }
     }
 }
 
-/// Builder used to create UpdateOrganizationAction
+/// Builder used to create SetActionPolicyAction
+///
+/// Leaving the smart permission unset detaches the action's smart permission.
 #[derive(Default, Clone)]
-pub struct UpdateOrganizationActionBuilder {
-    id: Option<String>,
-    name: Option<String>,
-    address: Option<String>,
+pub struct SetActionPolicyActionBuilder {
+    org_id: Option<String>,
+    action: Option<String>,
+    smart_permission: Option<String>,
 }
 
-impl UpdateOrganizationActionBuilder {
+impl SetActionPolicyActionBuilder {
     pub fn new() -> Self {
-        UpdateOrganizationActionBuilder::default()
+        SetActionPolicyActionBuilder::default()
     }
 
-    pub fn set_id(mut self, id: String) -> UpdateOrganizationActionBuilder {
-        self.id = Some(id);
+    pub fn set_org_id(mut self, org_id: String) -> SetActionPolicyActionBuilder {
+        self.org_id = Some(org_id);
         self
     }
 
-    pub fn set_name(mut self, name: String) -> UpdateOrganizationActionBuilder {
-        self.name = Some(name);
+    pub fn set_action(mut self, action: String) -> SetActionPolicyActionBuilder {
+        self.action = Some(action);
         self
     }
 
-    pub fn set_address(mut self, address: String) -> UpdateOrganizationActionBuilder {
-        self.address = Some(address);
+    pub fn set_smart_permission(
+        mut self,
+        smart_permission: String,
+    ) -> SetActionPolicyActionBuilder {
+        self.smart_permission = Some(smart_permission);
         self
     }
 
-    pub fn build(
-        self,
-    ) -> Result<UpdateOrganizationAction, UpdateOrganizationActionBuildError> {
-        let id = self.id.ok_or_else(|| {
-            UpdateOrganizationActionBuildError::MissingField(
-                "'id' field is required".to_string(),
-            )
+    pub fn build(self) -> Result<SetActionPolicyAction, SetActionPolicyActionBuildError> {
+        let org_id = self.org_id.ok_or_else(|| {
+            SetActionPolicyActionBuildError::MissingField("'org_id' field is required".to_string())
         })?;
 
-        let name = self.name.ok_or_else(|| {
-            UpdateOrganizationActionBuildError::MissingField(
-                "'name' field is required".to_string(),
-            )
+        let action = self.action.ok_or_else(|| {
+            SetActionPolicyActionBuildError::MissingField("'action' field is required".to_string())
         })?;
 
-        let address = self.address.ok_or_else(|| {
-            UpdateOrganizationActionBuildError::MissingField(
-                "'address' field is required".to_string(),
-            )
-        })?;
+        let smart_permission = self.smart_permission.unwrap_or_default();
 
-        Ok(UpdateOrganizationAction {
-            id,
-            name,
-            address,
+        Ok(SetActionPolicyAction {
+            org_id,
+            action,
+            smart_permission,
         })
     }
 }
@@ -2938,6 +4961,41 @@ impl FromProto<protos::payload::SmartPayload> for SmartPayload {
                     proto.get_update_organization().clone(),
                 )?)
             }
+            protos::payload::SmartPayload_Action::UPGRADE_CONTRACT => Action::UpgradeContract(
+                UpgradeContractAction::from_proto(proto.get_upgrade_contract().clone())?,
+            ),
+            protos::payload::SmartPayload_Action::DEACTIVATE_ACCOUNT => Action::DeactivateAccount(
+                DeactivateAccountAction::from_proto(proto.get_deactivate_account().clone())?,
+            ),
+            protos::payload::SmartPayload_Action::REACTIVATE_ACCOUNT => Action::ReactivateAccount(
+                ReactivateAccountAction::from_proto(proto.get_reactivate_account().clone())?,
+            ),
+            protos::payload::SmartPayload_Action::GRANT_ROLE => {
+                Action::GrantRole(GrantRoleAction::from_proto(proto.get_grant_role().clone())?)
+            }
+            protos::payload::SmartPayload_Action::REVOKE_ROLE => Action::RevokeRole(
+                RevokeRoleAction::from_proto(proto.get_revoke_role().clone())?,
+            ),
+            protos::payload::SmartPayload_Action::CREATE_ROLE => Action::CreateRole(
+                CreateRoleAction::from_proto(proto.get_create_role().clone())?,
+            ),
+            protos::payload::SmartPayload_Action::UPDATE_ROLE => Action::UpdateRole(
+                UpdateRoleAction::from_proto(proto.get_update_role().clone())?,
+            ),
+            protos::payload::SmartPayload_Action::DELETE_ROLE => Action::DeleteRole(
+                DeleteRoleAction::from_proto(proto.get_delete_role().clone())?,
+            ),
+            protos::payload::SmartPayload_Action::PROPOSE_OWNER_CHANGE => {
+                Action::ProposeOwnerChange(ProposeOwnerChangeAction::from_proto(
+                    proto.get_propose_owner_change().clone(),
+                )?)
+            }
+            protos::payload::SmartPayload_Action::VOTE_OWNER_CHANGE => Action::VoteOwnerChange(
+                VoteOwnerChangeAction::from_proto(proto.get_vote_owner_change().clone())?,
+            ),
+            protos::payload::SmartPayload_Action::SET_ACTION_POLICY => Action::SetActionPolicy(
+                SetActionPolicyAction::from_proto(proto.get_set_action_policy().clone())?,
+            ),
             protos::payload::SmartPayload_Action::ACTION_UNSET => {
                 return Err(ProtoConversionError::InvalidTypeError(
                     "Cannot convert SmartPayload_Action with type unset.".to_string(),
@@ -3034,6 +5092,50 @@ impl FromNative<SmartPayload> for protos::payload::SmartPayload {
                 proto.set_action(protos::payload::SmartPayload_Action::UPDATE_ORGANIZATION);
                 proto.set_update_organization(payload.clone().into_proto()?);
             }
+            Action::UpgradeContract(payload) => {
+                proto.set_action(protos::payload::SmartPayload_Action::UPGRADE_CONTRACT);
+                proto.set_upgrade_contract(payload.clone().into_proto()?);
+            }
+            Action::DeactivateAccount(payload) => {
+                proto.set_action(protos::payload::SmartPayload_Action::DEACTIVATE_ACCOUNT);
+                proto.set_deactivate_account(payload.clone().into_proto()?);
+            }
+            Action::ReactivateAccount(payload) => {
+                proto.set_action(protos::payload::SmartPayload_Action::REACTIVATE_ACCOUNT);
+                proto.set_reactivate_account(payload.clone().into_proto()?);
+            }
+            Action::GrantRole(payload) => {
+                proto.set_action(protos::payload::SmartPayload_Action::GRANT_ROLE);
+                proto.set_grant_role(payload.clone().into_proto()?);
+            }
+            Action::RevokeRole(payload) => {
+                proto.set_action(protos::payload::SmartPayload_Action::REVOKE_ROLE);
+                proto.set_revoke_role(payload.clone().into_proto()?);
+            }
+            Action::CreateRole(payload) => {
+                proto.set_action(protos::payload::SmartPayload_Action::CREATE_ROLE);
+                proto.set_create_role(payload.clone().into_proto()?);
+            }
+            Action::UpdateRole(payload) => {
+                proto.set_action(protos::payload::SmartPayload_Action::UPDATE_ROLE);
+                proto.set_update_role(payload.clone().into_proto()?);
+            }
+            Action::DeleteRole(payload) => {
+                proto.set_action(protos::payload::SmartPayload_Action::DELETE_ROLE);
+                proto.set_delete_role(payload.clone().into_proto()?);
+            }
+            Action::ProposeOwnerChange(payload) => {
+                proto.set_action(protos::payload::SmartPayload_Action::PROPOSE_OWNER_CHANGE);
+                proto.set_propose_owner_change(payload.clone().into_proto()?);
+            }
+            Action::VoteOwnerChange(payload) => {
+                proto.set_action(protos::payload::SmartPayload_Action::VOTE_OWNER_CHANGE);
+                proto.set_vote_owner_change(payload.clone().into_proto()?);
+            }
+            Action::SetActionPolicy(payload) => {
+                proto.set_action(protos::payload::SmartPayload_Action::SET_ACTION_POLICY);
+                proto.set_set_action_policy(payload.clone().into_proto()?);
+            }
         }
 
         Ok(proto)
@@ -3116,6 +5218,8 @@ impl SmartPayloadBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::protocol::state::{ContractSourceBuilder, KeyValueEntryBuilder};
+    use crate::protocol::POLICY_ACTIONS;
 
     #[test]
     // check that a create contract action is built correctly
@@ -3140,6 +5244,12 @@ mod tests {
     #[test]
     // check that a create contract can be converted to bytes and back
     fn check_create_contract_bytes() {
+        let source = ContractSourceBuilder::new()
+            .set_repository("https://github.com/dgc-network/contracts".to_string())
+            .set_commit("0123abcd".to_string())
+            .set_wasm_sha512("abcdef".to_string())
+            .build()
+            .unwrap();
         let builder = CreateContractActionBuilder::new();
         let original = builder
             .set_name("TestContract".to_string())
@@ -3147,6 +5257,7 @@ mod tests {
             .set_inputs(vec!["test".to_string(), "input".to_string()])
             .set_outputs(vec!["test".to_string(), "output".to_string()])
             .set_contract(b"test".to_vec())
+            .set_source(Some(source))
             .build()
             .unwrap();
 
@@ -3225,6 +5336,58 @@ mod tests {
         assert_eq!(execute, original);
     }
 
+    #[test]
+    // check that a upgrade contract action is built correctly
+    fn check_upgrade_contract_action() {
+        let builder = UpgradeContractActionBuilder::new();
+        let action = builder
+            .set_name("TestContract".to_string())
+            .set_version("0.2.0".to_string())
+            .set_previous_version("0.1.0".to_string())
+            .set_inputs(vec!["test".to_string(), "input".to_string()])
+            .set_outputs(vec!["test".to_string(), "output".to_string()])
+            .set_contract(b"test".to_vec())
+            .set_migrate_payload(b"migrate".to_vec())
+            .build()
+            .unwrap();
+
+        assert_eq!(action.get_name(), "TestContract");
+        assert_eq!(action.get_version(), "0.2.0");
+        assert_eq!(action.get_previous_version(), "0.1.0");
+        assert_eq!(action.get_inputs(), ["test".to_string(), "input".to_string()]);
+        assert_eq!(action.get_outputs(), ["test".to_string(), "output".to_string()]);
+        assert_eq!(action.get_contract(), b"test");
+        assert_eq!(action.get_migrate_payload(), b"migrate");
+        assert_eq!(action.get_source(), None);
+    }
+
+    #[test]
+    // check that a upgrade contract action can be converted to bytes and back
+    fn check_upgrade_contract_action_bytes() {
+        let source = ContractSourceBuilder::new()
+            .set_repository("https://github.com/dgc-network/contracts".to_string())
+            .set_commit("4567cdef".to_string())
+            .set_toolchain("1.40.0".to_string())
+            .build()
+            .unwrap();
+        let builder = UpgradeContractActionBuilder::new();
+        let original = builder
+            .set_name("TestContract".to_string())
+            .set_version("0.2.0".to_string())
+            .set_previous_version("0.1.0".to_string())
+            .set_inputs(vec!["test".to_string(), "input".to_string()])
+            .set_outputs(vec!["test".to_string(), "output".to_string()])
+            .set_contract(b"test".to_vec())
+            .set_source(Some(source))
+            .build()
+            .unwrap();
+
+        let bytes = original.clone().into_bytes().unwrap();
+
+        let upgrade = UpgradeContractAction::from_bytes(&bytes).unwrap();
+        assert_eq!(upgrade, original);
+    }
+
     #[test]
     // check that a create contract registry action is built correctly
     fn check_create_contract_registry_action() {
@@ -3435,6 +5598,31 @@ mod tests {
         assert_eq!(create, original);
     }
 
+    #[test]
+    // check that a scoped deny permission action is built and converted to bytes correctly
+    fn check_create_namespace_registry_permission_action_scoped_deny() {
+        let builder = CreateNamespaceRegistryPermissionActionBuilder::new();
+        let original = builder
+            .set_namespace("abcdef".to_string())
+            .set_contract_name("pike_*".to_string())
+            .set_write(true)
+            .set_prefix("abcdef01".to_string())
+            .set_deny(true)
+            .build()
+            .unwrap();
+
+        assert_eq!(original.get_contract_name(), "pike_*");
+        assert_eq!(original.get_read(), false);
+        assert_eq!(original.get_write(), true);
+        assert_eq!(original.get_prefix(), "abcdef01");
+        assert_eq!(original.get_deny(), true);
+
+        let bytes = original.clone().into_bytes().unwrap();
+
+        let create = CreateNamespaceRegistryPermissionAction::from_bytes(&bytes).unwrap();
+        assert_eq!(create, original);
+    }
+
     #[test]
     // check that a delete namespace registry permission action is built correctly
     fn check_delete_namespace_registry_permission_action() {
@@ -3456,6 +5644,7 @@ mod tests {
         let original = builder
             .set_namespace("TestNamespace".to_string())
             .set_contract_name("TestContract".to_string())
+            .set_prefix("TestNamespace01".to_string())
             .build()
             .unwrap();
 
@@ -3606,4 +5795,274 @@ mod tests {
         let payload = SmartPayload::from_bytes(&bytes).unwrap();
         assert_eq!(payload, original);
     }
+
+    #[test]
+    // check that an update account action defaults to merging metadata and keeps the metadata
+    // and mode when converted to bytes and back
+    fn check_update_account_action_metadata() {
+        let key_value = KeyValueEntryBuilder::new()
+            .set_key("Key".to_string())
+            .set_value("Value".to_string())
+            .build()
+            .unwrap();
+
+        let builder = UpdateAccountActionBuilder::new();
+        let action = builder
+            .set_org_id("organization".to_string())
+            .set_public_key("public_key".to_string())
+            .set_roles(vec!["admin".to_string()])
+            .build()
+            .unwrap();
+
+        assert!(action.get_metadata().is_empty());
+        assert_eq!(action.get_metadata_mode(), MetadataUpdateMode::Merge);
+
+        let builder = UpdateAccountActionBuilder::new();
+        let original = builder
+            .set_org_id("organization".to_string())
+            .set_public_key("public_key".to_string())
+            .set_roles(vec!["admin".to_string()])
+            .set_metadata(vec![key_value.clone()])
+            .set_metadata_mode(MetadataUpdateMode::Remove)
+            .build()
+            .unwrap();
+
+        let bytes = original.clone().into_bytes().unwrap();
+        let action = UpdateAccountAction::from_bytes(&bytes).unwrap();
+        assert_eq!(action.get_metadata(), [key_value]);
+        assert_eq!(action.get_metadata_mode(), MetadataUpdateMode::Remove);
+        assert_eq!(action, original);
+    }
+
+    #[test]
+    // check that create and update organization actions keep their metadata when converted to
+    // bytes and back
+    fn check_organization_action_metadata_bytes() {
+        let key_value = KeyValueEntryBuilder::new()
+            .set_key("Key".to_string())
+            .set_value("Value".to_string())
+            .build()
+            .unwrap();
+
+        let original = CreateOrganizationActionBuilder::new()
+            .set_id("organization".to_string())
+            .set_name("name".to_string())
+            .set_address("address".to_string())
+            .set_metadata(vec![key_value.clone()])
+            .build()
+            .unwrap();
+
+        let bytes = original.clone().into_bytes().unwrap();
+        let action = CreateOrganizationAction::from_bytes(&bytes).unwrap();
+        assert_eq!(action, original);
+
+        let original = UpdateOrganizationActionBuilder::new()
+            .set_id("organization".to_string())
+            .set_name("name".to_string())
+            .set_address("address".to_string())
+            .set_metadata(vec![key_value])
+            .set_metadata_mode(MetadataUpdateMode::Replace)
+            .build()
+            .unwrap();
+
+        let bytes = original.clone().into_bytes().unwrap();
+        let action = UpdateOrganizationAction::from_bytes(&bytes).unwrap();
+        assert_eq!(action, original);
+    }
+
+    #[test]
+    // check that deactivate and reactivate account actions can be converted to bytes and back
+    fn check_account_activation_actions_bytes() {
+        let original = DeactivateAccountActionBuilder::new()
+            .set_org_id("organization".to_string())
+            .set_public_key("public_key".to_string())
+            .build()
+            .unwrap();
+
+        let bytes = original.clone().into_bytes().unwrap();
+        let action = DeactivateAccountAction::from_bytes(&bytes).unwrap();
+        assert_eq!(action.get_org_id(), "organization");
+        assert_eq!(action.get_public_key(), "public_key");
+        assert_eq!(action, original);
+
+        let original = ReactivateAccountActionBuilder::new()
+            .set_org_id("organization".to_string())
+            .set_public_key("public_key".to_string())
+            .build()
+            .unwrap();
+
+        let bytes = original.clone().into_bytes().unwrap();
+        let action = ReactivateAccountAction::from_bytes(&bytes).unwrap();
+        assert_eq!(action, original);
+
+        assert!(DeactivateAccountActionBuilder::new()
+            .set_org_id("organization".to_string())
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    // check that grant and revoke role actions can be converted to bytes and back, including
+    // when wrapped in a payload
+    fn check_role_actions_bytes() {
+        let original = GrantRoleActionBuilder::new()
+            .set_org_id("organization".to_string())
+            .set_public_key("public_key".to_string())
+            .set_role("admin".to_string())
+            .build()
+            .unwrap();
+
+        let bytes = original.clone().into_bytes().unwrap();
+        let action = GrantRoleAction::from_bytes(&bytes).unwrap();
+        assert_eq!(action.get_role(), "admin");
+        assert_eq!(action, original);
+
+        let revoke = RevokeRoleActionBuilder::new()
+            .set_org_id("organization".to_string())
+            .set_public_key("public_key".to_string())
+            .set_role("admin".to_string())
+            .build()
+            .unwrap();
+
+        let original = SmartPayloadBuilder::new()
+            .set_action(Action::RevokeRole(revoke.clone()))
+            .build()
+            .unwrap();
+
+        let bytes = original.clone().into_bytes().unwrap();
+        let payload = SmartPayload::from_bytes(&bytes).unwrap();
+        assert_eq!(*payload.action(), Action::RevokeRole(revoke));
+
+        assert!(RevokeRoleActionBuilder::new()
+            .set_org_id("organization".to_string())
+            .set_public_key("public_key".to_string())
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    // check that the role actions can be converted to bytes and back, including when wrapped
+    // in a payload
+    fn check_role_management_actions_bytes() {
+        let original = CreateRoleActionBuilder::new()
+            .set_org_id("organization".to_string())
+            .set_name("auditor".to_string())
+            .set_permissions(vec!["account.update".to_string()])
+            .build()
+            .unwrap();
+
+        let bytes = original.clone().into_bytes().unwrap();
+        let action = CreateRoleAction::from_bytes(&bytes).unwrap();
+        assert_eq!(action.get_name(), "auditor");
+        assert_eq!(action.get_permissions(), ["account.update"]);
+        assert_eq!(action, original);
+
+        let update = UpdateRoleActionBuilder::new()
+            .set_org_id("organization".to_string())
+            .set_name("auditor".to_string())
+            .set_permissions(vec!["account.create".to_string()])
+            .build()
+            .unwrap();
+
+        let original = SmartPayloadBuilder::new()
+            .set_action(Action::UpdateRole(update.clone()))
+            .build()
+            .unwrap();
+
+        let bytes = original.clone().into_bytes().unwrap();
+        let payload = SmartPayload::from_bytes(&bytes).unwrap();
+        assert_eq!(*payload.action(), Action::UpdateRole(update));
+
+        let original = DeleteRoleActionBuilder::new()
+            .set_org_id("organization".to_string())
+            .set_name("auditor".to_string())
+            .build()
+            .unwrap();
+
+        let bytes = original.clone().into_bytes().unwrap();
+        let action = DeleteRoleAction::from_bytes(&bytes).unwrap();
+        assert_eq!(action, original);
+
+        assert!(DeleteRoleActionBuilder::new()
+            .set_org_id("organization".to_string())
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    // check that the owner change actions can be converted to bytes and back, including when
+    // wrapped in a payload
+    fn check_owner_change_actions_bytes() {
+        let original = ProposeOwnerChangeActionBuilder::new()
+            .set_registry_type(RegistryType::NamespaceRegistry)
+            .set_name("abcdef".to_string())
+            .set_owners(vec!["owner".to_string(), "new_owner".to_string()])
+            .set_owner_threshold(2)
+            .set_expiry_blocks(100)
+            .build()
+            .unwrap();
+
+        let bytes = original.clone().into_bytes().unwrap();
+        let action = ProposeOwnerChangeAction::from_bytes(&bytes).unwrap();
+        assert_eq!(action.get_registry_type(), RegistryType::NamespaceRegistry);
+        assert_eq!(action.get_owner_threshold(), 2);
+        assert_eq!(action.get_expiry_blocks(), 100);
+        assert_eq!(action, original);
+
+        let vote = VoteOwnerChangeActionBuilder::new()
+            .set_registry_type(RegistryType::ContractRegistry)
+            .set_name("contract".to_string())
+            .set_approve(false)
+            .build()
+            .unwrap();
+
+        let original = SmartPayloadBuilder::new()
+            .set_action(Action::VoteOwnerChange(vote.clone()))
+            .build()
+            .unwrap();
+
+        let bytes = original.clone().into_bytes().unwrap();
+        let payload = SmartPayload::from_bytes(&bytes).unwrap();
+        assert_eq!(*payload.action(), Action::VoteOwnerChange(vote));
+
+        assert!(ProposeOwnerChangeActionBuilder::new()
+            .set_registry_type(RegistryType::ContractRegistry)
+            .set_name("contract".to_string())
+            .set_owners(vec!["owner".to_string()])
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    // check that a SetActionPolicyAction can be converted to bytes and back, and that every
+    // guarded action has a known policy name
+    fn check_set_action_policy_action_bytes() {
+        let policy = SetActionPolicyActionBuilder::new()
+            .set_org_id("org_id".to_string())
+            .set_action("create_account".to_string())
+            .set_smart_permission("sp_name".to_string())
+            .build()
+            .unwrap();
+
+        let original = SmartPayloadBuilder::new()
+            .set_action(Action::SetActionPolicy(policy.clone()))
+            .build()
+            .unwrap();
+
+        let bytes = original.clone().into_bytes().unwrap();
+        let payload = SmartPayload::from_bytes(&bytes).unwrap();
+        assert_eq!(*payload.action(), Action::SetActionPolicy(policy));
+        assert_eq!(payload.action().policy_name(), None);
+
+        let detach = SetActionPolicyActionBuilder::new()
+            .set_org_id("org_id".to_string())
+            .set_action("create_account".to_string())
+            .build()
+            .unwrap();
+        assert_eq!(detach.get_smart_permission(), "");
+
+        let create_account = Action::CreateAccount(CreateAccountAction::default());
+        let name = create_account.policy_name().unwrap();
+        assert!(POLICY_ACTIONS.contains(&name));
+    }
 }
@@ -4,8 +4,10 @@
 use protobuf::Message;
 use protobuf::RepeatedField;
 
+use std::collections::HashSet;
 use std::error::Error as StdError;
 
+use crate::protocol::ROLE_PERMISSIONS;
 use crate::protos;
 use crate::protos::{
     FromBytes, FromNative, FromProto, IntoBytes, IntoNative, IntoProto, ProtoConversionError,
@@ -17,6 +19,7 @@ pub struct Version {
     version: String,
     contract_sha512: String,
     creator: String,
+    deprecated: bool,
 }
 
 impl Version {
@@ -32,11 +35,16 @@ impl Version {
         &self.creator
     }
 
+    pub fn get_deprecated(&self) -> bool {
+        self.deprecated
+    }
+
     pub fn into_builder(self) -> VersionBuilder {
         VersionBuilder::new()
             .set_version(self.version)
             .set_contract_sha512(self.contract_sha512)
             .set_creator(self.creator)
+            .set_deprecated(self.deprecated)
     }
 }
 
@@ -48,6 +56,7 @@ impl FromProto<protos::contract_registry::ContractRegistry_Version> for Version
             version: proto.get_version().to_string(),
             contract_sha512: proto.get_contract_sha512().to_string(),
             creator: proto.get_creator().to_string(),
+            deprecated: proto.get_deprecated(),
         })
     }
 }
@@ -59,6 +68,7 @@ impl FromNative<Version> for protos::contract_registry::ContractRegistry_Version
         proto.set_version(native.get_version().to_string());
         proto.set_contract_sha512(native.get_contract_sha512().to_string());
         proto.set_creator(native.get_creator().to_string());
+        proto.set_deprecated(native.get_deprecated());
 
         Ok(proto)
     }
@@ -94,6 +104,7 @@ pub struct VersionBuilder {
     version: Option<String>,
     contract_sha512: Option<String>,
     creator: Option<String>,
+    deprecated: bool,
 }
 
 impl VersionBuilder {
@@ -116,6 +127,11 @@ impl VersionBuilder {
         self
     }
 
+    pub fn set_deprecated(mut self, deprecated: bool) -> VersionBuilder {
+        self.deprecated = deprecated;
+        self
+    }
+
     pub fn build(self) -> Result<Version, VersionBuildError> {
         let version = self.version.ok_or_else(|| {
             VersionBuildError::MissingField("'versions' field is required".to_string())
@@ -133,6 +149,192 @@ impl VersionBuilder {
             version,
             contract_sha512,
             creator,
+            deprecated: self.deprecated,
+        })
+    }
+}
+
+/// Native implementation for OwnerChangeProposal
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct OwnerChangeProposal {
+    owners: Vec<String>,
+    owner_threshold: u32,
+    proposer: String,
+    approvals: Vec<String>,
+    rejections: Vec<String>,
+    expires_at_block: u64,
+}
+
+impl OwnerChangeProposal {
+    pub fn get_owners(&self) -> &[String] {
+        &self.owners
+    }
+
+    pub fn get_owner_threshold(&self) -> u32 {
+        self.owner_threshold
+    }
+
+    pub fn get_proposer(&self) -> &String {
+        &self.proposer
+    }
+
+    pub fn get_approvals(&self) -> &[String] {
+        &self.approvals
+    }
+
+    pub fn get_rejections(&self) -> &[String] {
+        &self.rejections
+    }
+
+    pub fn get_expires_at_block(&self) -> u64 {
+        self.expires_at_block
+    }
+
+    /// Returns true if the public key already voted on the proposal
+    pub fn has_voted(&self, public_key: &str) -> bool {
+        self.approvals
+            .iter()
+            .chain(self.rejections.iter())
+            .any(|voter| voter == public_key)
+    }
+
+    pub fn into_builder(self) -> OwnerChangeProposalBuilder {
+        OwnerChangeProposalBuilder::new()
+            .set_owners(self.owners)
+            .set_owner_threshold(self.owner_threshold)
+            .set_proposer(self.proposer)
+            .set_approvals(self.approvals)
+            .set_rejections(self.rejections)
+            .set_expires_at_block(self.expires_at_block)
+    }
+}
+
+impl FromProto<protos::owner_change::OwnerChangeProposal> for OwnerChangeProposal {
+    fn from_proto(
+        proto: protos::owner_change::OwnerChangeProposal,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(OwnerChangeProposal {
+            owners: proto.get_owners().to_vec(),
+            owner_threshold: proto.get_owner_threshold(),
+            proposer: proto.get_proposer().to_string(),
+            approvals: proto.get_approvals().to_vec(),
+            rejections: proto.get_rejections().to_vec(),
+            expires_at_block: proto.get_expires_at_block(),
+        })
+    }
+}
+
+impl FromNative<OwnerChangeProposal> for protos::owner_change::OwnerChangeProposal {
+    fn from_native(native: OwnerChangeProposal) -> Result<Self, ProtoConversionError> {
+        let mut proto = protos::owner_change::OwnerChangeProposal::new();
+
+        proto.set_owners(RepeatedField::from_vec(native.get_owners().to_vec()));
+        proto.set_owner_threshold(native.get_owner_threshold());
+        proto.set_proposer(native.get_proposer().to_string());
+        proto.set_approvals(RepeatedField::from_vec(native.get_approvals().to_vec()));
+        proto.set_rejections(RepeatedField::from_vec(native.get_rejections().to_vec()));
+        proto.set_expires_at_block(native.get_expires_at_block());
+
+        Ok(proto)
+    }
+}
+
+impl IntoProto<protos::owner_change::OwnerChangeProposal> for OwnerChangeProposal {}
+impl IntoNative<OwnerChangeProposal> for protos::owner_change::OwnerChangeProposal {}
+
+#[derive(Debug)]
+pub enum OwnerChangeProposalBuildError {
+    MissingField(String),
+}
+
+impl StdError for OwnerChangeProposalBuildError {
+    fn description(&self) -> &str {
+        match *self {
+            OwnerChangeProposalBuildError::MissingField(ref msg) => msg,
+        }
+    }
+}
+
+impl std::fmt::Display for OwnerChangeProposalBuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            OwnerChangeProposalBuildError::MissingField(ref s) => {
+                write!(f, "MissingField: {}", s)
+            }
+        }
+    }
+}
+
+/// Builder used to create an OwnerChangeProposal
+#[derive(Default, Clone)]
+pub struct OwnerChangeProposalBuilder {
+    owners: Vec<String>,
+    owner_threshold: u32,
+    proposer: Option<String>,
+    approvals: Vec<String>,
+    rejections: Vec<String>,
+    expires_at_block: Option<u64>,
+}
+
+impl OwnerChangeProposalBuilder {
+    pub fn new() -> Self {
+        OwnerChangeProposalBuilder::default()
+    }
+
+    pub fn set_owners(mut self, owners: Vec<String>) -> OwnerChangeProposalBuilder {
+        self.owners = owners;
+        self
+    }
+
+    pub fn set_owner_threshold(mut self, owner_threshold: u32) -> OwnerChangeProposalBuilder {
+        self.owner_threshold = owner_threshold;
+        self
+    }
+
+    pub fn set_proposer(mut self, proposer: String) -> OwnerChangeProposalBuilder {
+        self.proposer = Some(proposer);
+        self
+    }
+
+    pub fn set_approvals(mut self, approvals: Vec<String>) -> OwnerChangeProposalBuilder {
+        self.approvals = approvals;
+        self
+    }
+
+    pub fn set_rejections(mut self, rejections: Vec<String>) -> OwnerChangeProposalBuilder {
+        self.rejections = rejections;
+        self
+    }
+
+    pub fn set_expires_at_block(mut self, expires_at_block: u64) -> OwnerChangeProposalBuilder {
+        self.expires_at_block = Some(expires_at_block);
+        self
+    }
+
+    pub fn build(self) -> Result<OwnerChangeProposal, OwnerChangeProposalBuildError> {
+        if self.owners.is_empty() {
+            return Err(OwnerChangeProposalBuildError::MissingField(
+                "'owners' field is required".to_string(),
+            ));
+        }
+
+        let proposer = self.proposer.ok_or_else(|| {
+            OwnerChangeProposalBuildError::MissingField("'proposer' field is required".to_string())
+        })?;
+
+        let expires_at_block = self.expires_at_block.ok_or_else(|| {
+            OwnerChangeProposalBuildError::MissingField(
+                "'expires_at_block' field is required".to_string(),
+            )
+        })?;
+
+        Ok(OwnerChangeProposal {
+            owners: self.owners,
+            owner_threshold: self.owner_threshold,
+            proposer,
+            approvals: self.approvals,
+            rejections: self.rejections,
+            expires_at_block,
         })
     }
 }
@@ -143,6 +345,8 @@ pub struct ContractRegistry {
     name: String,
     versions: Vec<Version>,
     owners: Vec<String>,
+    owner_threshold: u32,
+    owner_change_proposal: Option<OwnerChangeProposal>,
 }
 
 impl ContractRegistry {
@@ -158,11 +362,21 @@ impl ContractRegistry {
         &self.owners
     }
 
+    pub fn get_owner_threshold(&self) -> u32 {
+        self.owner_threshold
+    }
+
+    pub fn get_owner_change_proposal(&self) -> Option<&OwnerChangeProposal> {
+        self.owner_change_proposal.as_ref()
+    }
+
     pub fn into_builder(self) -> ContractRegistryBuilder {
         ContractRegistryBuilder::new()
             .set_name(self.name)
             .set_versions(self.versions)
             .set_owners(self.owners)
+            .set_owner_threshold(self.owner_threshold)
+            .set_owner_change_proposal(self.owner_change_proposal)
     }
 }
 
@@ -179,6 +393,14 @@ impl FromProto<protos::contract_registry::ContractRegistry> for ContractRegistry
                 .map(Version::from_proto)
                 .collect::<Result<Vec<Version>, ProtoConversionError>>()?,
             owners: proto.get_owners().to_vec(),
+            owner_threshold: proto.get_owner_threshold(),
+            owner_change_proposal: if proto.has_owner_change_proposal() {
+                Some(OwnerChangeProposal::from_proto(
+                    proto.get_owner_change_proposal().clone(),
+                )?)
+            } else {
+                None
+            },
         })
     }
 }
@@ -199,6 +421,10 @@ impl FromNative<ContractRegistry> for protos::contract_registry::ContractRegistr
                 >>()?,
         ));
         proto.set_owners(RepeatedField::from_vec(contract_registry.get_owners().to_vec()));
+        proto.set_owner_threshold(contract_registry.get_owner_threshold());
+        if let Some(proposal) = contract_registry.owner_change_proposal {
+            proto.set_owner_change_proposal(proposal.into_proto()?);
+        }
 
         Ok(proto)
     }
@@ -258,6 +484,8 @@ pub struct ContractRegistryBuilder {
     name: Option<String>,
     versions: Vec<Version>,
     owners: Vec<String>,
+    owner_threshold: u32,
+    owner_change_proposal: Option<OwnerChangeProposal>,
 }
 
 impl ContractRegistryBuilder {
@@ -280,6 +508,19 @@ impl ContractRegistryBuilder {
         self
     }
 
+    pub fn set_owner_threshold(mut self, owner_threshold: u32) -> ContractRegistryBuilder {
+        self.owner_threshold = owner_threshold;
+        self
+    }
+
+    pub fn set_owner_change_proposal(
+        mut self,
+        owner_change_proposal: Option<OwnerChangeProposal>,
+    ) -> ContractRegistryBuilder {
+        self.owner_change_proposal = owner_change_proposal;
+        self
+    }
+
     pub fn build(self) -> Result<ContractRegistry, ContractRegistryBuildError> {
         let name = self.name.ok_or_else(|| {
             ContractRegistryBuildError::MissingField("'name' field is required".to_string())
@@ -301,6 +542,8 @@ impl ContractRegistryBuilder {
             name,
             versions,
             owners,
+            owner_threshold: self.owner_threshold,
+            owner_change_proposal: self.owner_change_proposal,
         })
     }
 }
@@ -435,6 +678,8 @@ pub struct Permission {
     contract_name: String,
     read: bool,
     write: bool,
+    prefix: String,
+    deny: bool,
 }
 
 impl Permission {
@@ -450,11 +695,21 @@ impl Permission {
         self.write
     }
 
+    pub fn get_prefix(&self) -> &String {
+        &self.prefix
+    }
+
+    pub fn get_deny(&self) -> bool {
+        self.deny
+    }
+
     pub fn into_builder(self) -> PermissionBuilder {
         PermissionBuilder::new()
             .set_contract_name(self.contract_name)
             .set_read(self.read)
             .set_write(self.write)
+            .set_prefix(self.prefix)
+            .set_deny(self.deny)
     }
 }
 
@@ -466,6 +721,8 @@ impl FromProto<protos::namespace_registry::NamespaceRegistry_Permission> for Per
             contract_name: proto.get_contract_name().to_string(),
             read: proto.get_read(),
             write: proto.get_write(),
+            prefix: proto.get_prefix().to_string(),
+            deny: proto.get_deny(),
         })
     }
 }
@@ -477,6 +734,8 @@ impl FromNative<Permission> for protos::namespace_registry::NamespaceRegistry_Pe
         proto.set_contract_name(native.get_contract_name().to_string());
         proto.set_read(native.get_read());
         proto.set_write(native.get_write());
+        proto.set_prefix(native.get_prefix().to_string());
+        proto.set_deny(native.get_deny());
 
         Ok(proto)
     }
@@ -512,6 +771,8 @@ pub struct PermissionBuilder {
     contract_name: Option<String>,
     read: Option<bool>,
     write: Option<bool>,
+    prefix: String,
+    deny: bool,
 }
 
 impl PermissionBuilder {
@@ -534,6 +795,16 @@ impl PermissionBuilder {
         self
     }
 
+    pub fn set_prefix(mut self, prefix: String) -> PermissionBuilder {
+        self.prefix = prefix;
+        self
+    }
+
+    pub fn set_deny(mut self, deny: bool) -> PermissionBuilder {
+        self.deny = deny;
+        self
+    }
+
     pub fn build(self) -> Result<Permission, PermissionBuildError> {
         let contract_name = self.contract_name.ok_or_else(|| {
             PermissionBuildError::MissingField("'contract_name' field is required".to_string())
@@ -547,6 +818,8 @@ impl PermissionBuilder {
             contract_name,
             read,
             write,
+            prefix: self.prefix,
+            deny: self.deny,
         })
     }
 }
@@ -557,6 +830,8 @@ pub struct NamespaceRegistry {
     namespace: String,
     owners: Vec<String>,
     permissions: Vec<Permission>,
+    owner_threshold: u32,
+    owner_change_proposal: Option<OwnerChangeProposal>,
 }
 
 impl NamespaceRegistry {
@@ -572,11 +847,21 @@ impl NamespaceRegistry {
         &self.permissions
     }
 
+    pub fn get_owner_threshold(&self) -> u32 {
+        self.owner_threshold
+    }
+
+    pub fn get_owner_change_proposal(&self) -> Option<&OwnerChangeProposal> {
+        self.owner_change_proposal.as_ref()
+    }
+
     pub fn into_builder(self) -> NamespaceRegistryBuilder {
         NamespaceRegistryBuilder::new()
             .set_namespace(self.namespace)
             .set_owners(self.owners)
             .set_permissions(self.permissions)
+            .set_owner_threshold(self.owner_threshold)
+            .set_owner_change_proposal(self.owner_change_proposal)
     }
 }
 
@@ -593,6 +878,14 @@ impl FromProto<protos::namespace_registry::NamespaceRegistry> for NamespaceRegis
                 .into_iter()
                 .map(Permission::from_proto)
                 .collect::<Result<Vec<Permission>, ProtoConversionError>>()?,
+            owner_threshold: proto.get_owner_threshold(),
+            owner_change_proposal: if proto.has_owner_change_proposal() {
+                Some(OwnerChangeProposal::from_proto(
+                    proto.get_owner_change_proposal().clone(),
+                )?)
+            } else {
+                None
+            },
         })
     }
 }
@@ -613,6 +906,10 @@ impl FromNative<NamespaceRegistry> for protos::namespace_registry::NamespaceRegi
                     ProtoConversionError,
                 >>()?,
         ));
+        proto.set_owner_threshold(native.get_owner_threshold());
+        if let Some(proposal) = native.owner_change_proposal {
+            proto.set_owner_change_proposal(proposal.into_proto()?);
+        }
 
         Ok(proto)
     }
@@ -672,6 +969,8 @@ pub struct NamespaceRegistryBuilder {
     namespace: Option<String>,
     owners: Vec<String>,
     permissions: Vec<Permission>,
+    owner_threshold: u32,
+    owner_change_proposal: Option<OwnerChangeProposal>,
 }
 
 impl NamespaceRegistryBuilder {
@@ -694,6 +993,19 @@ impl NamespaceRegistryBuilder {
         self
     }
 
+    pub fn set_owner_threshold(mut self, owner_threshold: u32) -> NamespaceRegistryBuilder {
+        self.owner_threshold = owner_threshold;
+        self
+    }
+
+    pub fn set_owner_change_proposal(
+        mut self,
+        owner_change_proposal: Option<OwnerChangeProposal>,
+    ) -> NamespaceRegistryBuilder {
+        self.owner_change_proposal = owner_change_proposal;
+        self
+    }
+
     pub fn build(self) -> Result<NamespaceRegistry, NamespaceRegistryBuildError> {
         let namespace = self.namespace.ok_or_else(|| {
             NamespaceRegistryBuildError::MissingField("'namespace' field is required".to_string())
@@ -715,6 +1027,8 @@ impl NamespaceRegistryBuilder {
             namespace,
             owners,
             permissions,
+            owner_threshold: self.owner_threshold,
+            owner_change_proposal: self.owner_change_proposal,
         })
     }
 }
@@ -845,6 +1159,141 @@ impl NamespaceRegistryListBuilder {
     }
 }
 
+/// Native implementation for ContractSource
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct ContractSource {
+    repository: String,
+    commit: String,
+    toolchain: String,
+    wasm_sha512: String,
+}
+
+impl ContractSource {
+    pub fn get_repository(&self) -> &String {
+        &self.repository
+    }
+
+    pub fn get_commit(&self) -> &String {
+        &self.commit
+    }
+
+    pub fn get_toolchain(&self) -> &String {
+        &self.toolchain
+    }
+
+    pub fn get_wasm_sha512(&self) -> &String {
+        &self.wasm_sha512
+    }
+
+    pub fn into_builder(self) -> ContractSourceBuilder {
+        ContractSourceBuilder::new()
+            .set_repository(self.repository)
+            .set_commit(self.commit)
+            .set_toolchain(self.toolchain)
+            .set_wasm_sha512(self.wasm_sha512)
+    }
+}
+
+impl FromProto<protos::contract::ContractSource> for ContractSource {
+    fn from_proto(proto: protos::contract::ContractSource) -> Result<Self, ProtoConversionError> {
+        Ok(ContractSource {
+            repository: proto.get_repository().to_string(),
+            commit: proto.get_commit().to_string(),
+            toolchain: proto.get_toolchain().to_string(),
+            wasm_sha512: proto.get_wasm_sha512().to_string(),
+        })
+    }
+}
+
+impl FromNative<ContractSource> for protos::contract::ContractSource {
+    fn from_native(native: ContractSource) -> Result<Self, ProtoConversionError> {
+        let mut proto = protos::contract::ContractSource::new();
+
+        proto.set_repository(native.get_repository().to_string());
+        proto.set_commit(native.get_commit().to_string());
+        proto.set_toolchain(native.get_toolchain().to_string());
+        proto.set_wasm_sha512(native.get_wasm_sha512().to_string());
+
+        Ok(proto)
+    }
+}
+
+impl IntoProto<protos::contract::ContractSource> for ContractSource {}
+impl IntoNative<ContractSource> for protos::contract::ContractSource {}
+
+#[derive(Debug)]
+pub enum ContractSourceBuildError {
+    MissingField(String),
+}
+
+impl StdError for ContractSourceBuildError {
+    fn description(&self) -> &str {
+        match *self {
+            ContractSourceBuildError::MissingField(ref msg) => msg,
+        }
+    }
+}
+
+impl std::fmt::Display for ContractSourceBuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            ContractSourceBuildError::MissingField(ref s) => write!(f, "MissingField: {}", s),
+        }
+    }
+}
+
+/// Builder used to create a ContractSource
+#[derive(Default, Clone)]
+pub struct ContractSourceBuilder {
+    repository: Option<String>,
+    commit: Option<String>,
+    toolchain: String,
+    wasm_sha512: String,
+}
+
+impl ContractSourceBuilder {
+    pub fn new() -> Self {
+        ContractSourceBuilder::default()
+    }
+
+    pub fn set_repository(mut self, repository: String) -> ContractSourceBuilder {
+        self.repository = Some(repository);
+        self
+    }
+
+    pub fn set_commit(mut self, commit: String) -> ContractSourceBuilder {
+        self.commit = Some(commit);
+        self
+    }
+
+    pub fn set_toolchain(mut self, toolchain: String) -> ContractSourceBuilder {
+        self.toolchain = toolchain;
+        self
+    }
+
+    pub fn set_wasm_sha512(mut self, wasm_sha512: String) -> ContractSourceBuilder {
+        self.wasm_sha512 = wasm_sha512;
+        self
+    }
+
+    pub fn build(self) -> Result<ContractSource, ContractSourceBuildError> {
+        let repository = self.repository.ok_or_else(|| {
+            ContractSourceBuildError::MissingField("'repository' field is required".to_string())
+        })?;
+
+        let commit = self.commit.ok_or_else(|| {
+            ContractSourceBuildError::MissingField("'commit' field is required".to_string())
+        })?;
+
+        Ok(ContractSource {
+            repository,
+            commit,
+            toolchain: self.toolchain,
+            wasm_sha512: self.wasm_sha512,
+        })
+    }
+}
+
 /// Native implementation for Contract
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct Contract {
@@ -854,6 +1303,7 @@ pub struct Contract {
     outputs: Vec<String>,
     creator: String,
     contract: Vec<u8>,
+    source: Option<ContractSource>,
 }
 
 impl Contract {
@@ -881,6 +1331,10 @@ impl Contract {
         &self.contract
     }
 
+    pub fn get_source(&self) -> Option<&ContractSource> {
+        self.source.as_ref()
+    }
+
     pub fn into_builder(self) -> ContractBuilder {
         ContractBuilder::new()
             .set_name(self.name)
@@ -889,6 +1343,7 @@ impl Contract {
             .set_outputs(self.outputs)
             .set_creator(self.creator)
             .set_contract(self.contract)
+            .set_source(self.source)
     }
 }
 
@@ -901,6 +1356,11 @@ impl FromProto<protos::contract::Contract> for Contract {
             outputs: proto.get_outputs().to_vec(),
             creator: proto.get_creator().to_string(),
             contract: proto.get_contract().to_vec(),
+            source: if proto.has_source() {
+                Some(ContractSource::from_proto(proto.get_source().clone())?)
+            } else {
+                None
+            },
         })
     }
 }
@@ -915,6 +1375,9 @@ impl FromNative<Contract> for protos::contract::Contract {
         proto.set_outputs(RepeatedField::from_vec(contract.get_outputs().to_vec()));
         proto.set_creator(contract.get_creator().to_string());
         proto.set_contract(contract.get_contract().to_vec());
+        if let Some(source) = contract.source {
+            proto.set_source(source.into_proto()?);
+        }
 
         Ok(proto)
     }
@@ -977,6 +1440,7 @@ pub struct ContractBuilder {
     outputs: Vec<String>,
     creator: Option<String>,
     contract: Vec<u8>,
+    source: Option<ContractSource>,
 }
 
 impl ContractBuilder {
@@ -1014,6 +1478,11 @@ impl ContractBuilder {
         self
     }
 
+    pub fn set_source(mut self, source: Option<ContractSource>) -> ContractBuilder {
+        self.source = source;
+        self
+    }
+
     pub fn build(self) -> Result<Contract, ContractBuildError> {
         let name = self.name.ok_or_else(|| {
             ContractBuildError::MissingField("'name' field is required".to_string())
@@ -1064,6 +1533,7 @@ impl ContractBuilder {
             outputs,
             creator,
             contract,
+            source: self.source,
         })
     }
 }
@@ -1577,6 +2047,50 @@ impl KeyValueEntryBuilder {
     }
 }
 
+/// Maximum number of metadata entries an account or organization may hold
+pub const MAX_METADATA_ENTRIES: usize = 64;
+
+/// Maximum length in bytes of a metadata key
+pub const MAX_METADATA_KEY_LENGTH: usize = 128;
+
+/// Maximum length in bytes of a metadata value
+pub const MAX_METADATA_VALUE_LENGTH: usize = 4096;
+
+/// Checks that metadata is within the size limits and that its keys are
+/// non-empty and unique
+pub fn validate_metadata(metadata: &[KeyValueEntry]) -> Result<(), String> {
+    if metadata.len() > MAX_METADATA_ENTRIES {
+        return Err(format!(
+            "metadata has {} entries, limit is {}",
+            metadata.len(),
+            MAX_METADATA_ENTRIES
+        ));
+    }
+
+    let mut keys = HashSet::new();
+    for entry in metadata {
+        if entry.key.is_empty() {
+            return Err("metadata keys must not be empty".to_string());
+        }
+        if entry.key.len() > MAX_METADATA_KEY_LENGTH {
+            return Err(format!(
+                "metadata key {} is longer than {} bytes",
+                entry.key, MAX_METADATA_KEY_LENGTH
+            ));
+        }
+        if entry.value.len() > MAX_METADATA_VALUE_LENGTH {
+            return Err(format!(
+                "metadata value for {} is longer than {} bytes",
+                entry.key, MAX_METADATA_VALUE_LENGTH
+            ));
+        }
+        if !keys.insert(entry.key.as_str()) {
+            return Err(format!("duplicate metadata key: {}", entry.key));
+        }
+    }
+    Ok(())
+}
+
 /// Native implementation of Account
 #[derive(Debug, Clone, PartialEq)]
 pub struct Account {
@@ -1683,12 +2197,14 @@ impl IntoNative<Account> for protos::account::Account {}
 #[derive(Debug)]
 pub enum AccountBuildError {
     MissingField(String),
+    InvalidField(String),
 }
 
 impl StdError for AccountBuildError {
     fn description(&self) -> &str {
         match *self {
             AccountBuildError::MissingField(ref msg) => msg,
+            AccountBuildError::InvalidField(ref msg) => msg,
         }
     }
 }
@@ -1697,6 +2213,7 @@ impl std::fmt::Display for AccountBuildError {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match *self {
             AccountBuildError::MissingField(ref s) => write!(f, "MissingField: {}", s),
+            AccountBuildError::InvalidField(ref s) => write!(f, "InvalidField: {}", s),
         }
     }
 }
@@ -1753,6 +2270,7 @@ impl AccountBuilder {
         let active = self.active.unwrap_or_default();
         let roles = self.roles;
         let metadata = self.metadata;
+        validate_metadata(&metadata).map_err(AccountBuildError::InvalidField)?;
 
         Ok(Account {
             org_id,
@@ -1885,43 +2403,182 @@ impl AccountListBuilder {
     }
 }
 
-/// Native implementation for Organization
+/// Native implementation for ActionPolicy
 #[derive(Debug, Clone, PartialEq)]
-pub struct Organization {
-    org_id: String,
-    name: String,
-    address: String,
-    metadata: Vec<KeyValueEntry>,
+pub struct ActionPolicy {
+    action: String,
+    smart_permission: String,
 }
 
-impl Organization {
-    pub fn get_org_id(&self) -> &str {
-        &self.org_id
+impl ActionPolicy {
+    pub fn get_action(&self) -> &str {
+        &self.action
     }
 
-    pub fn get_name(&self) -> &str {
-        &self.name
+    pub fn get_smart_permission(&self) -> &str {
+        &self.smart_permission
     }
+}
 
-    pub fn get_address(&self) -> &str {
-        &self.address
+impl FromProto<protos::account::ActionPolicy> for ActionPolicy {
+    fn from_proto(policy: protos::account::ActionPolicy) -> Result<Self, ProtoConversionError> {
+        Ok(ActionPolicy {
+            action: policy.get_action().to_string(),
+            smart_permission: policy.get_smart_permission().to_string(),
+        })
     }
+}
 
-    pub fn get_metadata(&self) -> &[KeyValueEntry] {
-        &self.metadata
+impl FromNative<ActionPolicy> for protos::account::ActionPolicy {
+    fn from_native(policy: ActionPolicy) -> Result<Self, ProtoConversionError> {
+        let mut policy_proto = protos::account::ActionPolicy::new();
+
+        policy_proto.set_action(policy.get_action().to_string());
+        policy_proto.set_smart_permission(policy.get_smart_permission().to_string());
+
+        Ok(policy_proto)
     }
+}
 
-    pub fn into_builder(self) -> OrganizationBuilder {
-        OrganizationBuilder::new()
-            .set_org_id(self.org_id)
-            .set_name(self.name)
-            .set_address(self.address)
-            .set_metadata(self.metadata)
+impl FromBytes<ActionPolicy> for ActionPolicy {
+    fn from_bytes(bytes: &[u8]) -> Result<ActionPolicy, ProtoConversionError> {
+        let proto: protos::account::ActionPolicy =
+            protobuf::parse_from_bytes(bytes).map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get ActionPolicy from bytes".to_string(),
+                )
+            })?;
+        proto.into_native()
     }
 }
 
-impl FromProto<protos::account::Organization> for Organization {
-    fn from_proto(org: protos::account::Organization) -> Result<Self, ProtoConversionError> {
+impl IntoBytes for ActionPolicy {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get bytes from ActionPolicy".to_string(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+
+impl IntoProto<protos::account::ActionPolicy> for ActionPolicy {}
+impl IntoNative<ActionPolicy> for protos::account::ActionPolicy {}
+
+#[derive(Debug)]
+pub enum ActionPolicyBuildError {
+    MissingField(String),
+}
+
+impl StdError for ActionPolicyBuildError {
+    fn description(&self) -> &str {
+        match *self {
+            ActionPolicyBuildError::MissingField(ref msg) => msg,
+        }
+    }
+}
+
+impl std::fmt::Display for ActionPolicyBuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            ActionPolicyBuildError::MissingField(ref s) => write!(f, "MissingField: {}", s),
+        }
+    }
+}
+
+/// Builder used to create an ActionPolicy
+#[derive(Default, Clone)]
+pub struct ActionPolicyBuilder {
+    pub action: Option<String>,
+    pub smart_permission: Option<String>,
+}
+
+impl ActionPolicyBuilder {
+    pub fn new() -> Self {
+        ActionPolicyBuilder::default()
+    }
+
+    pub fn set_action(mut self, action: String) -> ActionPolicyBuilder {
+        self.action = Some(action);
+        self
+    }
+
+    pub fn set_smart_permission(mut self, smart_permission: String) -> ActionPolicyBuilder {
+        self.smart_permission = Some(smart_permission);
+        self
+    }
+
+    pub fn build(self) -> Result<ActionPolicy, ActionPolicyBuildError> {
+        let action = self.action.ok_or_else(|| {
+            ActionPolicyBuildError::MissingField("'action' field is required".to_string())
+        })?;
+
+        let smart_permission = self.smart_permission.ok_or_else(|| {
+            ActionPolicyBuildError::MissingField(
+                "'smart_permission' field is required".to_string(),
+            )
+        })?;
+
+        Ok(ActionPolicy {
+            action,
+            smart_permission,
+        })
+    }
+}
+
+/// Native implementation for Organization
+#[derive(Debug, Clone, PartialEq)]
+pub struct Organization {
+    org_id: String,
+    name: String,
+    address: String,
+    metadata: Vec<KeyValueEntry>,
+    action_policies: Vec<ActionPolicy>,
+}
+
+impl Organization {
+    pub fn get_org_id(&self) -> &str {
+        &self.org_id
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_address(&self) -> &str {
+        &self.address
+    }
+
+    pub fn get_metadata(&self) -> &[KeyValueEntry] {
+        &self.metadata
+    }
+
+    pub fn get_action_policies(&self) -> &[ActionPolicy] {
+        &self.action_policies
+    }
+
+    /// Returns the name of the smart permission attached to the action, if any
+    pub fn get_action_policy(&self, action: &str) -> Option<&str> {
+        self.action_policies
+            .iter()
+            .find(|policy| policy.get_action() == action)
+            .map(ActionPolicy::get_smart_permission)
+    }
+
+    pub fn into_builder(self) -> OrganizationBuilder {
+        OrganizationBuilder::new()
+            .set_org_id(self.org_id)
+            .set_name(self.name)
+            .set_address(self.address)
+            .set_metadata(self.metadata)
+            .set_action_policies(self.action_policies)
+    }
+}
+
+impl FromProto<protos::account::Organization> for Organization {
+    fn from_proto(org: protos::account::Organization) -> Result<Self, ProtoConversionError> {
         Ok(Organization {
             org_id: org.get_org_id().to_string(),
             name: org.get_name().to_string(),
@@ -1932,6 +2589,12 @@ impl FromProto<protos::account::Organization> for Organization {
                 .into_iter()
                 .map(KeyValueEntry::from_proto)
                 .collect::<Result<Vec<KeyValueEntry>, ProtoConversionError>>()?,
+            action_policies: org
+                .get_action_policies()
+                .to_vec()
+                .into_iter()
+                .map(ActionPolicy::from_proto)
+                .collect::<Result<Vec<ActionPolicy>, ProtoConversionError>>()?,
         })
     }
 }
@@ -1951,6 +2614,13 @@ impl FromNative<Organization> for protos::account::Organization {
                 .collect::<Result<Vec<protos::account::KeyValueEntry>, ProtoConversionError>>(
                 )?,
         ));
+        org_proto.set_action_policies(RepeatedField::from_vec(
+            org.get_action_policies()
+                .to_vec()
+                .into_iter()
+                .map(ActionPolicy::into_proto)
+                .collect::<Result<Vec<protos::account::ActionPolicy>, ProtoConversionError>>()?,
+        ));
 
         Ok(org_proto)
     }
@@ -1986,12 +2656,14 @@ impl IntoNative<Organization> for protos::account::Organization {}
 #[derive(Debug)]
 pub enum OrganizationBuildError {
     MissingField(String),
+    InvalidField(String),
 }
 
 impl StdError for OrganizationBuildError {
     fn description(&self) -> &str {
         match *self {
             OrganizationBuildError::MissingField(ref msg) => msg,
+            OrganizationBuildError::InvalidField(ref msg) => msg,
         }
     }
 }
@@ -2000,6 +2672,7 @@ impl std::fmt::Display for OrganizationBuildError {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match *self {
             OrganizationBuildError::MissingField(ref s) => write!(f, "MissingField: {}", s),
+            OrganizationBuildError::InvalidField(ref s) => write!(f, "InvalidField: {}", s),
         }
     }
 }
@@ -2011,6 +2684,7 @@ pub struct OrganizationBuilder {
     pub name: Option<String>,
     pub address: Option<String>,
     pub metadata: Vec<KeyValueEntry>,
+    pub action_policies: Vec<ActionPolicy>,
 }
 
 impl OrganizationBuilder {
@@ -2038,6 +2712,14 @@ impl OrganizationBuilder {
         self
     }
 
+    pub fn set_action_policies(
+        mut self,
+        action_policies: Vec<ActionPolicy>,
+    ) -> OrganizationBuilder {
+        self.action_policies = action_policies;
+        self
+    }
+
     pub fn build(self) -> Result<Organization, OrganizationBuildError> {
         let org_id = self.org_id.ok_or_else(|| {
             OrganizationBuildError::MissingField("'org_id' field is required".to_string())
@@ -2052,139 +2734,684 @@ impl OrganizationBuilder {
         })?;
 
         let metadata = self.metadata;
+        validate_metadata(&metadata).map_err(OrganizationBuildError::InvalidField)?;
+
+        let action_policies = self.action_policies;
+        for (i, policy) in action_policies.iter().enumerate() {
+            if action_policies[..i]
+                .iter()
+                .any(|other| other.get_action() == policy.get_action())
+            {
+                return Err(OrganizationBuildError::InvalidField(format!(
+                    "duplicate action policy: {}",
+                    policy.get_action()
+                )));
+            }
+        }
 
         Ok(Organization {
             org_id,
             name,
             address,
             metadata,
+            action_policies,
+        })
+    }
+}
+
+/// Native implementation of OrganizationList
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrganizationList {
+    organizations: Vec<Organization>,
+}
+
+impl OrganizationList {
+    pub fn get_organizations(&self) -> &[Organization] {
+        &self.organizations
+    }
+}
+
+impl FromProto<protos::account::OrganizationList> for OrganizationList {
+    fn from_proto(
+        organization_list: protos::account::OrganizationList,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(OrganizationList {
+            organizations: organization_list
+                .get_organizations()
+                .to_vec()
+                .into_iter()
+                .map(Organization::from_proto)
+                .collect::<Result<Vec<Organization>, ProtoConversionError>>()?,
+        })
+    }
+}
+
+impl FromNative<OrganizationList> for protos::account::OrganizationList {
+    fn from_native(org_list: OrganizationList) -> Result<Self, ProtoConversionError> {
+        let mut org_list_proto = protos::account::OrganizationList::new();
+
+        org_list_proto.set_organizations(RepeatedField::from_vec(
+            org_list
+                .get_organizations()
+                .to_vec()
+                .into_iter()
+                .map(Organization::into_proto)
+                .collect::<Result<Vec<protos::account::Organization>, ProtoConversionError>>()?,
+        ));
+
+        Ok(org_list_proto)
+    }
+}
+
+impl FromBytes<OrganizationList> for OrganizationList {
+    fn from_bytes(bytes: &[u8]) -> Result<OrganizationList, ProtoConversionError> {
+        let proto: protos::account::OrganizationList = protobuf::parse_from_bytes(bytes)
+            .map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get OrganizationList from bytes".to_string(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+
+impl IntoBytes for OrganizationList {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get bytes from OrganizationList".to_string(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+
+impl IntoProto<protos::account::OrganizationList> for OrganizationList {}
+impl IntoNative<OrganizationList> for protos::account::OrganizationList {}
+
+#[derive(Debug)]
+pub enum OrganizationListBuildError {
+    MissingField(String),
+}
+
+impl StdError for OrganizationListBuildError {
+    fn description(&self) -> &str {
+        match *self {
+            OrganizationListBuildError::MissingField(ref msg) => msg,
+        }
+    }
+}
+
+impl std::fmt::Display for OrganizationListBuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            OrganizationListBuildError::MissingField(ref s) => write!(f, "MissingField: {}", s),
+        }
+    }
+}
+
+/// Builder used to create an OrganizationList
+#[derive(Default, Clone)]
+pub struct OrganizationListBuilder {
+    pub organizations: Vec<Organization>,
+}
+
+impl OrganizationListBuilder {
+    pub fn new() -> Self {
+        OrganizationListBuilder::default()
+    }
+
+    pub fn set_organizations(
+        mut self,
+        organizations: Vec<Organization>,
+    ) -> OrganizationListBuilder {
+        self.organizations = organizations;
+        self
+    }
+
+    pub fn build(self) -> Result<OrganizationList, OrganizationListBuildError> {
+        let organizations = {
+            if self.organizations.is_empty() {
+                return Err(OrganizationListBuildError::MissingField(
+                    "'organization' cannot be empty".to_string(),
+                ));
+            } else {
+                self.organizations
+            }
+        };
+
+        Ok(OrganizationList { organizations })
+    }
+}
+
+/// Native implementation for Role
+#[derive(Debug, Clone, PartialEq)]
+pub struct Role {
+    org_id: String,
+    name: String,
+    permissions: Vec<String>,
+}
+
+impl Role {
+    pub fn get_org_id(&self) -> &str {
+        &self.org_id
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_permissions(&self) -> &[String] {
+        &self.permissions
+    }
+
+    pub fn into_builder(self) -> RoleBuilder {
+        RoleBuilder::new()
+            .set_org_id(self.org_id)
+            .set_name(self.name)
+            .set_permissions(self.permissions)
+    }
+}
+
+impl FromProto<protos::account::Role> for Role {
+    fn from_proto(role: protos::account::Role) -> Result<Self, ProtoConversionError> {
+        Ok(Role {
+            org_id: role.get_org_id().to_string(),
+            name: role.get_name().to_string(),
+            permissions: role.get_permissions().to_vec(),
+        })
+    }
+}
+
+impl FromNative<Role> for protos::account::Role {
+    fn from_native(role: Role) -> Result<Self, ProtoConversionError> {
+        let mut role_proto = protos::account::Role::new();
+
+        role_proto.set_org_id(role.get_org_id().to_string());
+        role_proto.set_name(role.get_name().to_string());
+        role_proto.set_permissions(RepeatedField::from_vec(role.get_permissions().to_vec()));
+
+        Ok(role_proto)
+    }
+}
+
+impl FromBytes<Role> for Role {
+    fn from_bytes(bytes: &[u8]) -> Result<Role, ProtoConversionError> {
+        let proto: protos::account::Role = protobuf::parse_from_bytes(bytes).map_err(|_| {
+            ProtoConversionError::SerializationError("Unable to get Role from bytes".to_string())
+        })?;
+        proto.into_native()
+    }
+}
+
+impl IntoBytes for Role {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError("Unable to get bytes from Role".to_string())
+        })?;
+        Ok(bytes)
+    }
+}
+
+impl IntoProto<protos::account::Role> for Role {}
+impl IntoNative<Role> for protos::account::Role {}
+
+#[derive(Debug)]
+pub enum RoleBuildError {
+    MissingField(String),
+    InvalidField(String),
+}
+
+impl StdError for RoleBuildError {
+    fn description(&self) -> &str {
+        match *self {
+            RoleBuildError::MissingField(ref msg) => msg,
+            RoleBuildError::InvalidField(ref msg) => msg,
+        }
+    }
+}
+
+impl std::fmt::Display for RoleBuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            RoleBuildError::MissingField(ref s) => write!(f, "MissingField: {}", s),
+            RoleBuildError::InvalidField(ref s) => write!(f, "InvalidField: {}", s),
+        }
+    }
+}
+
+/// Builder used to create a Role
+#[derive(Default, Clone)]
+pub struct RoleBuilder {
+    pub org_id: Option<String>,
+    pub name: Option<String>,
+    pub permissions: Vec<String>,
+}
+
+impl RoleBuilder {
+    pub fn new() -> Self {
+        RoleBuilder::default()
+    }
+
+    pub fn set_org_id(mut self, org_id: String) -> RoleBuilder {
+        self.org_id = Some(org_id);
+        self
+    }
+
+    pub fn set_name(mut self, name: String) -> RoleBuilder {
+        self.name = Some(name);
+        self
+    }
+
+    pub fn set_permissions(mut self, permissions: Vec<String>) -> RoleBuilder {
+        self.permissions = permissions;
+        self
+    }
+
+    pub fn build(self) -> Result<Role, RoleBuildError> {
+        let org_id = self.org_id.ok_or_else(|| {
+            RoleBuildError::MissingField("'org_id' field is required".to_string())
+        })?;
+
+        let name = self
+            .name
+            .ok_or_else(|| RoleBuildError::MissingField("'name' field is required".to_string()))?;
+
+        let permissions = self.permissions;
+        validate_permissions(&permissions).map_err(RoleBuildError::InvalidField)?;
+
+        Ok(Role {
+            org_id,
+            name,
+            permissions,
+        })
+    }
+}
+
+/// Checks that a role grants at least one permission and that every permission
+/// is known and listed once
+pub fn validate_permissions(permissions: &[String]) -> Result<(), String> {
+    if permissions.is_empty() {
+        return Err("a role must grant at least one permission".to_string());
+    }
+
+    let mut seen = HashSet::new();
+    for permission in permissions {
+        if !ROLE_PERMISSIONS.contains(&permission.as_str()) {
+            return Err(format!("unknown permission: {}", permission));
+        }
+        if !seen.insert(permission.as_str()) {
+            return Err(format!("duplicate permission: {}", permission));
+        }
+    }
+    Ok(())
+}
+
+/// Native implementation of RoleList
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoleList {
+    roles: Vec<Role>,
+}
+
+impl RoleList {
+    pub fn get_roles(&self) -> &[Role] {
+        &self.roles
+    }
+}
+
+impl FromProto<protos::account::RoleList> for RoleList {
+    fn from_proto(role_list: protos::account::RoleList) -> Result<Self, ProtoConversionError> {
+        Ok(RoleList {
+            roles: role_list
+                .get_roles()
+                .to_vec()
+                .into_iter()
+                .map(Role::from_proto)
+                .collect::<Result<Vec<Role>, ProtoConversionError>>()?,
         })
     }
-}
+}
+
+impl FromNative<RoleList> for protos::account::RoleList {
+    fn from_native(role_list: RoleList) -> Result<Self, ProtoConversionError> {
+        let mut role_list_proto = protos::account::RoleList::new();
+
+        role_list_proto.set_roles(RepeatedField::from_vec(
+            role_list
+                .get_roles()
+                .to_vec()
+                .into_iter()
+                .map(Role::into_proto)
+                .collect::<Result<Vec<protos::account::Role>, ProtoConversionError>>()?,
+        ));
+
+        Ok(role_list_proto)
+    }
+}
+
+impl FromBytes<RoleList> for RoleList {
+    fn from_bytes(bytes: &[u8]) -> Result<RoleList, ProtoConversionError> {
+        let proto: protos::account::RoleList = protobuf::parse_from_bytes(bytes).map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get RoleList from bytes".to_string(),
+            )
+        })?;
+        proto.into_native()
+    }
+}
+
+impl IntoBytes for RoleList {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get bytes from RoleList".to_string(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+
+impl IntoProto<protos::account::RoleList> for RoleList {}
+impl IntoNative<RoleList> for protos::account::RoleList {}
+
+#[derive(Debug)]
+pub enum RoleListBuildError {
+    MissingField(String),
+}
+
+impl StdError for RoleListBuildError {
+    fn description(&self) -> &str {
+        match *self {
+            RoleListBuildError::MissingField(ref msg) => msg,
+        }
+    }
+}
+
+impl std::fmt::Display for RoleListBuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            RoleListBuildError::MissingField(ref s) => write!(f, "MissingField: {}", s),
+        }
+    }
+}
+
+/// Builder used to create a RoleList
+#[derive(Default, Clone)]
+pub struct RoleListBuilder {
+    pub roles: Vec<Role>,
+}
+
+impl RoleListBuilder {
+    pub fn new() -> Self {
+        RoleListBuilder::default()
+    }
+
+    pub fn set_roles(mut self, roles: Vec<Role>) -> RoleListBuilder {
+        self.roles = roles;
+        self
+    }
+
+    pub fn build(self) -> Result<RoleList, RoleListBuildError> {
+        let roles = {
+            if self.roles.is_empty() {
+                return Err(RoleListBuildError::MissingField(
+                    "'roles' cannot be empty".to_string(),
+                ));
+            } else {
+                self.roles
+            }
+        };
+
+        Ok(RoleList { roles })
+    }
+}
+
+/// Native implementation for AccountChangeReceipt_ChangeType
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AccountChangeType {
+    Deactivated,
+    Reactivated,
+    RoleGranted,
+    RoleRevoked,
+}
+
+impl FromProto<protos::account::AccountChangeReceipt_ChangeType> for AccountChangeType {
+    fn from_proto(
+        change_type: protos::account::AccountChangeReceipt_ChangeType,
+    ) -> Result<Self, ProtoConversionError> {
+        match change_type {
+            protos::account::AccountChangeReceipt_ChangeType::DEACTIVATED => {
+                Ok(AccountChangeType::Deactivated)
+            }
+            protos::account::AccountChangeReceipt_ChangeType::REACTIVATED => {
+                Ok(AccountChangeType::Reactivated)
+            }
+            protos::account::AccountChangeReceipt_ChangeType::ROLE_GRANTED => {
+                Ok(AccountChangeType::RoleGranted)
+            }
+            protos::account::AccountChangeReceipt_ChangeType::ROLE_REVOKED => {
+                Ok(AccountChangeType::RoleRevoked)
+            }
+            protos::account::AccountChangeReceipt_ChangeType::CHANGE_TYPE_UNSET => {
+                Err(ProtoConversionError::InvalidTypeError(
+                    "Cannot convert AccountChangeReceipt_ChangeType with type unset.".to_string(),
+                ))
+            }
+        }
+    }
+}
+
+impl FromNative<AccountChangeType> for protos::account::AccountChangeReceipt_ChangeType {
+    fn from_native(change_type: AccountChangeType) -> Result<Self, ProtoConversionError> {
+        match change_type {
+            AccountChangeType::Deactivated => {
+                Ok(protos::account::AccountChangeReceipt_ChangeType::DEACTIVATED)
+            }
+            AccountChangeType::Reactivated => {
+                Ok(protos::account::AccountChangeReceipt_ChangeType::REACTIVATED)
+            }
+            AccountChangeType::RoleGranted => {
+                Ok(protos::account::AccountChangeReceipt_ChangeType::ROLE_GRANTED)
+            }
+            AccountChangeType::RoleRevoked => {
+                Ok(protos::account::AccountChangeReceipt_ChangeType::ROLE_REVOKED)
+            }
+        }
+    }
+}
+
+impl IntoProto<protos::account::AccountChangeReceipt_ChangeType> for AccountChangeType {}
+impl IntoNative<AccountChangeType> for protos::account::AccountChangeReceipt_ChangeType {}
+
+/// Native implementation for AccountChangeReceipt
+///
+/// Records who changed the active flag or the roles of an account, and is added to the
+/// transaction receipt by the transaction processor.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccountChangeReceipt {
+    change_type: AccountChangeType,
+    org_id: String,
+    public_key: String,
+    signer: String,
+    role: String,
+}
+
+impl AccountChangeReceipt {
+    pub fn get_change_type(&self) -> AccountChangeType {
+        self.change_type
+    }
+
+    pub fn get_org_id(&self) -> &str {
+        &self.org_id
+    }
 
-/// Native implementation of OrganizationList
-#[derive(Debug, Clone, PartialEq)]
-pub struct OrganizationList {
-    organizations: Vec<Organization>,
-}
+    pub fn get_public_key(&self) -> &str {
+        &self.public_key
+    }
 
-impl OrganizationList {
-    pub fn get_organizations(&self) -> &[Organization] {
-        &self.organizations
+    pub fn get_signer(&self) -> &str {
+        &self.signer
+    }
+
+    pub fn get_role(&self) -> &str {
+        &self.role
     }
 }
 
-impl FromProto<protos::account::OrganizationList> for OrganizationList {
+impl FromProto<protos::account::AccountChangeReceipt> for AccountChangeReceipt {
     fn from_proto(
-        organization_list: protos::account::OrganizationList,
+        receipt: protos::account::AccountChangeReceipt,
     ) -> Result<Self, ProtoConversionError> {
-        Ok(OrganizationList {
-            organizations: organization_list
-                .get_organizations()
-                .to_vec()
-                .into_iter()
-                .map(Organization::from_proto)
-                .collect::<Result<Vec<Organization>, ProtoConversionError>>()?,
+        Ok(AccountChangeReceipt {
+            change_type: AccountChangeType::from_proto(receipt.get_change_type())?,
+            org_id: receipt.get_org_id().to_string(),
+            public_key: receipt.get_public_key().to_string(),
+            signer: receipt.get_signer().to_string(),
+            role: receipt.get_role().to_string(),
         })
     }
 }
 
-impl FromNative<OrganizationList> for protos::account::OrganizationList {
-    fn from_native(org_list: OrganizationList) -> Result<Self, ProtoConversionError> {
-        let mut org_list_proto = protos::account::OrganizationList::new();
+impl FromNative<AccountChangeReceipt> for protos::account::AccountChangeReceipt {
+    fn from_native(receipt: AccountChangeReceipt) -> Result<Self, ProtoConversionError> {
+        let mut proto = protos::account::AccountChangeReceipt::new();
 
-        org_list_proto.set_organizations(RepeatedField::from_vec(
-            org_list
-                .get_organizations()
-                .to_vec()
-                .into_iter()
-                .map(Organization::into_proto)
-                .collect::<Result<Vec<protos::account::Organization>, ProtoConversionError>>()?,
-        ));
+        proto.set_change_type(receipt.get_change_type().into_proto()?);
+        proto.set_org_id(receipt.get_org_id().to_string());
+        proto.set_public_key(receipt.get_public_key().to_string());
+        proto.set_signer(receipt.get_signer().to_string());
+        proto.set_role(receipt.get_role().to_string());
 
-        Ok(org_list_proto)
+        Ok(proto)
     }
 }
 
-impl FromBytes<OrganizationList> for OrganizationList {
-    fn from_bytes(bytes: &[u8]) -> Result<OrganizationList, ProtoConversionError> {
-        let proto: protos::account::OrganizationList = protobuf::parse_from_bytes(bytes)
+impl FromBytes<AccountChangeReceipt> for AccountChangeReceipt {
+    fn from_bytes(bytes: &[u8]) -> Result<AccountChangeReceipt, ProtoConversionError> {
+        let proto: protos::account::AccountChangeReceipt = protobuf::parse_from_bytes(bytes)
             .map_err(|_| {
                 ProtoConversionError::SerializationError(
-                    "Unable to get OrganizationList from bytes".to_string(),
+                    "Unable to get AccountChangeReceipt from bytes".to_string(),
                 )
             })?;
         proto.into_native()
     }
 }
 
-impl IntoBytes for OrganizationList {
+impl IntoBytes for AccountChangeReceipt {
     fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
         let proto = self.into_proto()?;
         let bytes = proto.write_to_bytes().map_err(|_| {
             ProtoConversionError::SerializationError(
-                "Unable to get bytes from OrganizationList".to_string(),
+                "Unable to get bytes from AccountChangeReceipt".to_string(),
             )
         })?;
         Ok(bytes)
     }
 }
 
-impl IntoProto<protos::account::OrganizationList> for OrganizationList {}
-impl IntoNative<OrganizationList> for protos::account::OrganizationList {}
+impl IntoProto<protos::account::AccountChangeReceipt> for AccountChangeReceipt {}
+impl IntoNative<AccountChangeReceipt> for protos::account::AccountChangeReceipt {}
 
 #[derive(Debug)]
-pub enum OrganizationListBuildError {
+pub enum AccountChangeReceiptBuildError {
     MissingField(String),
 }
 
-impl StdError for OrganizationListBuildError {
+impl StdError for AccountChangeReceiptBuildError {
     fn description(&self) -> &str {
         match *self {
-            OrganizationListBuildError::MissingField(ref msg) => msg,
+            AccountChangeReceiptBuildError::MissingField(ref msg) => msg,
         }
     }
 }
 
-impl std::fmt::Display for OrganizationListBuildError {
+impl std::fmt::Display for AccountChangeReceiptBuildError {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match *self {
-            OrganizationListBuildError::MissingField(ref s) => write!(f, "MissingField: {}", s),
+            AccountChangeReceiptBuildError::MissingField(ref s) => {
+                write!(f, "MissingField: {}", s)
+            }
         }
     }
 }
 
-/// Builder used to create an OrganizationList
+/// Builder used to create an AccountChangeReceipt
 #[derive(Default, Clone)]
-pub struct OrganizationListBuilder {
-    pub organizations: Vec<Organization>,
+pub struct AccountChangeReceiptBuilder {
+    pub change_type: Option<AccountChangeType>,
+    pub org_id: Option<String>,
+    pub public_key: Option<String>,
+    pub signer: Option<String>,
+    pub role: Option<String>,
 }
 
-impl OrganizationListBuilder {
+impl AccountChangeReceiptBuilder {
     pub fn new() -> Self {
-        OrganizationListBuilder::default()
+        AccountChangeReceiptBuilder::default()
     }
 
-    pub fn set_organizations(
+    pub fn set_change_type(
         mut self,
-        organizations: Vec<Organization>,
-    ) -> OrganizationListBuilder {
-        self.organizations = organizations;
+        change_type: AccountChangeType,
+    ) -> AccountChangeReceiptBuilder {
+        self.change_type = Some(change_type);
         self
     }
 
-    pub fn build(self) -> Result<OrganizationList, OrganizationListBuildError> {
-        let organizations = {
-            if self.organizations.is_empty() {
-                return Err(OrganizationListBuildError::MissingField(
-                    "'organization' cannot be empty".to_string(),
-                ));
-            } else {
-                self.organizations
-            }
-        };
+    pub fn set_org_id(mut self, org_id: String) -> AccountChangeReceiptBuilder {
+        self.org_id = Some(org_id);
+        self
+    }
 
-        Ok(OrganizationList { organizations })
+    pub fn set_public_key(mut self, public_key: String) -> AccountChangeReceiptBuilder {
+        self.public_key = Some(public_key);
+        self
+    }
+
+    pub fn set_signer(mut self, signer: String) -> AccountChangeReceiptBuilder {
+        self.signer = Some(signer);
+        self
+    }
+
+    pub fn set_role(mut self, role: String) -> AccountChangeReceiptBuilder {
+        self.role = Some(role);
+        self
+    }
+
+    pub fn build(self) -> Result<AccountChangeReceipt, AccountChangeReceiptBuildError> {
+        let change_type = self.change_type.ok_or_else(|| {
+            AccountChangeReceiptBuildError::MissingField(
+                "'change_type' field is required".to_string(),
+            )
+        })?;
+
+        let org_id = self.org_id.ok_or_else(|| {
+            AccountChangeReceiptBuildError::MissingField("'org_id' field is required".to_string())
+        })?;
+
+        let public_key = self.public_key.ok_or_else(|| {
+            AccountChangeReceiptBuildError::MissingField(
+                "'public_key' field is required".to_string(),
+            )
+        })?;
+
+        let signer = self.signer.ok_or_else(|| {
+            AccountChangeReceiptBuildError::MissingField("'signer' field is required".to_string())
+        })?;
+
+        let role = self.role.unwrap_or_default();
+
+        Ok(AccountChangeReceipt {
+            change_type,
+            org_id,
+            public_key,
+            signer,
+            role,
+        })
     }
 }
 
@@ -2267,6 +3494,88 @@ mod tests {
         assert_eq!(builder.owners, ["owner"]);
     }
 
+    #[test]
+    // check that a deprecated version survives conversion to bytes and into a builder
+    fn check_deprecated_version() {
+        let builder = VersionBuilder::new();
+        let version = builder
+            .set_version("0.1.0".to_string())
+            .set_contract_sha512("sha512".to_string())
+            .set_creator("The Creator".to_string())
+            .set_deprecated(true)
+            .build()
+            .unwrap();
+
+        assert!(version.get_deprecated());
+
+        let builder = ContractRegistryBuilder::new();
+        let original = builder
+            .set_name("Tests".to_string())
+            .set_versions(vec![version.clone()])
+            .set_owners(vec!["owner".to_string()])
+            .build()
+            .unwrap();
+
+        let bytes = original.clone().into_bytes().unwrap();
+        let contract_registry = ContractRegistry::from_bytes(&bytes).unwrap();
+        assert_eq!(contract_registry, original);
+        assert!(contract_registry.get_versions()[0].get_deprecated());
+
+        let rebuilt = version.clone().into_builder().build().unwrap();
+        assert_eq!(rebuilt, version);
+    }
+
+    #[test]
+    // check that an owner change proposal survives conversion to bytes on both registries
+    fn check_owner_change_proposal() {
+        let proposal = OwnerChangeProposalBuilder::new()
+            .set_owners(vec!["owner".to_string(), "new_owner".to_string()])
+            .set_owner_threshold(2)
+            .set_proposer("owner".to_string())
+            .set_approvals(vec!["owner".to_string()])
+            .set_expires_at_block(100)
+            .build()
+            .unwrap();
+
+        assert!(proposal.has_voted("owner"));
+        assert!(!proposal.has_voted("new_owner"));
+
+        let original = ContractRegistryBuilder::new()
+            .set_name("Tests".to_string())
+            .set_owners(vec!["owner".to_string()])
+            .set_owner_threshold(1)
+            .set_owner_change_proposal(Some(proposal.clone()))
+            .build()
+            .unwrap();
+
+        let bytes = original.clone().into_bytes().unwrap();
+        let contract_registry = ContractRegistry::from_bytes(&bytes).unwrap();
+        assert_eq!(contract_registry, original);
+        assert_eq!(contract_registry.get_owner_change_proposal(), Some(&proposal));
+
+        let original = NamespaceRegistryBuilder::new()
+            .set_namespace("Tests".to_string())
+            .set_owners(vec!["owner".to_string()])
+            .set_owner_threshold(1)
+            .set_owner_change_proposal(Some(proposal.clone()))
+            .build()
+            .unwrap();
+
+        let bytes = original.clone().into_bytes().unwrap();
+        let namespace_registry = NamespaceRegistry::from_bytes(&bytes).unwrap();
+        assert_eq!(namespace_registry, original);
+        assert_eq!(namespace_registry.get_owner_threshold(), 1);
+
+        let cleared = namespace_registry
+            .into_builder()
+            .set_owner_change_proposal(None)
+            .build()
+            .unwrap();
+        let bytes = cleared.clone().into_bytes().unwrap();
+        let namespace_registry = NamespaceRegistry::from_bytes(&bytes).unwrap();
+        assert_eq!(namespace_registry.get_owner_change_proposal(), None);
+    }
+
     #[test]
     // check that a contract registry list is built correctly
     fn check_contract_registry_list() {
@@ -2350,6 +3659,38 @@ mod tests {
         assert_eq!(namespace_registry.get_owners(), ["owner"]);
     }
 
+    #[test]
+    // check that a scoped deny permission survives conversion to bytes and into a builder
+    fn check_namespace_registry_scoped_deny_permission() {
+        let builder = PermissionBuilder::new();
+        let permission = builder
+            .set_contract_name("pike_*".to_string())
+            .set_write(true)
+            .set_prefix("abcdef01".to_string())
+            .set_deny(true)
+            .build()
+            .unwrap();
+
+        assert_eq!(permission.get_prefix(), "abcdef01");
+        assert!(permission.get_deny());
+        assert!(!permission.get_read());
+
+        let builder = NamespaceRegistryBuilder::new();
+        let original = builder
+            .set_namespace("abcdef".to_string())
+            .set_owners(vec!["owner".to_string()])
+            .set_permissions(vec![permission.clone()])
+            .build()
+            .unwrap();
+
+        let bytes = original.clone().into_bytes().unwrap();
+        let namespace_registry = NamespaceRegistry::from_bytes(&bytes).unwrap();
+        assert_eq!(namespace_registry, original);
+
+        let rebuilt = permission.clone().into_builder().build().unwrap();
+        assert_eq!(rebuilt, permission);
+    }
+
     #[test]
     // check that a namespace registry can be converted to bytes and back
     fn check_namespace_registry_bytes() {
@@ -2486,11 +3827,45 @@ mod tests {
         );
         assert_eq!(contract.get_creator(), "The Creator");
         assert_eq!(contract.get_contract(), b"test_contract");
+        assert_eq!(contract.get_source(), None);
+    }
+
+    #[test]
+    // check that a contract source is built correctly
+    fn check_contract_source() {
+        let source = ContractSourceBuilder::new()
+            .set_repository("https://github.com/dgc-network/contracts".to_string())
+            .set_commit("0123abcd".to_string())
+            .set_toolchain("1.40.0".to_string())
+            .set_wasm_sha512("abcdef".to_string())
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            source.get_repository(),
+            "https://github.com/dgc-network/contracts"
+        );
+        assert_eq!(source.get_commit(), "0123abcd");
+        assert_eq!(source.get_toolchain(), "1.40.0");
+        assert_eq!(source.get_wasm_sha512(), "abcdef");
+
+        let builder = source.clone().into_builder();
+        assert_eq!(builder.build().unwrap(), source);
+
+        assert!(ContractSourceBuilder::new()
+            .set_repository("https://github.com/dgc-network/contracts".to_string())
+            .build()
+            .is_err());
     }
 
     #[test]
     // check that a contract can be converted to bytes and back
     fn check_contract_bytes() {
+        let source = ContractSourceBuilder::new()
+            .set_repository("https://github.com/dgc-network/contracts".to_string())
+            .set_commit("0123abcd".to_string())
+            .build()
+            .unwrap();
         let builder = ContractBuilder::new();
         let original = builder
             .set_name("Tests".to_string())
@@ -2499,6 +3874,7 @@ mod tests {
             .set_outputs(vec!["output1".to_string(), "output2".to_string()])
             .set_creator("The Creator".to_string())
             .set_contract(b"test_contract".to_vec())
+            .set_source(Some(source))
             .build()
             .unwrap();
 
@@ -2691,8 +4067,8 @@ mod tests {
             .build()
             .unwrap();
 
-        assert_eq!(key_value.key(), "Key");
-        assert_eq!(key_value.value(), "Value");
+        assert_eq!(key_value.get_key(), "Key");
+        assert_eq!(key_value.get_value(), "Value");
     }
 
     #[test]
@@ -2732,7 +4108,7 @@ mod tests {
 
         assert_eq!(account.get_org_id(), "organization");
         assert_eq!(account.get_public_key(), "public_key");
-        assert!(account.get_active());
+        assert!(*account.get_active());
         assert_eq!(account.get_roles(), ["Role".to_string()]);
         assert_eq!(account.get_metadata(), [key_value]);
     }
@@ -2865,6 +4241,90 @@ mod tests {
         assert_eq!(org, original);
     }
 
+    #[test]
+    // check that metadata with duplicate or empty keys, or over the size limits, is rejected
+    fn check_metadata_validation() {
+        let entry = |key: &str, value: &str| {
+            KeyValueEntryBuilder::new()
+                .set_key(key.to_string())
+                .set_value(value.to_string())
+                .build()
+                .unwrap()
+        };
+
+        let account = AccountBuilder::new()
+            .set_org_id("organization".to_string())
+            .set_public_key("public_key".to_string())
+            .set_metadata(vec![entry("Key", "Value"), entry("Key", "Other")])
+            .build();
+        match account {
+            Err(AccountBuildError::InvalidField(_)) => (),
+            res => panic!("Duplicate metadata keys were accepted: {:?}", res),
+        }
+
+        let organization = OrganizationBuilder::new()
+            .set_org_id("organization".to_string())
+            .set_name("name".to_string())
+            .set_address("address".to_string())
+            .set_metadata(vec![entry("", "Value")])
+            .build();
+        match organization {
+            Err(OrganizationBuildError::InvalidField(_)) => (),
+            res => panic!("An empty metadata key was accepted: {:?}", res),
+        }
+
+        let long_value = "v".repeat(MAX_METADATA_VALUE_LENGTH + 1);
+        assert!(validate_metadata(&[entry("Key", &long_value)]).is_err());
+
+        let long_key = "k".repeat(MAX_METADATA_KEY_LENGTH + 1);
+        assert!(validate_metadata(&[entry(&long_key, "Value")]).is_err());
+
+        let too_many: Vec<KeyValueEntry> = (0..=MAX_METADATA_ENTRIES)
+            .map(|i| entry(&format!("Key{}", i), "Value"))
+            .collect();
+        assert!(validate_metadata(&too_many).is_err());
+        assert!(validate_metadata(&too_many[1..]).is_ok());
+    }
+
+    #[test]
+    // check that action policies survive a round trip and cannot repeat an action
+    fn check_organization_action_policies() {
+        let policy = |action: &str, smart_permission: &str| {
+            ActionPolicyBuilder::new()
+                .set_action(action.to_string())
+                .set_smart_permission(smart_permission.to_string())
+                .build()
+                .unwrap()
+        };
+
+        let original = OrganizationBuilder::new()
+            .set_org_id("organization".to_string())
+            .set_name("name".to_string())
+            .set_address("address".to_string())
+            .set_action_policies(vec![policy("create_account", "sp_name")])
+            .build()
+            .unwrap();
+
+        assert_eq!(original.get_action_policy("create_account"), Some("sp_name"));
+        assert_eq!(original.get_action_policy("update_account"), None);
+
+        let bytes = original.clone().into_bytes().unwrap();
+        let org = Organization::from_bytes(&bytes).unwrap();
+        assert_eq!(org, original);
+
+        let duplicate = org
+            .into_builder()
+            .set_action_policies(vec![
+                policy("create_account", "sp_name"),
+                policy("create_account", "other"),
+            ])
+            .build();
+        match duplicate {
+            Err(OrganizationBuildError::InvalidField(_)) => (),
+            res => panic!("Duplicate action policies were accepted: {:?}", res),
+        }
+    }
+
     #[test]
     // check that an OrganizationList is built correctly
     fn check_organization_lists_builder() {
@@ -2922,4 +4382,85 @@ mod tests {
         let org_list = OrganizationList::from_bytes(&bytes).unwrap();
         assert_eq!(org_list, original);
     }
+
+    #[test]
+    // check that an AccountChangeReceipt can be converted to bytes and back
+    fn check_account_change_receipt_bytes() {
+        let builder = AccountChangeReceiptBuilder::new();
+        let original = builder
+            .set_change_type(AccountChangeType::RoleGranted)
+            .set_org_id("organization".to_string())
+            .set_public_key("public_key".to_string())
+            .set_signer("signer".to_string())
+            .set_role("admin".to_string())
+            .build()
+            .unwrap();
+
+        let bytes = original.clone().into_bytes().unwrap();
+        let receipt = AccountChangeReceipt::from_bytes(&bytes).unwrap();
+        assert_eq!(receipt.get_change_type(), AccountChangeType::RoleGranted);
+        assert_eq!(receipt.get_role(), "admin");
+        assert_eq!(receipt, original);
+
+        let receipt = AccountChangeReceiptBuilder::new()
+            .set_change_type(AccountChangeType::Deactivated)
+            .set_org_id("organization".to_string())
+            .set_public_key("public_key".to_string())
+            .set_signer("signer".to_string())
+            .build()
+            .unwrap();
+        assert_eq!(receipt.get_role(), "");
+    }
+
+    #[test]
+    // check that a Role is built correctly and can be converted to bytes and back
+    fn check_role_bytes() {
+        let builder = RoleBuilder::new();
+        let original = builder
+            .set_org_id("organization".to_string())
+            .set_name("auditor".to_string())
+            .set_permissions(vec![
+                "account.update".to_string(),
+                "smart_permission.create".to_string(),
+            ])
+            .build()
+            .unwrap();
+
+        assert_eq!(original.get_org_id(), "organization");
+        assert_eq!(original.get_name(), "auditor");
+        assert_eq!(
+            original.get_permissions(),
+            ["account.update", "smart_permission.create"]
+        );
+
+        let builder = RoleListBuilder::new();
+        let role_list = builder.set_roles(vec![original.clone()]).build().unwrap();
+
+        let bytes = role_list.clone().into_bytes().unwrap();
+        let roles = RoleList::from_bytes(&bytes).unwrap();
+        assert_eq!(roles, role_list);
+        assert_eq!(roles.get_roles(), [original]);
+    }
+
+    #[test]
+    // check that a Role with no, unknown or duplicate permissions is rejected
+    fn check_role_permissions_validated() {
+        let builder = RoleBuilder::new()
+            .set_org_id("organization".to_string())
+            .set_name("auditor".to_string());
+
+        assert!(builder.clone().build().is_err());
+        assert!(builder
+            .clone()
+            .set_permissions(vec!["account.destroy".to_string()])
+            .build()
+            .is_err());
+        assert!(builder
+            .set_permissions(vec![
+                "account.update".to_string(),
+                "account.update".to_string(),
+            ])
+            .build()
+            .is_err());
+    }
 }
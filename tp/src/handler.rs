@@ -3,20 +3,44 @@
 
 //! Provides a Sawtooth Transaction Handler for executing Smart transactions.
 
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard};
+
 use crypto::digest::Digest;
 use crypto::sha2::Sha512;
 use sawtooth_sdk::messages::processor::TpProcessRequest;
 use sawtooth_sdk::processor::handler::ApplyError;
 use sawtooth_sdk::processor::handler::TransactionContext;
 use sawtooth_sdk::processor::handler::TransactionHandler;
+use wasmi::Module;
 
+use crate::namespace::{check_namespace_permission, NamespaceAccess};
+use crate::owner_change::{self, ProposalStatus};
 use crate::payload::SmartRequestPayload;
+use crate::roles::{
+    check_can_change_account, check_can_grant_role, check_granted, check_holds_permissions,
+    check_member_permission, check_permission, get_signer_permissions,
+};
 use crate::state::SmartState;
+use crate::versioning::resolve_version;
+use crate::wasm_executor::limits::{ExecutionLimits, ExecutionUsage, DEFAULT_MAX_CONTRACT_SIZE};
+use crate::wasm_executor::metering::load_metered_module;
+use crate::wasm_executor::module_cache::{ModuleCache, ModuleCacheKey, DEFAULT_MODULE_CACHE_SIZE};
+use crate::wasm_executor::trace::ExecutionTrace;
+use crate::wasm_executor::validation::validate_contract;
+use crate::wasm_executor::wasm_externals::{
+    AuthorizedAddresses, ExternalsError, ExternalsErrorKind, WasmExternals,
+};
 use crate::wasm_executor::wasm_module::WasmModule;
+use dgc_contract_sdk::addressing::{compute_contract_address, compute_smart_permission_address};
 use dgc_contract_sdk::protocol::state::{
-    ContractBuilder, ContractRegistry, ContractRegistryBuilder, NamespaceRegistry,
-    NamespaceRegistryBuilder, PermissionBuilder, SmartPermissionBuilder, VersionBuilder,
-    AccountBuilder, OrganizationBuilder,
+    Contract, ContractBuilder, ContractRegistry, ContractRegistryBuilder, ContractSource,
+    NamespaceRegistry, NamespaceRegistryBuilder, PermissionBuilder, SmartPermissionBuilder,
+    VersionBuilder,
+    Account, AccountBuilder, AccountChangeReceiptBuilder, AccountChangeType, KeyValueEntry,
+    OrganizationBuilder, RoleBuilder, ActionPolicy, ActionPolicyBuilder,
 };
 use dgc_contract_sdk::protocol::payload::{
     Action, CreateContractAction, CreateContractRegistryAction, CreateNamespaceRegistryAction,
@@ -24,11 +48,28 @@ use dgc_contract_sdk::protocol::payload::{
     DeleteContractRegistryAction, DeleteNamespaceRegistryAction,
     DeleteNamespaceRegistryPermissionAction, DeleteSmartPermissionAction, ExecuteContractAction,
     UpdateContractRegistryOwnersAction, UpdateNamespaceRegistryOwnersAction,
-    UpdateSmartPermissionAction,
-    CreateAccountAction, UpdateAccountAction,
-    CreateOrganizationAction, UpdateOrganizationAction,
+    UpdateSmartPermissionAction, UpgradeContractAction,
+    CreateAccountAction, UpdateAccountAction, DeactivateAccountAction, ReactivateAccountAction,
+    GrantRoleAction, RevokeRoleAction, CreateRoleAction, UpdateRoleAction, DeleteRoleAction,
+    CreateOrganizationAction, UpdateOrganizationAction, MetadataUpdateMode,
+    ProposeOwnerChangeAction, RegistryType, VoteOwnerChangeAction, SetActionPolicyAction,
+};
+use dgc_contract_sdk::protos::contract_error::ContractError;
+use dgc_contract_sdk::protos::IntoBytes;
+use dgc_contract_sdk::protocol::{
+    ACCOUNT_CREATE_PERMISSION, ACCOUNT_DEACTIVATE_PERMISSION, ACCOUNT_GRANT_ROLE_PERMISSION,
+    ACCOUNT_UPDATE_PERMISSION, ADMIN_ROLE, CONTRACT_UPLOAD_PERMISSION,
+    ORGANIZATION_CREATE_PERMISSION,
+    ORGANIZATION_UPDATE_PERMISSION, ROLE_CREATE_PERMISSION, ROLE_DELETE_PERMISSION,
+    ROLE_UPDATE_PERMISSION, SMART_PERMISSION_CREATE_PERMISSION,
+    SMART_PERMISSION_DELETE_PERMISSION, SMART_PERMISSION_UPDATE_PERMISSION,
+    ADMINISTRATORS_SETTING_KEY, FUEL_LIMIT_SETTING_ADDRESS, FUEL_LIMIT_SETTING_KEY,
+    MAX_ADDRESSES_TOUCHED_SETTING_ADDRESS, MAX_ADDRESSES_TOUCHED_SETTING_KEY,
+    MAX_BYTES_WRITTEN_SETTING_ADDRESS, MAX_BYTES_WRITTEN_SETTING_KEY,
+    MAX_CONTRACT_SIZE_SETTING_ADDRESS, MAX_CONTRACT_SIZE_SETTING_KEY,
+    MAX_MEMORY_PAGES_SETTING_ADDRESS, MAX_MEMORY_PAGES_SETTING_KEY,
+    MAX_STATE_ENTRY_SIZE_SETTING_ADDRESS, MAX_STATE_ENTRY_SIZE_SETTING_KEY,
 };
-use dgc_contract_sdk::protocol::ADMINISTRATORS_SETTING_KEY;
 
 /// The namespace registry prefix for global state (00ec00)
 const NAMESPACE_REGISTRY_PREFIX: &str = "00ec00";
@@ -39,6 +80,26 @@ const CONTRACT_REGISTRY_PREFIX: &str = "00ec01";
 /// The contract prefix for global state (00ec02)
 const CONTRACT_PREFIX: &str = "00ec02";
 
+/// Where the handler emits the execution traces of contracts
+#[derive(Clone, Debug)]
+pub enum TraceOutput {
+    /// Logs each trace record as a line of JSON at trace level
+    Log,
+    /// Writes the trace records of each transaction as JSON lines to
+    /// `<transaction signature>.jsonl` in the directory
+    Directory(PathBuf),
+}
+
+impl TraceOutput {
+    /// Returns the file the trace of a transaction is written to, if any
+    pub fn trace_file(&self, signature: &str) -> Option<PathBuf> {
+        match self {
+            TraceOutput::Log => None,
+            TraceOutput::Directory(dir) => Some(dir.join(format!("{}.jsonl", signature))),
+        }
+    }
+}
+
 /// Handles Smart Transactions
 ///
 /// This handler implements the Sawtooth TransactionHandler trait, in order to execute Smart
@@ -52,6 +113,8 @@ pub struct SmartTransactionHandler {
     family_name: String,
     family_versions: Vec<String>,
     namespaces: Vec<String>,
+    module_cache: Mutex<ModuleCache>,
+    trace_output: Option<TraceOutput>,
 }
 
 impl SmartTransactionHandler {
@@ -66,6 +129,50 @@ impl SmartTransactionHandler {
                 CONTRACT_REGISTRY_PREFIX.into(),
                 CONTRACT_PREFIX.into(),
             ],
+            module_cache: Mutex::new(ModuleCache::new(DEFAULT_MODULE_CACHE_SIZE)),
+            trace_output: None,
+        }
+    }
+
+    /// Traces the host calls and log lines of every contract and smart
+    /// permission execution, emitting the records of each transaction to the
+    /// given output
+    pub fn with_trace_output(mut self, trace_output: TraceOutput) -> SmartTransactionHandler {
+        self.trace_output = Some(trace_output);
+        self
+    }
+
+    fn write_trace(&self, request: &TpProcessRequest, trace: Option<&ExecutionTrace>) {
+        let (output, trace) = match (self.trace_output.as_ref(), trace) {
+            (Some(output), Some(trace)) => (output, trace),
+            _ => return,
+        };
+
+        let records = trace.records();
+        let path = match output.trace_file(request.get_signature()) {
+            Some(path) => path,
+            None => {
+                for record in records {
+                    trace!("{}", record);
+                }
+                return;
+            }
+        };
+
+        // A trace that cannot be written must not change the outcome of the
+        // transaction, so failures are only logged
+        let result = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| File::create(&path))
+            .and_then(|mut file| {
+                for record in records {
+                    writeln!(file, "{}", record)?;
+                }
+                Ok(())
+            });
+        if let Err(err) = result {
+            warn!("Unable to write trace {}: {}", path.display(), err);
         }
     }
 }
@@ -113,18 +220,42 @@ impl TransactionHandler for SmartTransactionHandler {
             request.get_header().get_outputs()
         );
 
-        match payload.get_action() {
-            Action::CreateContract(create_contract_payload) => {
-                create_contract(create_contract_payload, signer, &mut state)
-            }
-            Action::DeleteContract(delete_contract_payload) => {
-                delete_contract(delete_contract_payload, signer, &mut state)
+        let trace = self.trace_output.as_ref().map(|_| ExecutionTrace::new());
+
+        let action = payload.get_action();
+        if let Some(policy_name) = action.policy_name() {
+            if let Err(err) = check_action_policy(
+                policy_name,
+                signer,
+                request.get_payload(),
+                &mut state,
+                trace.as_ref(),
+            ) {
+                self.write_trace(request, trace.as_ref());
+                return Err(err);
             }
+        }
+
+        let result = match action {
+            Action::CreateContract(create_contract_payload) => create_contract(
+                create_contract_payload,
+                signer,
+                &mut state,
+                &self.module_cache,
+            ),
+            Action::DeleteContract(delete_contract_payload) => delete_contract(
+                delete_contract_payload,
+                signer,
+                &mut state,
+                &self.module_cache,
+            ),
             Action::ExecuteContract(execute_contract_payload) => execute_contract(
                 execute_contract_payload,
                 signer,
                 request.get_signature(),
                 &mut state,
+                &self.module_cache,
+                trace.as_ref(),
             ),
             Action::CreateContractRegistry(create_contract_registry_payload) => {
                 create_contract_registry(create_contract_registry_payload, signer, &mut state)
@@ -181,13 +312,44 @@ impl TransactionHandler for SmartTransactionHandler {
             Action::UpdateAccount(payload) => {
                 update_account(payload, signer, &mut state)
             }
+            Action::DeactivateAccount(payload) => {
+                deactivate_account(payload, signer, &mut state)
+            }
+            Action::ReactivateAccount(payload) => {
+                reactivate_account(payload, signer, &mut state)
+            }
+            Action::GrantRole(payload) => {
+                grant_role(payload, signer, &mut state)
+            }
+            Action::RevokeRole(payload) => {
+                revoke_role(payload, signer, &mut state)
+            }
+            Action::CreateRole(payload) => create_role(payload, signer, &mut state),
+            Action::UpdateRole(payload) => update_role(payload, signer, &mut state),
+            Action::DeleteRole(payload) => delete_role(payload, signer, &mut state),
             Action::CreateOrganization(payload) => {
                 create_organization(payload, signer, &mut state)
             }
             Action::UpdateOrganization(payload) => {
                 update_organization(payload, signer, &mut state)
             }
-        }
+            Action::ProposeOwnerChange(payload) => {
+                propose_owner_change(payload, signer, &mut state)
+            }
+            Action::VoteOwnerChange(payload) => vote_owner_change(payload, signer, &mut state),
+            Action::SetActionPolicy(payload) => set_action_policy(payload, signer, &mut state),
+            Action::UpgradeContract(upgrade_contract_payload) => upgrade_contract(
+                upgrade_contract_payload,
+                signer,
+                request.get_signature(),
+                &mut state,
+                &self.module_cache,
+                trace.as_ref(),
+            ),
+        };
+
+        self.write_trace(request, trace.as_ref());
+        result
     }
 }
 
@@ -195,6 +357,7 @@ fn create_contract(
     payload: CreateContractAction,
     signer: &str,
     state: &mut SmartState,
+    module_cache: &Mutex<ModuleCache>,
 ) -> Result<(), ApplyError> {
     let name = payload.get_name();
     let version = payload.get_version();
@@ -237,6 +400,14 @@ fn create_contract(
             signer,
         )));
     }
+    check_member_permission(state, signer, CONTRACT_UPLOAD_PERMISSION)?;
+
+    check_uploaded_contract(name, version, payload.get_contract(), state)?;
+
+    let mut sha = Sha512::new();
+    sha.input(payload.get_contract());
+    let contract_sha512 = sha.result_str();
+    check_contract_source(name, version, payload.get_source(), &contract_sha512)?;
 
     let contract = ContractBuilder::new()
         .set_name(name.into())
@@ -245,17 +416,16 @@ fn create_contract(
         .set_outputs(payload.get_outputs().to_vec())
         .set_creator(signer.into())
         .set_contract(payload.get_contract().to_vec())
+        .set_source(payload.get_source().cloned())
         .build()
         .map_err(|_| ApplyError::InvalidTransaction(String::from("Cannot build contract")))?;
 
     state.set_contract(name, version, contract)?;
-
-    let mut sha = Sha512::new();
-    sha.input(payload.get_contract());
+    invalidate_cached_modules(name, version, module_cache)?;
 
     let contract_registry_version = VersionBuilder::new()
         .set_version(version.into())
-        .set_contract_sha512(sha.result_str())
+        .set_contract_sha512(contract_sha512)
         .set_creator(signer.into())
         .build()
         .map_err(|_| {
@@ -280,6 +450,7 @@ fn delete_contract(
     payload: DeleteContractAction,
     signer: &str,
     state: &mut SmartState,
+    module_cache: &Mutex<ModuleCache>,
 ) -> Result<(), ApplyError> {
     let name = payload.get_name();
     let version = payload.get_version();
@@ -340,7 +511,8 @@ fn delete_contract(
         })?;
 
     state.set_contract_registry(name, contract_registry)?;
-    state.delete_contract(name, version)
+    state.delete_contract(name, version)?;
+    invalidate_cached_modules(name, version, module_cache)
 }
 
 fn execute_contract(
@@ -348,15 +520,67 @@ fn execute_contract(
     signer: &str,
     signature: &str,
     state: &mut SmartState,
+    module_cache: &Mutex<ModuleCache>,
+    trace: Option<&ExecutionTrace>,
+) -> Result<(), ApplyError> {
+    let name = payload.get_name();
+    let contract = get_requested_contract(name, payload.get_version(), state)?;
+    let version = contract.get_version();
+
+    for input in payload.get_inputs() {
+        check_namespace_permission(state, name, input, NamespaceAccess::Read)?;
+    }
+
+    for output in payload.get_outputs() {
+        check_namespace_permission(state, name, output, NamespaceAccess::Write)?;
+    }
+
+    let limits = get_execution_limits(state)?;
+    let fuel_limit = limits.fuel;
+
+    let compiled = get_compiled_module(&contract, limits.memory_pages, module_cache)?;
+    let authorized = AuthorizedAddresses::new(payload.get_inputs(), payload.get_outputs());
+    let mut module = WasmModule::new(compiled, state.context(), name, limits)
+        .authorize(authorized)
+        .cached(module_cache)
+        .traced(trace.cloned());
+
+    let result = module
+        .entrypoint(
+            payload.get_payload().to_vec(),
+            signer.into(),
+            signature.into(),
+        )
+        .map_err(|e| map_execution_error(name, version, e))?;
+
+    info!(
+        "Wasm contract used {} of {} fuel: {}, {}",
+        module.usage().fuel,
+        fuel_limit,
+        name,
+        version,
+    );
+
+    check_contract_result(name, version, result, module.error_data())
+}
+
+fn upgrade_contract(
+    payload: UpgradeContractAction,
+    signer: &str,
+    signature: &str,
+    state: &mut SmartState,
+    module_cache: &Mutex<ModuleCache>,
+    trace: Option<&ExecutionTrace>,
 ) -> Result<(), ApplyError> {
     let name = payload.get_name();
     let version = payload.get_version();
+    let previous_version = payload.get_previous_version();
 
-    let contract = match state.get_contract(name, version) {
-        Ok(Some(contract)) => contract,
-        Ok(None) => {
+    match state.get_contract(name, version) {
+        Ok(None) => (),
+        Ok(Some(_)) => {
             return Err(ApplyError::InvalidTransaction(format!(
-                "Contract does not exist: {}, {}",
+                "Contract already exists: {}, {}",
                 name, version,
             )));
         }
@@ -368,142 +592,145 @@ fn execute_contract(
         }
     };
 
-    for input in payload.get_inputs() {
-        let namespace = match input.get(..6) {
-            Some(namespace) => namespace,
-            None => {
-                return Err(ApplyError::InvalidTransaction(format!(
-                    "Input must have at least 6 characters: {}",
-                    input,
-                )));
-            }
-        };
-        let registries = match state.get_namespace_registries(namespace) {
-            Ok(Some(registries)) => registries,
-            Ok(None) => {
-                return Err(ApplyError::InvalidTransaction(format!(
-                    "Namespace Registry does not exist: {}",
-                    namespace,
-                )));
-            }
-            Err(err) => {
-                return Err(ApplyError::InvalidTransaction(format!(
-                    "Unable to check state: {}",
-                    err,
-                )));
-            }
-        };
-
-        let mut namespace_registry = None;
-        for registry in registries.get_registries() {
-            if input.starts_with(registry.get_namespace()) {
-                namespace_registry = Some(registry)
-            }
+    let contract_registry = match state.get_contract_registry(name) {
+        Ok(None) => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "The Contract Registry does not exist: {}",
+                name,
+            )));
         }
-
-        let mut permissioned = false;
-        match namespace_registry {
-            Some(registry) => {
-                for permission in registry.get_permissions() {
-                    if name == permission.get_contract_name() && permission.get_read() {
-                        permissioned = true;
-                        break;
-                    }
-                }
-                if !permissioned {
-                    return Err(ApplyError::InvalidTransaction(format!(
-                        "Contract does not have permission to read from state : {} {}",
-                        name, input
-                    )));
-                }
-            }
-            None => {
-                return Err(ApplyError::InvalidTransaction(format!(
-                    "No namespace registry exists for namespace: {} input: {}",
-                    namespace, input
-                )));
-            }
+        Ok(Some(contract_registry)) => contract_registry,
+        Err(err) => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Unable to check state: {}",
+                err,
+            )));
         }
+    };
+
+    if !contract_registry.get_owners().contains(&signer.into()) {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Only owners can upgrade contracts: {}",
+            signer,
+        )));
     }
+    check_member_permission(state, signer, CONTRACT_UPLOAD_PERMISSION)?;
 
-    for output in payload.get_outputs() {
-        let namespace = match output.get(..6) {
-            Some(namespace) => namespace,
-            None => {
-                return Err(ApplyError::InvalidTransaction(format!(
-                    "Output must have at least 6 characters: {}",
-                    output,
-                )));
-            }
-        };
-        let registries = match state.get_namespace_registries(namespace) {
-            Ok(Some(registries)) => registries,
-            Ok(None) => {
-                return Err(ApplyError::InvalidTransaction(format!(
-                    "Namespace Registry does not exist: {}",
-                    namespace,
-                )));
-            }
-            Err(err) => {
-                return Err(ApplyError::InvalidTransaction(format!(
-                    "Unable to check state: {}",
-                    err,
-                )));
-            }
-        };
+    check_uploaded_contract(name, version, payload.get_contract(), state)?;
 
-        let mut namespace_registry = None;
-        for registry in registries.get_registries() {
-            if output.starts_with(registry.get_namespace()) {
-                namespace_registry = Some(registry)
-            }
+    let mut sha = Sha512::new();
+    sha.input(payload.get_contract());
+    let contract_sha512 = sha.result_str();
+    check_contract_source(name, version, payload.get_source(), &contract_sha512)?;
+
+    match contract_registry
+        .get_versions()
+        .iter()
+        .find(|registry_version| registry_version.get_version() == previous_version)
+    {
+        Some(registry_version) if registry_version.get_deprecated() => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Contract version is already deprecated: {}, {}",
+                name, previous_version,
+            )));
         }
-        let mut permissioned = false;
-        match namespace_registry {
-            Some(registry) => {
-                for permission in registry.get_permissions() {
-                    if name == permission.get_contract_name() && permission.get_write() {
-                        permissioned = true;
-                        break;
-                    }
-                }
-                if !permissioned {
-                    return Err(ApplyError::InvalidTransaction(format!(
-                        "Contract does not have permission to write to state: {}, {}",
-                        name, output
-                    )));
-                }
-            }
-            None => {
-                return Err(ApplyError::InvalidTransaction(format!(
-                    "No namespace registry exists for namespace: {} output: {}",
-                    namespace, output
-                )));
-            }
+        Some(_) => (),
+        None => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Contract version does not exist: {}, {}",
+                name, previous_version,
+            )));
+        }
+    };
+
+    let contract = ContractBuilder::new()
+        .set_name(name.into())
+        .set_version(version.into())
+        .set_inputs(payload.get_inputs().to_vec())
+        .set_outputs(payload.get_outputs().to_vec())
+        .set_creator(signer.into())
+        .set_contract(payload.get_contract().to_vec())
+        .set_source(payload.get_source().cloned())
+        .build()
+        .map_err(|_| ApplyError::InvalidTransaction(String::from("Cannot build contract")))?;
+
+    state.set_contract(name, version, contract.clone())?;
+    invalidate_cached_modules(name, version, module_cache)?;
+
+    let contract_registry_version = VersionBuilder::new()
+        .set_version(version.into())
+        .set_contract_sha512(contract_sha512)
+        .set_creator(signer.into())
+        .build()
+        .map_err(|_| {
+            ApplyError::InvalidTransaction(String::from("Cannot build contract version"))
+        })?;
+
+    let mut versions = Vec::new();
+    for registry_version in contract_registry.get_versions() {
+        if registry_version.get_version() == previous_version {
+            versions.push(
+                registry_version
+                    .clone()
+                    .into_builder()
+                    .set_deprecated(true)
+                    .build()
+                    .map_err(|_| {
+                        ApplyError::InvalidTransaction(String::from(
+                            "Cannot build contract version",
+                        ))
+                    })?,
+            );
+        } else {
+            versions.push(registry_version.clone());
         }
     }
+    versions.push(contract_registry_version);
+
+    let contract_registry = contract_registry
+        .into_builder()
+        .set_versions(versions)
+        .build()
+        .map_err(|_| {
+            ApplyError::InvalidTransaction(String::from("Cannot build contract registry"))
+        })?;
+
+    state.set_contract_registry(name, contract_registry)?;
+
+    // run the migration of the new version, if it exports one; any failure
+    // invalidates the transaction, so the upgrade is not applied either
+    for input in payload.get_inputs() {
+        check_namespace_permission(state, name, input, NamespaceAccess::Read)?;
+    }
+
+    for output in payload.get_outputs() {
+        check_namespace_permission(state, name, output, NamespaceAccess::Write)?;
+    }
 
-    let mut module = WasmModule::new(contract.get_contract(), state.context())
-        .expect("Failed to create can_add module");
+    let limits = get_execution_limits(state)?;
+    let compiled = get_compiled_module(&contract, limits.memory_pages, module_cache)?;
+    let authorized = AuthorizedAddresses::new(payload.get_inputs(), payload.get_outputs());
+    let mut module = WasmModule::new(compiled, state.context(), name, limits)
+        .authorize(authorized)
+        .traced(trace.cloned());
 
     let result = module
-        .entrypoint(payload.get_payload().to_vec(), signer.into(), signature.into())
-        .map_err(|e| ApplyError::InvalidTransaction(format!("{:?}", e)))?;
+        .migrate(
+            payload.get_migrate_payload().to_vec(),
+            signer.into(),
+            signature.into(),
+        )
+        .map_err(|e| map_execution_error(name, version, e))?;
 
     match result {
-        None => Err(ApplyError::InvalidTransaction(format!(
-            "Wasm contract did not return a result: {}, {}",
-            name, version,
-        ))),
-        Some(1) => Ok(()),
-        Some(-3) => Err(ApplyError::InvalidTransaction(format!(
-            "Wasm contract returned invalid transaction: {}, {}",
-            name, version,
-        ))),
-        Some(num) => Err(ApplyError::InternalError(format!(
-            "Wasm contract returned internal error: {}",
-            num
-        ))),
+        None => {
+            info!(
+                "Wasm contract does not export migrate: {}, {}",
+                name, version
+            );
+            Ok(())
+        }
+        Some(_) => check_contract_result(name, version, result, module.error_data()),
     }
 }
 
@@ -562,6 +789,7 @@ fn create_contract_registry(
     let contract_registry = ContractRegistryBuilder::new()
         .set_name(name.into())
         .set_owners(payload.get_owners().to_vec())
+        .set_owner_threshold(payload.get_owner_threshold())
         .build()
         .map_err(|_| {
             ApplyError::InvalidTransaction(String::from("Cannot build contract registry"))
@@ -630,6 +858,13 @@ fn update_contract_registry_owners(
     // Check if signer is an owner or an admin
     can_update_contract_registry(contract_registry.clone(), signer, state)?;
 
+    if contract_registry.get_owner_threshold() > 1 {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Contract Registry owners can only be changed through an owner change proposal: {}",
+            name,
+        )));
+    }
+
     let contract_registry = contract_registry
         .into_builder()
         .set_owners(payload.get_owners().to_vec())
@@ -703,6 +938,7 @@ fn create_namespace_registry(
     let namespace_registry = NamespaceRegistryBuilder::new()
         .set_namespace(namespace.into())
         .set_owners(payload.get_owners().to_vec())
+        .set_owner_threshold(payload.get_owner_threshold())
         .build()
         .map_err(|_| {
             ApplyError::InvalidTransaction(String::from("Cannot build namespace registry"))
@@ -769,6 +1005,14 @@ fn update_namespace_registry_owners(
 
     // Check if signer is an owner or an admin
     can_update_namespace_registry(namespace_registry.clone(), signer, state)?;
+
+    if namespace_registry.get_owner_threshold() > 1 {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Namespace Registry owners can only be changed through an owner change proposal: {}",
+            namespace,
+        )));
+    }
+
     let namespace_registry = namespace_registry
         .into_builder()
         .set_owners(payload.get_owners().to_vec())
@@ -780,47 +1024,216 @@ fn update_namespace_registry_owners(
     state.set_namespace_registry(namespace, namespace_registry)
 }
 
-fn create_namespace_registry_permission(
-    payload: CreateNamespaceRegistryPermissionAction,
+fn propose_owner_change(
+    payload: ProposeOwnerChangeAction,
     signer: &str,
     state: &mut SmartState,
 ) -> Result<(), ApplyError> {
-    let namespace = payload.get_namespace();
-    let contract_name = payload.get_contract_name();
-    let namespace_registry = match state.get_namespace_registry(namespace) {
-        Ok(None) => {
-            return Err(ApplyError::InvalidTransaction(format!(
-                "Namespace Registry does not exist: {}",
-                namespace,
-            )));
-        }
-        Ok(Some(namespace_registry)) => namespace_registry,
-        Err(err) => {
-            return Err(ApplyError::InvalidTransaction(format!(
-                "Unable to check state: {}",
-                err,
-            )));
+    let block_num = state.get_block_num()?;
+    let name = payload.get_name();
+
+    match payload.get_registry_type() {
+        RegistryType::ContractRegistry => {
+            let contract_registry = get_contract_registry(name, state)?;
+            let status = owner_change::propose(
+                contract_registry.get_owners(),
+                contract_registry.get_owner_threshold(),
+                contract_registry.get_owner_change_proposal(),
+                &payload,
+                signer,
+                block_num,
+            )?;
+            let contract_registry =
+                apply_contract_registry_owner_change(contract_registry, status)?;
+            state.set_contract_registry(name, contract_registry)
+        }
+        RegistryType::NamespaceRegistry => {
+            let namespace_registry = get_namespace_registry(name, state)?;
+            let status = owner_change::propose(
+                namespace_registry.get_owners(),
+                namespace_registry.get_owner_threshold(),
+                namespace_registry.get_owner_change_proposal(),
+                &payload,
+                signer,
+                block_num,
+            )?;
+            let namespace_registry =
+                apply_namespace_registry_owner_change(namespace_registry, status)?;
+            state.set_namespace_registry(name, namespace_registry)
         }
-    };
-    // Check if signer is an owner or an admin
-    can_update_namespace_registry(namespace_registry.clone(), signer, state)?;
+    }
+}
 
-    let new_permission = PermissionBuilder::new()
-        .set_contract_name(contract_name.into())
-        .set_read(payload.get_read())
-        .set_write(payload.get_write())
-        .build()
-        .map_err(|_| {
-            ApplyError::InvalidTransaction(String::from(
-                "Cannot build namespace registry permission",
-            ))
-        })?;
+fn vote_owner_change(
+    payload: VoteOwnerChangeAction,
+    signer: &str,
+    state: &mut SmartState,
+) -> Result<(), ApplyError> {
+    let block_num = state.get_block_num()?;
+    let name = payload.get_name();
+
+    match payload.get_registry_type() {
+        RegistryType::ContractRegistry => {
+            let contract_registry = get_contract_registry(name, state)?;
+            let status = owner_change::vote(
+                contract_registry.get_owners(),
+                contract_registry.get_owner_threshold(),
+                contract_registry.get_owner_change_proposal(),
+                payload.get_registry_type(),
+                name,
+                payload.get_approve(),
+                signer,
+                block_num,
+            )?;
+            let contract_registry =
+                apply_contract_registry_owner_change(contract_registry, status)?;
+            state.set_contract_registry(name, contract_registry)
+        }
+        RegistryType::NamespaceRegistry => {
+            let namespace_registry = get_namespace_registry(name, state)?;
+            let status = owner_change::vote(
+                namespace_registry.get_owners(),
+                namespace_registry.get_owner_threshold(),
+                namespace_registry.get_owner_change_proposal(),
+                payload.get_registry_type(),
+                name,
+                payload.get_approve(),
+                signer,
+                block_num,
+            )?;
+            let namespace_registry =
+                apply_namespace_registry_owner_change(namespace_registry, status)?;
+            state.set_namespace_registry(name, namespace_registry)
+        }
+    }
+}
+
+// helper function to load a contract registry that must exist
+fn get_contract_registry(
+    name: &str,
+    state: &mut SmartState,
+) -> Result<ContractRegistry, ApplyError> {
+    match state.get_contract_registry(name) {
+        Ok(None) => Err(ApplyError::InvalidTransaction(format!(
+            "Contract Registry does not exist: {}",
+            name,
+        ))),
+        Ok(Some(contract_registry)) => Ok(contract_registry),
+        Err(err) => Err(ApplyError::InvalidTransaction(format!(
+            "Unable to check state: {}",
+            err,
+        ))),
+    }
+}
+
+// helper function to load a namespace registry that must exist
+fn get_namespace_registry(
+    namespace: &str,
+    state: &mut SmartState,
+) -> Result<NamespaceRegistry, ApplyError> {
+    match state.get_namespace_registry(namespace) {
+        Ok(None) => Err(ApplyError::InvalidTransaction(format!(
+            "Namespace Registry does not exist: {}",
+            namespace,
+        ))),
+        Ok(Some(namespace_registry)) => Ok(namespace_registry),
+        Err(err) => Err(ApplyError::InvalidTransaction(format!(
+            "Unable to check state: {}",
+            err,
+        ))),
+    }
+}
+
+fn apply_contract_registry_owner_change(
+    contract_registry: ContractRegistry,
+    status: ProposalStatus,
+) -> Result<ContractRegistry, ApplyError> {
+    let builder = contract_registry.into_builder();
+    let builder = match status {
+        ProposalStatus::Pending(proposal) => builder.set_owner_change_proposal(Some(proposal)),
+        ProposalStatus::Approved(proposal) => builder
+            .set_owners(proposal.get_owners().to_vec())
+            .set_owner_threshold(proposal.get_owner_threshold())
+            .set_owner_change_proposal(None),
+        ProposalStatus::Rejected => builder.set_owner_change_proposal(None),
+    };
+
+    builder.build().map_err(|_| {
+        ApplyError::InvalidTransaction(String::from("Cannot build contract registry"))
+    })
+}
+
+fn apply_namespace_registry_owner_change(
+    namespace_registry: NamespaceRegistry,
+    status: ProposalStatus,
+) -> Result<NamespaceRegistry, ApplyError> {
+    let builder = namespace_registry.into_builder();
+    let builder = match status {
+        ProposalStatus::Pending(proposal) => builder.set_owner_change_proposal(Some(proposal)),
+        ProposalStatus::Approved(proposal) => builder
+            .set_owners(proposal.get_owners().to_vec())
+            .set_owner_threshold(proposal.get_owner_threshold())
+            .set_owner_change_proposal(None),
+        ProposalStatus::Rejected => builder.set_owner_change_proposal(None),
+    };
+
+    builder.build().map_err(|_| {
+        ApplyError::InvalidTransaction(String::from("Cannot build namespace registry"))
+    })
+}
+
+fn create_namespace_registry_permission(
+    payload: CreateNamespaceRegistryPermissionAction,
+    signer: &str,
+    state: &mut SmartState,
+) -> Result<(), ApplyError> {
+    let namespace = payload.get_namespace();
+    let contract_name = payload.get_contract_name();
+    let namespace_registry = match state.get_namespace_registry(namespace) {
+        Ok(None) => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Namespace Registry does not exist: {}",
+                namespace,
+            )));
+        }
+        Ok(Some(namespace_registry)) => namespace_registry,
+        Err(err) => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Unable to check state: {}",
+                err,
+            )));
+        }
+    };
+    // Check if signer is an owner or an admin
+    can_update_namespace_registry(namespace_registry.clone(), signer, state)?;
+
+    let prefix = payload.get_prefix();
+    if !prefix.is_empty() && !prefix.starts_with(namespace) {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Permission prefix must start with the namespace {}: {}",
+            namespace, prefix,
+        )));
+    }
+
+    let new_permission = PermissionBuilder::new()
+        .set_contract_name(contract_name.into())
+        .set_read(payload.get_read())
+        .set_write(payload.get_write())
+        .set_prefix(prefix.into())
+        .set_deny(payload.get_deny())
+        .build()
+        .map_err(|_| {
+            ApplyError::InvalidTransaction(String::from(
+                "Cannot build namespace registry permission",
+            ))
+        })?;
 
-    // remove old permission for contract if one exists and replace with the new permission
+    // remove old permission for contract and prefix if one exists and replace with the new
+    // permission
     let mut permissions = namespace_registry.get_permissions().to_vec();
     let mut index = None;
     for (count, permission) in permissions.iter().enumerate() {
-        if permission.get_contract_name() == contract_name {
+        if permission.get_contract_name() == contract_name && permission.get_prefix() == prefix {
             index = Some(count);
             break;
         }
@@ -869,11 +1282,12 @@ fn delete_namespace_registry_permission(
     // Check if signer is an owner or an admin
     can_update_namespace_registry(namespace_registry.clone(), signer, state)?;
 
-    // remove old permission for contract
+    // remove old permission for contract and prefix
+    let prefix = payload.get_prefix();
     let mut permissions = namespace_registry.get_permissions().to_vec();
     let mut index = None;
     for (count, permission) in permissions.iter().enumerate() {
-        if permission.get_contract_name() == contract_name {
+        if permission.get_contract_name() == contract_name && permission.get_prefix() == prefix {
             index = Some(count);
             break;
         }
@@ -901,56 +1315,18 @@ fn delete_namespace_registry_permission(
     state.set_namespace_registry(namespace, namespace_registry)
 }
 
-pub(crate) fn is_admin(
-    signer: &str,
-    org_id: &str,
-    state: &mut SmartState,
-) -> Result<(), ApplyError> {
-    let admin = match state.get_account(signer) {
-        Ok(None) => {
-            return Err(ApplyError::InvalidTransaction(format!(
-                "Signer is not an agent: {}",
-                signer,
-            )));
-        }
-        Ok(Some(admin)) => admin,
-        Err(err) => {
-            return Err(ApplyError::InvalidTransaction(format!(
-                "Failed to retrieve state: {}",
-                err,
-            )));
-        }
-    };
-
-    if admin.get_org_id() != org_id {
-        return Err(ApplyError::InvalidTransaction(format!(
-            "Signer is not associated with the organization: {}",
-            signer,
-        )));
-    }
-    if !admin.get_roles().contains(&"admin".to_string()) {
-        return Err(ApplyError::InvalidTransaction(format!(
-            "Signer is not an admin: {}",
-            signer,
-        )));
-    };
-
-    if !admin.get_active() {
-        return Err(ApplyError::InvalidTransaction(format!(
-            "Admin is not currently an active agent: {}",
-            signer,
-        )));
-    }
-    Ok(())
-}
-
 fn create_smart_permission(
     payload: CreateSmartPermissionAction,
     signer: &str,
     state: &mut SmartState,
 ) -> Result<(), ApplyError> {
     // verify the signer of the transaction is authorized to create smart permissions
-    is_admin(signer, payload.get_org_id(), state)?;
+    check_permission(
+        state,
+        signer,
+        payload.get_org_id(),
+        SMART_PERMISSION_CREATE_PERMISSION,
+    )?;
 
     // Check if the smart permissions already exists
     match state.get_smart_permission(payload.get_org_id(), payload.get_name()) {
@@ -1004,7 +1380,12 @@ fn update_smart_permission(
     state: &mut SmartState,
 ) -> Result<(), ApplyError> {
     // verify the signer of the transaction is authorized to update smart permissions
-    is_admin(signer, payload.get_org_id(), state)?;
+    check_permission(
+        state,
+        signer,
+        payload.get_org_id(),
+        SMART_PERMISSION_UPDATE_PERMISSION,
+    )?;
 
     // verify that the smart permission exists
     let smart_permission = match state.get_smart_permission(payload.get_org_id(), payload.get_name()) {
@@ -1039,7 +1420,12 @@ fn delete_smart_permission(
     state: &mut SmartState,
 ) -> Result<(), ApplyError> {
     // verify the signer of the transaction is authorized to delete smart permissions
-    is_admin(signer, payload.get_org_id(), state)?;
+    check_permission(
+        state,
+        signer,
+        payload.get_org_id(),
+        SMART_PERMISSION_DELETE_PERMISSION,
+    )?;
 
     // verify that the smart permission exists
     match state.get_smart_permission(payload.get_org_id(), payload.get_name()) {
@@ -1067,7 +1453,15 @@ fn create_account(
     state: &mut SmartState,
 ) -> Result<(), ApplyError> {
     // verify the signer of the transaction is authorized to create account
-    is_admin(signer, payload.get_org_id(), state)?;
+    let granted = check_permission(
+        state,
+        signer,
+        payload.get_org_id(),
+        ACCOUNT_CREATE_PERMISSION,
+    )?;
+    for role in payload.get_roles() {
+        check_can_grant_role(state, &granted, payload.get_org_id(), role)?;
+    }
 
     // Check if the account already exists
     match state.get_account(payload.get_public_key()) {
@@ -1106,12 +1500,11 @@ fn create_account(
     let account = AccountBuilder::new()
         .set_public_key(payload.get_public_key().to_string())
         .set_org_id(payload.get_org_id().to_string())
+        .set_active(true)
         .set_roles(payload.get_roles().to_vec())
-        //.set_metadata(payload.get_metadata().to_vec())
+        .set_metadata(payload.get_metadata().to_vec())
         .build()
-        .map_err(|_| {
-            ApplyError::InvalidTransaction(String::from("Cannot build account"))
-        })?;
+        .map_err(|err| ApplyError::InvalidTransaction(format!("Cannot build account: {}", err)))?;
 
     state.set_account(payload.get_public_key(), account)
 }
@@ -1121,15 +1514,401 @@ fn update_account(
     signer: &str,
     state: &mut SmartState,
 ) -> Result<(), ApplyError> {
-    // verify the signer of the transaction is authorized to update account
-    is_admin(signer, payload.get_org_id(), state)?;
+    let granted = get_signer_permissions(state, signer, payload.get_org_id())?;
+
+    // verify that the account exists and belongs to the organization the signer acts for, so
+    // the account cannot be moved to another organization
+    let account = get_org_account(payload.get_org_id(), payload.get_public_key(), state)?;
+
+    let granted_roles: Vec<String> = payload
+        .get_roles()
+        .iter()
+        .filter(|role| !account.get_roles().contains(role))
+        .cloned()
+        .collect();
+    let revoked_roles: Vec<String> = account
+        .get_roles()
+        .iter()
+        .filter(|role| !payload.get_roles().contains(role))
+        .cloned()
+        .collect();
+
+    let metadata = update_metadata(
+        account.get_metadata(),
+        payload.get_metadata(),
+        payload.get_metadata_mode(),
+    );
+
+    // verify the signer holds the permission for each part of the account that changes, the
+    // same permissions the GrantRole and RevokeRole actions require for roles
+    let roles_changed = !granted_roles.is_empty() || !revoked_roles.is_empty();
+    if metadata.as_slice() != account.get_metadata() || !roles_changed {
+        check_granted(&granted, signer, ACCOUNT_UPDATE_PERMISSION)?;
+    }
+    if roles_changed {
+        check_granted(&granted, signer, ACCOUNT_GRANT_ROLE_PERMISSION)?;
+    }
+
+    check_can_change_account(&granted, &account)?;
+    for role in &granted_roles {
+        check_can_grant_role(state, &granted, payload.get_org_id(), role)?;
+    }
+
+    // an admin cannot take away their own admin rights, so the organization always keeps at
+    // least one active admin
+    if payload.get_public_key() == signer
+        && granted.is_admin()
+        && revoked_roles.iter().any(|role| role == ADMIN_ROLE)
+    {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Admin cannot remove the admin role from their own account: {}",
+            signer,
+        )));
+    }
+
+    let account = account
+        .into_builder()
+        .set_roles(payload.get_roles().to_vec())
+        .set_metadata(metadata)
+        .build()
+        .map_err(|err| ApplyError::InvalidTransaction(format!("Cannot build account: {}", err)))?;
+    state.set_account(payload.get_public_key(), account)?;
+
+    // role changes are recorded like those of the GrantRole and RevokeRole actions
+    for role in &granted_roles {
+        add_account_change_receipt(
+            AccountChangeType::RoleGranted,
+            payload.get_org_id(),
+            payload.get_public_key(),
+            signer,
+            role,
+            state,
+        )?;
+    }
+    for role in &revoked_roles {
+        add_account_change_receipt(
+            AccountChangeType::RoleRevoked,
+            payload.get_org_id(),
+            payload.get_public_key(),
+            signer,
+            role,
+            state,
+        )?;
+    }
+    Ok(())
+}
+
+fn deactivate_account(
+    payload: DeactivateAccountAction,
+    signer: &str,
+    state: &mut SmartState,
+) -> Result<(), ApplyError> {
+    // verify the signer of the transaction is authorized to deactivate accounts
+    let granted = check_permission(
+        state,
+        signer,
+        payload.get_org_id(),
+        ACCOUNT_DEACTIVATE_PERMISSION,
+    )?;
+
+    // the signer remains active, so an organization cannot lose its last active admin
+    if payload.get_public_key() == signer {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Signer cannot deactivate their own account: {}",
+            signer,
+        )));
+    }
+
+    let account = get_org_account(payload.get_org_id(), payload.get_public_key(), state)?;
+    check_can_change_account(&granted, &account)?;
+    if !*account.get_active() {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Account is already inactive: {}",
+            payload.get_public_key(),
+        )));
+    }
+
+    let account = account
+        .into_builder()
+        .set_active(false)
+        .build()
+        .map_err(|err| ApplyError::InvalidTransaction(format!("Cannot build account: {}", err)))?;
+    state.set_account(payload.get_public_key(), account)?;
+
+    add_account_change_receipt(
+        AccountChangeType::Deactivated,
+        payload.get_org_id(),
+        payload.get_public_key(),
+        signer,
+        "",
+        state,
+    )
+}
+
+fn reactivate_account(
+    payload: ReactivateAccountAction,
+    signer: &str,
+    state: &mut SmartState,
+) -> Result<(), ApplyError> {
+    // verify the signer of the transaction is authorized to reactivate accounts
+    let granted = check_permission(
+        state,
+        signer,
+        payload.get_org_id(),
+        ACCOUNT_DEACTIVATE_PERMISSION,
+    )?;
+
+    let account = get_org_account(payload.get_org_id(), payload.get_public_key(), state)?;
+    check_can_change_account(&granted, &account)?;
+    if *account.get_active() {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Account is already active: {}",
+            payload.get_public_key(),
+        )));
+    }
+
+    let account = account
+        .into_builder()
+        .set_active(true)
+        .build()
+        .map_err(|err| ApplyError::InvalidTransaction(format!("Cannot build account: {}", err)))?;
+    state.set_account(payload.get_public_key(), account)?;
+
+    add_account_change_receipt(
+        AccountChangeType::Reactivated,
+        payload.get_org_id(),
+        payload.get_public_key(),
+        signer,
+        "",
+        state,
+    )
+}
+
+fn grant_role(
+    payload: GrantRoleAction,
+    signer: &str,
+    state: &mut SmartState,
+) -> Result<(), ApplyError> {
+    // verify the signer of the transaction is authorized to grant roles
+    let granted = check_permission(
+        state,
+        signer,
+        payload.get_org_id(),
+        ACCOUNT_GRANT_ROLE_PERMISSION,
+    )?;
+    check_can_grant_role(state, &granted, payload.get_org_id(), payload.get_role())?;
+
+    let account = get_org_account(payload.get_org_id(), payload.get_public_key(), state)?;
+    if account
+        .get_roles()
+        .iter()
+        .any(|role| role == payload.get_role())
+    {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Account {} already has role: {}",
+            payload.get_public_key(),
+            payload.get_role(),
+        )));
+    }
+
+    let mut roles = account.get_roles().to_vec();
+    roles.push(payload.get_role().to_string());
+
+    let account = account
+        .into_builder()
+        .set_roles(roles)
+        .build()
+        .map_err(|err| ApplyError::InvalidTransaction(format!("Cannot build account: {}", err)))?;
+    state.set_account(payload.get_public_key(), account)?;
+
+    add_account_change_receipt(
+        AccountChangeType::RoleGranted,
+        payload.get_org_id(),
+        payload.get_public_key(),
+        signer,
+        payload.get_role(),
+        state,
+    )
+}
+
+fn revoke_role(
+    payload: RevokeRoleAction,
+    signer: &str,
+    state: &mut SmartState,
+) -> Result<(), ApplyError> {
+    // verify the signer of the transaction is authorized to revoke roles
+    let granted = check_permission(
+        state,
+        signer,
+        payload.get_org_id(),
+        ACCOUNT_GRANT_ROLE_PERMISSION,
+    )?;
+
+    // the signer remains an active admin, so the organization cannot lose its last one
+    if payload.get_public_key() == signer && payload.get_role() == ADMIN_ROLE {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Admin cannot revoke the admin role from their own account: {}",
+            signer,
+        )));
+    }
+
+    let account = get_org_account(payload.get_org_id(), payload.get_public_key(), state)?;
+    check_can_change_account(&granted, &account)?;
+    if !account
+        .get_roles()
+        .iter()
+        .any(|role| role == payload.get_role())
+    {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Account {} does not have role: {}",
+            payload.get_public_key(),
+            payload.get_role(),
+        )));
+    }
+
+    let roles = account
+        .get_roles()
+        .iter()
+        .filter(|role| *role != payload.get_role())
+        .cloned()
+        .collect();
+
+    let account = account
+        .into_builder()
+        .set_roles(roles)
+        .build()
+        .map_err(|err| ApplyError::InvalidTransaction(format!("Cannot build account: {}", err)))?;
+    state.set_account(payload.get_public_key(), account)?;
+
+    add_account_change_receipt(
+        AccountChangeType::RoleRevoked,
+        payload.get_org_id(),
+        payload.get_public_key(),
+        signer,
+        payload.get_role(),
+        state,
+    )
+}
+
+fn create_role(
+    payload: CreateRoleAction,
+    signer: &str,
+    state: &mut SmartState,
+) -> Result<(), ApplyError> {
+    // verify the signer of the transaction is authorized to create roles
+    let granted = check_permission(state, signer, payload.get_org_id(), ROLE_CREATE_PERMISSION)?;
+    check_holds_permissions(&granted, payload.get_permissions())?;
+
+    if payload.get_name() == ADMIN_ROLE {
+        return Err(ApplyError::InvalidTransaction(String::from(
+            "The admin role is reserved and grants every permission",
+        )));
+    }
+
+    // Check if the role already exists
+    match state.get_role(payload.get_org_id(), payload.get_name()) {
+        Ok(None) => (),
+        Ok(Some(_)) => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Role already exists: {} ",
+                payload.get_name(),
+            )));
+        }
+        Err(err) => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Failed to retrieve state: {}",
+                err,
+            )));
+        }
+    };
+
+    let role = RoleBuilder::new()
+        .set_org_id(payload.get_org_id().to_string())
+        .set_name(payload.get_name().to_string())
+        .set_permissions(payload.get_permissions().to_vec())
+        .build()
+        .map_err(|err| ApplyError::InvalidTransaction(format!("Cannot build role: {}", err)))?;
+
+    state.set_role(payload.get_org_id(), payload.get_name(), role)
+}
+
+fn update_role(
+    payload: UpdateRoleAction,
+    signer: &str,
+    state: &mut SmartState,
+) -> Result<(), ApplyError> {
+    // verify the signer of the transaction is authorized to update roles
+    let granted = check_permission(state, signer, payload.get_org_id(), ROLE_UPDATE_PERMISSION)?;
+
+    // verify that the role exists
+    let role = match state.get_role(payload.get_org_id(), payload.get_name()) {
+        Ok(None) => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Role does not exist: {} ",
+                payload.get_name(),
+            )));
+        }
+        Ok(Some(role)) => role,
+        Err(err) => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Failed to retrieve state: {}",
+                err,
+            )));
+        }
+    };
+
+    // the signer must hold both the permissions taken away and the ones added
+    check_holds_permissions(&granted, role.get_permissions())?;
+    check_holds_permissions(&granted, payload.get_permissions())?;
+
+    let role = role
+        .into_builder()
+        .set_permissions(payload.get_permissions().to_vec())
+        .build()
+        .map_err(|err| ApplyError::InvalidTransaction(format!("Cannot build role: {}", err)))?;
+    state.set_role(payload.get_org_id(), payload.get_name(), role)
+}
+
+fn delete_role(
+    payload: DeleteRoleAction,
+    signer: &str,
+    state: &mut SmartState,
+) -> Result<(), ApplyError> {
+    // verify the signer of the transaction is authorized to delete roles
+    let granted = check_permission(state, signer, payload.get_org_id(), ROLE_DELETE_PERMISSION)?;
+
+    // verify that the role exists
+    let role = match state.get_role(payload.get_org_id(), payload.get_name()) {
+        Ok(None) => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Role does not exist: {} ",
+                payload.get_name(),
+            )));
+        }
+        Ok(Some(role)) => role,
+        Err(err) => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Failed to retrieve state: {}",
+                err,
+            )));
+        }
+    };
+    check_holds_permissions(&granted, role.get_permissions())?;
+
+    state.delete_role(payload.get_org_id(), payload.get_name())
+}
 
-    // verify that the account exists
-    let account = match state.get_account(payload.get_public_key()) {
+// helper function to load an account that must belong to the given organization
+fn get_org_account(
+    org_id: &str,
+    public_key: &str,
+    state: &mut SmartState,
+) -> Result<Account, ApplyError> {
+    let account = match state.get_account(public_key) {
         Ok(None) => {
             return Err(ApplyError::InvalidTransaction(format!(
                 "Account does not exist: {} ",
-                payload.get_public_key(),
+                public_key,
             )));
         }
         Ok(Some(account)) => account,
@@ -1141,16 +1920,44 @@ fn update_account(
         }
     };
 
-    let account = account
-        .into_builder()
-        .set_org_id(payload.get_org_id().to_string())
-        .set_roles(payload.get_roles().to_vec())
-        //.set_metadata(payload.get_metadata().to_vec())
+    if account.get_org_id() != org_id {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Account is not associated with the organization: {}",
+            public_key,
+        )));
+    }
+
+    Ok(account)
+}
+
+// helper function to record a change to an account in the transaction receipt
+fn add_account_change_receipt(
+    change_type: AccountChangeType,
+    org_id: &str,
+    public_key: &str,
+    signer: &str,
+    role: &str,
+    state: &mut SmartState,
+) -> Result<(), ApplyError> {
+    let receipt = AccountChangeReceiptBuilder::new()
+        .set_change_type(change_type)
+        .set_org_id(org_id.to_string())
+        .set_public_key(public_key.to_string())
+        .set_signer(signer.to_string())
+        .set_role(role.to_string())
         .build()
-        .map_err(|_| {
-            ApplyError::InvalidTransaction(String::from("Cannot build account"))
+        .map_err(|err| {
+            ApplyError::InvalidTransaction(format!("Cannot build account receipt: {}", err))
         })?;
-    state.set_account(payload.get_public_key(), account)
+
+    let bytes = receipt.into_bytes().map_err(|err| {
+        ApplyError::InvalidTransaction(format!("Cannot serialize account receipt: {}", err))
+    })?;
+
+    state
+        .context()
+        .add_receipt_data(&bytes)
+        .map_err(|err| ApplyError::InternalError(format!("Cannot add receipt data: {}", err)))
 }
 
 fn create_organization(
@@ -1159,7 +1966,12 @@ fn create_organization(
     state: &mut SmartState,
 ) -> Result<(), ApplyError> {
     // verify the signer of the transaction is authorized to create organization
-    is_admin(signer, payload.get_id(), state)?;
+    check_permission(
+        state,
+        signer,
+        payload.get_id(),
+        ORGANIZATION_CREATE_PERMISSION,
+    )?;
 
     // Check if the organization already exists
     match state.get_organization(payload.get_id()) {
@@ -1182,9 +1994,10 @@ fn create_organization(
         .set_org_id(payload.get_id().to_string())
         .set_name(payload.get_name().to_string())
         .set_address(payload.get_address().to_string())
+        .set_metadata(payload.get_metadata().to_vec())
         .build()
-        .map_err(|_| {
-            ApplyError::InvalidTransaction(String::from("Cannot build organization"))
+        .map_err(|err| {
+            ApplyError::InvalidTransaction(format!("Cannot build organization: {}", err))
         })?;
 
     state.set_organization(payload.get_id(), organization)
@@ -1196,7 +2009,12 @@ fn update_organization(
     state: &mut SmartState,
 ) -> Result<(), ApplyError> {
     // verify the signer of the transaction is authorized to update organization
-    is_admin(signer, payload.get_id(), state)?;
+    check_permission(
+        state,
+        signer,
+        payload.get_id(),
+        ORGANIZATION_UPDATE_PERMISSION,
+    )?;
 
     // verify that the organization exists
     let organization = match state.get_organization(payload.get_id()) {
@@ -1215,17 +2033,522 @@ fn update_organization(
         }
     };
 
+    let metadata = update_metadata(
+        organization.get_metadata(),
+        payload.get_metadata(),
+        payload.get_metadata_mode(),
+    );
+
     let organization = organization
         .into_builder()
         .set_name(payload.get_name().to_string())
         .set_address(payload.get_address().to_string())
+        .set_metadata(metadata)
         .build()
-        .map_err(|_| {
-            ApplyError::InvalidTransaction(String::from("Cannot build organization"))
+        .map_err(|err| {
+            ApplyError::InvalidTransaction(format!("Cannot build organization: {}", err))
         })?;
     state.set_organization(payload.get_id(), organization)
 }
 
+fn set_action_policy(
+    payload: SetActionPolicyAction,
+    signer: &str,
+    state: &mut SmartState,
+) -> Result<(), ApplyError> {
+    // only admins may change the policies that govern the organization's accounts
+    let granted = get_signer_permissions(state, signer, payload.get_org_id())?;
+    if !granted.is_admin() {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Only admins can set action policies: {}",
+            signer,
+        )));
+    }
+
+    let organization = match state.get_organization(payload.get_org_id()) {
+        Ok(None) => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Organization does not exist: {} ",
+                payload.get_org_id(),
+            )));
+        }
+        Ok(Some(organization)) => organization,
+        Err(err) => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Failed to retrieve state: {}",
+                err,
+            )));
+        }
+    };
+
+    let mut action_policies: Vec<ActionPolicy> = organization
+        .get_action_policies()
+        .iter()
+        .filter(|policy| policy.get_action() != payload.get_action())
+        .cloned()
+        .collect();
+
+    if !payload.get_smart_permission().is_empty() {
+        match state.get_smart_permission(payload.get_org_id(), payload.get_smart_permission()) {
+            Ok(None) => {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Smart Permission does not exist: {} ",
+                    payload.get_smart_permission(),
+                )));
+            }
+            Ok(Some(_)) => (),
+            Err(err) => {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Failed to retrieve state: {}",
+                    err,
+                )));
+            }
+        };
+
+        let policy = ActionPolicyBuilder::new()
+            .set_action(payload.get_action().to_string())
+            .set_smart_permission(payload.get_smart_permission().to_string())
+            .build()
+            .map_err(|err| {
+                ApplyError::InvalidTransaction(format!("Cannot build action policy: {}", err))
+            })?;
+        action_policies.push(policy);
+    }
+
+    let organization = organization
+        .into_builder()
+        .set_action_policies(action_policies)
+        .build()
+        .map_err(|err| {
+            ApplyError::InvalidTransaction(format!("Cannot build organization: {}", err))
+        })?;
+    state.set_organization(payload.get_org_id(), organization)
+}
+
+// helper function to run the smart permission the signer's organization attached to an action.
+// Signers without an account, and organizations that attached nothing to the action, are left to
+// the action's own checks.
+fn check_action_policy(
+    action: &str,
+    signer: &str,
+    payload: &[u8],
+    state: &mut SmartState,
+    trace: Option<&ExecutionTrace>,
+) -> Result<(), ApplyError> {
+    let account = match state.get_account(signer) {
+        Ok(None) => return Ok(()),
+        Ok(Some(account)) => account,
+        Err(err) => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Failed to retrieve state: {}",
+                err,
+            )));
+        }
+    };
+    let org_id = account.get_org_id();
+
+    let smart_permission = match state.get_organization(org_id) {
+        Ok(Some(organization)) => match organization.get_action_policy(action) {
+            Some(smart_permission) => smart_permission.to_string(),
+            None => return Ok(()),
+        },
+        Ok(None) => return Ok(()),
+        Err(err) => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Failed to retrieve state: {}",
+                err,
+            )));
+        }
+    };
+
+    let limits = get_execution_limits(state)?;
+    let address = compute_smart_permission_address(org_id, &smart_permission);
+    let mut externals = WasmExternals::new(
+        None,
+        state.context(),
+        limits,
+        ExecutionUsage {
+            trace: trace.cloned(),
+            ..ExecutionUsage::default()
+        },
+        None,
+    )
+    .map_err(|err| ApplyError::InternalError(format!("Cannot create wasm externals: {}", err)))?;
+
+    let result = externals
+        .run_smart_permission(
+            &address,
+            &smart_permission,
+            account.get_roles().to_vec(),
+            org_id.to_string(),
+            signer.to_string(),
+            payload.to_vec(),
+        )
+        .map_err(|err| {
+            ApplyError::InvalidTransaction(format!(
+                "Smart permission {} of organization {} failed: {}",
+                smart_permission, org_id, err,
+            ))
+        })?;
+
+    match result {
+        Some(1) => Ok(()),
+        Some(result) => Err(ApplyError::InvalidTransaction(format!(
+            "Smart permission {} of organization {} did not permit {} ({}): {}",
+            smart_permission, org_id, action, result, signer,
+        ))),
+        None => Err(ApplyError::InvalidTransaction(format!(
+            "Smart permission {} attached to {} does not exist in organization {}",
+            smart_permission, action, org_id,
+        ))),
+    }
+}
+
+// helper function to apply the metadata of an update action to the existing metadata
+fn update_metadata(
+    existing: &[KeyValueEntry],
+    entries: &[KeyValueEntry],
+    mode: MetadataUpdateMode,
+) -> Vec<KeyValueEntry> {
+    match mode {
+        MetadataUpdateMode::Replace => entries.to_vec(),
+        MetadataUpdateMode::Remove => existing
+            .iter()
+            .filter(|entry| {
+                !entries
+                    .iter()
+                    .any(|removed| removed.get_key() == entry.get_key())
+            })
+            .cloned()
+            .collect(),
+        MetadataUpdateMode::Merge => {
+            let mut metadata = existing.to_vec();
+            for entry in entries {
+                match metadata
+                    .iter()
+                    .position(|existing| existing.get_key() == entry.get_key())
+                {
+                    Some(index) => metadata[index] = entry.clone(),
+                    None => metadata.push(entry.clone()),
+                }
+            }
+            metadata
+        }
+    }
+}
+
+// helper function to load the contract for the version requested by an ExecuteContractAction,
+// which may be an exact version, "latest" or a semver range
+pub(crate) fn get_requested_contract(
+    name: &str,
+    requested: &str,
+    state: &mut SmartState,
+) -> Result<Contract, ApplyError> {
+    match state.get_contract(name, requested) {
+        Ok(Some(contract)) => return Ok(contract),
+        Ok(None) => (),
+        Err(err) => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Unable to check state: {}",
+                err,
+            )));
+        }
+    };
+
+    let contract_registry = match state.get_contract_registry(name) {
+        Ok(Some(contract_registry)) => contract_registry,
+        Ok(None) => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Contract does not exist: {}, {}",
+                name, requested,
+            )));
+        }
+        Err(err) => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Unable to check state: {}",
+                err,
+            )));
+        }
+    };
+
+    let version = match resolve_version(&contract_registry, requested) {
+        Some(version) => version,
+        None => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "No contract version matches: {}, {}",
+                name, requested,
+            )));
+        }
+    };
+
+    info!(
+        "Resolved contract version: {}, {} -> {}",
+        name, requested, version
+    );
+
+    match state.get_contract(name, &version) {
+        Ok(Some(contract)) => Ok(contract),
+        Ok(None) => Err(ApplyError::InvalidTransaction(format!(
+            "Contract does not exist: {}, {}",
+            name, version,
+        ))),
+        Err(err) => Err(ApplyError::InvalidTransaction(format!(
+            "Unable to check state: {}",
+            err,
+        ))),
+    }
+}
+
+// helper function to describe a failed wasm execution
+fn map_execution_error(name: &str, version: &str, err: ExternalsError) -> ApplyError {
+    match err.kind() {
+        ExternalsErrorKind::Execution => ApplyError::InvalidTransaction(format!("{:?}", err)),
+        ExternalsErrorKind::FuelExhausted => ApplyError::InvalidTransaction(format!(
+            "Wasm contract ran out of fuel: {}, {}: {}",
+            name, version, err,
+        )),
+        ExternalsErrorKind::PermissionDenied => ApplyError::InvalidTransaction(format!(
+            "Wasm contract invoked a contract without permission for its state: {}, {}: {}",
+            name, version, err,
+        )),
+        ExternalsErrorKind::UnauthorizedAddress => ApplyError::InvalidTransaction(format!(
+            "Wasm contract accessed an address outside its inputs and outputs: {}, {}: {}",
+            name, version, err,
+        )),
+        _ => ApplyError::InvalidTransaction(format!(
+            "Wasm contract exceeded an execution limit: {}, {}: {}",
+            name, version, err,
+        )),
+    }
+}
+
+// helper function to interpret the return code of a wasm contract, reporting the message of the
+// ContractError it set, if any. The return code decides the outcome, so contracts that only
+// return a status code keep working.
+fn check_contract_result(
+    name: &str,
+    version: &str,
+    result: Option<i32>,
+    error_data: Option<&[u8]>,
+) -> Result<(), ApplyError> {
+    let message = error_data
+        .and_then(|data| protobuf::parse_from_bytes::<ContractError>(data).ok())
+        .map(|error| format!(": {}", error.get_message()))
+        .unwrap_or_default();
+
+    match result {
+        None => Err(ApplyError::InvalidTransaction(format!(
+            "Wasm contract did not return a result: {}, {}",
+            name, version,
+        ))),
+        Some(1) => Ok(()),
+        Some(-3) => Err(ApplyError::InvalidTransaction(format!(
+            "Wasm contract returned invalid transaction: {}, {}{}",
+            name, version, message,
+        ))),
+        Some(num) => Err(ApplyError::InternalError(format!(
+            "Wasm contract returned internal error: {}{}",
+            num, message,
+        ))),
+    }
+}
+
+// helper function to fetch a contract's compiled module from the cache, loading and caching it
+// on a miss
+pub(crate) fn get_compiled_module(
+    contract: &Contract,
+    memory_pages: u32,
+    module_cache: &Mutex<ModuleCache>,
+) -> Result<Arc<Module>, ApplyError> {
+    let mut sha = Sha512::new();
+    sha.input(contract.get_contract());
+
+    let key = ModuleCacheKey {
+        contract_address: compute_contract_address(contract.get_name(), contract.get_version()),
+        contract_sha512: sha.result_str(),
+        memory_pages,
+    };
+
+    if let Some(module) = lock_module_cache(module_cache)?.get(&key) {
+        return Ok(module);
+    }
+
+    // compile without holding the lock, so a miss on a large contract does not
+    // block executions of other cached contracts
+    let module = load_metered_module(contract.get_contract(), memory_pages)
+        .map(Arc::new)
+        .map_err(|err| {
+            ApplyError::InvalidTransaction(format!(
+                "Unable to load wasm contract: {}, {}: {}",
+                contract.get_name(),
+                contract.get_version(),
+                err,
+            ))
+        })?;
+
+    let mut cache = lock_module_cache(module_cache)?;
+    cache.insert(key, module.clone());
+    debug!(
+        "Module cache: {} hits, {} misses, {} cached",
+        cache.hits(),
+        cache.misses(),
+        cache.len(),
+    );
+
+    Ok(module)
+}
+
+// helper function to lock the module cache, failing the transaction if the lock is poisoned
+fn lock_module_cache(
+    module_cache: &Mutex<ModuleCache>,
+) -> Result<MutexGuard<ModuleCache>, ApplyError> {
+    module_cache
+        .lock()
+        .map_err(|_| ApplyError::InternalError(String::from("Module cache lock poisoned")))
+}
+
+// helper function to drop any compiled modules for a contract that is being created or deleted
+fn invalidate_cached_modules(
+    name: &str,
+    version: &str,
+    module_cache: &Mutex<ModuleCache>,
+) -> Result<(), ApplyError> {
+    let address = compute_contract_address(name, version);
+    lock_module_cache(module_cache)?.invalidate(&address);
+    Ok(())
+}
+
+// helper function to reject a contract that cannot be executed before it is stored, using the
+// memory page and contract size limits from the on-chain settings
+fn check_uploaded_contract(
+    name: &str,
+    version: &str,
+    wasm: &[u8],
+    state: &mut SmartState,
+) -> Result<(), ApplyError> {
+    let max_size = get_limit_setting(
+        state,
+        MAX_CONTRACT_SIZE_SETTING_ADDRESS,
+        MAX_CONTRACT_SIZE_SETTING_KEY,
+        DEFAULT_MAX_CONTRACT_SIZE,
+    )?;
+    let limits = ExecutionLimits {
+        memory_pages: get_limit_setting(
+            state,
+            MAX_MEMORY_PAGES_SETTING_ADDRESS,
+            MAX_MEMORY_PAGES_SETTING_KEY,
+            ExecutionLimits::default().memory_pages,
+        )?,
+        ..ExecutionLimits::default()
+    };
+
+    validate_contract(wasm, max_size, limits, state.context()).map_err(|err| {
+        ApplyError::InvalidTransaction(format!(
+            "Invalid wasm contract: {}, {}: {}",
+            name, version, err,
+        ))
+    })
+}
+
+// helper function to reject source metadata recording a hash other than the uploaded contract's,
+// so the recorded source can be trusted to describe the stored bytes
+fn check_contract_source(
+    name: &str,
+    version: &str,
+    source: Option<&ContractSource>,
+    contract_sha512: &str,
+) -> Result<(), ApplyError> {
+    let source = match source {
+        Some(source) => source,
+        None => return Ok(()),
+    };
+
+    // an abbreviated commit could match more than one commit of the repository
+    let commit = source.get_commit();
+    if commit.len() != 40 || !commit.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Contract source commit must be a full 40 character commit hash: {}, {}",
+            name, version,
+        )));
+    }
+
+    if !source.get_wasm_sha512().is_empty()
+        && !source
+            .get_wasm_sha512()
+            .eq_ignore_ascii_case(contract_sha512)
+    {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Contract source hash does not match the contract: {}, {}",
+            name, version,
+        )));
+    }
+
+    Ok(())
+}
+
+// helper function to read the per-transaction execution limits from the on-chain settings
+fn get_execution_limits(state: &mut SmartState) -> Result<ExecutionLimits, ApplyError> {
+    let defaults = ExecutionLimits::default();
+    Ok(ExecutionLimits {
+        fuel: get_limit_setting(
+            state,
+            FUEL_LIMIT_SETTING_ADDRESS,
+            FUEL_LIMIT_SETTING_KEY,
+            defaults.fuel,
+        )?,
+        memory_pages: get_limit_setting(
+            state,
+            MAX_MEMORY_PAGES_SETTING_ADDRESS,
+            MAX_MEMORY_PAGES_SETTING_KEY,
+            defaults.memory_pages,
+        )?,
+        state_entry_size: get_limit_setting(
+            state,
+            MAX_STATE_ENTRY_SIZE_SETTING_ADDRESS,
+            MAX_STATE_ENTRY_SIZE_SETTING_KEY,
+            defaults.state_entry_size,
+        )?,
+        addresses_touched: get_limit_setting(
+            state,
+            MAX_ADDRESSES_TOUCHED_SETTING_ADDRESS,
+            MAX_ADDRESSES_TOUCHED_SETTING_KEY,
+            defaults.addresses_touched,
+        )?,
+        bytes_written: get_limit_setting(
+            state,
+            MAX_BYTES_WRITTEN_SETTING_ADDRESS,
+            MAX_BYTES_WRITTEN_SETTING_KEY,
+            defaults.bytes_written,
+        )?,
+    })
+}
+
+// helper function to read a single numeric limit, falling back to the default if it is not set
+fn get_limit_setting<T>(
+    state: &mut SmartState,
+    address: &str,
+    key: &str,
+    default: T,
+) -> Result<T, ApplyError>
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    match state.get_setting_value(address, key) {
+        Ok(Some(value)) => value.parse::<T>().map_err(|err| {
+            ApplyError::InvalidTransaction(format!(
+                "Invalid value for {}: {}: {}",
+                key, value, err,
+            ))
+        }),
+        Ok(None) => Ok(default),
+        Err(err) => Err(ApplyError::InvalidTransaction(format!(
+            "Unable to check state: {}",
+            err,
+        ))),
+    }
+}
+
 // helper function to check if the signer is allowed to update a namespace_registry
 fn can_update_namespace_registry(
     namespace_registry: NamespaceRegistry,
@@ -3,11 +3,17 @@
 
 #[macro_use]
 extern crate log;
+#[macro_use]
+extern crate serde_json;
 
-mod addressing;
 pub mod handler;
+mod namespace;
+mod owner_change;
 mod payload;
+mod roles;
+pub mod simulator;
 mod state;
+mod versioning;
 mod wasm_executor;
 //mod protocol;
 //mod protos;
@@ -6,7 +6,7 @@ extern crate clap;
 
 use log::Level;
 
-use dgc_contract_tp::handler::SmartTransactionHandler;
+use dgc_contract_tp::handler::{SmartTransactionHandler, TraceOutput};
 use sawtooth_sdk::processor::TransactionProcessor;
 
 fn main() {
@@ -16,10 +16,13 @@ fn main() {
         (@arg connect: -C --connect +takes_value
          "connection endpoint for validator")
         (@arg verbose: -v --verbose +multiple
-         "increase output verbosity"))
+         "increase output verbosity; -vvvv also logs contract execution traces")
+        (@arg trace_dir: --("trace-dir") +takes_value
+         "directory to write contract execution traces to, one JSON lines file per transaction"))
     .get_matches();
 
-    let logger = match matches.occurrences_of("verbose") {
+    let verbosity = matches.occurrences_of("verbose");
+    let logger = match verbosity {
         0 => simple_logger::init_with_level(Level::Warn),
         1 => simple_logger::init_with_level(Level::Info),
         2 => simple_logger::init_with_level(Level::Debug),
@@ -32,7 +35,12 @@ fn main() {
         .value_of("connect")
         .unwrap_or("tcp://localhost:4004");
 
-    let handler = SmartTransactionHandler::new();
+    let mut handler = SmartTransactionHandler::new();
+    if let Some(trace_dir) = matches.value_of("trace_dir") {
+        handler = handler.with_trace_output(TraceOutput::Directory(trace_dir.into()));
+    } else if verbosity >= 4 {
+        handler = handler.with_trace_output(TraceOutput::Log);
+    }
     let mut processor = TransactionProcessor::new(connect);
 
     processor.add_handler(&handler);
@@ -0,0 +1,295 @@
+// Copyright (c) The dgc.network
+// SPDX-License-Identifier: Apache-2.0
+
+use sawtooth_sdk::processor::handler::ApplyError;
+
+use dgc_contract_sdk::protocol::state::{NamespaceRegistry, Permission};
+
+use crate::state::SmartState;
+
+/// The kind of state access a contract is requesting for an address
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NamespaceAccess {
+    Read,
+    Write,
+}
+
+/// Checks that the namespace registry covering an address grants the contract
+/// the requested access
+///
+/// # Arguments
+///
+/// * `state` - the state to load namespace registries from
+/// * `contract_name` - name of the contract requesting access
+/// * `address` - the state address being accessed
+/// * `access` - whether the contract reads or writes the address
+pub fn check_namespace_permission(
+    state: &mut SmartState,
+    contract_name: &str,
+    address: &str,
+    access: NamespaceAccess,
+) -> Result<(), ApplyError> {
+    let direction = match access {
+        NamespaceAccess::Read => "Input",
+        NamespaceAccess::Write => "Output",
+    };
+
+    let namespace = match address.get(..6) {
+        Some(namespace) => namespace,
+        None => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "{} must have at least 6 characters: {}",
+                direction, address,
+            )));
+        }
+    };
+    let registries = match state.get_namespace_registries(namespace) {
+        Ok(Some(registries)) => registries,
+        Ok(None) => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Namespace Registry does not exist: {}",
+                namespace,
+            )));
+        }
+        Err(err) => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Unable to check state: {}",
+                err,
+            )));
+        }
+    };
+
+    // the registry with the longest namespace covering the address applies
+    let namespace_registry = registries
+        .get_registries()
+        .iter()
+        .filter(|registry| address.starts_with(registry.get_namespace().as_str()))
+        .max_by_key(|registry| registry.get_namespace().len());
+
+    let registry = match namespace_registry {
+        Some(registry) => registry,
+        None => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "No namespace registry exists for namespace: {} {}: {}",
+                namespace,
+                direction.to_lowercase(),
+                address
+            )));
+        }
+    };
+
+    match find_permission(registry, contract_name, address, access) {
+        Some(permission) if !permission.get_deny() => (),
+        Some(permission) => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Contract is denied permission to {} state by {} in {}: {}, {}",
+                direction_verb(access),
+                permission.get_contract_name(),
+                permission_scope(registry.get_namespace(), permission),
+                contract_name,
+                address
+            )));
+        }
+        None => {
+            return Err(ApplyError::InvalidTransaction(match access {
+                NamespaceAccess::Read => format!(
+                    "Contract does not have permission to read from state : {} {}",
+                    contract_name, address
+                ),
+                NamespaceAccess::Write => format!(
+                    "Contract does not have permission to write to state: {}, {}",
+                    contract_name, address
+                ),
+            }));
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the most specific permission of a registry covering the address,
+/// contract and access, which decides whether the access is granted
+fn find_permission<'a>(
+    registry: &'a NamespaceRegistry,
+    contract_name: &str,
+    address: &str,
+    access: NamespaceAccess,
+) -> Option<&'a Permission> {
+    registry
+        .get_permissions()
+        .iter()
+        .filter(|permission| {
+            address.starts_with(permission_scope(registry.get_namespace(), permission))
+                && contract_name_matches(permission.get_contract_name(), contract_name)
+                && match access {
+                    NamespaceAccess::Read => permission.get_read(),
+                    NamespaceAccess::Write => permission.get_write(),
+                }
+        })
+        .max_by_key(|permission| permission_specificity(registry.get_namespace(), permission))
+}
+
+/// Returns the address prefix a permission applies to
+fn permission_scope<'a>(namespace: &'a str, permission: &'a Permission) -> &'a str {
+    if permission.get_prefix().is_empty() {
+        namespace
+    } else {
+        permission.get_prefix()
+    }
+}
+
+/// Orders permissions from least to most specific: a longer address prefix is
+/// more specific, then an exact contract name over a pattern, then a pattern
+/// with more literal characters. At equal specificity a deny entry wins.
+fn permission_specificity(namespace: &str, permission: &Permission) -> (usize, bool, usize, bool) {
+    let pattern = permission.get_contract_name();
+    (
+        permission_scope(namespace, permission).len(),
+        !pattern.contains('*'),
+        pattern.chars().filter(|c| *c != '*').count(),
+        permission.get_deny(),
+    )
+}
+
+fn direction_verb(access: NamespaceAccess) -> &'static str {
+    match access {
+        NamespaceAccess::Read => "read from",
+        NamespaceAccess::Write => "write to",
+    }
+}
+
+/// Matches a contract name against a permission's contract name, in which
+/// '*' matches any sequence of characters
+fn contract_name_matches(pattern: &str, contract_name: &str) -> bool {
+    if !pattern.contains('*') {
+        return pattern == contract_name;
+    }
+
+    let parts: Vec<&str> = pattern.split('*').collect();
+    let first = parts[0];
+    let last = parts[parts.len() - 1];
+    if !contract_name.starts_with(first) || contract_name.len() < first.len() + last.len() {
+        return false;
+    }
+
+    let mut remaining = &contract_name[first.len()..contract_name.len() - last.len()];
+    for part in &parts[1..parts.len() - 1] {
+        match remaining.find(part) {
+            Some(index) => remaining = &remaining[index + part.len()..],
+            None => return false,
+        }
+    }
+
+    contract_name.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use dgc_contract_sdk::protocol::state::{NamespaceRegistryBuilder, PermissionBuilder};
+
+    fn permission(contract_name: &str, prefix: &str, deny: bool) -> Permission {
+        PermissionBuilder::new()
+            .set_contract_name(contract_name.to_string())
+            .set_read(true)
+            .set_write(true)
+            .set_prefix(prefix.to_string())
+            .set_deny(deny)
+            .build()
+            .unwrap()
+    }
+
+    fn check(permissions: Vec<Permission>, contract_name: &str, address: &str) -> bool {
+        let registry = NamespaceRegistryBuilder::new()
+            .set_namespace("abcdef".to_string())
+            .set_owners(vec!["owner".to_string()])
+            .set_permissions(permissions)
+            .build()
+            .unwrap();
+
+        find_permission(&registry, contract_name, address, NamespaceAccess::Write)
+            .map_or(false, |permission| !permission.get_deny())
+    }
+
+    #[test]
+    // check that '*' matches any sequence of characters, including none
+    fn check_contract_name_matches() {
+        assert!(contract_name_matches("intkey", "intkey"));
+        assert!(!contract_name_matches("intkey", "intkey2"));
+
+        assert!(contract_name_matches("*", "intkey"));
+        assert!(contract_name_matches("*", ""));
+        assert!(contract_name_matches("int*", "intkey"));
+        assert!(contract_name_matches("*key", "intkey"));
+        assert!(contract_name_matches("i*t*y", "intkey"));
+        assert!(!contract_name_matches("int*", "xintkey"));
+
+        assert!(contract_name_matches("a*a", "aa"));
+        assert!(contract_name_matches("a*a", "aba"));
+        assert!(!contract_name_matches("a*a", "a"));
+        assert!(!contract_name_matches("a*a", "ab"));
+    }
+
+    #[test]
+    // check that longer prefixes, exact names and deny entries are more specific
+    fn check_permission_specificity() {
+        let namespace_wide = permission("intkey", "", false);
+        let prefixed = permission("*", "abcdef01", false);
+        assert!(
+            permission_specificity("abcdef", &prefixed)
+                > permission_specificity("abcdef", &namespace_wide)
+        );
+
+        let exact = permission("intkey", "", false);
+        let pattern = permission("intke*", "", false);
+        assert!(
+            permission_specificity("abcdef", &exact) > permission_specificity("abcdef", &pattern)
+        );
+
+        let short_pattern = permission("int*", "", false);
+        assert!(
+            permission_specificity("abcdef", &pattern)
+                > permission_specificity("abcdef", &short_pattern)
+        );
+
+        let deny = permission("intkey", "", true);
+        assert!(permission_specificity("abcdef", &deny) > permission_specificity("abcdef", &exact));
+    }
+
+    #[test]
+    // check that the most specific permission decides, and a deny entry wins at
+    // equal specificity
+    fn check_namespace_permission_rules() {
+        assert!(check(
+            vec![permission("*", "", false)],
+            "intkey",
+            "abcdef00"
+        ));
+        assert!(!check(vec![], "intkey", "abcdef00"));
+        assert!(!check(
+            vec![permission("other", "", false)],
+            "intkey",
+            "abcdef00"
+        ));
+
+        let allow_and_deny = vec![
+            permission("intkey", "", false),
+            permission("intkey", "", true),
+        ];
+        assert!(!check(allow_and_deny, "intkey", "abcdef00"));
+
+        let deny_all_allow_exact = vec![permission("*", "", true), permission("intkey", "", false)];
+        assert!(check(deny_all_allow_exact.clone(), "intkey", "abcdef00"));
+        assert!(!check(deny_all_allow_exact, "other", "abcdef00"));
+
+        let deny_prefix = vec![
+            permission("intkey", "", false),
+            permission("*", "abcdef01", true),
+        ];
+        assert!(check(deny_prefix.clone(), "intkey", "abcdef00"));
+        assert!(!check(deny_prefix, "intkey", "abcdef01"));
+
+        assert!(!check(vec![permission("*", "", false)], "intkey", "abcde"));
+    }
+}
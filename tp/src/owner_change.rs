@@ -0,0 +1,328 @@
+// Copyright (c) The dgc.network
+// SPDX-License-Identifier: Apache-2.0
+
+use sawtooth_sdk::processor::handler::ApplyError;
+
+use dgc_contract_sdk::protocol::payload::{ProposeOwnerChangeAction, RegistryType};
+use dgc_contract_sdk::protocol::state::{OwnerChangeProposal, OwnerChangeProposalBuilder};
+
+/// The state of an owner change proposal after a vote has been counted
+#[derive(Debug, PartialEq)]
+pub enum ProposalStatus {
+    /// The proposal is waiting for more votes and stays in the registry
+    Pending(OwnerChangeProposal),
+    /// Enough owners approved the proposal; its owners and threshold apply
+    Approved(OwnerChangeProposal),
+    /// Enough owners rejected the proposal that it can no longer pass
+    Rejected,
+}
+
+/// Creates a new owner change proposal, counting the proposer's approval
+///
+/// A registry holds one proposal at a time, so a new one can only be made
+/// once the pending proposal, if any, has expired.
+///
+/// # Arguments
+///
+/// * `owners` - the registry's current owners
+/// * `owner_threshold` - the registry's current owner threshold
+/// * `pending` - the proposal stored in the registry, if any
+/// * `payload` - the proposed change
+/// * `signer` - public key of the transaction signer
+/// * `block_num` - number of the latest block
+pub fn propose(
+    owners: &[String],
+    owner_threshold: u32,
+    pending: Option<&OwnerChangeProposal>,
+    payload: &ProposeOwnerChangeAction,
+    signer: &str,
+    block_num: u64,
+) -> Result<ProposalStatus, ApplyError> {
+    check_is_owner(
+        owners,
+        signer,
+        payload.get_registry_type(),
+        payload.get_name(),
+    )?;
+
+    if let Some(pending) = pending {
+        if !is_expired(pending, block_num) {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "An owner change is already pending for {} {} until block {}",
+                payload.get_registry_type(),
+                payload.get_name(),
+                pending.get_expires_at_block(),
+            )));
+        }
+    }
+
+    let proposal = OwnerChangeProposalBuilder::new()
+        .set_owners(payload.get_owners().to_vec())
+        .set_owner_threshold(payload.get_owner_threshold())
+        .set_proposer(signer.to_string())
+        .set_approvals(vec![signer.to_string()])
+        .set_expires_at_block(block_num.saturating_add(payload.get_expiry_blocks()))
+        .build()
+        .map_err(|err| {
+            ApplyError::InvalidTransaction(format!("Cannot build owner change proposal: {}", err))
+        })?;
+
+    Ok(tally(owners, owner_threshold, proposal))
+}
+
+/// Counts the signer's vote on the pending owner change proposal
+///
+/// # Arguments
+///
+/// * `owners` - the registry's current owners
+/// * `owner_threshold` - the registry's current owner threshold
+/// * `pending` - the proposal stored in the registry, if any
+/// * `registry_type` - the kind of registry, used in error messages
+/// * `name` - the registry's contract name or namespace
+/// * `approve` - whether the signer approves the change
+/// * `signer` - public key of the transaction signer
+/// * `block_num` - number of the latest block
+#[allow(clippy::too_many_arguments)]
+pub fn vote(
+    owners: &[String],
+    owner_threshold: u32,
+    pending: Option<&OwnerChangeProposal>,
+    registry_type: RegistryType,
+    name: &str,
+    approve: bool,
+    signer: &str,
+    block_num: u64,
+) -> Result<ProposalStatus, ApplyError> {
+    check_is_owner(owners, signer, registry_type, name)?;
+
+    let proposal = match pending {
+        Some(proposal) if !is_expired(proposal, block_num) => proposal.clone(),
+        Some(_) => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "The owner change proposal for {} {} has expired",
+                registry_type, name,
+            )));
+        }
+        None => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "No owner change is pending for {} {}",
+                registry_type, name,
+            )));
+        }
+    };
+
+    if proposal.has_voted(signer) {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Owner has already voted on the owner change: {}",
+            signer,
+        )));
+    }
+
+    let mut approvals = proposal.get_approvals().to_vec();
+    let mut rejections = proposal.get_rejections().to_vec();
+    if approve {
+        approvals.push(signer.to_string());
+    } else {
+        rejections.push(signer.to_string());
+    }
+
+    let proposal = proposal
+        .into_builder()
+        .set_approvals(approvals)
+        .set_rejections(rejections)
+        .build()
+        .map_err(|err| {
+            ApplyError::InvalidTransaction(format!("Cannot build owner change proposal: {}", err))
+        })?;
+
+    Ok(tally(owners, owner_threshold, proposal))
+}
+
+fn tally(owners: &[String], owner_threshold: u32, proposal: OwnerChangeProposal) -> ProposalStatus {
+    // a threshold of 0 or 1 lets any single owner change the owners
+    let required = owner_threshold.max(1) as usize;
+
+    if proposal.get_approvals().len() >= required {
+        ProposalStatus::Approved(proposal)
+    } else if proposal.get_rejections().len() > owners.len().saturating_sub(required) {
+        ProposalStatus::Rejected
+    } else {
+        ProposalStatus::Pending(proposal)
+    }
+}
+
+fn is_expired(proposal: &OwnerChangeProposal, block_num: u64) -> bool {
+    block_num >= proposal.get_expires_at_block()
+}
+
+fn check_is_owner(
+    owners: &[String],
+    signer: &str,
+    registry_type: RegistryType,
+    name: &str,
+) -> Result<(), ApplyError> {
+    if !owners.iter().any(|owner| owner == signer) {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Only owners of {} {} can propose or vote on an owner change: {}",
+            registry_type, name, signer,
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use dgc_contract_sdk::protocol::payload::ProposeOwnerChangeActionBuilder;
+
+    fn owners() -> Vec<String> {
+        vec!["alice".to_string(), "bob".to_string(), "carol".to_string()]
+    }
+
+    fn proposal(approvals: &[&str], rejections: &[&str]) -> OwnerChangeProposal {
+        OwnerChangeProposalBuilder::new()
+            .set_owners(vec!["dave".to_string()])
+            .set_owner_threshold(1)
+            .set_proposer("alice".to_string())
+            .set_approvals(approvals.iter().map(|owner| owner.to_string()).collect())
+            .set_rejections(rejections.iter().map(|owner| owner.to_string()).collect())
+            .set_expires_at_block(10)
+            .build()
+            .unwrap()
+    }
+
+    fn vote_on(
+        owners: &[&str],
+        owner_threshold: u32,
+        pending: &OwnerChangeProposal,
+        approve: bool,
+        signer: &str,
+    ) -> Result<ProposalStatus, ApplyError> {
+        let owners: Vec<String> = owners.iter().map(|owner| owner.to_string()).collect();
+        vote(
+            &owners,
+            owner_threshold,
+            Some(pending),
+            RegistryType::ContractRegistry,
+            "intkey",
+            approve,
+            signer,
+            5,
+        )
+    }
+
+    fn outcome(status: Result<ProposalStatus, ApplyError>) -> &'static str {
+        match status.unwrap() {
+            ProposalStatus::Pending(_) => "pending",
+            ProposalStatus::Approved(_) => "approved",
+            ProposalStatus::Rejected => "rejected",
+        }
+    }
+
+    #[test]
+    // check that a proposal is rejected exactly when the threshold can no
+    // longer be reached
+    fn check_rejection_boundary() {
+        let owners = ["alice", "bob", "carol"];
+
+        // 2 of 3 owners must approve, so one rejection leaves it pending
+        let pending = proposal(&["alice"], &[]);
+        assert_eq!(
+            outcome(vote_on(&owners, 2, &pending, false, "bob")),
+            "pending"
+        );
+
+        let pending = proposal(&["alice"], &["bob"]);
+        assert_eq!(
+            outcome(vote_on(&owners, 2, &pending, false, "carol")),
+            "rejected"
+        );
+
+        // all 3 owners must approve, so a single rejection is enough
+        let pending = proposal(&["alice"], &[]);
+        assert_eq!(
+            outcome(vote_on(&owners, 3, &pending, false, "bob")),
+            "rejected"
+        );
+    }
+
+    #[test]
+    // check that a proposal is approved once enough owners approve it
+    fn check_approval() {
+        let owners = ["alice", "bob", "carol"];
+
+        let pending = proposal(&["alice"], &[]);
+        assert_eq!(
+            outcome(vote_on(&owners, 2, &pending, true, "bob")),
+            "approved"
+        );
+
+        let pending = proposal(&["alice"], &[]);
+        assert_eq!(
+            outcome(vote_on(&owners, 3, &pending, true, "bob")),
+            "pending"
+        );
+    }
+
+    #[test]
+    // check that a threshold of 0 or 1 lets the proposer change the owners alone
+    fn check_single_owner_threshold() {
+        let payload = ProposeOwnerChangeActionBuilder::new()
+            .set_registry_type(RegistryType::ContractRegistry)
+            .set_name("intkey".to_string())
+            .set_owners(vec!["dave".to_string()])
+            .set_expiry_blocks(5)
+            .build()
+            .unwrap();
+
+        for threshold in &[0, 1] {
+            assert_eq!(
+                outcome(propose(&owners(), *threshold, None, &payload, "alice", 1)),
+                "approved"
+            );
+        }
+
+        assert_eq!(
+            outcome(propose(&owners(), 2, None, &payload, "alice", 1)),
+            "pending"
+        );
+    }
+
+    #[test]
+    // check that only owners vote, each once, before the proposal expires
+    fn check_invalid_votes() {
+        let pending = proposal(&["alice"], &[]);
+        assert!(vote_on(&["alice", "bob", "carol"], 2, &pending, true, "dave").is_err());
+        assert!(vote_on(&["alice", "bob", "carol"], 2, &pending, false, "alice").is_err());
+
+        let result = vote(
+            &owners(),
+            2,
+            Some(&pending),
+            RegistryType::ContractRegistry,
+            "intkey",
+            true,
+            "bob",
+            10,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    // check that a new proposal waits for the pending one to expire
+    fn check_pending_proposal() {
+        let payload = ProposeOwnerChangeActionBuilder::new()
+            .set_registry_type(RegistryType::ContractRegistry)
+            .set_name("intkey".to_string())
+            .set_owners(vec!["dave".to_string()])
+            .set_owner_threshold(2)
+            .set_expiry_blocks(5)
+            .build()
+            .unwrap();
+        let pending = proposal(&["alice"], &[]);
+
+        assert!(propose(&owners(), 2, Some(&pending), &payload, "bob", 9).is_err());
+        assert!(propose(&owners(), 2, Some(&pending), &payload, "bob", 10).is_ok());
+    }
+}
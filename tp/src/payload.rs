@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use dgc_contract_sdk::protocol::payload::{Action, SmartPayload};
+use dgc_contract_sdk::protocol::POLICY_ACTIONS;
 use dgc_contract_sdk::protos::FromBytes;
 use sawtooth_sdk::processor::handler::ApplyError;
 
@@ -99,6 +100,10 @@ impl SmartRequestPayload {
                         "Contract Registry owners cannot be an empty",
                     )));
                 }
+                check_owner_threshold(
+                    create_contract_registry.get_owners(),
+                    create_contract_registry.get_owner_threshold(),
+                )?;
             }
             Action::DeleteContractRegistry(delete_contract_registry) => {
                 if delete_contract_registry.get_name().is_empty() {
@@ -130,6 +135,10 @@ impl SmartRequestPayload {
                         "Namespace owners cannot be an empty",
                     )));
                 }
+                check_owner_threshold(
+                    create_namespace_registry.get_owners(),
+                    create_namespace_registry.get_owner_threshold(),
+                )?;
             }
             Action::DeleteNamespaceRegistry(delete_namespace_registry) => {
                 if delete_namespace_registry.get_namespace().is_empty() {
@@ -295,6 +304,185 @@ impl SmartRequestPayload {
                     ));
                 }
             }
+            Action::UpgradeContract(upgrade_contract) => {
+                if upgrade_contract.get_name().is_empty() {
+                    return Err(ApplyError::InvalidTransaction(String::from(
+                        "Contract name cannot be an empty string",
+                    )));
+                }
+                if upgrade_contract.get_version().is_empty() {
+                    return Err(ApplyError::InvalidTransaction(String::from(
+                        "Contract version cannot be an empty string",
+                    )));
+                }
+                if upgrade_contract.get_previous_version().is_empty() {
+                    return Err(ApplyError::InvalidTransaction(String::from(
+                        "Contract previous version cannot be an empty string",
+                    )));
+                }
+                if upgrade_contract.get_inputs().is_empty() {
+                    return Err(ApplyError::InvalidTransaction(String::from(
+                        "Contract inputs cannot be an empty",
+                    )));
+                }
+                if upgrade_contract.get_outputs().is_empty() {
+                    return Err(ApplyError::InvalidTransaction(String::from(
+                        "Contract outputs cannot be an empty",
+                    )));
+                }
+                if upgrade_contract.get_contract().is_empty() {
+                    return Err(ApplyError::InvalidTransaction(String::from(
+                        "Contract bytes cannot be an empty",
+                    )));
+                }
+            }
+            Action::DeactivateAccount(deactivate_account) => {
+                if deactivate_account.get_org_id().is_empty() {
+                    return Err(ApplyError::InvalidTransaction(
+                        "Organization ID required".into(),
+                    ));
+                }
+
+                if deactivate_account.get_public_key().is_empty() {
+                    return Err(ApplyError::InvalidTransaction(
+                        "Account public_key required".into(),
+                    ));
+                }
+            }
+            Action::ReactivateAccount(reactivate_account) => {
+                if reactivate_account.get_org_id().is_empty() {
+                    return Err(ApplyError::InvalidTransaction(
+                        "Organization ID required".into(),
+                    ));
+                }
+
+                if reactivate_account.get_public_key().is_empty() {
+                    return Err(ApplyError::InvalidTransaction(
+                        "Account public_key required".into(),
+                    ));
+                }
+            }
+            Action::GrantRole(grant_role) => {
+                if grant_role.get_org_id().is_empty() {
+                    return Err(ApplyError::InvalidTransaction(
+                        "Organization ID required".into(),
+                    ));
+                }
+
+                if grant_role.get_public_key().is_empty() {
+                    return Err(ApplyError::InvalidTransaction(
+                        "Account public_key required".into(),
+                    ));
+                }
+
+                if grant_role.get_role().is_empty() {
+                    return Err(ApplyError::InvalidTransaction("Role required".into()));
+                }
+            }
+            Action::RevokeRole(revoke_role) => {
+                if revoke_role.get_org_id().is_empty() {
+                    return Err(ApplyError::InvalidTransaction(
+                        "Organization ID required".into(),
+                    ));
+                }
+
+                if revoke_role.get_public_key().is_empty() {
+                    return Err(ApplyError::InvalidTransaction(
+                        "Account public_key required".into(),
+                    ));
+                }
+
+                if revoke_role.get_role().is_empty() {
+                    return Err(ApplyError::InvalidTransaction("Role required".into()));
+                }
+            }
+            Action::CreateRole(create_role) => {
+                if create_role.get_org_id().is_empty() {
+                    return Err(ApplyError::InvalidTransaction(
+                        "Organization ID required".into(),
+                    ));
+                }
+
+                if create_role.get_name().is_empty() {
+                    return Err(ApplyError::InvalidTransaction("Role name required".into()));
+                }
+
+                if create_role.get_permissions().is_empty() {
+                    return Err(ApplyError::InvalidTransaction(
+                        "Role permissions cannot be empty".into(),
+                    ));
+                }
+            }
+            Action::UpdateRole(update_role) => {
+                if update_role.get_org_id().is_empty() {
+                    return Err(ApplyError::InvalidTransaction(
+                        "Organization ID required".into(),
+                    ));
+                }
+
+                if update_role.get_name().is_empty() {
+                    return Err(ApplyError::InvalidTransaction("Role name required".into()));
+                }
+
+                if update_role.get_permissions().is_empty() {
+                    return Err(ApplyError::InvalidTransaction(
+                        "Role permissions cannot be empty".into(),
+                    ));
+                }
+            }
+            Action::DeleteRole(delete_role) => {
+                if delete_role.get_org_id().is_empty() {
+                    return Err(ApplyError::InvalidTransaction(
+                        "Organization ID required".into(),
+                    ));
+                }
+
+                if delete_role.get_name().is_empty() {
+                    return Err(ApplyError::InvalidTransaction("Role name required".into()));
+                }
+            }
+            Action::ProposeOwnerChange(propose_owner_change) => {
+                if propose_owner_change.get_name().is_empty() {
+                    return Err(ApplyError::InvalidTransaction(String::from(
+                        "Registry name cannot be an empty string",
+                    )));
+                }
+                if propose_owner_change.get_owners().is_empty() {
+                    return Err(ApplyError::InvalidTransaction(String::from(
+                        "Proposed owners cannot be an empty",
+                    )));
+                }
+                check_owner_threshold(
+                    propose_owner_change.get_owners(),
+                    propose_owner_change.get_owner_threshold(),
+                )?;
+                if propose_owner_change.get_expiry_blocks() == 0 {
+                    return Err(ApplyError::InvalidTransaction(String::from(
+                        "Proposal expiry must be at least one block",
+                    )));
+                }
+            }
+            Action::VoteOwnerChange(vote_owner_change) => {
+                if vote_owner_change.get_name().is_empty() {
+                    return Err(ApplyError::InvalidTransaction(String::from(
+                        "Registry name cannot be an empty string",
+                    )));
+                }
+            }
+            Action::SetActionPolicy(set_action_policy) => {
+                if set_action_policy.get_org_id().is_empty() {
+                    return Err(ApplyError::InvalidTransaction(
+                        "Organization ID required".into(),
+                    ));
+                }
+
+                if !POLICY_ACTIONS.contains(&set_action_policy.get_action()) {
+                    return Err(ApplyError::InvalidTransaction(format!(
+                        "A smart permission cannot be attached to action: {}",
+                        set_action_policy.get_action(),
+                    )));
+                }
+            }
         };
 
         Ok(Some(SmartRequestPayload {
@@ -306,3 +494,15 @@ impl SmartRequestPayload {
         self.action.clone()
     }
 }
+
+// An owner threshold cannot require more approvals than there are owners
+fn check_owner_threshold(owners: &[String], owner_threshold: u32) -> Result<(), ApplyError> {
+    if owner_threshold as usize > owners.len() {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Owner threshold {} is greater than the number of owners {}",
+            owner_threshold,
+            owners.len(),
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,358 @@
+// Copyright (c) The dgc.network
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashSet;
+
+use sawtooth_sdk::processor::handler::ApplyError;
+
+use dgc_contract_sdk::protocol::state::Account;
+use dgc_contract_sdk::protocol::{ADMINISTRATORS_SETTING_KEY, ADMIN_ROLE};
+
+use crate::state::SmartState;
+
+/// The permissions an account holds in its organization through its roles
+#[derive(Clone, Debug, PartialEq)]
+pub enum GrantedPermissions {
+    /// The account holds the admin role, which grants every permission
+    All,
+    /// The union of the permissions of the account's roles
+    Only(HashSet<String>),
+}
+
+impl GrantedPermissions {
+    pub fn is_admin(&self) -> bool {
+        *self == GrantedPermissions::All
+    }
+
+    pub fn contains(&self, permission: &str) -> bool {
+        match self {
+            GrantedPermissions::All => true,
+            GrantedPermissions::Only(permissions) => permissions.contains(permission),
+        }
+    }
+}
+
+/// Loads the permissions the signer holds in an organization
+///
+/// The signer must have an active account in the organization. Roles of the
+/// account that have no Role object in state grant no permissions.
+///
+/// # Arguments
+///
+/// * `state` - the state to load the account and roles from
+/// * `signer` - public key of the transaction signer
+/// * `org_id` - the organization the signer acts for
+pub fn get_signer_permissions(
+    state: &mut SmartState,
+    signer: &str,
+    org_id: &str,
+) -> Result<GrantedPermissions, ApplyError> {
+    let account = match state.get_account(signer) {
+        Ok(None) => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Signer is not an agent: {}",
+                signer,
+            )));
+        }
+        Ok(Some(account)) => account,
+        Err(err) => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Failed to retrieve state: {}",
+                err,
+            )));
+        }
+    };
+
+    if account.get_org_id() != org_id {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Signer is not associated with the organization: {}",
+            signer,
+        )));
+    }
+
+    if !*account.get_active() {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Signer is not currently an active agent: {}",
+            signer,
+        )));
+    }
+
+    get_account_permissions(state, &account)
+}
+
+/// Checks that the signer holds a permission in an organization
+///
+/// # Arguments
+///
+/// * `state` - the state to load the account and roles from
+/// * `signer` - public key of the transaction signer
+/// * `org_id` - the organization the signer acts for
+/// * `permission` - the permission required by the action
+pub fn check_permission(
+    state: &mut SmartState,
+    signer: &str,
+    org_id: &str,
+    permission: &str,
+) -> Result<GrantedPermissions, ApplyError> {
+    let granted = get_signer_permissions(state, signer, org_id)?;
+    check_granted(&granted, signer, permission)?;
+    Ok(granted)
+}
+
+/// Checks that already loaded signer permissions include a permission
+pub fn check_granted(
+    granted: &GrantedPermissions,
+    signer: &str,
+    permission: &str,
+) -> Result<(), ApplyError> {
+    if !granted.contains(permission) {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Signer does not have the {} permission: {}",
+            permission, signer,
+        )));
+    }
+    Ok(())
+}
+
+/// Checks that the signer holds a permission in the organization of its
+/// account
+///
+/// Actions such as contract uploads are not scoped to an organization, so the
+/// organization of the signer's account is used. A signer without an account
+/// is rejected unless it is listed in the `sawtooth.swa.administrators`
+/// setting.
+///
+/// # Arguments
+///
+/// * `state` - the state to load the account, roles and setting from
+/// * `signer` - public key of the transaction signer
+/// * `permission` - the permission required by the action
+pub fn check_member_permission(
+    state: &mut SmartState,
+    signer: &str,
+    permission: &str,
+) -> Result<(), ApplyError> {
+    let org_id = match state.get_account(signer) {
+        Ok(Some(account)) => account.get_org_id().to_string(),
+        Ok(None) if is_administrator(state, signer)? => return Ok(()),
+        Ok(None) => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Signer is not an agent: {}",
+                signer,
+            )));
+        }
+        Err(err) => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Failed to retrieve state: {}",
+                err,
+            )));
+        }
+    };
+
+    check_permission(state, signer, &org_id, permission).map(|_| ())
+}
+
+/// Checks that the signer may give a role to an account
+///
+/// Only admins can give the admin role. Any other role can only be given by a
+/// signer that holds every permission the role grants, so that permissions
+/// cannot be escalated through role assignment.
+///
+/// # Arguments
+///
+/// * `state` - the state to load the role from
+/// * `granted` - the permissions of the signer
+/// * `org_id` - the organization of the role
+/// * `role` - name of the role being given
+pub fn check_can_grant_role(
+    state: &mut SmartState,
+    granted: &GrantedPermissions,
+    org_id: &str,
+    role: &str,
+) -> Result<(), ApplyError> {
+    if granted.is_admin() {
+        return Ok(());
+    }
+
+    if role == ADMIN_ROLE {
+        return Err(ApplyError::InvalidTransaction(String::from(
+            "Only an admin can grant the admin role",
+        )));
+    }
+
+    if let Some(role) = state.get_role(org_id, role)? {
+        check_holds_permissions(granted, role.get_permissions())?;
+    }
+    Ok(())
+}
+
+/// Checks that the signer holds every permission in a list
+pub fn check_holds_permissions(
+    granted: &GrantedPermissions,
+    permissions: &[String],
+) -> Result<(), ApplyError> {
+    match permissions
+        .iter()
+        .find(|permission| !granted.contains(permission))
+    {
+        Some(permission) => Err(ApplyError::InvalidTransaction(format!(
+            "Signer cannot grant a permission it does not hold: {}",
+            permission,
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Checks that an account holding the admin role is only changed by an admin
+pub fn check_can_change_account(
+    granted: &GrantedPermissions,
+    account: &Account,
+) -> Result<(), ApplyError> {
+    if !granted.is_admin() && account.get_roles().iter().any(|role| role == ADMIN_ROLE) {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Only an admin can change an admin account: {}",
+            account.get_public_key(),
+        )));
+    }
+    Ok(())
+}
+
+/// Returns whether the signer is listed in the `sawtooth.swa.administrators`
+/// setting
+fn is_administrator(state: &mut SmartState, signer: &str) -> Result<bool, ApplyError> {
+    let setting = match state.get_admin_setting()? {
+        Some(setting) => setting,
+        None => return Ok(false),
+    };
+
+    Ok(setting
+        .get_entries()
+        .iter()
+        .filter(|entry| entry.key == ADMINISTRATORS_SETTING_KEY)
+        .any(|entry| entry.value.split(',').any(|admin| admin == signer)))
+}
+
+fn get_account_permissions(
+    state: &mut SmartState,
+    account: &Account,
+) -> Result<GrantedPermissions, ApplyError> {
+    if account.get_roles().iter().any(|role| role == ADMIN_ROLE) {
+        return Ok(GrantedPermissions::All);
+    }
+
+    let mut permissions = HashSet::new();
+    for name in account.get_roles() {
+        if let Some(role) = state.get_role(account.get_org_id(), name)? {
+            permissions.extend(role.get_permissions().iter().cloned());
+        }
+    }
+    Ok(GrantedPermissions::Only(permissions))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::BTreeMap;
+
+    use dgc_contract_sdk::protocol::state::{AccountBuilder, RoleBuilder};
+    use dgc_contract_sdk::protocol::{
+        ACCOUNT_CREATE_PERMISSION, ACCOUNT_UPDATE_PERMISSION, ADMINISTRATORS_SETTING_ADDRESS,
+        CONTRACT_UPLOAD_PERMISSION,
+    };
+    use protobuf::{Message, RepeatedField};
+    use sawtooth_sdk::messages::setting::{Setting, Setting_Entry};
+
+    use crate::simulator::InMemoryContext;
+
+    fn account(public_key: &str, roles: &[&str]) -> Account {
+        AccountBuilder::new()
+            .set_org_id("org".to_string())
+            .set_public_key(public_key.to_string())
+            .set_active(true)
+            .set_roles(roles.iter().map(|role| role.to_string()).collect())
+            .build()
+            .unwrap()
+    }
+
+    fn only(permissions: &[&str]) -> GrantedPermissions {
+        GrantedPermissions::Only(permissions.iter().map(|p| p.to_string()).collect())
+    }
+
+    #[test]
+    // check that the admin role grants every permission
+    fn check_granted_permissions() {
+        assert!(GrantedPermissions::All.is_admin());
+        assert!(GrantedPermissions::All.contains(CONTRACT_UPLOAD_PERMISSION));
+
+        let granted = only(&[ACCOUNT_CREATE_PERMISSION]);
+        assert!(!granted.is_admin());
+        assert!(granted.contains(ACCOUNT_CREATE_PERMISSION));
+        assert!(!granted.contains(CONTRACT_UPLOAD_PERMISSION));
+        assert!(check_granted(&granted, "signer", ACCOUNT_CREATE_PERMISSION).is_ok());
+        assert!(check_granted(&granted, "signer", CONTRACT_UPLOAD_PERMISSION).is_err());
+    }
+
+    #[test]
+    // check that a signer can only grant permissions it holds
+    fn check_holds_all_permissions() {
+        let permissions = vec![
+            ACCOUNT_CREATE_PERMISSION.to_string(),
+            ACCOUNT_UPDATE_PERMISSION.to_string(),
+        ];
+        assert!(check_holds_permissions(&GrantedPermissions::All, &permissions).is_ok());
+        assert!(check_holds_permissions(
+            &only(&[ACCOUNT_CREATE_PERMISSION, ACCOUNT_UPDATE_PERMISSION]),
+            &permissions
+        )
+        .is_ok());
+        assert!(
+            check_holds_permissions(&only(&[ACCOUNT_CREATE_PERMISSION]), &permissions).is_err()
+        );
+    }
+
+    #[test]
+    // check that only an admin can change an admin account
+    fn check_change_account() {
+        let admin = account("admin", &[ADMIN_ROLE]);
+        let editor = account("editor", &["editor"]);
+        let granted = only(&[ACCOUNT_UPDATE_PERMISSION]);
+
+        assert!(check_can_change_account(&GrantedPermissions::All, &admin).is_ok());
+        assert!(check_can_change_account(&granted, &admin).is_err());
+        assert!(check_can_change_account(&granted, &editor).is_ok());
+    }
+
+    #[test]
+    // check that a signer without an account can only act as an administrator
+    fn check_member_permissions() {
+        let mut entry = Setting_Entry::new();
+        entry.set_key(ADMINISTRATORS_SETTING_KEY.to_string());
+        entry.set_value("admin,other".to_string());
+        let mut setting = Setting::new();
+        setting.set_entries(RepeatedField::from_vec(vec![entry]));
+        let mut entries = BTreeMap::new();
+        entries.insert(
+            ADMINISTRATORS_SETTING_ADDRESS.to_string(),
+            setting.write_to_bytes().unwrap(),
+        );
+
+        let mut context = InMemoryContext::new(entries, &[], &[]);
+        let mut state = SmartState::new(&mut context);
+        let role = RoleBuilder::new()
+            .set_org_id("org".to_string())
+            .set_name("editor".to_string())
+            .set_permissions(vec![ACCOUNT_CREATE_PERMISSION.to_string()])
+            .build()
+            .unwrap();
+        state.set_role("org", "editor", role).unwrap();
+        state
+            .set_account("editor", account("editor", &["editor"]))
+            .unwrap();
+
+        assert!(check_member_permission(&mut state, "nobody", CONTRACT_UPLOAD_PERMISSION).is_err());
+        assert!(check_member_permission(&mut state, "admin", CONTRACT_UPLOAD_PERMISSION).is_ok());
+        assert!(check_member_permission(&mut state, "editor", ACCOUNT_CREATE_PERMISSION).is_ok());
+        assert!(check_member_permission(&mut state, "editor", CONTRACT_UPLOAD_PERMISSION).is_err());
+    }
+}
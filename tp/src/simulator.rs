@@ -0,0 +1,328 @@
+// Copyright (c) The dgc.network
+// SPDX-License-Identifier: Apache-2.0
+
+//! Applies Smart transactions to in-memory state, so contracts can be tried
+//! out without a validator.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use sawtooth_sdk::messages::processor::TpProcessRequest;
+use sawtooth_sdk::messages::transaction::TransactionHeader;
+use sawtooth_sdk::processor::handler::{ContextError, TransactionContext, TransactionHandler};
+
+use crate::handler::{SmartTransactionHandler, TraceOutput};
+
+/// An event added by a transaction
+#[derive(Clone, Debug, PartialEq)]
+pub struct SimulatedEvent {
+    pub event_type: String,
+    pub attributes: Vec<(String, String)>,
+    pub data: Vec<u8>,
+}
+
+/// A state entry changed by a transaction
+#[derive(Clone, Debug, PartialEq)]
+pub struct StateChange {
+    pub address: String,
+    /// The value before the transaction, None if the address was not set
+    pub before: Option<Vec<u8>>,
+    /// The value after the transaction, None if the address was deleted
+    pub after: Option<Vec<u8>>,
+}
+
+/// A transaction to apply with the Simulator
+#[derive(Clone, Debug, Default)]
+pub struct SimulatedTransaction {
+    pub signer_public_key: String,
+    pub signature: String,
+    /// A serialized SmartPayload
+    pub payload: Vec<u8>,
+    /// Addresses or address prefixes the transaction may read; if empty, the
+    /// transaction may read any address
+    pub inputs: Vec<String>,
+    /// Addresses or address prefixes the transaction may write; if empty, the
+    /// transaction may write any address
+    pub outputs: Vec<String>,
+}
+
+/// The outcome of applying a transaction
+#[derive(Clone, Debug, Default)]
+pub struct SimulationResult {
+    /// The error the transaction was rejected with, None if it was applied
+    pub error: Option<String>,
+    /// The state entries the transaction changed, ordered by address; empty
+    /// if it was rejected
+    pub changes: Vec<StateChange>,
+    /// The events the transaction added; empty if it was rejected
+    pub events: Vec<SimulatedEvent>,
+    /// The receipt data the transaction added; empty if it was rejected
+    pub receipt_data: Vec<Vec<u8>>,
+    /// The file the execution trace of the transaction was written to, if
+    /// tracing is enabled
+    pub trace_file: Option<PathBuf>,
+}
+
+/// Applies transactions to in-memory state with the SmartTransactionHandler
+///
+/// Like a validator, the state changes, events and receipt data of a
+/// transaction are only kept if it is applied successfully.
+pub struct Simulator {
+    handler: SmartTransactionHandler,
+    state: BTreeMap<String, Vec<u8>>,
+    trace_output: Option<TraceOutput>,
+}
+
+impl Simulator {
+    /// Creates a simulator whose state starts out as the given entries
+    pub fn new(state: BTreeMap<String, Vec<u8>>) -> Simulator {
+        Simulator {
+            handler: SmartTransactionHandler::new(),
+            state,
+            trace_output: None,
+        }
+    }
+
+    /// Writes the execution trace of each transaction as JSON lines to a file
+    /// named after its signature in the given directory
+    pub fn with_trace_dir(mut self, trace_dir: PathBuf) -> Simulator {
+        let trace_output = TraceOutput::Directory(trace_dir);
+        self.handler = self.handler.with_trace_output(trace_output.clone());
+        self.trace_output = Some(trace_output);
+        self
+    }
+
+    /// Returns the current state
+    pub fn state(&self) -> &BTreeMap<String, Vec<u8>> {
+        &self.state
+    }
+
+    /// Applies a transaction, keeping its state changes if it succeeds
+    pub fn apply(&mut self, txn: &SimulatedTransaction) -> SimulationResult {
+        let mut header = TransactionHeader::new();
+        header.set_family_name(self.handler.family_name());
+        if let Some(version) = self.handler.family_versions().first() {
+            header.set_family_version(version.clone());
+        }
+        header.set_signer_public_key(txn.signer_public_key.clone());
+        header.set_inputs(protobuf::RepeatedField::from_vec(txn.inputs.clone()));
+        header.set_outputs(protobuf::RepeatedField::from_vec(txn.outputs.clone()));
+
+        let mut request = TpProcessRequest::new();
+        request.set_header(header);
+        request.set_payload(txn.payload.clone());
+        request.set_signature(txn.signature.clone());
+
+        let mut context = InMemoryContext::new(self.state.clone(), &txn.inputs, &txn.outputs);
+        let result = self.handler.apply(&request, &mut context);
+        let trace_file = self
+            .trace_output
+            .as_ref()
+            .and_then(|output| output.trace_file(&txn.signature));
+
+        if let Err(err) = result {
+            return SimulationResult {
+                error: Some(format!("{:?}", err)),
+                trace_file,
+                ..SimulationResult::default()
+            };
+        }
+
+        let state = context.state.into_inner();
+        let changes = diff_state(&self.state, &state);
+        self.state = state;
+
+        SimulationResult {
+            error: None,
+            changes,
+            events: context.events.into_inner(),
+            receipt_data: context.receipt_data.into_inner(),
+            trace_file,
+        }
+    }
+}
+
+// helper function to list the entries that differ between two states
+fn diff_state(
+    before: &BTreeMap<String, Vec<u8>>,
+    after: &BTreeMap<String, Vec<u8>>,
+) -> Vec<StateChange> {
+    let mut addresses: Vec<&String> = before.keys().chain(after.keys()).collect();
+    addresses.sort();
+    addresses.dedup();
+
+    addresses
+        .into_iter()
+        .filter(|address| before.get(*address) != after.get(*address))
+        .map(|address| StateChange {
+            address: address.clone(),
+            before: before.get(address).cloned(),
+            after: after.get(address).cloned(),
+        })
+        .collect()
+}
+
+/// A TransactionContext backed by a map, which enforces the inputs and
+/// outputs of the transaction like the validator does
+pub(crate) struct InMemoryContext {
+    state: RefCell<BTreeMap<String, Vec<u8>>>,
+    inputs: Vec<String>,
+    outputs: Vec<String>,
+    events: RefCell<Vec<SimulatedEvent>>,
+    receipt_data: RefCell<Vec<Vec<u8>>>,
+}
+
+impl InMemoryContext {
+    pub(crate) fn new(
+        state: BTreeMap<String, Vec<u8>>,
+        inputs: &[String],
+        outputs: &[String],
+    ) -> InMemoryContext {
+        InMemoryContext {
+            state: RefCell::new(state),
+            inputs: inputs.to_vec(),
+            outputs: outputs.to_vec(),
+            events: RefCell::new(Vec::new()),
+            receipt_data: RefCell::new(Vec::new()),
+        }
+    }
+
+    fn check_authorized(&self, prefixes: &[String], address: &str) -> Result<(), ContextError> {
+        if prefixes.is_empty() || prefixes.iter().any(|prefix| address.starts_with(prefix)) {
+            Ok(())
+        } else {
+            Err(ContextError::AuthorizationError(format!(
+                "Tried to access unauthorized address: {}",
+                address
+            )))
+        }
+    }
+}
+
+impl TransactionContext for InMemoryContext {
+    fn get_state_entries(
+        &self,
+        addresses: &[String],
+    ) -> Result<Vec<(String, Vec<u8>)>, ContextError> {
+        let state = self.state.borrow();
+        let mut entries = Vec::new();
+        for address in addresses {
+            self.check_authorized(&self.inputs, address)?;
+            if let Some(value) = state.get(address) {
+                entries.push((address.clone(), value.clone()));
+            }
+        }
+        Ok(entries)
+    }
+
+    fn set_state_entries(&self, entries: Vec<(String, Vec<u8>)>) -> Result<(), ContextError> {
+        for (address, _) in &entries {
+            self.check_authorized(&self.outputs, address)?;
+        }
+        self.state.borrow_mut().extend(entries);
+        Ok(())
+    }
+
+    fn delete_state_entries(&self, addresses: &[String]) -> Result<Vec<String>, ContextError> {
+        let mut state = self.state.borrow_mut();
+        let mut deleted = Vec::new();
+        for address in addresses {
+            self.check_authorized(&self.outputs, address)?;
+            if state.remove(address).is_some() {
+                deleted.push(address.clone());
+            }
+        }
+        Ok(deleted)
+    }
+
+    fn add_receipt_data(&self, data: &[u8]) -> Result<(), ContextError> {
+        self.receipt_data.borrow_mut().push(data.to_vec());
+        Ok(())
+    }
+
+    fn add_event(
+        &self,
+        event_type: String,
+        attributes: Vec<(String, String)>,
+        data: &[u8],
+    ) -> Result<(), ContextError> {
+        self.events.borrow_mut().push(SimulatedEvent {
+            event_type,
+            attributes,
+            data: data.to_vec(),
+        });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use dgc_contract_sdk::addressing::compute_namespace_registry_address;
+    use dgc_contract_sdk::protocol::payload::{
+        Action, CreateNamespaceRegistryActionBuilder, SmartPayloadBuilder,
+    };
+    use dgc_contract_sdk::protocol::{ADMINISTRATORS_SETTING_ADDRESS, ADMINISTRATORS_SETTING_KEY};
+    use dgc_contract_sdk::protos::IntoBytes;
+    use protobuf::{Message, RepeatedField};
+    use sawtooth_sdk::messages::setting::{Setting, Setting_Entry};
+
+    fn create_namespace_registry(signer: &str, namespace: &str) -> SimulatedTransaction {
+        let action = CreateNamespaceRegistryActionBuilder::new()
+            .set_namespace(namespace.to_string())
+            .set_owners(vec![signer.to_string()])
+            .build()
+            .unwrap();
+        let payload = SmartPayloadBuilder::new()
+            .set_action(Action::CreateNamespaceRegistry(action))
+            .build()
+            .unwrap();
+
+        SimulatedTransaction {
+            signer_public_key: signer.to_string(),
+            signature: "signature".to_string(),
+            payload: payload.into_bytes().unwrap(),
+            ..SimulatedTransaction::default()
+        }
+    }
+
+    #[test]
+    // check that an applied transaction keeps its changes and a rejected one
+    // leaves the state untouched and reports no changes, events or receipt data
+    fn check_apply_and_reject() {
+        let mut entry = Setting_Entry::new();
+        entry.set_key(ADMINISTRATORS_SETTING_KEY.to_string());
+        entry.set_value("admin".to_string());
+        let mut setting = Setting::new();
+        setting.set_entries(RepeatedField::from_vec(vec![entry]));
+        let mut entries = BTreeMap::new();
+        entries.insert(
+            ADMINISTRATORS_SETTING_ADDRESS.to_string(),
+            setting.write_to_bytes().unwrap(),
+        );
+        let mut simulator = Simulator::new(entries);
+        let address = compute_namespace_registry_address("1cf126").unwrap();
+
+        let result = simulator.apply(&create_namespace_registry("admin", "1cf126"));
+        assert_eq!(result.error, None);
+        assert_eq!(result.changes.len(), 1);
+        assert_eq!(result.changes[0].address, address);
+        assert_eq!(result.changes[0].before, None);
+        assert!(simulator.state().contains_key(&address));
+
+        let state = simulator.state().clone();
+        for txn in &[
+            create_namespace_registry("admin", "1cf126"),
+            create_namespace_registry("nobody", "abcdef"),
+        ] {
+            let result = simulator.apply(txn);
+            assert!(result.error.is_some());
+            assert!(result.changes.is_empty());
+            assert!(result.events.is_empty());
+            assert!(result.receipt_data.is_empty());
+            assert_eq!(simulator.state(), &state);
+        }
+    }
+}
@@ -7,17 +7,19 @@ use dgc_contract_sdk::protocol::state::{
     ContractRegistryListBuilder, NamespaceRegistry, NamespaceRegistryList,
     NamespaceRegistryListBuilder, SmartPermission, SmartPermissionList, SmartPermissionListBuilder,
     Account, AccountList, Organization, OrganizationList,
-    AccountListBuilder, OrganizationListBuilder,
+    AccountListBuilder, OrganizationListBuilder, Role, RoleList, RoleListBuilder,
 };
-use dgc_contract_sdk::protocol::ADMINISTRATORS_SETTING_ADDRESS;
+use dgc_contract_sdk::protocol::{ADMINISTRATORS_SETTING_ADDRESS, BLOCK_INFO_CONFIG_ADDRESS};
+use dgc_contract_sdk::protos::block_info::BlockInfoConfig;
 use dgc_contract_sdk::protos::{FromBytes, IntoBytes};
 use sawtooth_sdk::messages::setting::Setting;
 use sawtooth_sdk::processor::handler::ApplyError;
 use sawtooth_sdk::processor::handler::TransactionContext;
 
-use crate::addressing::{
-    compute_account_address, compute_org_address, compute_smart_permission_address,
-    make_contract_address, make_contract_registry_address, make_namespace_registry_address,
+use dgc_contract_sdk::addressing::{
+    compute_account_address, compute_contract_address, compute_contract_registry_address,
+    compute_namespace_registry_address, compute_org_address, compute_role_address,
+    compute_smart_permission_address,
 };
 
 pub struct SmartState<'a> {
@@ -53,12 +55,69 @@ impl<'a> SmartState<'a> {
         }
     }
 
+    /// Returns the value of an on-chain setting, if it has been set
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - the state address of the setting
+    /// * `key` - the setting key to look for at that address
+    pub fn get_setting_value(
+        &mut self,
+        address: &str,
+        key: &str,
+    ) -> Result<Option<String>, ApplyError> {
+        let d = self.context.get_state_entry(address)?;
+        match d {
+            Some(packed) => {
+                let setting: Setting =
+                    protobuf::parse_from_bytes(packed.as_slice()).map_err(|err| {
+                        ApplyError::InvalidTransaction(format!(
+                            "Cannot deserialize setting: {:?}",
+                            err,
+                        ))
+                    })?;
+
+                Ok(setting
+                    .get_entries()
+                    .iter()
+                    .find(|entry| entry.key == key)
+                    .map(|entry| entry.value.clone()))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the number of the latest block recorded by the BlockInfo
+    /// transaction family
+    ///
+    /// The BlockInfo transaction processor must be running and its config
+    /// address must be in the transaction inputs.
+    pub fn get_block_num(&mut self) -> Result<u64, ApplyError> {
+        let d = self.context.get_state_entry(BLOCK_INFO_CONFIG_ADDRESS)?;
+        match d {
+            Some(packed) => {
+                let config: BlockInfoConfig =
+                    protobuf::parse_from_bytes(packed.as_slice()).map_err(|err| {
+                        ApplyError::InvalidTransaction(format!(
+                            "Cannot deserialize block info config: {:?}",
+                            err,
+                        ))
+                    })?;
+
+                Ok(config.get_latest_block())
+            }
+            None => Err(ApplyError::InvalidTransaction(String::from(
+                "Block info is not available; the BlockInfo transaction family is required",
+            ))),
+        }
+    }
+
     pub fn get_contract(
         &mut self,
         name: &str,
         version: &str,
     ) -> Result<Option<Contract>, ApplyError> {
-        let address = make_contract_address(name, version)?;
+        let address = compute_contract_address(name, version);
         let d = self.context.get_state_entry(&address)?;
         match d {
             Some(packed) => {
@@ -84,7 +143,7 @@ impl<'a> SmartState<'a> {
         version: &str,
         new_contract: Contract,
     ) -> Result<(), ApplyError> {
-        let address = make_contract_address(name, version)?;
+        let address = compute_contract_address(name, version);
         let d = self.context.get_state_entry(&address)?;
         let mut contracts = match d {
             Some(packed) => match ContractList::from_bytes(packed.as_slice()) {
@@ -128,7 +187,7 @@ impl<'a> SmartState<'a> {
     }
 
     pub fn delete_contract(&mut self, name: &str, version: &str) -> Result<(), ApplyError> {
-        let address = make_contract_address(name, version)?;
+        let address = compute_contract_address(name, version);
         let d = self.context.delete_state_entry(&address)?;
         let deleted = match d {
             Some(deleted) => deleted,
@@ -150,7 +209,7 @@ impl<'a> SmartState<'a> {
         &mut self,
         name: &str,
     ) -> Result<Option<ContractRegistry>, ApplyError> {
-        let address = make_contract_registry_address(name)?;
+        let address = compute_contract_registry_address(name);
         let d = self.context.get_state_entry(&address)?;
         match d {
             Some(packed) => {
@@ -177,7 +236,7 @@ impl<'a> SmartState<'a> {
         name: &str,
         new_contract_registry: ContractRegistry,
     ) -> Result<(), ApplyError> {
-        let address = make_contract_registry_address(name)?;
+        let address = compute_contract_registry_address(name);
         let d = self.context.get_state_entry(&address)?;
         let mut contract_registries = match d {
             Some(packed) => match ContractRegistryList::from_bytes(packed.as_slice()) {
@@ -223,7 +282,7 @@ impl<'a> SmartState<'a> {
     }
 
     pub fn delete_contract_registry(&mut self, name: &str) -> Result<(), ApplyError> {
-        let address = make_contract_registry_address(name)?;
+        let address = compute_contract_registry_address(name);
         let d = self.context.delete_state_entry(&address)?;
         let deleted = match d {
             Some(deleted) => deleted,
@@ -245,7 +304,8 @@ impl<'a> SmartState<'a> {
         &mut self,
         namespace: &str,
     ) -> Result<Option<NamespaceRegistry>, ApplyError> {
-        let address = make_namespace_registry_address(namespace)?;
+        let address = compute_namespace_registry_address(namespace)
+            .map_err(|err| ApplyError::InvalidTransaction(err.to_string()))?;
         let d = self.context.get_state_entry(&address)?;
         match d {
             Some(packed) => {
@@ -271,7 +331,8 @@ impl<'a> SmartState<'a> {
         &mut self,
         namespace: &str,
     ) -> Result<Option<NamespaceRegistryList>, ApplyError> {
-        let address = make_namespace_registry_address(namespace)?;
+        let address = compute_namespace_registry_address(namespace)
+            .map_err(|err| ApplyError::InvalidTransaction(err.to_string()))?;
         let d = self.context.get_state_entry(&address)?;
         match d {
             Some(packed) => {
@@ -293,7 +354,8 @@ impl<'a> SmartState<'a> {
         namespace: &str,
         new_namespace_registry: NamespaceRegistry,
     ) -> Result<(), ApplyError> {
-        let address = make_namespace_registry_address(namespace)?;
+        let address = compute_namespace_registry_address(namespace)
+            .map_err(|err| ApplyError::InvalidTransaction(err.to_string()))?;
         let d = self.context.get_state_entry(&address)?;
         let mut namespace_registries = match d {
             Some(packed) => match NamespaceRegistryList::from_bytes(packed.as_slice()) {
@@ -338,7 +400,8 @@ impl<'a> SmartState<'a> {
     }
 
     pub fn delete_namespace_registry(&mut self, namespace: &str) -> Result<(), ApplyError> {
-        let address = make_namespace_registry_address(namespace)?;
+        let address = compute_namespace_registry_address(namespace)
+            .map_err(|err| ApplyError::InvalidTransaction(err.to_string()))?;
         let d = self.context.delete_state_entry(&address)?;
         let deleted = match d {
             Some(deleted) => deleted,
@@ -599,4 +662,103 @@ impl<'a> SmartState<'a> {
             .map_err(|err| ApplyError::InvalidTransaction(format!("{}", err)))?;
         Ok(())
     }
+
+    pub fn get_role(&mut self, org_id: &str, name: &str) -> Result<Option<Role>, ApplyError> {
+        let address = compute_role_address(org_id, name);
+        let d = self.context.get_state_entry(&address)?;
+        match d {
+            Some(packed) => {
+                let roles = RoleList::from_bytes(packed.as_slice()).map_err(|err| {
+                    ApplyError::InvalidTransaction(format!(
+                        "Cannot deserialize role list: {:?}",
+                        err,
+                    ))
+                })?;
+
+                Ok(roles
+                    .get_roles()
+                    .iter()
+                    .find(|role| role.get_org_id() == org_id && role.get_name() == name)
+                    .cloned())
+            }
+            None => Ok(None),
+        }
+    }
+
+    pub fn set_role(&mut self, org_id: &str, name: &str, new_role: Role) -> Result<(), ApplyError> {
+        let address = compute_role_address(org_id, name);
+        let mut roles = self.get_other_roles(&address, org_id, name)?;
+
+        roles.push(new_role);
+        // sort the roles by organization and name
+        roles.sort_by_key(|role| (role.get_org_id().to_string(), role.get_name().to_string()));
+
+        self.set_roles(address, roles)
+    }
+
+    pub fn delete_role(&mut self, org_id: &str, name: &str) -> Result<(), ApplyError> {
+        let address = compute_role_address(org_id, name);
+        let roles = self.get_other_roles(&address, org_id, name)?;
+
+        // keep the roles of other organizations that share the address
+        if !roles.is_empty() {
+            return self.set_roles(address, roles);
+        }
+
+        let d = self.context.delete_state_entry(&address.clone())?;
+        let deleted = match d {
+            Some(deleted) => deleted,
+            None => {
+                return Err(ApplyError::InvalidTransaction(String::from(
+                    "Cannot delete role",
+                )));
+            }
+        };
+        if deleted != address {
+            return Err(ApplyError::InvalidTransaction(String::from(
+                "Cannot delete role",
+            )));
+        };
+        Ok(())
+    }
+
+    // returns the roles stored at the address, except the given role
+    fn get_other_roles(
+        &mut self,
+        address: &str,
+        org_id: &str,
+        name: &str,
+    ) -> Result<Vec<Role>, ApplyError> {
+        let d = self.context.get_state_entry(address)?;
+        match d {
+            Some(packed) => match RoleList::from_bytes(packed.as_slice()) {
+                Ok(roles) => Ok(roles
+                    .get_roles()
+                    .iter()
+                    .filter(|role| role.get_org_id() != org_id || role.get_name() != name)
+                    .cloned()
+                    .collect::<Vec<Role>>()),
+                Err(err) => Err(ApplyError::InvalidTransaction(format!(
+                    "Cannot deserialize role list: {}",
+                    err,
+                ))),
+            },
+            None => Ok(vec![]),
+        }
+    }
+
+    fn set_roles(&mut self, address: String, roles: Vec<Role>) -> Result<(), ApplyError> {
+        let role_list = RoleListBuilder::new()
+            .set_roles(roles)
+            .build()
+            .map_err(|_| ApplyError::InvalidTransaction(String::from("Cannot build role list")))?;
+
+        let serialized = role_list.into_bytes().map_err(|err| {
+            ApplyError::InvalidTransaction(format!("Cannot serialize role list: {:?}", err))
+        })?;
+        self.context
+            .set_state_entry(address, serialized)
+            .map_err(|err| ApplyError::InvalidTransaction(format!("{}", err)))?;
+        Ok(())
+    }
 }
@@ -0,0 +1,128 @@
+// Copyright (c) The dgc.network
+// SPDX-License-Identifier: Apache-2.0
+
+use semver::{Version as SemVer, VersionReq};
+
+use dgc_contract_sdk::protocol::state::ContractRegistry;
+
+/// Version selector resolving to the newest version that is not deprecated
+pub const LATEST_VERSION: &str = "latest";
+
+/// Resolves the version requested by an ExecuteContractAction to a version
+/// listed in the contract registry
+///
+/// A version listed in the registry is always used as-is, even if it is
+/// deprecated. Otherwise the request must be `latest` or a semver range such
+/// as `^1.2` or `>=1.0, <2.0`, and resolves to the highest non-deprecated
+/// version that satisfies it. Registry versions that are not valid semver are
+/// never selected by `latest` or a range.
+///
+/// Returns None if no version satisfies the request.
+pub fn resolve_version(registry: &ContractRegistry, requested: &str) -> Option<String> {
+    if registry
+        .get_versions()
+        .iter()
+        .any(|version| version.get_version() == requested)
+    {
+        return Some(requested.to_string());
+    }
+
+    let requirement = if requested == LATEST_VERSION {
+        VersionReq::any()
+    } else {
+        VersionReq::parse(requested).ok()?
+    };
+
+    registry
+        .get_versions()
+        .iter()
+        .filter(|version| !version.get_deprecated())
+        .filter_map(|version| {
+            SemVer::parse(version.get_version())
+                .ok()
+                .map(|semver| (semver, version.get_version()))
+        })
+        .filter(|(semver, _)| requirement.matches(semver))
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(_, version)| version.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use dgc_contract_sdk::protocol::state::{ContractRegistryBuilder, Version, VersionBuilder};
+
+    fn version(version: &str, deprecated: bool) -> Version {
+        VersionBuilder::new()
+            .set_version(version.to_string())
+            .set_contract_sha512("sha512".to_string())
+            .set_creator("creator".to_string())
+            .set_deprecated(deprecated)
+            .build()
+            .unwrap()
+    }
+
+    fn contract_registry(versions: Vec<Version>) -> ContractRegistry {
+        ContractRegistryBuilder::new()
+            .set_name("intkey".to_string())
+            .set_versions(versions)
+            .set_owners(vec!["owner".to_string()])
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    // check that latest resolves to the highest version that is not deprecated
+    fn check_resolve_latest() {
+        let registry = contract_registry(vec![
+            version("1.0.0", false),
+            version("1.10.0", false),
+            version("1.9.0", false),
+            version("2.0.0", true),
+            version("nightly", false),
+        ]);
+        assert_eq!(
+            resolve_version(&registry, LATEST_VERSION),
+            Some("1.10.0".to_string())
+        );
+
+        let deprecated = contract_registry(vec![version("1.0.0", true)]);
+        assert_eq!(resolve_version(&deprecated, LATEST_VERSION), None);
+    }
+
+    #[test]
+    // check that a listed version is used as-is, even if deprecated or not semver
+    fn check_resolve_listed_version() {
+        let registry = contract_registry(vec![version("1.0.0", true), version("nightly", false)]);
+        assert_eq!(
+            resolve_version(&registry, "1.0.0"),
+            Some("1.0.0".to_string())
+        );
+        assert_eq!(
+            resolve_version(&registry, "nightly"),
+            Some("nightly".to_string())
+        );
+    }
+
+    #[test]
+    // check that a range resolves to the highest matching version
+    fn check_resolve_range() {
+        let registry = contract_registry(vec![
+            version("1.2.0", false),
+            version("1.4.1", false),
+            version("1.5.0", true),
+            version("2.0.0", false),
+        ]);
+        assert_eq!(
+            resolve_version(&registry, "^1.2"),
+            Some("1.4.1".to_string())
+        );
+        assert_eq!(
+            resolve_version(&registry, ">=1.0.0, <1.3.0"),
+            Some("1.2.0".to_string())
+        );
+        assert_eq!(resolve_version(&registry, "^3"), None);
+        assert_eq!(resolve_version(&registry, "not a version"), None);
+    }
+}
@@ -0,0 +1,66 @@
+// Copyright (c) The dgc.network
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashSet;
+
+use crate::wasm_executor::trace::ExecutionTrace;
+
+/// Fuel budget used for a transaction when the fuel limit setting is not set
+pub const DEFAULT_FUEL_LIMIT: u64 = 50_000_000;
+
+/// Linear memory pages (64 KiB each) available when the setting is not set
+pub const DEFAULT_MAX_MEMORY_PAGES: u32 = 256;
+
+/// Largest value a single `set_state` entry may hold when the setting is not set
+pub const DEFAULT_MAX_STATE_ENTRY_SIZE: usize = 1024 * 1024;
+
+/// Number of distinct addresses a transaction may touch when the setting is not set
+pub const DEFAULT_MAX_ADDRESSES_TOUCHED: usize = 1024;
+
+/// Total bytes a transaction may write to state when the setting is not set
+pub const DEFAULT_MAX_BYTES_WRITTEN: usize = 10 * 1024 * 1024;
+
+/// Largest contract, in bytes, that may be uploaded when the setting is not set
+pub const DEFAULT_MAX_CONTRACT_SIZE: usize = 4 * 1024 * 1024;
+
+/// Deepest chain of contracts invoking other contracts with `invoke_contract`
+pub const MAX_CALL_DEPTH: u32 = 8;
+
+/// Ceilings enforced on the contract execution of a single transaction
+///
+/// The values are read from the on-chain settings so that every validator
+/// enforces identical limits.
+#[derive(Clone, Debug)]
+pub struct ExecutionLimits {
+    pub fuel: u64,
+    pub memory_pages: u32,
+    pub state_entry_size: usize,
+    pub addresses_touched: usize,
+    pub bytes_written: usize,
+}
+
+impl Default for ExecutionLimits {
+    fn default() -> Self {
+        ExecutionLimits {
+            fuel: DEFAULT_FUEL_LIMIT,
+            memory_pages: DEFAULT_MAX_MEMORY_PAGES,
+            state_entry_size: DEFAULT_MAX_STATE_ENTRY_SIZE,
+            addresses_touched: DEFAULT_MAX_ADDRESSES_TOUCHED,
+            bytes_written: DEFAULT_MAX_BYTES_WRITTEN,
+        }
+    }
+}
+
+/// Resources consumed so far by the contract execution of a single transaction
+///
+/// Smart permissions invoked by a contract continue from the caller's usage,
+/// so the limits apply to the transaction as a whole.
+#[derive(Clone, Debug, Default)]
+pub struct ExecutionUsage {
+    pub fuel: u64,
+    pub addresses_touched: HashSet<String>,
+    pub bytes_written: usize,
+    /// Records the host calls and log lines of the execution, if tracing is
+    /// enabled
+    pub trace: Option<ExecutionTrace>,
+}
@@ -0,0 +1,116 @@
+// Copyright (c) The dgc.network
+// SPDX-License-Identifier: Apache-2.0
+
+use parity_wasm::elements::{External, MemoryType};
+use pwasm_utils::rules::Set;
+use wasmi::Module;
+
+use crate::wasm_executor::wasm_externals::{ExternalsError, ExternalsErrorKind};
+
+/// Fuel charged for every host function call made by a contract
+pub const HOST_CALL_FUEL_COST: u64 = 100;
+
+/// Parses a wasm module and injects fuel accounting into it
+///
+/// Every metered block of instructions calls the `gas` import provided by
+/// `WasmExternals` with the fuel consumed by that block, so execution can be
+/// stopped deterministically once the transaction's budget is exhausted.
+///
+/// The module's linear memory is capped at `max_memory_pages`; modules that
+/// require more than that up front are rejected.
+pub fn load_metered_module(wasm: &[u8], max_memory_pages: u32) -> Result<Module, ExternalsError> {
+    let mut module = parity_wasm::deserialize_buffer(wasm)?;
+
+    if let Some(imports) = module.import_section() {
+        for entry in imports.entries() {
+            if let External::Memory(ref memory) = *entry.external() {
+                check_initial_pages(memory.limits().initial(), max_memory_pages)?;
+            }
+        }
+    }
+
+    if let Some(memories) = module.memory_section_mut() {
+        for memory in memories.entries_mut().iter_mut() {
+            let initial = memory.limits().initial();
+            check_initial_pages(initial, max_memory_pages)?;
+
+            let maximum = match memory.limits().maximum() {
+                Some(maximum) if maximum < max_memory_pages => maximum,
+                _ => max_memory_pages,
+            };
+            *memory = MemoryType::new(initial, Some(maximum));
+        }
+    }
+
+    let module = pwasm_utils::inject_gas_counter(module, &Set::default())
+        .map_err(|_| ExternalsError::from("Unable to inject fuel metering into module"))?;
+
+    Module::from_parity_wasm_module(module).map_err(ExternalsError::from)
+}
+
+fn check_initial_pages(initial: u32, max_memory_pages: u32) -> Result<(), ExternalsError> {
+    if initial > max_memory_pages {
+        return Err(ExternalsError::with_kind(
+            ExternalsErrorKind::MemoryLimitExceeded,
+            format!(
+                "Module requires {} memory pages, limit is {}",
+                initial, max_memory_pages
+            ),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEADER: &[u8] = &[0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00];
+    /// One function type: (i32, i32, i32) -> i32
+    const TYPES: &[u8] = &[0x01, 0x08, 0x01, 0x60, 0x03, 0x7f, 0x7f, 0x7f, 0x01, 0x7f];
+    /// Imports `env.memory` with 17 initial pages
+    const IMPORTED_MEMORY: &[u8] = &[
+        0x02, 0x0f, 0x01, 0x03, 0x65, 0x6e, 0x76, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79, 0x02,
+        0x00, 0x11,
+    ];
+    const FUNCTIONS: &[u8] = &[0x03, 0x02, 0x01, 0x00];
+    /// Exports function 0 as `entrypoint`
+    const EXPORTS: &[u8] = &[
+        0x07, 0x0e, 0x01, 0x0a, 0x65, 0x6e, 0x74, 0x72, 0x79, 0x70, 0x6f, 0x69, 0x6e, 0x74, 0x00,
+        0x00,
+    ];
+    /// A body returning 1
+    const CODE: &[u8] = &[0x0a, 0x06, 0x01, 0x04, 0x00, 0x41, 0x01, 0x0b];
+
+    /// Builds a module declaring `memory_pages` initial pages of its own
+    /// memory, with an entrypoint returning 1
+    fn module(memory_pages: u8) -> Vec<u8> {
+        let memory = [0x05, 0x03, 0x01, 0x00, memory_pages];
+        [HEADER, TYPES, FUNCTIONS, &memory[..], EXPORTS, CODE].concat()
+    }
+
+    #[test]
+    // check that a module is loaded when its memory is within the limit
+    fn check_memory_within_limit() {
+        assert!(load_metered_module(&module(1), 16).is_ok());
+        assert!(load_metered_module(&module(16), 16).is_ok());
+    }
+
+    #[test]
+    // check that a module requiring more memory pages than allowed is rejected
+    fn check_memory_over_limit() {
+        // wasmi's Module is not Debug, so unwrap_err is not available
+        let err = load_metered_module(&module(17), 16).err().unwrap();
+        assert_eq!(err.kind(), ExternalsErrorKind::MemoryLimitExceeded);
+
+        let wasm = [HEADER, TYPES, IMPORTED_MEMORY, FUNCTIONS, EXPORTS, CODE].concat();
+        let err = load_metered_module(&wasm, 16).err().unwrap();
+        assert_eq!(err.kind(), ExternalsErrorKind::MemoryLimitExceeded);
+    }
+
+    #[test]
+    // check that bytes which are not a wasm module are rejected
+    fn check_invalid_module() {
+        assert!(load_metered_module(b"not wasm", 16).is_err());
+    }
+}
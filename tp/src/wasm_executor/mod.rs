@@ -1,5 +1,10 @@
 // Copyright (c) The dgc.network
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod limits;
+pub mod metering;
+pub mod module_cache;
+pub mod trace;
+pub mod validation;
 pub mod wasm_externals;
 pub mod wasm_module;
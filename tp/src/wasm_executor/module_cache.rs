@@ -0,0 +1,193 @@
+// Copyright (c) The dgc.network
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use wasmi::Module;
+
+/// Number of compiled modules kept by the transaction handler
+pub const DEFAULT_MODULE_CACHE_SIZE: usize = 32;
+
+/// Identifies a compiled module
+///
+/// The content hash guards against serving a stale module if the bytes stored
+/// at a contract address change, and the memory limit is included because it
+/// is baked into the module when it is loaded.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct ModuleCacheKey {
+    pub contract_address: String,
+    pub contract_sha512: String,
+    pub memory_pages: u32,
+}
+
+/// A least-recently-used cache of validated, metered wasm modules
+pub struct ModuleCache {
+    capacity: usize,
+    entries: HashMap<ModuleCacheKey, (Arc<Module>, u64)>,
+    clock: u64,
+    hits: u64,
+    misses: u64,
+}
+
+impl ModuleCache {
+    pub fn new(capacity: usize) -> ModuleCache {
+        ModuleCache {
+            capacity,
+            entries: HashMap::new(),
+            clock: 0,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Returns the cached module for the key, counting the lookup as a hit or a miss
+    pub fn get(&mut self, key: &ModuleCacheKey) -> Option<Arc<Module>> {
+        self.clock += 1;
+        match self.entries.get_mut(key) {
+            Some((module, last_used)) => {
+                *last_used = self.clock;
+                self.hits += 1;
+                Some(module.clone())
+            }
+            None => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// Adds a module to the cache, evicting the least recently used module if full
+    pub fn insert(&mut self, key: ModuleCacheKey, module: Arc<Module>) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if !self.entries.contains_key(&key) && self.entries.len() >= self.capacity {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                self.entries.remove(&oldest);
+            }
+        }
+
+        self.clock += 1;
+        self.entries.insert(key, (module, self.clock));
+    }
+
+    /// Removes every cached module compiled from the given contract address
+    pub fn invalidate(&mut self, contract_address: &str) {
+        self.entries.retain(|key, _| key.contract_address != contract_address);
+    }
+
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The smallest valid wasm module: the magic number and version
+    const EMPTY_MODULE: &[u8] = &[0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00];
+
+    fn key(contract_address: &str) -> ModuleCacheKey {
+        ModuleCacheKey {
+            contract_address: contract_address.to_string(),
+            contract_sha512: "sha512".to_string(),
+            memory_pages: 16,
+        }
+    }
+
+    fn module() -> Arc<Module> {
+        Arc::new(Module::from_buffer(EMPTY_MODULE).unwrap())
+    }
+
+    #[test]
+    // check that the least recently used module is evicted once the cache is full
+    fn check_lru_eviction() {
+        let mut cache = ModuleCache::new(2);
+        cache.insert(key("a"), module());
+        cache.insert(key("b"), module());
+
+        // using a makes b the least recently used module
+        assert!(cache.get(&key("a")).is_some());
+        cache.insert(key("c"), module());
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(&key("a")).is_some());
+        assert!(cache.get(&key("b")).is_none());
+        assert!(cache.get(&key("c")).is_some());
+    }
+
+    #[test]
+    // check that replacing a cached module does not evict another one
+    fn check_reinsert() {
+        let mut cache = ModuleCache::new(2);
+        cache.insert(key("a"), module());
+        cache.insert(key("b"), module());
+        cache.insert(key("a"), module());
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(&key("b")).is_some());
+    }
+
+    #[test]
+    // check that the memory limit and content hash are part of the key
+    fn check_key_fields() {
+        let mut cache = ModuleCache::new(4);
+        cache.insert(key("a"), module());
+
+        let mut other_pages = key("a");
+        other_pages.memory_pages = 32;
+        assert!(cache.get(&other_pages).is_none());
+
+        let mut other_hash = key("a");
+        other_hash.contract_sha512 = "other".to_string();
+        assert!(cache.get(&other_hash).is_none());
+    }
+
+    #[test]
+    // check that invalidating an address removes only its modules
+    fn check_invalidate() {
+        let mut cache = ModuleCache::new(4);
+        cache.insert(key("a"), module());
+        let mut other_pages = key("a");
+        other_pages.memory_pages = 32;
+        cache.insert(other_pages, module());
+        cache.insert(key("b"), module());
+
+        cache.invalidate("a");
+        assert_eq!(cache.len(), 1);
+        assert!(cache.get(&key("b")).is_some());
+    }
+
+    #[test]
+    // check that lookups are counted and a cache without capacity keeps nothing
+    fn check_hits_and_misses() {
+        let mut cache = ModuleCache::new(0);
+        cache.insert(key("a"), module());
+        assert_eq!(cache.len(), 0);
+        assert!(cache.get(&key("a")).is_none());
+
+        let mut cache = ModuleCache::new(1);
+        cache.insert(key("a"), module());
+        cache.get(&key("a"));
+        cache.get(&key("a"));
+        cache.get(&key("b"));
+        assert_eq!(cache.hits(), 2);
+        assert_eq!(cache.misses(), 1);
+    }
+}
@@ -0,0 +1,104 @@
+// Copyright (c) The dgc.network
+// SPDX-License-Identifier: Apache-2.0
+
+//! Opt-in recording of the host calls and log lines of contract executions,
+//! used to debug and profile contracts.
+
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+
+use serde_json::Value;
+
+/// A host function called by a contract or smart permission
+#[derive(Clone, Debug, PartialEq)]
+pub struct HostCallRecord {
+    /// The contract making the call, None for a smart permission
+    pub contract: Option<String>,
+    pub depth: u32,
+    pub function: String,
+    pub args: Vec<i64>,
+    /// The state addresses the call read, wrote or deleted
+    pub addresses: Vec<String>,
+    /// The number of bytes the call read from or wrote to state or the
+    /// contract's memory
+    pub bytes: usize,
+    pub duration_micros: u64,
+    /// The fuel consumed by the transaction once the call returned
+    pub fuel: u64,
+    /// Whether the call trapped, aborting the execution
+    pub trapped: bool,
+}
+
+/// A line logged by a contract or smart permission
+#[derive(Clone, Debug, PartialEq)]
+pub struct LogRecord {
+    /// The contract logging the line, None for a smart permission
+    pub contract: Option<String>,
+    pub depth: u32,
+    pub level: String,
+    pub message: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TraceRecord {
+    HostCall(HostCallRecord),
+    Log(LogRecord),
+}
+
+impl TraceRecord {
+    pub fn to_json(&self) -> Value {
+        match self {
+            TraceRecord::HostCall(call) => json!({
+                "type": "host_call",
+                "contract": call.contract,
+                "depth": call.depth,
+                "function": call.function,
+                "args": call.args,
+                "addresses": call.addresses,
+                "bytes": call.bytes,
+                "duration_micros": call.duration_micros,
+                "fuel": call.fuel,
+                "trapped": call.trapped,
+            }),
+            TraceRecord::Log(log) => json!({
+                "type": "log",
+                "contract": log.contract,
+                "depth": log.depth,
+                "level": log.level,
+                "message": log.message,
+            }),
+        }
+    }
+}
+
+impl fmt::Display for TraceRecord {
+    /// Formats the record as a single line of JSON
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_json())
+    }
+}
+
+/// The records of a transaction's contract executions, in the order the calls
+/// returned
+///
+/// Clones share the same records, so the trace follows the execution into
+/// nested contracts and smart permissions along with the `ExecutionUsage`.
+#[derive(Clone, Debug, Default)]
+pub struct ExecutionTrace {
+    records: Rc<RefCell<Vec<TraceRecord>>>,
+}
+
+impl ExecutionTrace {
+    pub fn new() -> ExecutionTrace {
+        ExecutionTrace::default()
+    }
+
+    pub fn record(&self, record: TraceRecord) {
+        self.records.borrow_mut().push(record);
+    }
+
+    pub fn records(&self) -> Vec<TraceRecord> {
+        self.records.borrow().clone()
+    }
+}
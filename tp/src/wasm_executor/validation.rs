@@ -0,0 +1,288 @@
+// Copyright (c) The dgc.network
+// SPDX-License-Identifier: Apache-2.0
+
+use parity_wasm::elements::{External, Instruction, Internal, Module, Type, ValueType};
+use sawtooth_sdk::processor::handler::TransactionContext;
+use wasmi::{ImportsBuilder, ModuleInstance};
+
+use crate::wasm_executor::limits::{ExecutionLimits, ExecutionUsage};
+use crate::wasm_executor::metering::load_metered_module;
+use crate::wasm_executor::wasm_externals::{ExternalsError, ExternalsErrorKind, WasmExternals};
+
+/// Checks that a contract being uploaded can be executed, so that a broken
+/// contract is rejected by the transaction storing it rather than by every
+/// transaction executing it
+///
+/// The contract must be at most `max_size` bytes, parse as a wasm module,
+/// avoid floating-point and start functions, export an `entrypoint` taking
+/// the payload, signer and signature pointers and returning a status code, and
+/// import only host functions provided by `WasmExternals`, with matching
+/// signatures.
+pub fn validate_contract(
+    wasm: &[u8],
+    max_size: usize,
+    limits: ExecutionLimits,
+    context: &mut dyn TransactionContext,
+) -> Result<(), ExternalsError> {
+    if wasm.len() > max_size {
+        return Err(invalid(format!(
+            "Contract is {} bytes, limit is {}",
+            wasm.len(),
+            max_size
+        )));
+    }
+
+    let module: Module = parity_wasm::deserialize_buffer(wasm)
+        .map_err(|err| invalid(format!("Contract is not a valid wasm module: {}", err)))?;
+
+    check_deterministic(&module)?;
+    check_entrypoint(&module)?;
+
+    // Instantiate the module the same way it is executed, which resolves its
+    // imports against the host functions and checks their signatures
+    let metered = load_metered_module(wasm, limits.memory_pages)
+        .map_err(|err| invalid(format!("Contract cannot be loaded: {}", err)))?;
+    let env = WasmExternals::new(None, context, limits, ExecutionUsage::default(), None)?;
+    ModuleInstance::new(&metered, &ImportsBuilder::new().with_resolver("env", &env))
+        .map_err(|err| invalid(format!("Contract cannot be instantiated: {}", err)))?;
+
+    Ok(())
+}
+
+/// Rejects features whose results may differ between validators
+///
+/// Floating-point arithmetic is not bit-for-bit reproducible across
+/// platforms. In a validated module a float value can only come from a float
+/// parameter, local, global or result, or from one of the instructions
+/// producing one, so it is enough to reject those. Start functions run when the
+/// module is instantiated, outside of the entrypoint and its error handling.
+fn check_deterministic(module: &Module) -> Result<(), ExternalsError> {
+    if module.start_section().is_some() {
+        return Err(invalid("Contract must not have a start function".into()));
+    }
+
+    let is_float = |value_type: &ValueType| match value_type {
+        ValueType::F32 | ValueType::F64 => true,
+        _ => false,
+    };
+
+    if let Some(types) = module.type_section() {
+        for Type::Function(function_type) in types.types() {
+            if function_type.params().iter().any(is_float)
+                || function_type.return_type().as_ref().map_or(false, is_float)
+            {
+                return Err(invalid("Contract must not use floating-point values".into()));
+            }
+        }
+    }
+
+    let imported_globals = module
+        .import_section()
+        .map(|imports| imports.entries())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|entry| match entry.external() {
+            External::Global(global_type) => Some(global_type.content_type()),
+            _ => None,
+        });
+    let declared_globals = module
+        .global_section()
+        .map(|globals| globals.entries())
+        .unwrap_or_default()
+        .iter()
+        .map(|entry| entry.global_type().content_type());
+    if imported_globals.chain(declared_globals).any(|value_type| is_float(&value_type)) {
+        return Err(invalid("Contract must not use floating-point globals".into()));
+    }
+
+    if let Some(code) = module.code_section() {
+        for body in code.bodies() {
+            if body.locals().iter().any(|local| is_float(&local.value_type())) {
+                return Err(invalid("Contract must not use floating-point locals".into()));
+            }
+            if let Some(instruction) = body.code().elements().iter().find(|i| produces_float(i)) {
+                return Err(invalid(format!(
+                    "Contract must not use floating-point instructions: {:?}",
+                    instruction
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn produces_float(instruction: &Instruction) -> bool {
+    match instruction {
+        Instruction::F32Const(_)
+        | Instruction::F64Const(_)
+        | Instruction::F32Load(..)
+        | Instruction::F64Load(..)
+        | Instruction::F32ConvertSI32
+        | Instruction::F32ConvertUI32
+        | Instruction::F32ConvertSI64
+        | Instruction::F32ConvertUI64
+        | Instruction::F64ConvertSI32
+        | Instruction::F64ConvertUI32
+        | Instruction::F64ConvertSI64
+        | Instruction::F64ConvertUI64
+        | Instruction::F32ReinterpretI32
+        | Instruction::F64ReinterpretI64 => true,
+        _ => false,
+    }
+}
+
+/// Checks that the module exports `entrypoint(i32, i32, i32) -> i32`
+fn check_entrypoint(module: &Module) -> Result<(), ExternalsError> {
+    let index = module
+        .export_section()
+        .map(|exports| exports.entries())
+        .unwrap_or_default()
+        .iter()
+        .find(|entry| entry.field() == "entrypoint")
+        .and_then(|entry| match entry.internal() {
+            Internal::Function(index) => Some(*index as usize),
+            _ => None,
+        })
+        .ok_or_else(|| invalid("Contract does not export an entrypoint function".into()))?;
+
+    // Imported functions come first in the function index space
+    let imported = module
+        .import_section()
+        .map(|imports| imports.functions())
+        .unwrap_or(0);
+    let type_ref = index
+        .checked_sub(imported)
+        .and_then(|index| module.function_section()?.entries().get(index))
+        .map(|function| function.type_ref() as usize)
+        .ok_or_else(|| invalid("Contract entrypoint must be defined by the contract".into()))?;
+
+    let signature_matches = match module.type_section().and_then(|t| t.types().get(type_ref)) {
+        Some(Type::Function(function_type)) => {
+            function_type.params() == [ValueType::I32, ValueType::I32, ValueType::I32]
+                && function_type.return_type() == Some(ValueType::I32)
+        }
+        None => false,
+    };
+    if !signature_matches {
+        return Err(invalid(
+            "Contract entrypoint must take three i32 pointers and return an i32".into(),
+        ));
+    }
+
+    Ok(())
+}
+
+fn invalid(message: String) -> ExternalsError {
+    ExternalsError::with_kind(ExternalsErrorKind::InvalidModule, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::BTreeMap;
+
+    use crate::simulator::InMemoryContext;
+
+    const HEADER: &[u8] = &[0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00];
+    /// One function type: (i32, i32, i32) -> i32
+    const TYPES: &[u8] = &[0x01, 0x08, 0x01, 0x60, 0x03, 0x7f, 0x7f, 0x7f, 0x01, 0x7f];
+    /// One function type: () -> i32
+    const NO_PARAM_TYPES: &[u8] = &[0x01, 0x05, 0x01, 0x60, 0x00, 0x01, 0x7f];
+    /// Imports a function `env.bogus`, which the host does not provide
+    const UNKNOWN_IMPORT: &[u8] = &[
+        0x02, 0x0d, 0x01, 0x03, 0x65, 0x6e, 0x76, 0x05, 0x62, 0x6f, 0x67, 0x75, 0x73, 0x00, 0x00,
+    ];
+    const FUNCTIONS: &[u8] = &[0x03, 0x02, 0x01, 0x00];
+    const MEMORY: &[u8] = &[0x05, 0x03, 0x01, 0x00, 0x01];
+    /// Exports function 0 as `entrypoint`
+    const EXPORTS: &[u8] = &[
+        0x07, 0x0e, 0x01, 0x0a, 0x65, 0x6e, 0x74, 0x72, 0x79, 0x70, 0x6f, 0x69, 0x6e, 0x74, 0x00,
+        0x00,
+    ];
+    /// Exports function 1, the first one defined after a single import, as
+    /// `entrypoint`
+    const EXPORTS_AFTER_IMPORT: &[u8] = &[
+        0x07, 0x0e, 0x01, 0x0a, 0x65, 0x6e, 0x74, 0x72, 0x79, 0x70, 0x6f, 0x69, 0x6e, 0x74, 0x00,
+        0x01,
+    ];
+    /// Exports function 0 as `main`
+    const MAIN_EXPORT: &[u8] = &[0x07, 0x08, 0x01, 0x04, 0x6d, 0x61, 0x69, 0x6e, 0x00, 0x00];
+    /// A body returning 1
+    const CODE: &[u8] = &[0x0a, 0x06, 0x01, 0x04, 0x00, 0x41, 0x01, 0x0b];
+    /// A body pushing and dropping an f32 constant before returning 1
+    const FLOAT_CODE: &[u8] = &[
+        0x0a, 0x0c, 0x01, 0x0a, 0x00, 0x43, 0x00, 0x00, 0x00, 0x00, 0x1a, 0x41, 0x01, 0x0b,
+    ];
+
+    fn validate(sections: &[&[u8]], max_size: usize) -> Result<(), ExternalsError> {
+        let mut context = InMemoryContext::new(BTreeMap::new(), &[], &[]);
+        validate_contract(
+            &sections.concat(),
+            max_size,
+            ExecutionLimits::default(),
+            &mut context,
+        )
+    }
+
+    fn check_invalid(sections: &[&[u8]]) {
+        let err = validate(sections, 1024).unwrap_err();
+        assert_eq!(err.kind(), ExternalsErrorKind::InvalidModule);
+    }
+
+    #[test]
+    // check that a module exporting a well-formed entrypoint is accepted
+    fn check_valid_contract() {
+        assert!(validate(&[HEADER, TYPES, FUNCTIONS, MEMORY, EXPORTS, CODE], 1024).is_ok());
+    }
+
+    #[test]
+    // check that a contract larger than the size limit is rejected
+    fn check_contract_size() {
+        let sections = [HEADER, TYPES, FUNCTIONS, MEMORY, EXPORTS, CODE];
+        let size = sections.concat().len();
+        assert!(validate(&sections, size).is_ok());
+
+        let err = validate(&sections, size - 1).unwrap_err();
+        assert_eq!(err.kind(), ExternalsErrorKind::InvalidModule);
+    }
+
+    #[test]
+    // check that bytes which are not a wasm module are rejected
+    fn check_not_wasm() {
+        check_invalid(&[&b"not wasm"[..]]);
+    }
+
+    #[test]
+    // check that floating-point instructions are rejected
+    fn check_float_instruction() {
+        check_invalid(&[HEADER, TYPES, FUNCTIONS, MEMORY, EXPORTS, FLOAT_CODE]);
+    }
+
+    #[test]
+    // check that a module without an entrypoint export is rejected
+    fn check_missing_entrypoint() {
+        check_invalid(&[HEADER, TYPES, FUNCTIONS, MEMORY, MAIN_EXPORT, CODE]);
+    }
+
+    #[test]
+    // check that an entrypoint with the wrong signature is rejected
+    fn check_entrypoint_signature() {
+        check_invalid(&[HEADER, NO_PARAM_TYPES, FUNCTIONS, MEMORY, EXPORTS, CODE]);
+    }
+
+    #[test]
+    // check that importing a function the host does not provide is rejected
+    fn check_unknown_import() {
+        check_invalid(&[
+            HEADER,
+            TYPES,
+            UNKNOWN_IMPORT,
+            FUNCTIONS,
+            MEMORY,
+            EXPORTS_AFTER_IMPORT,
+            CODE,
+        ]);
+    }
+}
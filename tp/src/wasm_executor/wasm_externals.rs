@@ -6,6 +6,7 @@ use std::collections::HashMap;
 use std::error::Error as StdError;
 use std::fmt;
 use std::string::FromUtf8Error;
+use std::sync::Mutex;
 use std::time::Instant;
 
 use log::{max_level, LevelFilter};
@@ -14,13 +15,22 @@ use dgc_contract_sdk::protos::FromBytes;
 //use crate::protocol::state::{SmartPermission, SmartPermissionList};
 //use crate::protos::FromBytes;
 use sawtooth_sdk::processor::handler::{ContextError, TransactionContext};
-use wasmi::memory_units::Pages;
+use wasmi::memory_units::{Bytes, Pages, RoundUpTo};
 use wasmi::{
     Error, Externals, FuncInstance, FuncRef, HostError, ImportsBuilder, MemoryDescriptor,
     MemoryInstance, MemoryRef, Module, ModuleImportResolver, ModuleInstance, RuntimeArgs,
     RuntimeValue, Signature, Trap, TrapKind, ValueType,
 };
 
+use crate::handler::{get_compiled_module, get_requested_contract};
+use crate::namespace::{check_namespace_permission, NamespaceAccess};
+use crate::state::SmartState;
+use crate::wasm_executor::limits::{ExecutionLimits, ExecutionUsage, MAX_CALL_DEPTH};
+use crate::wasm_executor::metering::{load_metered_module, HOST_CALL_FUEL_COST};
+use crate::wasm_executor::module_cache::ModuleCache;
+use crate::wasm_executor::trace::{HostCallRecord, LogRecord, TraceRecord};
+use crate::wasm_executor::wasm_module::WasmModule;
+
 // External function indices
 
 /// Args
@@ -149,23 +159,158 @@ const LOG: usize = 13;
 /// Returns the current logleel set on the transaction processor
 const LOG_LEVEL: usize = 14;
 
+/// Args
+///
+/// 1) amount of fuel consumed by the block of instructions being entered
+///
+/// Calls to this function are injected into the module by the fuel metering
+/// at load time and are not meant to be called by contracts directly.
+const GAS_IDX: usize = 15;
+
+/// Args
+///
+/// 1) Pointer to the contract name
+/// 2) Pointer to the contract version
+/// 3) Pointer to the payload for the contract
+///
+/// Returns - the return code of the invoked contract's entrypoint. The output
+/// buffer it set can be retrieved with `get_return_data`.
+///
+const INVOKE_CONTRACT_IDX: usize = 16;
+
+/// Args
+///
+/// 1) Pointer to the output buffer of the running contract
+///
+/// Returns - 1 if successful, or -1 if the pointer does not exist
+///
+const SET_RETURN_DATA_IDX: usize = 17;
+
+/// Returns - pointer to the output buffer set by the last contract invoked
+/// with `invoke_contract`, or -1 if it did not set one
+///
+const GET_RETURN_DATA_IDX: usize = 18;
+
+/// Args
+///
+/// 1) Pointer to the event type
+/// 2) First pointer in a collection of alternating attribute keys and values,
+///    or -1 if the event has no attributes
+/// 3) Pointer to the event data
+///
+/// Returns - 1 if successful, -1 if a pointer or the attribute collection is
+/// invalid, or 0 if the event could not be added
+///
+const ADD_EVENT_IDX: usize = 19;
+
+/// Args
+///
+/// 1) Pointer to the receipt data
+///
+/// Returns - 1 if successful, -1 if the pointer does not exist, or 0 if the
+/// data could not be added
+///
+const ADD_RECEIPT_DATA_IDX: usize = 20;
+
+/// Args
+///
+/// 1) Pointer to a serialized ContractError describing why the running
+///    contract failed
+///
+/// Returns - 1 if successful, or -1 if the pointer does not exist
+///
+const SET_ERROR_DATA_IDX: usize = 21;
+
+/// Identifies the contract a WasmExternals is executing and the transaction it
+/// is executing on behalf of
+#[derive(Clone, Debug)]
+pub struct ContractCall {
+    pub name: String,
+    pub signer: String,
+    pub signature: String,
+    /// Zero for the contract named in the transaction, incremented for each
+    /// nested `invoke_contract`
+    pub depth: u32,
+    /// The addresses the transaction authorized the contract to access, or
+    /// None if its state access is not restricted
+    pub authorized: Option<AuthorizedAddresses>,
+}
+
+/// The state addresses declared by the inputs and outputs of the action that
+/// executes a contract
+///
+/// Each entry is an address or an address prefix, as in the inputs and outputs
+/// of a Sawtooth transaction.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AuthorizedAddresses {
+    pub inputs: Vec<String>,
+    pub outputs: Vec<String>,
+}
+
+impl AuthorizedAddresses {
+    pub fn new(inputs: &[String], outputs: &[String]) -> AuthorizedAddresses {
+        AuthorizedAddresses {
+            inputs: inputs.to_vec(),
+            outputs: outputs.to_vec(),
+        }
+    }
+
+    /// Returns the first address not covered by the inputs, for reads, or the
+    /// outputs, for writes
+    fn find_unauthorized<'b>(
+        &self,
+        addresses: &'b [String],
+        access: NamespaceAccess,
+    ) -> Option<&'b String> {
+        let authorized = match access {
+            NamespaceAccess::Read => &self.inputs,
+            NamespaceAccess::Write => &self.outputs,
+        };
+        addresses.iter().find(|address| {
+            !authorized
+                .iter()
+                .any(|prefix| address.starts_with(prefix.as_str()))
+        })
+    }
+}
+
 pub struct WasmExternals<'a> {
     pub memory_ref: MemoryRef,
     context: &'a mut dyn TransactionContext,
     ptrs: HashMap<u32, Pointer>,
     ptr_collections: HashMap<u32, Vec<u32>>,
     memory_write_offset: u32,
+    limits: ExecutionLimits,
+    usage: ExecutionUsage,
+    call: Option<ContractCall>,
+    /// The cache the contracts invoked with `invoke_contract` are loaded from
+    module_cache: Option<&'a Mutex<ModuleCache>>,
+    return_data: Option<Vec<u8>>,
+    last_return_data: Option<Vec<u8>>,
+    error_data: Option<Vec<u8>>,
+    /// The state addresses and bytes accessed by the current host call, noted
+    /// for its trace record
+    trace_access: Option<(Vec<String>, usize)>,
+    /// Set while running a smart permission, which may read state but not
+    /// change it
+    read_only: bool,
 }
 
 impl<'a> WasmExternals<'a> {
     pub fn new(
         memory_ref: Option<MemoryRef>,
         context: &'a mut dyn TransactionContext,
+        limits: ExecutionLimits,
+        usage: ExecutionUsage,
+        call: Option<ContractCall>,
     ) -> Result<WasmExternals, ExternalsError> {
         let m_ref = if let Some(m) = memory_ref {
             m
         } else {
-            MemoryInstance::alloc(Pages(256), None)?
+            MemoryInstance::alloc(
+                Pages(limits.memory_pages.min(256) as usize),
+                Some(Pages(limits.memory_pages as usize)),
+            )?
         };
 
         Ok(WasmExternals {
@@ -174,9 +319,189 @@ impl<'a> WasmExternals<'a> {
             ptrs: HashMap::new(),
             ptr_collections: HashMap::new(),
             memory_write_offset: 0,
+            limits,
+            usage,
+            call,
+            module_cache: None,
+            return_data: None,
+            last_return_data: None,
+            error_data: None,
+            trace_access: None,
+            read_only: false,
         })
     }
 
+    /// Sets the module cache the contracts invoked with `invoke_contract` are
+    /// loaded from
+    pub fn cached(mut self, module_cache: Option<&'a Mutex<ModuleCache>>) -> WasmExternals<'a> {
+        self.module_cache = module_cache;
+        self
+    }
+
+    /// Takes the output buffer set by the contract with `set_return_data`
+    pub fn take_return_data(&mut self) -> Option<Vec<u8>> {
+        self.return_data.take()
+    }
+
+    /// Takes the serialized ContractError set by the contract with
+    /// `set_error_data`
+    pub fn take_error_data(&mut self) -> Option<Vec<u8>> {
+        self.error_data.take()
+    }
+
+    /// Checks a nested contract's state access against its own namespace
+    /// registry permissions; the contract named in the transaction is checked
+    /// by the handler before execution
+    fn check_namespace_permissions(
+        &mut self,
+        addresses: &[String],
+        access: NamespaceAccess,
+    ) -> Result<(), ExternalsError> {
+        let contract_name = match self.call {
+            Some(ref call) if call.depth > 0 => call.name.clone(),
+            _ => return Ok(()),
+        };
+
+        let mut state = SmartState::new(self.context);
+        for address in addresses {
+            if let Err(err) =
+                check_namespace_permission(&mut state, &contract_name, address, access)
+            {
+                return Err(ExternalsError::with_kind(
+                    ExternalsErrorKind::PermissionDenied,
+                    format!("{:?}", err),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that the addresses a contract accesses were declared by the
+    /// inputs or outputs of the action executing it
+    fn check_authorized_addresses(
+        &self,
+        addresses: &[String],
+        access: NamespaceAccess,
+    ) -> Result<(), ExternalsError> {
+        let call = match self.call {
+            Some(ref call) => call,
+            None => return Ok(()),
+        };
+        let authorized = match call.authorized {
+            Some(ref authorized) => authorized,
+            None => return Ok(()),
+        };
+
+        if let Some(address) = authorized.find_unauthorized(addresses, access) {
+            let declared = match access {
+                NamespaceAccess::Read => "inputs",
+                NamespaceAccess::Write => "outputs",
+            };
+            return Err(ExternalsError::with_kind(
+                ExternalsErrorKind::UnauthorizedAddress,
+                format!(
+                    "Unauthorized address: {} is not in the {} declared for {}",
+                    address, declared, call.name
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Checks that the module being executed may change state, which smart
+    /// permissions may not
+    fn check_writable(&self) -> Result<(), ExternalsError> {
+        if self.read_only {
+            return Err(ExternalsError::with_kind(
+                ExternalsErrorKind::PermissionDenied,
+                "Smart permissions cannot change state".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the resources consumed so far
+    pub fn usage(&self) -> &ExecutionUsage {
+        &self.usage
+    }
+
+    /// Notes the state addresses and bytes accessed by the current host call,
+    /// if the execution is being traced
+    fn trace_access(&mut self, addresses: &[String], bytes: usize) {
+        if self.usage.trace.is_some() {
+            self.trace_access = Some((addresses.to_vec(), bytes));
+        }
+    }
+
+    /// Charges fuel against the transaction's budget, returning an error once
+    /// the budget has been exhausted
+    fn consume_fuel(&mut self, amount: u64) -> Result<(), ExternalsError> {
+        self.usage.fuel = self.usage.fuel.saturating_add(amount);
+        if self.usage.fuel > self.limits.fuel {
+            return Err(ExternalsError::with_kind(
+                ExternalsErrorKind::FuelExhausted,
+                format!(
+                    "Fuel budget exhausted: used {} of {}",
+                    self.usage.fuel, self.limits.fuel
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Records the addresses accessed by a state call, returning an error once
+    /// more distinct addresses have been touched than allowed
+    fn touch_addresses(&mut self, addresses: &[String]) -> Result<(), ExternalsError> {
+        for address in addresses {
+            self.usage.addresses_touched.insert(address.clone());
+        }
+        if self.usage.addresses_touched.len() > self.limits.addresses_touched {
+            return Err(ExternalsError::with_kind(
+                ExternalsErrorKind::AddressLimitExceeded,
+                format!(
+                    "Address limit exceeded: touched {} addresses, limit is {}",
+                    self.usage.addresses_touched.len(),
+                    self.limits.addresses_touched
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Records the data about to be written to state, returning an error if an
+    /// entry is too large or the transaction has written too many bytes
+    fn record_writes(&mut self, entries: &[(String, Vec<u8>)]) -> Result<(), ExternalsError> {
+        for (address, data) in entries {
+            self.record_write(&format!("State entry for {}", address), data.len())?;
+        }
+        Ok(())
+    }
+
+    /// Charges `len` bytes written to state, events or receipt data against
+    /// the entry size and total write limits
+    fn record_write(&mut self, target: &str, len: usize) -> Result<(), ExternalsError> {
+        if len > self.limits.state_entry_size {
+            return Err(ExternalsError::with_kind(
+                ExternalsErrorKind::StateEntryTooLarge,
+                format!(
+                    "{} is {} bytes, limit is {}",
+                    target, len, self.limits.state_entry_size
+                ),
+            ));
+        }
+        self.usage.bytes_written += len;
+        if self.usage.bytes_written > self.limits.bytes_written {
+            return Err(ExternalsError::with_kind(
+                ExternalsErrorKind::WriteLimitExceeded,
+                format!(
+                    "Write limit exceeded: wrote {} bytes, limit is {}",
+                    self.usage.bytes_written, self.limits.bytes_written
+                ),
+            ));
+        }
+        Ok(())
+    }
+
     fn ptr_to_string(&mut self, raw_ptr: u32) -> Result<String, ExternalsError> {
         if let Some(p) = self.ptrs.get(&raw_ptr) {
             let bytes = self.get_memory_ref().get(p.raw, p.length)?;
@@ -208,7 +533,15 @@ impl<'a> WasmExternals<'a> {
     }
 
     pub fn write_data(&mut self, data: Vec<u8>) -> Result<u32, ExternalsError> {
-        self.get_memory_ref().set(self.memory_write_offset, &data)?;
+        let required = self.check_memory_limit(data.capacity())?;
+
+        let memory = self.get_memory_ref();
+        let current = memory.current_size();
+        if required > current {
+            memory.grow(Pages(required.0 - current.0))?;
+        }
+
+        memory.set(self.memory_write_offset, &data)?;
 
         let ptr = Pointer {
             raw: self.memory_write_offset,
@@ -224,6 +557,24 @@ impl<'a> WasmExternals<'a> {
         Ok(raw_ptr)
     }
 
+    /// Checks that writing `len` more bytes stays within the memory page
+    /// limit, returning the pages the memory must then have
+    fn check_memory_limit(&self, len: usize) -> Result<Pages, ExternalsError> {
+        let end = self.memory_write_offset as usize + len;
+        let max_pages = Pages(self.limits.memory_pages as usize);
+        let required: Pages = Bytes(end).round_up_to();
+        if required > max_pages {
+            return Err(ExternalsError::with_kind(
+                ExternalsErrorKind::MemoryLimitExceeded,
+                format!(
+                    "Memory limit exceeded: {} pages required, limit is {}",
+                    required.0, max_pages.0
+                ),
+            ));
+        }
+        Ok(required)
+    }
+
     /// Takes a list of pointers and associates them,
     /// effectively creating a list
     ///
@@ -271,9 +622,10 @@ impl<'a> WasmExternals<'a> {
                 let smart_permissions = match SmartPermissionList::from_bytes(packed.as_slice()) {
                     Ok(smart_permissions) => smart_permissions,
                     Err(err) => {
-                        return Err(ExternalsError {
-                            message: format!("Cannot deserialize smart permission list: {:?}", err),
-                        });
+                        return Err(ExternalsError::from(format!(
+                            "Cannot deserialize smart permission list: {:?}",
+                            err
+                        )));
                     }
                 };
 
@@ -288,6 +640,40 @@ impl<'a> WasmExternals<'a> {
         }
     }
 
+    /// Runs the smart permission `name` stored at `address`, continuing from
+    /// the current usage
+    ///
+    /// Returns None if the smart permission does not exist, otherwise the
+    /// result of its entrypoint, which is 1 if the operation is permitted.
+    pub fn run_smart_permission(
+        &mut self,
+        address: &str,
+        name: &str,
+        roles: Vec<String>,
+        org_id: String,
+        public_key: String,
+        payload: Vec<u8>,
+    ) -> Result<Option<i32>, ExternalsError> {
+        let smart_permission = match self.get_smart_permission(address, name)? {
+            Some(smart_permission) => smart_permission,
+            None => return Ok(None),
+        };
+
+        let mut module = SmartPermissionModule::new(
+            smart_permission.get_function(),
+            self.context,
+            self.limits.clone(),
+            self.usage.clone(),
+        )?;
+        let result = module.entrypoint(roles, org_id, public_key, payload)?;
+        self.usage = module.usage().clone();
+
+        match result {
+            Some(result) => Ok(Some(result)),
+            None => Err(ExternalsError::from("No result returned")),
+        }
+    }
+
     fn smart_permission(&mut self, args: RuntimeArgs) -> Result<Option<RuntimeValue>, Trap> {
         let timer = Instant::now();
         let contract_addr_ptr: i32 = args.nth(0);
@@ -312,23 +698,90 @@ impl<'a> WasmExternals<'a> {
         let name = self.ptr_to_string(name as u32)?;
         let contract_addr = self.ptr_to_string(contract_addr_ptr as u32)?;
 
-        let contract = if let Some(sp) = self.get_smart_permission(&contract_addr, &name)? {
-            sp
-        } else {
-            return Ok(Some(RuntimeValue::I32(-2)));
+        let result = self.run_smart_permission(
+            &contract_addr,
+            &name,
+            role_vec,
+            org_id,
+            public_key,
+            payload,
+        )?;
+
+        match result {
+            Some(x) => {
+                info!(
+                    "SMART_PERMISSION Execution time: {} secs {} ms",
+                    timer.elapsed().as_secs(),
+                    timer.elapsed().subsec_millis()
+                );
+                Ok(Some(RuntimeValue::I32(x)))
+            }
+            None => Ok(Some(RuntimeValue::I32(-2))),
+        }
+    }
+
+    fn invoke_contract(&mut self, args: RuntimeArgs) -> Result<Option<RuntimeValue>, Trap> {
+        let timer = Instant::now();
+        let name_ptr: u32 = args.nth(0);
+        let version_ptr: u32 = args.nth(1);
+        let payload_ptr: u32 = args.nth(2);
+
+        let call = match self.call {
+            Some(ref call) => call.clone(),
+            None => {
+                return Err(ExternalsError::trap(
+                    "invoke_contract can only be called by a contract".into(),
+                ));
+            }
+        };
+
+        if call.depth >= MAX_CALL_DEPTH {
+            return Err(Trap::from(TrapKind::Host(Box::new(
+                ExternalsError::with_kind(
+                    ExternalsErrorKind::CallDepthExceeded,
+                    format!(
+                        "Maximum call depth of {} exceeded by {}",
+                        MAX_CALL_DEPTH, call.name
+                    ),
+                ),
+            ))));
+        }
+
+        let name = self.ptr_to_string(name_ptr)?;
+        let version = self.ptr_to_string(version_ptr)?;
+        let payload = self.ptr_to_vec(payload_ptr)?;
+
+        info!("Invoking contract {}, {} from {}", name, version, call.name);
+        self.trace_access(&[], payload.len());
+
+        let module_cache = match self.module_cache {
+            Some(module_cache) => module_cache,
+            None => {
+                return Err(ExternalsError::trap(
+                    "invoke_contract requires a module cache".into(),
+                ));
+            }
         };
 
-        // Invoke Smart Permission
-        let mut module = SmartPermissionModule::new(contract.get_function(), self.context)
-            .expect("Failed to create can_add module");
-        let result = module
-            .entrypoint(role_vec, org_id, public_key, payload.to_vec())
-            .map_err(|e| ExternalsError::from(format!("{:?}", e)))?;
+        // Resolve and load the callee the same way as an ExecuteContractAction
+        let memory_pages = self.limits.memory_pages;
+        let module = get_requested_contract(&name, &version, &mut SmartState::new(self.context))
+            .and_then(|contract| get_compiled_module(&contract, memory_pages, module_cache))
+            .map_err(|err| ExternalsError::trap(format!("{}", err)))?;
+
+        // Run the callee under its own name so its namespace permissions apply
+        let mut callee = WasmModule::new(module, self.context, &name, self.limits.clone())
+            .nested(call.depth + 1, self.usage.clone(), call.authorized)
+            .cached(module_cache);
+        let result = callee.entrypoint(payload, call.signer, call.signature)?;
+
+        self.usage = callee.usage().clone();
+        self.last_return_data = callee.return_data().map(|data| data.to_vec());
 
         match result {
             Some(x) => {
                 info!(
-                    "SMART_PERMISSION Execution time: {} secs {} ms",
+                    "INVOKE_CONTRACT Execution time: {} secs {} ms",
                     timer.elapsed().as_secs(),
                     timer.elapsed().subsec_millis()
                 );
@@ -338,6 +791,79 @@ impl<'a> WasmExternals<'a> {
         }
     }
 
+    fn add_event(&mut self, args: RuntimeArgs) -> Result<Option<RuntimeValue>, Trap> {
+        let event_type_ptr: u32 = args.nth(0);
+        let attributes_ptr: i32 = args.nth(1);
+        let data_ptr: u32 = args.nth(2);
+
+        if !self.ptrs.contains_key(&event_type_ptr) || !self.ptrs.contains_key(&data_ptr) {
+            return Ok(Some(RuntimeValue::I32(-1)));
+        }
+
+        let attribute_ptrs = if attributes_ptr < 0 {
+            Vec::new()
+        } else {
+            match self.ptr_collections.get(&(attributes_ptr as u32)) {
+                Some(ptrs) => ptrs.clone(),
+                None => return Ok(Some(RuntimeValue::I32(-1))),
+            }
+        };
+
+        // if the length is not even return deserialization error
+        if (attribute_ptrs.len() % 2) != 0 {
+            return Ok(Some(RuntimeValue::I32(-1)));
+        }
+
+        let event_type = self.ptr_to_string(event_type_ptr)?;
+        let data = self.ptr_to_vec(data_ptr)?;
+        let mut attributes = Vec::new();
+        for attribute in attribute_ptrs.chunks(2) {
+            let key = self.ptr_to_string(attribute[0])?;
+            let value = self.ptr_to_string(attribute[1])?;
+            attributes.push((key, value));
+        }
+
+        info!(
+            "Attempting to add event {}, attributes: {:?}",
+            event_type, attributes
+        );
+        let event_size = event_type.len()
+            + attributes
+                .iter()
+                .map(|(key, value)| key.len() + value.len())
+                .sum::<usize>()
+            + data.len();
+        self.record_write(&format!("Event {}", event_type), event_size)?;
+        self.trace_access(&[], data.len());
+        match self.context.add_event(event_type, attributes, &data) {
+            Ok(()) => Ok(Some(RuntimeValue::I32(1))),
+            Err(err) => {
+                info!("Add event error: {}", err);
+                Ok(Some(RuntimeValue::I32(0)))
+            }
+        }
+    }
+
+    fn add_receipt_data(&mut self, args: RuntimeArgs) -> Result<Option<RuntimeValue>, Trap> {
+        let data_ptr: u32 = args.nth(0);
+
+        if !self.ptrs.contains_key(&data_ptr) {
+            return Ok(Some(RuntimeValue::I32(-1)));
+        }
+        let data = self.ptr_to_vec(data_ptr)?;
+
+        info!("Attempting to add receipt data, {} bytes", data.len());
+        self.record_write("Receipt data", data.len())?;
+        self.trace_access(&[], data.len());
+        match self.context.add_receipt_data(&data) {
+            Ok(()) => Ok(Some(RuntimeValue::I32(1))),
+            Err(err) => {
+                info!("Add receipt data error: {}", err);
+                Ok(Some(RuntimeValue::I32(0)))
+            }
+        }
+    }
+
     fn get_state(
         &mut self,
         args: RuntimeArgs,
@@ -355,11 +881,16 @@ impl<'a> WasmExternals<'a> {
         }
 
         info!("Attempting to get state, addresses: {:?}", addr_vec);
+        self.check_authorized_addresses(&addr_vec, NamespaceAccess::Read)?;
+        self.touch_addresses(&addr_vec)?;
+        self.check_namespace_permissions(&addr_vec, NamespaceAccess::Read)?;
 
         let state = self
             .context
             .get_state_entries(&addr_vec)
             .map_err(ExternalsError::from)?;
+        let bytes_read = state.iter().map(|(_, data)| data.len()).sum();
+        self.trace_access(&addr_vec, bytes_read);
 
         let mut ptr_vec = Vec::new();
         for (addr, data) in state {
@@ -410,6 +941,14 @@ impl<'a> WasmExternals<'a> {
         }
 
         info!("Attempting to set state, entries: {:?}", entries);
+        self.check_writable()?;
+        let addresses: Vec<String> = entries.iter().map(|(addr, _)| addr.clone()).collect();
+        self.check_authorized_addresses(&addresses, NamespaceAccess::Write)?;
+        self.touch_addresses(&addresses)?;
+        self.check_namespace_permissions(&addresses, NamespaceAccess::Write)?;
+        self.record_writes(&entries)?;
+        let bytes_written = entries.iter().map(|(_, data)| data.len()).sum();
+        self.trace_access(&addresses, bytes_written);
 
         match self.context.set_state_entries(entries) {
             Ok(()) => {
@@ -445,6 +984,11 @@ impl<'a> WasmExternals<'a> {
             addr_vec.push(address);
         }
         info!("Attempting to delete state, addresses: {:?}", addr_vec);
+        self.check_writable()?;
+        self.check_authorized_addresses(&addr_vec, NamespaceAccess::Write)?;
+        self.touch_addresses(&addr_vec)?;
+        self.check_namespace_permissions(&addr_vec, NamespaceAccess::Write)?;
+        self.trace_access(&addr_vec, 0);
         let result = self
             .context
             .delete_state_entries(&addr_vec)
@@ -467,13 +1011,44 @@ impl<'a> WasmExternals<'a> {
     }
 }
 
-impl<'a> Externals for WasmExternals<'a> {
-    fn invoke_index(
+impl<'a> WasmExternals<'a> {
+    /// The contract making host calls and its call depth, for trace records
+    fn trace_caller(&self) -> (Option<String>, u32) {
+        match self.call {
+            Some(ref call) => (Some(call.name.clone()), call.depth),
+            None => (None, 0),
+        }
+    }
+
+    fn trace_log(&self, log_level: u32, message: &str) {
+        if let Some(ref trace) = self.usage.trace {
+            let (contract, depth) = self.trace_caller();
+            let level = match log_level {
+                0 => "error",
+                1 => "warn",
+                2 => "info",
+                3 => "debug",
+                4 => "trace",
+                _ => "unknown",
+            };
+            trace.record(TraceRecord::Log(LogRecord {
+                contract,
+                depth,
+                level: level.into(),
+                message: message.into(),
+            }));
+        }
+    }
+
+    fn call_host_function(
         &mut self,
         index: usize,
         args: RuntimeArgs,
     ) -> Result<Option<RuntimeValue>, Trap> {
         let timer = Instant::now();
+        if index != GAS_IDX {
+            self.consume_fuel(HOST_CALL_FUEL_COST)?;
+        }
         match index {
             GET_STATE_IDX => self.get_state(args, timer),
             SET_STATE_IDX => self.set_state(args, timer),
@@ -498,7 +1073,16 @@ impl<'a> Externals for WasmExternals<'a> {
             }
             ALLOC_IDX => {
                 let len: i32 = args.nth(0);
+                if len < 0 {
+                    return Err(ExternalsError::trap(format!(
+                        "Cannot allocate a negative length: {}",
+                        len
+                    )));
+                }
 
+                // check the limit before the buffer is allocated on the host
+                self.check_memory_limit(len as usize)?;
+                self.trace_access(&[], len as usize);
                 let raw_ptr = self.write_data(vec![0; len as usize])?;
                 info!(
                     "ALLOC Execution time: {} secs {} ms",
@@ -578,6 +1162,7 @@ impl<'a> Externals for WasmExternals<'a> {
                 let log_level: u32 = args.nth(0);
                 let log_ptr: u32 = args.nth(1);
                 let log_string = self.ptr_to_string(log_ptr)?;
+                self.trace_log(log_level, &log_string);
                 match log_level {
                     0 => error!("{}", log_string),
                     1 => warn!("{}", log_string),
@@ -595,11 +1180,121 @@ impl<'a> Externals for WasmExternals<'a> {
                 LevelFilter::Warn => Ok(Some(RuntimeValue::I32(1))),
                 LevelFilter::Error | _ => Ok(Some(RuntimeValue::I32(0))),
             },
+            GAS_IDX => {
+                let amount: u32 = args.nth(0);
+                self.consume_fuel(u64::from(amount))?;
+                Ok(None)
+            }
+            INVOKE_CONTRACT_IDX => self.invoke_contract(args),
+            SET_RETURN_DATA_IDX => {
+                let ptr: u32 = args.nth(0);
+
+                if self.ptrs.contains_key(&ptr) {
+                    self.return_data = Some(self.ptr_to_vec(ptr)?);
+                    Ok(Some(RuntimeValue::I32(1)))
+                } else {
+                    Ok(Some(RuntimeValue::I32(-1)))
+                }
+            }
+            GET_RETURN_DATA_IDX => match self.last_return_data.clone() {
+                Some(data) => {
+                    let raw_ptr = self.write_data(data)?;
+                    Ok(Some(RuntimeValue::I32(raw_ptr as i32)))
+                }
+                None => Ok(Some(RuntimeValue::I32(-1))),
+            },
+            ADD_EVENT_IDX => self.add_event(args),
+            ADD_RECEIPT_DATA_IDX => self.add_receipt_data(args),
+            SET_ERROR_DATA_IDX => {
+                let ptr: u32 = args.nth(0);
+
+                if self.ptrs.contains_key(&ptr) {
+                    self.error_data = Some(self.ptr_to_vec(ptr)?);
+                    Ok(Some(RuntimeValue::I32(1)))
+                } else {
+                    Ok(Some(RuntimeValue::I32(-1)))
+                }
+            }
             _ => Err(ExternalsError::trap("Function does not exist".into())),
         }
     }
 }
 
+impl<'a> Externals for WasmExternals<'a> {
+    fn invoke_index(
+        &mut self,
+        index: usize,
+        args: RuntimeArgs,
+    ) -> Result<Option<RuntimeValue>, Trap> {
+        // Fuel metering calls are left out of the trace; each record carries
+        // the fuel consumed so far instead
+        let trace = match self.usage.trace {
+            Some(ref trace) if index != GAS_IDX => trace.clone(),
+            _ => return self.call_host_function(index, args),
+        };
+
+        let timer = Instant::now();
+        let traced_args = args
+            .as_ref()
+            .iter()
+            .filter_map(|value| match value {
+                RuntimeValue::I32(value) => Some(i64::from(*value)),
+                RuntimeValue::I64(value) => Some(*value),
+                _ => None,
+            })
+            .collect();
+        self.trace_access = None;
+
+        let result = self.call_host_function(index, args);
+
+        let elapsed = timer.elapsed();
+        let (addresses, bytes) = self.trace_access.take().unwrap_or_default();
+        let (contract, depth) = self.trace_caller();
+        trace.record(TraceRecord::HostCall(HostCallRecord {
+            contract,
+            depth,
+            function: host_function_name(index).into(),
+            args: traced_args,
+            addresses,
+            bytes,
+            duration_micros: elapsed.as_secs() * 1_000_000 + u64::from(elapsed.subsec_micros()),
+            fuel: self.usage.fuel,
+            trapped: result.is_err(),
+        }));
+
+        result
+    }
+}
+
+/// Returns the name a host function is imported by
+fn host_function_name(index: usize) -> &'static str {
+    match index {
+        GET_STATE_IDX => "get_state",
+        SET_STATE_IDX => "set_state",
+        GET_PTR_LEN_IDX => "get_ptr_len",
+        GET_PTR_CAP_IDX => "get_ptr_capacity",
+        ALLOC_IDX => "alloc",
+        READ_BYTE_IDX => "read_byte",
+        WRITE_BYTE_IDX => "write_byte",
+        GET_COLLECTION_LEN_IDX => "get_ptr_collection_len",
+        GET_PTR_FROM_COLLECTION_IDX => "get_ptr_from_collection",
+        DELETE_STATE_IDX => "delete_state",
+        CREATE_COLLECTION => "create_collection",
+        ADD_TO_COLLECTION => "add_to_collection",
+        SMART_PERMISSION => "invoke_smart_permission",
+        LOG => "log_buffer",
+        LOG_LEVEL => "log_level",
+        GAS_IDX => "gas",
+        INVOKE_CONTRACT_IDX => "invoke_contract",
+        SET_RETURN_DATA_IDX => "set_return_data",
+        GET_RETURN_DATA_IDX => "get_return_data",
+        ADD_EVENT_IDX => "add_event",
+        ADD_RECEIPT_DATA_IDX => "add_receipt_data",
+        SET_ERROR_DATA_IDX => "set_error_data",
+        _ => "unknown",
+    }
+}
+
 impl<'a> ModuleImportResolver for WasmExternals<'a> {
     fn resolve_func(&self, field_name: &str, _signature: &Signature) -> Result<FuncRef, Error> {
         match field_name {
@@ -676,6 +1371,40 @@ impl<'a> ModuleImportResolver for WasmExternals<'a> {
                 Signature::new(&[][..], Some(ValueType::I32)),
                 LOG_LEVEL,
             )),
+            "gas" => Ok(FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32][..], None),
+                GAS_IDX,
+            )),
+            "invoke_contract" => Ok(FuncInstance::alloc_host(
+                Signature::new(
+                    &[ValueType::I32, ValueType::I32, ValueType::I32][..],
+                    Some(ValueType::I32),
+                ),
+                INVOKE_CONTRACT_IDX,
+            )),
+            "set_return_data" => Ok(FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32][..], Some(ValueType::I32)),
+                SET_RETURN_DATA_IDX,
+            )),
+            "get_return_data" => Ok(FuncInstance::alloc_host(
+                Signature::new(&[][..], Some(ValueType::I32)),
+                GET_RETURN_DATA_IDX,
+            )),
+            "add_event" => Ok(FuncInstance::alloc_host(
+                Signature::new(
+                    &[ValueType::I32, ValueType::I32, ValueType::I32][..],
+                    Some(ValueType::I32),
+                ),
+                ADD_EVENT_IDX,
+            )),
+            "add_receipt_data" => Ok(FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32][..], Some(ValueType::I32)),
+                ADD_RECEIPT_DATA_IDX,
+            )),
+            "set_error_data" => Ok(FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32][..], Some(ValueType::I32)),
+                SET_ERROR_DATA_IDX,
+            )),
             _ => Err(Error::Instantiation(format!(
                 "Export {} not found",
                 field_name
@@ -715,8 +1444,37 @@ impl fmt::Debug for Pointer {
     }
 }
 
-#[derive(Debug)]
+/// The reason an `ExternalsError` was raised
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExternalsErrorKind {
+    /// A failure while loading or executing a module
+    Execution,
+    /// The transaction's fuel budget was exhausted
+    FuelExhausted,
+    /// The module required more linear memory than allowed
+    MemoryLimitExceeded,
+    /// A single `set_state` entry, event or receipt data entry was larger than
+    /// allowed
+    StateEntryTooLarge,
+    /// The transaction touched more distinct addresses than allowed
+    AddressLimitExceeded,
+    /// The transaction wrote more bytes to state than allowed
+    WriteLimitExceeded,
+    /// A nested contract accessed state its namespace permissions do not
+    /// cover, or a smart permission tried to change state
+    PermissionDenied,
+    /// Contracts invoked each other deeper than allowed
+    CallDepthExceeded,
+    /// A contract accessed an address not declared by the transaction's
+    /// inputs or outputs
+    UnauthorizedAddress,
+    /// An uploaded contract failed validation
+    InvalidModule,
+}
+
+#[derive(Clone, Debug)]
 pub struct ExternalsError {
+    kind: ExternalsErrorKind,
     message: String,
 }
 
@@ -724,6 +1482,14 @@ impl ExternalsError {
     fn trap(msg: String) -> Trap {
         Trap::from(TrapKind::Host(Box::new(ExternalsError::from(msg))))
     }
+
+    pub fn with_kind(kind: ExternalsErrorKind, message: String) -> ExternalsError {
+        ExternalsError { kind, message }
+    }
+
+    pub fn kind(&self) -> ExternalsErrorKind {
+        self.kind
+    }
 }
 
 impl fmt::Display for ExternalsError {
@@ -737,6 +1503,7 @@ impl HostError for ExternalsError {}
 impl<'a> From<&'a str> for ExternalsError {
     fn from(s: &'a str) -> Self {
         ExternalsError {
+            kind: ExternalsErrorKind::Execution,
             message: String::from(s),
         }
     }
@@ -744,7 +1511,17 @@ impl<'a> From<&'a str> for ExternalsError {
 
 impl From<Error> for ExternalsError {
     fn from(e: Error) -> Self {
+        // keep errors raised by host functions intact so their kind survives the trap
+        if let Error::Trap(ref trap) = e {
+            if let TrapKind::Host(ref host_error) = *trap.kind() {
+                if let Some(err) = host_error.downcast_ref::<ExternalsError>() {
+                    return err.clone();
+                }
+            }
+        }
+
         ExternalsError {
+            kind: ExternalsErrorKind::Execution,
             message: format!("{:?}", e),
         }
     }
@@ -752,13 +1529,17 @@ impl From<Error> for ExternalsError {
 
 impl From<String> for ExternalsError {
     fn from(s: String) -> Self {
-        ExternalsError { message: s }
+        ExternalsError {
+            kind: ExternalsErrorKind::Execution,
+            message: s,
+        }
     }
 }
 
 impl From<FromUtf8Error> for ExternalsError {
     fn from(e: FromUtf8Error) -> Self {
         ExternalsError {
+            kind: ExternalsErrorKind::Execution,
             message: e.description().to_string(),
         }
     }
@@ -767,6 +1548,16 @@ impl From<FromUtf8Error> for ExternalsError {
 impl From<ContextError> for ExternalsError {
     fn from(e: ContextError) -> Self {
         ExternalsError {
+            kind: ExternalsErrorKind::Execution,
+            message: format!("{:?}", e),
+        }
+    }
+}
+
+impl From<parity_wasm::elements::Error> for ExternalsError {
+    fn from(e: parity_wasm::elements::Error) -> Self {
+        ExternalsError {
+            kind: ExternalsErrorKind::Execution,
             message: format!("{:?}", e),
         }
     }
@@ -775,15 +1566,28 @@ impl From<ContextError> for ExternalsError {
 struct SmartPermissionModule<'a> {
     context: &'a mut dyn TransactionContext,
     module: Module,
+    limits: ExecutionLimits,
+    usage: ExecutionUsage,
 }
 
 impl<'a> SmartPermissionModule<'a> {
     pub fn new(
         wasm: &[u8],
         context: &'a mut dyn TransactionContext,
+        limits: ExecutionLimits,
+        usage: ExecutionUsage,
     ) -> Result<SmartPermissionModule<'a>, ExternalsError> {
-        let module = Module::from_buffer(wasm)?;
-        Ok(SmartPermissionModule { context, module })
+        let module = load_metered_module(wasm, limits.memory_pages)?;
+        Ok(SmartPermissionModule {
+            context,
+            module,
+            limits,
+            usage,
+        })
+    }
+
+    pub fn usage(&self) -> &ExecutionUsage {
+        &self.usage
     }
 
     pub fn entrypoint(
@@ -793,7 +1597,14 @@ impl<'a> SmartPermissionModule<'a> {
         public_key: String,
         payload: Vec<u8>,
     ) -> Result<Option<i32>, ExternalsError> {
-        let mut env = WasmExternals::new(None, self.context)?;
+        let mut env = WasmExternals::new(
+            None,
+            self.context,
+            self.limits.clone(),
+            self.usage.clone(),
+            None,
+        )?;
+        env.read_only = true;
 
         let instance = ModuleInstance::new(
             &self.module,
@@ -844,6 +1655,7 @@ impl<'a> SmartPermissionModule<'a> {
             ],
             &mut env,
         )?;
+        self.usage = env.usage().clone();
 
         if let Some(RuntimeValue::I32(i)) = result {
             Ok(Some(i))
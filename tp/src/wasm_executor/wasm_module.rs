@@ -1,23 +1,106 @@
 // Copyright (c) The dgc.network
 // SPDX-License-Identifier: Apache-2.0
 
+use std::sync::{Arc, Mutex};
+
 use sawtooth_sdk::processor::handler::TransactionContext;
 use wasmi::{ImportsBuilder, Module, ModuleInstance, RuntimeValue};
 
-use crate::wasm_executor::wasm_externals::{ExternalsError, WasmExternals};
+use crate::wasm_executor::limits::{ExecutionLimits, ExecutionUsage};
+use crate::wasm_executor::module_cache::ModuleCache;
+use crate::wasm_executor::trace::ExecutionTrace;
+use crate::wasm_executor::wasm_externals::{
+    AuthorizedAddresses, ContractCall, ExternalsError, WasmExternals,
+};
 
 pub struct WasmModule<'a> {
     context: &'a mut dyn TransactionContext,
-    module: Module,
+    module: Arc<Module>,
+    name: String,
+    limits: ExecutionLimits,
+    usage: ExecutionUsage,
+    call_depth: u32,
+    authorized: Option<AuthorizedAddresses>,
+    module_cache: Option<&'a Mutex<ModuleCache>>,
+    return_data: Option<Vec<u8>>,
+    error_data: Option<Vec<u8>>,
 }
 
 impl<'a> WasmModule<'a> {
+    /// Creates a WasmModule from a module loaded with `load_metered_module`,
+    /// such as one kept in the module cache
     pub fn new(
-        wasm: &[u8],
+        module: Arc<Module>,
         context: &'a mut dyn TransactionContext,
-    ) -> Result<WasmModule<'a>, ExternalsError> {
-        let module = Module::from_buffer(wasm)?;
-        Ok(WasmModule { context, module })
+        name: &str,
+        limits: ExecutionLimits,
+    ) -> WasmModule<'a> {
+        WasmModule {
+            context,
+            module,
+            name: name.to_string(),
+            limits,
+            usage: ExecutionUsage::default(),
+            call_depth: 0,
+            authorized: None,
+            module_cache: None,
+            return_data: None,
+            error_data: None,
+        }
+    }
+
+    /// Restricts the state the contract can access to the addresses declared
+    /// by the inputs and outputs of the action executing it
+    pub fn authorize(mut self, authorized: AuthorizedAddresses) -> WasmModule<'a> {
+        self.authorized = Some(authorized);
+        self
+    }
+
+    /// Runs the module as a contract invoked by another contract, continuing
+    /// from the resources the transaction has already consumed and restricted
+    /// to the addresses the caller was authorized to access
+    pub fn nested(
+        mut self,
+        call_depth: u32,
+        usage: ExecutionUsage,
+        authorized: Option<AuthorizedAddresses>,
+    ) -> WasmModule<'a> {
+        self.call_depth = call_depth;
+        self.usage = usage;
+        self.authorized = authorized;
+        self
+    }
+
+    /// Loads the contracts invoked with `invoke_contract` through the given
+    /// module cache, which is required for the contract to invoke others
+    pub fn cached(mut self, module_cache: &'a Mutex<ModuleCache>) -> WasmModule<'a> {
+        self.module_cache = Some(module_cache);
+        self
+    }
+
+    /// Records the host calls and log lines of the execution, including those
+    /// of nested contracts and smart permissions, in the given trace
+    pub fn traced(mut self, trace: Option<ExecutionTrace>) -> WasmModule<'a> {
+        self.usage.trace = trace;
+        self
+    }
+
+    /// Returns the resources consumed by the transaction as of the last call
+    /// to `entrypoint`
+    pub fn usage(&self) -> &ExecutionUsage {
+        &self.usage
+    }
+
+    /// Returns the output buffer set by the contract during the last call to
+    /// `entrypoint`, if any
+    pub fn return_data(&self) -> Option<&[u8]> {
+        self.return_data.as_ref().map(|data| data.as_slice())
+    }
+
+    /// Returns the serialized ContractError set by the contract during the
+    /// last call to `entrypoint` or `migrate`, if any
+    pub fn error_data(&self) -> Option<&[u8]> {
+        self.error_data.as_ref().map(|data| data.as_slice())
     }
 
     pub fn entrypoint(
@@ -26,7 +109,45 @@ impl<'a> WasmModule<'a> {
         signer: String,
         signature: String,
     ) -> Result<Option<i32>, ExternalsError> {
-        let mut env = WasmExternals::new(None, self.context)?;
+        self.call_export("entrypoint", false, payload, signer, signature)
+    }
+
+    /// Runs the contract's exported `migrate` function, which has the same
+    /// signature as `entrypoint`, after the contract has been upgraded
+    ///
+    /// Returns None if the contract does not export `migrate`.
+    pub fn migrate(
+        &mut self,
+        payload: Vec<u8>,
+        signer: String,
+        signature: String,
+    ) -> Result<Option<i32>, ExternalsError> {
+        self.call_export("migrate", true, payload, signer, signature)
+    }
+
+    fn call_export(
+        &mut self,
+        export: &str,
+        optional: bool,
+        payload: Vec<u8>,
+        signer: String,
+        signature: String,
+    ) -> Result<Option<i32>, ExternalsError> {
+        let call = ContractCall {
+            name: self.name.clone(),
+            signer: signer.clone(),
+            signature: signature.clone(),
+            depth: self.call_depth,
+            authorized: self.authorized.clone(),
+        };
+        let mut env = WasmExternals::new(
+            None,
+            self.context,
+            self.limits.clone(),
+            self.usage.clone(),
+            Some(call),
+        )?
+        .cached(self.module_cache);
 
         let instance = ModuleInstance::new(
             &self.module,
@@ -34,6 +155,10 @@ impl<'a> WasmModule<'a> {
         )?
         .assert_no_start();
 
+        if optional && instance.export_by_name(export).is_none() {
+            return Ok(None);
+        }
+
         let payload_ptr = env.write_data(payload)? as i32;
         info!("Payload written to memory");
 
@@ -44,7 +169,7 @@ impl<'a> WasmModule<'a> {
         info!("Signature written to memory");
 
         let result = instance.invoke_export(
-            "entrypoint",
+            export,
             &[
                 RuntimeValue::I32(payload_ptr),
                 RuntimeValue::I32(signer_ptr),
@@ -52,9 +177,17 @@ impl<'a> WasmModule<'a> {
             ],
             &mut env,
         )?;
+        self.usage = env.usage().clone();
+        self.return_data = env.take_return_data();
+        self.error_data = env.take_error_data();
 
         if let Some(RuntimeValue::I32(i)) = result {
             Ok(Some(i))
+        } else if optional {
+            Err(ExternalsError::from(format!(
+                "Exported function {} did not return a result",
+                export
+            )))
         } else {
             Ok(None)
         }